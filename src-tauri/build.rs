@@ -1,3 +1,112 @@
+/// Every app command, so each gets an `allow-<command>` permission; the
+/// windows only get the ones their capability grants.
+const COMMANDS: &[&str] = &[
+    "get_calendar_events",
+    "get_calendars",
+    "get_event_details",
+    "enter_alert_mode",
+    "exit_alert_mode",
+    "set_pill_mode",
+    "open_link",
+    "open_in_calendar",
+    "get_settings",
+    "save_settings",
+    "preview_template",
+    "get_alert_history",
+    "get_punctuality_insights",
+    "get_recent_meetings",
+    "dismiss_alert",
+    "request_dismiss",
+    "confirm_dismiss",
+    "begin_escape",
+    "end_escape",
+    "snooze_alert",
+    "join_meeting",
+    "get_active_alert",
+    "get_scheduler_state",
+    "test_webhook",
+    "get_integrations_status",
+    "get_health",
+    "run_self_test",
+    "get_system_appearance",
+    "get_overlay_opacity",
+    "end_focus_grace",
+    "load_simulation",
+    "minimize_alert_to_corner",
+    "restore_alert_fullscreen",
+    "get_window_pool_stats",
+    "get_sources",
+    "refresh_source",
+    "set_source_enabled",
+    "adopt_series_settings",
+    "get_alert_rules",
+    "save_alert_rule",
+    "delete_alert_rule",
+    "simulate_rule_actions",
+    "reset_auto_tuned_rules",
+    "dispatch_action",
+    "list_actions",
+    "get_free_gaps",
+    "get_digest",
+    "get_meeting_budget_status",
+    "trigger_tomorrow_preview",
+    "list_profiles",
+    "switch_profile",
+    "save_current_as_profile",
+    "export_backup",
+    "import_backup",
+    "rollback_import",
+    "classify_location",
+    "open_map",
+    "get_power_state",
+    "get_presentation_status",
+    "set_lateness_annotation",
+    "get_accessibility_prefs",
+    "mute_event",
+    "unmute_event",
+    "force_alert",
+    "pause_until_free",
+    "resume_reminders",
+    "get_week_agenda",
+    "explain_event",
+    "open_dashboard",
+    "export_attendance",
+    "get_storage_usage",
+    "compact_storage",
+    "set_event_note",
+    "get_event_note",
+    "enable_board_mode",
+    "disable_board_mode",
+    "get_api_version",
+    "get_api_manifest",
+    "reveal_event",
+    "rebuild_tray",
+    "get_startup_timings",
+    "get_feature_flags",
+    "set_feature_flag",
+    "get_whats_new",
+    "get_audio_outputs",
+    "set_contact_timezone",
+    "apply_rules_bulk",
+    "mute_series_bulk",
+    "add_ics_subscriptions_bulk",
+    "copy_from_profile",
+];
+
 fn main() {
-    tauri_build::build()
+    println!("cargo:rerun-if-changed=permissions");
+    // Picks the feature-flag defaults; see features.rs.
+    println!("cargo:rerun-if-env-changed=RELEASE_CHANNEL");
+    // The same list, for the API manifest the frontend can ask for.
+    let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("commands.rs");
+    std::fs::write(
+        out,
+        format!("pub const COMMANDS: &[&str] = &{:?};\n", COMMANDS),
+    )
+    .expect("failed to write the command list");
+    tauri_build::try_build(
+        tauri_build::Attributes::new()
+            .app_manifest(tauri_build::AppManifest::new().commands(COMMANDS)),
+    )
+    .expect("failed to run the tauri build script")
 }
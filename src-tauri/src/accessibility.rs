@@ -0,0 +1,116 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use tauri::AppHandle;
+
+// --- Accessibility ---
+//
+// VoiceOver gets an announcement for every alert, since the webview doesn't
+// reliably move focus on its own. The system "reduce motion" setting turns
+// the alert fade off.
+
+#[derive(Clone, Copy, Debug, Default, Serialize, JsonSchema)]
+pub struct AccessibilityPrefs {
+    pub reduce_motion: bool,
+    pub reduce_transparency: bool,
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use objc2::runtime::{AnyClass, AnyObject};
+    use objc2::{class, msg_send};
+    use std::ffi::CString;
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {
+        static NSAccessibilityAnnouncementRequestedNotification: *mut AnyObject;
+        static NSAccessibilityAnnouncementKey: *mut AnyObject;
+        static NSAccessibilityPriorityKey: *mut AnyObject;
+        fn NSAccessibilityPostNotificationWithUserInfo(
+            element: *mut AnyObject,
+            notification: *mut AnyObject,
+            user_info: *mut AnyObject,
+        );
+    }
+
+    /// NSAccessibilityPriorityHigh.
+    const PRIORITY_HIGH: isize = 90;
+
+    fn workspace() -> Option<&'static AnyObject> {
+        // SAFETY: the shared NSWorkspace lives for the whole process.
+        unsafe {
+            let workspace: *mut AnyObject = msg_send![class!(NSWorkspace), sharedWorkspace];
+            workspace.as_ref()
+        }
+    }
+
+    pub fn reduce_motion() -> bool {
+        // SAFETY: plain BOOL getter.
+        workspace().is_some_and(|w| unsafe { msg_send![w, accessibilityDisplayShouldReduceMotion] })
+    }
+
+    pub fn reduce_transparency() -> bool {
+        // SAFETY: plain BOOL getter.
+        workspace()
+            .is_some_and(|w| unsafe { msg_send![w, accessibilityDisplayShouldReduceTransparency] })
+    }
+
+    /// Must run on the main thread.
+    pub fn announce(text: &str) {
+        let Ok(text) = CString::new(text) else {
+            return;
+        };
+        // SAFETY: all objects are autoreleased Foundation objects used within
+        // this call; the notification constants are AppKit statics.
+        unsafe {
+            let string_class: &AnyClass = class!(NSString);
+            let message: *mut AnyObject =
+                msg_send![string_class, stringWithUTF8String: text.as_ptr()];
+            let priority: *mut AnyObject =
+                msg_send![class!(NSNumber), numberWithInteger: PRIORITY_HIGH];
+            let keys = [NSAccessibilityAnnouncementKey, NSAccessibilityPriorityKey];
+            let values = [message, priority];
+            let info: *mut AnyObject = msg_send![
+                class!(NSDictionary),
+                dictionaryWithObjects: values.as_ptr(),
+                forKeys: keys.as_ptr(),
+                count: keys.len()
+            ];
+            let app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
+            if message.is_null() || info.is_null() || app.is_null() {
+                return;
+            }
+            NSAccessibilityPostNotificationWithUserInfo(
+                app,
+                NSAccessibilityAnnouncementRequestedNotification,
+                info,
+            );
+        }
+    }
+}
+
+pub fn prefs() -> AccessibilityPrefs {
+    #[cfg(target_os = "macos")]
+    {
+        AccessibilityPrefs {
+            reduce_motion: macos::reduce_motion(),
+            reduce_transparency: macos::reduce_transparency(),
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    AccessibilityPrefs::default()
+}
+
+/// Announces an alert to VoiceOver; see `templates::speech` for the text.
+pub fn announce_alert(app: &AppHandle, text: String) {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = app.run_on_main_thread(move || macos::announce(&text));
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = (app, text);
+}
+
+#[tauri::command]
+pub fn get_accessibility_prefs() -> AccessibilityPrefs {
+    prefs()
+}
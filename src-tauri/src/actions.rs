@@ -0,0 +1,399 @@
+use chrono::Duration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tauri::{AppHandle, Manager, Window};
+
+use crate::calendar::CalendarEvent;
+use crate::history::{self, HistoryKind};
+use crate::ipc;
+use crate::scheduler::{self, SchedulerState};
+use crate::{agenda, journal, settings, simulate, untrusted, window};
+
+// --- Actions ---
+//
+// Named backend actions behind one entry point, for the dashboard's buttons
+// and for launchers such as Raycast or Shortcuts. Each action declares its
+// arguments, which are checked before it runs; `list_actions` describes
+// them all for building pickers. New actions only need an entry in
+// `ACTIONS`.
+//
+// Alert rules can chain actions onto a dismiss or a join. The steps run in
+// order on a thread of their own once the alert is gone, each on its own:
+// one that fails is noted and the next one runs anyway. Actions that act on
+// the alert itself can't be chained.
+
+/// How far from its start a meeting still counts for joining the next one.
+const JOIN_WINDOW_MINUTES: i64 = 10;
+
+type Args = Map<String, Value>;
+type RunFn = fn(&AppHandle, &Args) -> Result<Value, String>;
+
+#[derive(Clone, Copy, Debug, Serialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ArgKind {
+    Integer { min: i64, max: i64 },
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    #[serde(flatten)]
+    pub kind: ArgKind,
+    pub required: bool,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct Action {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub args: &'static [ArgSpec],
+    /// Can be a step of an alert rule's `on_acknowledge` or `on_join`.
+    pub chainable: bool,
+    #[serde(skip)]
+    run: RunFn,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum ActionError {
+    UnknownAction(String),
+    InvalidArgs(String),
+    Failed(String),
+    /// Called from a window other than the dashboard; carries its label.
+    UnexpectedCaller(String),
+}
+
+impl ActionError {
+    pub fn message(&self) -> String {
+        match self {
+            ActionError::UnknownAction(name) => format!("Unknown action: {}", name),
+            ActionError::InvalidArgs(detail) | ActionError::Failed(detail) => detail.clone(),
+            ActionError::UnexpectedCaller(window) => {
+                format!("Actions can't be run from window {}", window)
+            }
+        }
+    }
+}
+
+static ACTIONS: [Action; 6] = [
+    Action {
+        name: "snooze-next",
+        description: "Snooze the alert that is showing",
+        args: &[ArgSpec {
+            name: "minutes",
+            description: "How long to snooze for; 5 if left out",
+            kind: ArgKind::Integer { min: 1, max: 120 },
+            required: false,
+        }],
+        chainable: false,
+        run: snooze_next,
+    },
+    Action {
+        name: "pause-1h",
+        description: "Pause everything but meeting alerts for an hour",
+        args: &[],
+        chainable: true,
+        run: pause_hour,
+    },
+    Action {
+        name: "toggle-pause",
+        description: "Pause until the current meetings are over, or resume",
+        args: &[],
+        chainable: true,
+        run: toggle_pause,
+    },
+    Action {
+        name: "join-next-meeting",
+        description: "Join the meeting starting closest to now, within 10 minutes",
+        args: &[],
+        chainable: false,
+        run: join_next_meeting,
+    },
+    Action {
+        name: "copy-next-link",
+        description: "Copy the call link of the current or next meeting",
+        args: &[],
+        chainable: true,
+        run: copy_next_link,
+    },
+    Action {
+        name: "trigger-digest",
+        description: "Send today's meeting digest now",
+        args: &[],
+        chainable: true,
+        run: trigger_digest,
+    },
+];
+
+/// One action of an alert rule's chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ActionStep {
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<Value>,
+}
+
+/// How one step of a chain went, kept with the history entry.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StepResult {
+    pub action: String,
+    pub ok: bool,
+    /// What the action returned, or why it failed.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub detail: Value,
+}
+
+fn find(name: &str) -> Result<&'static Action, ActionError> {
+    ACTIONS
+        .iter()
+        .find(|a| a.name == name)
+        .ok_or(ActionError::UnknownAction(name.to_string()))
+}
+
+fn validate(action: &Action, args: Option<Value>) -> Result<Args, ActionError> {
+    let args = match args {
+        None | Some(Value::Null) => Map::new(),
+        Some(Value::Object(args)) => args,
+        Some(_) => {
+            return Err(ActionError::InvalidArgs(
+                "Arguments must be an object".to_string(),
+            ))
+        }
+    };
+    if let Some(unknown) = args
+        .keys()
+        .find(|key| !action.args.iter().any(|spec| spec.name == *key))
+    {
+        return Err(ActionError::InvalidArgs(format!(
+            "Unknown argument: {}",
+            unknown
+        )));
+    }
+    for spec in action.args {
+        let Some(value) = args.get(spec.name) else {
+            if spec.required {
+                return Err(ActionError::InvalidArgs(format!(
+                    "Missing argument: {}",
+                    spec.name
+                )));
+            }
+            continue;
+        };
+        match spec.kind {
+            ArgKind::Integer { min, max } => {
+                if !value.as_i64().is_some_and(|n| (min..=max).contains(&n)) {
+                    return Err(ActionError::InvalidArgs(format!(
+                        "{} must be a whole number from {} to {}",
+                        spec.name, min, max
+                    )));
+                }
+            }
+        }
+    }
+    Ok(args)
+}
+
+/// The meeting in progress or starting next, whether or not it has a link.
+fn next_meeting(app: &AppHandle) -> Option<CalendarEvent> {
+    let settings = settings::current(app);
+    let now = simulate::now();
+    scheduler::cached_events(app)
+        .into_iter()
+        .filter(|e| {
+            !e.is_all_day
+                && !e.is_long_block
+                && !e.is_declined()
+                && !settings.calendar_filter.excludes(e)
+        })
+        .filter(|e| e.end_time().is_some_and(|end| end > now))
+        .min_by_key(|e| e.start_time())
+}
+
+fn next_link(app: &AppHandle) -> Result<String, String> {
+    let event = next_meeting(app).ok_or("No meeting coming up".to_string())?;
+    event
+        .video_link
+        .ok_or(format!("No call link for {}", event.title))
+}
+
+fn active_event(app: &AppHandle) -> Option<CalendarEvent> {
+    app.state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .active_event()
+        .cloned()
+}
+
+fn snooze_next(app: &AppHandle, args: &Args) -> Result<Value, String> {
+    if active_event(app).is_none() {
+        return Err("No alert is showing".to_string());
+    }
+    let minutes = args.get("minutes").and_then(Value::as_i64).unwrap_or(5);
+    scheduler::snooze_alert(app.clone(), Some(minutes))?;
+    window::exit_alert_mode(app.clone());
+    Ok(json!(minutes))
+}
+
+fn pause_hour(app: &AppHandle, _: &Args) -> Result<Value, String> {
+    let until = app
+        .state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .pause_for(simulate::now(), Duration::hours(1));
+    journal::paused(app, Some(until));
+    Ok(json!(until))
+}
+
+fn toggle_pause(app: &AppHandle, _: &Args) -> Result<Value, String> {
+    let now = simulate::now();
+    let paused = app
+        .state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .paused_until()
+        .is_some_and(|until| now < until);
+    if paused {
+        scheduler::resume_reminders(app.clone(), app.state());
+        return Ok(Value::Null);
+    }
+    scheduler::pause_until_free(app.clone()).map(|until| json!(until))
+}
+
+/// The meeting starting closest to now, up to `JOIN_WINDOW_MINUTES` before
+/// or after.
+fn joinable_meeting(app: &AppHandle) -> Result<CalendarEvent, String> {
+    let settings = settings::current(app);
+    let now = simulate::now();
+    let window = Duration::minutes(JOIN_WINDOW_MINUTES);
+    scheduler::cached_events(app)
+        .into_iter()
+        .filter(|e| !e.is_all_day && !e.is_long_block && !e.is_declined())
+        .filter(|e| !settings.calendar_filter.excludes(e))
+        .filter_map(|e| Some(((e.start_time()? - now).abs(), e)))
+        .filter(|(distance, _)| *distance <= window)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, e)| e)
+        .ok_or(format!("Nothing within {} minutes", JOIN_WINDOW_MINUTES))
+}
+
+/// Joins the meeting that is alerting, which also acknowledges the alert,
+/// or else the one starting closest to now. A meeting joined before its
+/// alert is recorded as joined and won't alert any more.
+pub fn join_next(app: &AppHandle) -> Result<String, String> {
+    if let Some(link) = active_event(app).and_then(|e| e.video_link) {
+        scheduler::join_meeting(app.clone(), link.clone())?;
+        window::exit_alert_mode(app.clone());
+        return Ok(link);
+    }
+    let event = joinable_meeting(app)?;
+    let link = event
+        .video_link
+        .clone()
+        .ok_or("Next meeting has no link".to_string())?;
+    let _ = open::that(untrusted::openable_link(&link)?);
+    app.state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .mark_joined(&event, simulate::now());
+    history::record(app, &event, HistoryKind::Joined);
+    Ok(link)
+}
+
+fn join_next_meeting(app: &AppHandle, _: &Args) -> Result<Value, String> {
+    join_next(app).map(|link| json!(link))
+}
+
+pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let mut child = Command::new("pbcopy")
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    // Dropped after writing, so pbcopy sees the end of its input.
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| e.to_string())?;
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if !status.success() {
+        return Err(format!("pbcopy failed: {}", status));
+    }
+    Ok(())
+}
+
+fn copy_next_link(app: &AppHandle, _: &Args) -> Result<Value, String> {
+    let link = next_link(app)?;
+    copy_to_clipboard(&link)?;
+    Ok(json!(link))
+}
+
+fn trigger_digest(app: &AppHandle, _: &Args) -> Result<Value, String> {
+    agenda::send_digest(app);
+    Ok(Value::Null)
+}
+
+fn run(app: &AppHandle, action: &str, args: Option<Value>) -> Result<Value, ActionError> {
+    let entry = find(action)?;
+    let args = validate(entry, args)?;
+    (entry.run)(app, &args).map_err(ActionError::Failed)
+}
+
+/// Checks a step of a chain without running it: the action exists, can be
+/// chained, and its arguments are valid.
+pub fn check_step(step: &ActionStep) -> Result<&'static Action, ActionError> {
+    let entry = find(&step.action)?;
+    if !entry.chainable {
+        return Err(ActionError::InvalidArgs(format!(
+            "{} acts on the alert and can't be chained",
+            entry.name
+        )));
+    }
+    validate(entry, step.args.clone())?;
+    Ok(entry)
+}
+
+/// Runs the steps in order, each regardless of how the ones before it went.
+pub fn run_chain(app: &AppHandle, steps: &[ActionStep]) -> Vec<StepResult> {
+    steps
+        .iter()
+        .map(|step| {
+            let outcome = check_step(step).and_then(|_| run(app, &step.action, step.args.clone()));
+            let (ok, detail) = match outcome {
+                Ok(value) => (true, value),
+                Err(error) => (false, json!(error.message())),
+            };
+            StepResult {
+                action: step.action.clone(),
+                ok,
+                detail,
+            }
+        })
+        .collect()
+}
+
+/// Runs the named action; what it returns depends on the action, e.g. the
+/// resume time for the pauses or the link for the link actions.
+#[tauri::command]
+pub fn dispatch_action(
+    app: AppHandle,
+    window: Window,
+    action: String,
+    args: Option<Value>,
+) -> Result<Value, ActionError> {
+    ipc::require_dashboard(&window, "dispatch_action")
+        .map_err(|_| ActionError::UnexpectedCaller(window.label().to_string()))?;
+    run(&app, &action, args)
+}
+
+#[tauri::command]
+pub fn list_actions() -> Vec<&'static Action> {
+    ACTIONS.iter().collect()
+}
@@ -0,0 +1,328 @@
+use chrono::{DateTime, Datelike, Days, Duration, Local, NaiveDate, NaiveTime};
+use schemars::JsonSchema;
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::budget::{self, DayUsage};
+use crate::calendar::{self, CalendarEvent};
+use crate::locale::{format_time, FormatSettings};
+use crate::settings::PreviewSettings;
+use crate::working_hours::WorkingHours;
+use crate::{notify, scheduler, settings, simulate};
+
+// --- Agenda (free gaps, the daily digest and tomorrow's preview) ---
+
+/// Gaps shorter than this between meetings still count as back-to-back.
+const MIN_FREE_MINUTES: i64 = 10;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct FreeGap {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub minutes: i64,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Digest {
+    pub date: NaiveDate,
+    pub events: Vec<CalendarEvent>,
+    pub summary: String,
+    /// Meeting hours against the budget, if one is set.
+    pub budget: Option<DayUsage>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Preview {
+    pub date: NaiveDate,
+    pub events: Vec<CalendarEvent>,
+    /// Events that start early or fall outside working hours.
+    pub flagged: Vec<CalendarEvent>,
+    pub summary: String,
+    pub budget: Option<DayUsage>,
+}
+
+fn timed_span(event: &CalendarEvent) -> Option<(DateTime<Local>, DateTime<Local>)> {
+    if event.is_all_day {
+        return None;
+    }
+    Some((event.start_time()?, event.end_time()?))
+}
+
+fn end_of_day(date: NaiveDate) -> Option<DateTime<Local>> {
+    date.checked_add_days(Days::new(1))?
+        .and_time(NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+}
+
+/// Free time between `from` and `to`, clipped to working hours.
+pub fn free_gaps(
+    events: &[CalendarEvent],
+    from: DateTime<Local>,
+    to: DateTime<Local>,
+    hours: &WorkingHours,
+) -> Vec<FreeGap> {
+    let mut busy: Vec<_> = events.iter().filter_map(timed_span).collect();
+    busy.sort();
+
+    let mut free = Vec::new();
+    let mut cursor = from;
+    for (start, end) in busy {
+        if start > cursor {
+            free.push((cursor, start.min(to)));
+        }
+        cursor = cursor.max(end);
+        if cursor >= to {
+            break;
+        }
+    }
+    if cursor < to {
+        free.push((cursor, to));
+    }
+
+    free.into_iter()
+        .flat_map(|(start, end)| hours.clip(start, end))
+        .map(|(start, end)| FreeGap {
+            start,
+            end,
+            minutes: (end - start).num_minutes(),
+        })
+        .filter(|gap| gap.minutes > 0)
+        .collect()
+}
+
+/// End of the block of back-to-back meetings in progress (or about to start)
+/// at `now`; `None` if the next stretch is free.
+pub fn busy_until(events: &[CalendarEvent], now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let mut busy: Vec<_> = events
+        .iter()
+        .filter_map(timed_span)
+        .filter(|(_, end)| *end > now)
+        .collect();
+    busy.sort();
+
+    let slack = Duration::minutes(MIN_FREE_MINUTES);
+    let mut until: Option<DateTime<Local>> = None;
+    for (start, end) in busy {
+        if start > until.unwrap_or(now) + slack {
+            break;
+        }
+        until = Some(until.map_or(end, |u| u.max(end)));
+    }
+    until
+}
+
+/// Timed events starting on `date` that `keep` accepts, in start order.
+fn events_on(
+    events: &[CalendarEvent],
+    date: NaiveDate,
+    keep: impl Fn(DateTime<Local>, DateTime<Local>) -> bool,
+) -> Vec<CalendarEvent> {
+    let mut listed: Vec<CalendarEvent> = events
+        .iter()
+        .filter(|event| match timed_span(event) {
+            Some((start, end)) => start.date_naive() == date && keep(start, end),
+            None => false,
+        })
+        .cloned()
+        .collect();
+    listed.sort_by_key(|e| e.start_time());
+    listed
+}
+
+fn meeting_count(count: usize) -> String {
+    let noun = if count == 1 { "meeting" } else { "meetings" };
+    format!("{} {}", count, noun)
+}
+
+/// "09:00 Standup, 14:00 Review".
+fn list_items(events: &[CalendarEvent], format: &FormatSettings) -> String {
+    let items: Vec<String> = events
+        .iter()
+        .filter_map(|e| {
+            Some(format!(
+                "{} {}",
+                format_time(format, e.start_time()?),
+                e.title
+            ))
+        })
+        .collect();
+    items.join(", ")
+}
+
+pub fn compose_digest(
+    events: &[CalendarEvent],
+    date: NaiveDate,
+    hours: &WorkingHours,
+    include_outside_hours: bool,
+    format: &FormatSettings,
+    budget: Option<DayUsage>,
+) -> Digest {
+    let listed = events_on(events, date, |start, end| {
+        include_outside_hours || hours.overlaps(start, end)
+    });
+
+    let mut summary = if listed.is_empty() {
+        "No meetings today".to_string()
+    } else {
+        format!(
+            "{} today: {}",
+            meeting_count(listed.len()),
+            list_items(&listed, format)
+        )
+    };
+
+    if let Some(usage) = budget.as_ref().filter(|_| !listed.is_empty()) {
+        summary.push_str(&format!(". {}", usage.summary));
+    }
+
+    Digest {
+        date,
+        events: listed,
+        summary,
+        budget,
+    }
+}
+
+pub fn compose_preview(
+    events: &[CalendarEvent],
+    date: NaiveDate,
+    hours: &WorkingHours,
+    preview: &PreviewSettings,
+    format: &FormatSettings,
+    budget: Option<DayUsage>,
+) -> Preview {
+    let listed = events_on(events, date, |_, _| true);
+    let early = |start: DateTime<Local>| start.time() < preview.early_before;
+    let flagged: Vec<CalendarEvent> = listed
+        .iter()
+        .filter(|e| match timed_span(e) {
+            Some((start, end)) => early(start) || !hours.overlaps(start, end),
+            None => false,
+        })
+        .cloned()
+        .collect();
+
+    let mut summary = match listed.first().and_then(CalendarEvent::start_time) {
+        None => "No meetings tomorrow".to_string(),
+        Some(first) => {
+            let mut summary = format!(
+                "{} tomorrow, first meeting {}",
+                meeting_count(listed.len()),
+                format_time(format, first)
+            );
+            if early(first) {
+                summary.push_str(" — set an alarm");
+            }
+            if !flagged.is_empty() {
+                summary.push_str(&format!(
+                    ". Outside usual hours: {}",
+                    list_items(&flagged, format)
+                ));
+            }
+            summary
+        }
+    };
+
+    if let Some(usage) = budget.as_ref().filter(|_| !listed.is_empty()) {
+        summary.push_str(&format!(". {}", usage.summary));
+    }
+
+    Preview {
+        date,
+        events: listed,
+        flagged,
+        summary,
+        budget,
+    }
+}
+
+pub fn send_digest(app: &AppHandle) {
+    let settings = settings::current(app);
+    let events = scheduler::cached_events(app);
+    let today = simulate::now().date_naive();
+    let digest = compose_digest(
+        &events,
+        today,
+        &settings.working_hours,
+        false,
+        &settings.format,
+        budget::day_usage(&settings, &events, today),
+    );
+    notify::show(app, "Today's meetings", &digest.summary);
+}
+
+/// Tomorrow's events come from a ranged fetch; the scheduler's cache only
+/// reaches 24 hours ahead.
+fn tomorrow_preview(app: &AppHandle) -> Option<Preview> {
+    let settings = settings::current(app);
+    let tomorrow = simulate::now().date_naive().succ_opt()?;
+    let from = end_of_day(simulate::now().date_naive())?;
+    let to = end_of_day(tomorrow)?;
+    let mut events = calendar::fetch_events_between(from, to);
+    events.retain(|e| !settings.calendar_filter.excludes(e));
+    Some(compose_preview(
+        &events,
+        tomorrow,
+        &settings.working_hours,
+        &settings.tomorrow_preview,
+        &settings.format,
+        budget::day_usage(&settings, &events, tomorrow),
+    ))
+}
+
+/// Skipped when tomorrow is empty on the configured days (by default
+/// Fridays), so there's no "no meetings tomorrow" before every weekend.
+pub fn send_tomorrow_preview(app: &AppHandle) {
+    let Some(preview) = tomorrow_preview(app) else {
+        return;
+    };
+    let settings = settings::current(app);
+    let today = simulate::now().weekday();
+    if preview.events.is_empty() && settings.tomorrow_preview.skip_if_empty_on.contains(&today) {
+        return;
+    }
+    notify::show(app, "Tomorrow", &preview.summary);
+}
+
+/// Sends the preview now, ignoring the schedule and the empty-day rule.
+#[tauri::command]
+pub async fn trigger_tomorrow_preview(app: AppHandle) -> Result<Preview, String> {
+    let handle = app.clone();
+    let preview = tauri::async_runtime::spawn_blocking(move || tomorrow_preview(&handle))
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Tomorrow is out of range".to_string())?;
+    notify::show(&app, "Tomorrow", &preview.summary);
+    Ok(preview)
+}
+
+#[tauri::command]
+pub fn get_free_gaps(app: AppHandle) -> Vec<FreeGap> {
+    let settings = settings::current(&app);
+    let now = simulate::now();
+    let Some(until) = end_of_day(now.date_naive()) else {
+        return Vec::new();
+    };
+    free_gaps(
+        &scheduler::cached_events(&app),
+        now,
+        until,
+        &settings.working_hours,
+    )
+}
+
+#[tauri::command]
+pub fn get_digest(app: AppHandle, include_outside_hours: Option<bool>) -> Digest {
+    let settings = settings::current(&app);
+    let events = scheduler::cached_events(&app);
+    let today = simulate::now().date_naive();
+    compose_digest(
+        &events,
+        today,
+        &settings.working_hours,
+        include_outside_hours.unwrap_or(false),
+        &settings.format,
+        budget::day_usage(&settings, &events, today),
+    )
+}
@@ -0,0 +1,869 @@
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+use tauri::ipc::Invoke;
+use tauri::Runtime;
+
+use crate::ipc::IpcError;
+use crate::{
+    accessibility, actions, agenda, appearance, attendance, audio, backup, budget, bulk, calendar,
+    dismissal, explain, features, health, history, journal, lateness, location, notes, power,
+    presentation, profiles, recent, retention, rules, selftest, settings, sources, startup,
+    webhooks, week, window,
+};
+
+// --- API Version ---
+//
+// The frontend and the backend can skew while developing or after a partial
+// update. The frontend sends the API version it was written against with
+// every call, as the `api-version` header; a call from a version this
+// backend can't serve is refused with `incompatible_client` before the
+// command runs, instead of failing later on a payload that doesn't fit.
+// Calls without the header (older frontends) are let through.
+// `API_VERSION` goes up with every change that breaks existing callers;
+// `MIN_CLIENT_VERSION` is the oldest frontend still served. The command list
+// in the manifest is generated by build.rs from its `COMMANDS`.
+//
+// Every command's payloads are in `PAYLOADS` below: its arguments, by the
+// camelCase names the frontend sends, what it returns, and what a rejected
+// call carries. The manifest has their JSON schemas, and
+// `src/api-types.d.ts` the same types for the frontend, written by
+// `cargo api-types`; the tests check the file is current and that every
+// command's arguments round-trip through their schema.
+
+pub const API_VERSION: u32 = 1;
+pub const MIN_CLIENT_VERSION: u32 = 1;
+const VERSION_HEADER: &str = "api-version";
+
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/commands.rs"));
+}
+
+#[derive(Clone, Copy, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiVersion {
+    pub version: u32,
+    pub min_client_version: u32,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiManifest {
+    #[serde(flatten)]
+    pub version: ApiVersion,
+    pub app_version: &'static str,
+    pub commands: &'static [&'static str],
+    pub payloads: Vec<CommandPayload>,
+    /// The types the payload schemas refer to (`#/$defs/<name>`).
+    #[serde(rename = "$defs")]
+    pub definitions: Map<String, Value>,
+}
+
+/// A command's JSON schemas.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandPayload {
+    pub command: &'static str,
+    /// An object of the arguments; optional ones may be left out.
+    pub args: Schema,
+    pub response: Schema,
+    /// What a rejected call carries, if it can be rejected.
+    pub error: Option<Schema>,
+}
+
+/// One entry of `PAYLOADS`.
+struct PayloadSpec {
+    command: &'static str,
+    args: fn(&mut SchemaGenerator) -> Vec<(&'static str, Schema)>,
+    response: fn(&mut SchemaGenerator) -> Schema,
+    error: Option<fn(&mut SchemaGenerator) -> Schema>,
+    /// Reads the arguments into their types and writes them back.
+    #[cfg(test)]
+    round_trip: fn(&Value) -> Result<Value, String>,
+}
+
+/// `command(argument: Type, ...) -> Response` or `-> Response | Error`.
+macro_rules! payloads {
+    ($($command:ident($($arg:ident: $ty:ty),*) -> $response:ty $(| $error:ty)?;)*) => {
+        &[$(PayloadSpec {
+            command: stringify!($command),
+            args: |generator| {
+                // Unused by commands without arguments.
+                let _ = &generator;
+                vec![$((stringify!($arg), generator.subschema_for::<$ty>())),*]
+            },
+            response: |generator| generator.subschema_for::<$response>(),
+            error: payloads!(@error $($error)?),
+            #[cfg(test)]
+            round_trip: |args| {
+                let _ = &args;
+                #[allow(unused_mut)]
+                let mut out = Map::new();
+                $(
+                    let name = camel_case(stringify!($arg));
+                    let value = args.get(&name).cloned().unwrap_or(Value::Null);
+                    let value: $ty = serde_json::from_value(value)
+                        .map_err(|e| format!("{}: {}", name, e))?;
+                    out.insert(name, serde_json::to_value(value).map_err(|e| e.to_string())?);
+                )*
+                Ok(Value::Object(out))
+            },
+        }),*]
+    };
+    (@error) => { None };
+    (@error $error:ty) => { Some(|generator| generator.subschema_for::<$error>()) };
+}
+
+/// Commands that return nothing return `()`; `get_calendar_events` returns
+/// its page as a JSON string.
+const PAYLOADS: &[PayloadSpec] = payloads! {
+    get_calendar_events(
+        limit: Option<usize>,
+        offset: Option<usize>,
+        include_details: Option<bool>,
+        include_filtered: Option<bool>
+    ) -> String | String;
+    get_calendars() -> Vec<calendar::CalendarInfo>;
+    get_event_details(event_id: String, occurrence_start: Option<String>)
+        -> calendar::CalendarEventDetails | calendar::DetailsError;
+    enter_alert_mode(event: Option<Value>) -> ();
+    exit_alert_mode() -> ();
+    set_pill_mode(enabled: bool) -> ();
+    open_link(url: String) -> () | String;
+    open_in_calendar(event_id: String) -> ();
+    get_settings() -> settings::Settings | IpcError;
+    save_settings(settings: settings::Settings) -> () | IpcError;
+    preview_template(template: String) -> String | String;
+    get_alert_history() -> Vec<history::HistoryEntry>;
+    get_punctuality_insights() -> Vec<history::PunctualityInsight>;
+    get_recent_meetings(limit: Option<usize>) -> Vec<recent::RecentMeeting>;
+    dismiss_alert() -> () | String;
+    request_dismiss(event_id: String) -> Option<dismissal::ChallengePrompt> | String;
+    confirm_dismiss(event_id: String, answer: String) -> () | String;
+    begin_escape() -> ();
+    end_escape() -> bool;
+    snooze_alert(minutes: Option<i64>) -> () | String;
+    join_meeting(url: String) -> () | String;
+    get_active_alert() -> Option<calendar::CalendarEvent>;
+    get_scheduler_state() -> journal::PendingWork;
+    test_webhook(url: String) -> u16 | IpcError;
+    get_integrations_status() -> webhooks::IntegrationsStatus | IpcError;
+    get_health() -> health::HealthReport | String;
+    run_self_test() -> selftest::SelfTestReport;
+    get_system_appearance() -> appearance::SystemAppearance;
+    get_overlay_opacity() -> f64;
+    end_focus_grace() -> ();
+    load_simulation(path: String) -> usize | IpcError;
+    minimize_alert_to_corner() -> () | String;
+    restore_alert_fullscreen() -> () | String;
+    get_window_pool_stats() -> window::WindowPoolStats;
+    get_sources() -> Vec<sources::SourceStatus>;
+    refresh_source(id: String) -> () | String;
+    set_source_enabled(id: String, enabled: bool) -> () | IpcError;
+    adopt_series_settings(old_id: String, new_id: String) -> usize | IpcError;
+    get_alert_rules() -> Vec<rules::AlertRule>;
+    save_alert_rule(rule: rules::AlertRule) -> rules::AlertRule | IpcError;
+    delete_alert_rule(id: String) -> () | IpcError;
+    simulate_rule_actions(rule_id: String) -> Vec<rules::SimulatedStep> | String;
+    reset_auto_tuned_rules() -> () | IpcError;
+    dispatch_action(action: String, args: Option<Value>) -> Value | actions::ActionError;
+    list_actions() -> Vec<&'static actions::Action>;
+    get_free_gaps() -> Vec<agenda::FreeGap>;
+    get_digest(include_outside_hours: Option<bool>) -> agenda::Digest;
+    get_meeting_budget_status() -> budget::BudgetStatus;
+    trigger_tomorrow_preview() -> agenda::Preview | String;
+    list_profiles() -> profiles::ProfileList;
+    switch_profile(name: String) -> () | IpcError;
+    save_current_as_profile(name: String) -> () | IpcError;
+    export_backup(path: String) -> () | IpcError;
+    import_backup(path: String) -> backup::ImportResult | IpcError;
+    rollback_import(snapshot_path: Option<String>) -> PathBuf | IpcError;
+    classify_location(event_id: String) -> location::LocationInfo | String;
+    open_map(event_id: String) -> () | String;
+    get_power_state() -> power::PowerState;
+    get_presentation_status() -> presentation::PresentationStatus;
+    set_lateness_annotation(enabled: bool, threshold_minutes: i64, mode: lateness::LatenessMode)
+        -> () | IpcError;
+    get_accessibility_prefs() -> accessibility::AccessibilityPrefs;
+    mute_event(event_id: String, occurrence_start: String) -> () | String;
+    unmute_event(event_id: String, occurrence_start: String) -> () | String;
+    force_alert(event_id: String, occurrence_start: String, lead_minutes: i64) -> () | String;
+    pause_until_free() -> chrono::DateTime<chrono::Local> | String;
+    resume_reminders() -> ();
+    get_week_agenda(week_offset: i32) -> week::WeekAgenda | String;
+    explain_event(event_id: String, occurrence_start: String) -> explain::Explanation | String;
+    open_dashboard() -> ();
+    export_attendance(
+        range: attendance::AttendanceRange,
+        format: attendance::ExportFormat,
+        hash_titles: Option<bool>
+    ) -> String | IpcError;
+    get_storage_usage() -> retention::StorageUsage | IpcError;
+    compact_storage() -> retention::CompactResult | IpcError;
+    set_event_note(
+        event_id: String,
+        occurrence: String,
+        text: String,
+        sticky: Option<bool>,
+        kept: Option<bool>
+    ) -> Option<notes::EventNote> | String;
+    get_event_note(event_id: String, occurrence: String) -> Option<notes::EventNote> | String;
+    enable_board_mode(display_name: String) -> () | IpcError;
+    disable_board_mode() -> () | IpcError;
+    get_api_version() -> ApiVersion;
+    get_api_manifest() -> ApiManifest;
+    reveal_event(event_id: String, occurrence_start: Option<String>)
+        -> calendar::CalendarEvent | IpcError;
+    rebuild_tray() -> () | IpcError;
+    get_startup_timings() -> startup::StartupTimings;
+    get_feature_flags() -> Vec<features::FeatureFlag>;
+    set_feature_flag(name: String, enabled: bool) -> features::FeatureFlag | IpcError;
+    get_whats_new() -> Option<features::WhatsNew> | IpcError;
+    get_audio_outputs() -> Vec<audio::OutputDevice>;
+    set_contact_timezone(email: String, zone: String) -> () | IpcError;
+    apply_rules_bulk(items: Vec<(bulk::RuleSelector, rules::AlertRule)>, mode: Option<bulk::BulkMode>)
+        -> bulk::BulkReport | IpcError;
+    mute_series_bulk(selectors: Vec<bulk::SeriesSelector>, mode: Option<bulk::BulkMode>)
+        -> bulk::BulkReport | IpcError;
+    add_ics_subscriptions_bulk(urls: Vec<String>, mode: Option<bulk::BulkMode>)
+        -> bulk::BulkReport | IpcError;
+    copy_from_profile(name: String, mode: Option<bulk::BulkMode>) -> bulk::BulkReport | IpcError;
+};
+
+/// What Tauri calls an argument on the wire: `event_id` is `eventId`.
+fn camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Whether `schema` lets the value be null, as it does for an `Option`.
+fn is_nullable(schema: &Schema) -> bool {
+    let allows_null = |schema: &Value| match schema.get("type") {
+        Some(Value::String(kind)) => kind == "null",
+        Some(Value::Array(kinds)) => kinds.iter().any(|kind| kind == "null"),
+        _ => false,
+    };
+    let value = schema.as_value();
+    allows_null(value)
+        || ["anyOf", "oneOf"].iter().any(|key| {
+            value
+                .get(key)
+                .and_then(Value::as_array)
+                .is_some_and(|arms| arms.iter().any(allows_null))
+        })
+}
+
+fn payload(spec: &PayloadSpec, generator: &mut SchemaGenerator) -> CommandPayload {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for (name, schema) in (spec.args)(generator) {
+        let name = camel_case(name);
+        if !is_nullable(&schema) {
+            required.push(Value::String(name.clone()));
+        }
+        properties.insert(name, schema.to_value());
+    }
+    let args = json!({ "type": "object", "properties": properties, "required": required });
+    CommandPayload {
+        command: spec.command,
+        args: Schema::try_from(args).expect("an object is a schema"),
+        response: (spec.response)(generator),
+        error: spec.error.map(|error| error(generator)),
+    }
+}
+
+fn current() -> ApiVersion {
+    ApiVersion {
+        version: API_VERSION,
+        min_client_version: MIN_CLIENT_VERSION,
+    }
+}
+
+/// Whether a frontend declaring `client` can be served.
+fn compatible(client: &str) -> bool {
+    client
+        .trim()
+        .parse::<u32>()
+        .is_ok_and(|v| (MIN_CLIENT_VERSION..=API_VERSION).contains(&v))
+}
+
+/// Wraps the command handler so calls from incompatible frontends, and
+/// calls made while the app is still starting, are refused before they
+/// reach any command.
+pub fn checked<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke: Invoke<R>| {
+        let client = invoke
+            .message
+            .headers()
+            .get(VERSION_HEADER)
+            .map(|value| value.to_str().unwrap_or_default().to_string());
+        match client {
+            Some(client) if !compatible(&client) => {
+                retention::log!(
+                    "{} refused for API version {}",
+                    invoke.message.command(),
+                    client
+                );
+                invoke.resolver.reject(IpcError::IncompatibleClient {
+                    client,
+                    backend: API_VERSION,
+                    min_client: MIN_CLIENT_VERSION,
+                });
+                true
+            }
+            _ if !startup::is_ready()
+                && !startup::SERVED_WHILE_STARTING.contains(&invoke.message.command()) =>
+            {
+                let command = invoke.message.command().to_string();
+                invoke.resolver.reject(IpcError::Starting { command });
+                true
+            }
+            _ => handler(invoke),
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_api_version() -> ApiVersion {
+    current()
+}
+
+/// The version and every command the backend has.
+#[tauri::command]
+pub fn get_api_manifest() -> ApiManifest {
+    let mut generator = SchemaGenerator::default();
+    let payloads = PAYLOADS
+        .iter()
+        .map(|spec| payload(spec, &mut generator))
+        .collect();
+    ApiManifest {
+        version: current(),
+        app_version: env!("CARGO_PKG_VERSION"),
+        commands: generated::COMMANDS,
+        payloads,
+        definitions: generator.take_definitions(true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    const TYPES_FILE: &str = "../src/api-types.d.ts";
+    /// How deep examples go before leaving out what they can.
+    const EXAMPLE_DEPTH: usize = 6;
+
+    fn definition<'a>(defs: &'a Map<String, Value>, reference: &str) -> &'a Value {
+        let name = reference.trim_start_matches("#/$defs/");
+        defs.get(name)
+            .unwrap_or_else(|| panic!("no definition for {}", reference))
+    }
+
+    /// A value `schema` accepts, with every property it knows of filled in
+    /// down to `EXAMPLE_DEPTH`.
+    fn example(schema: &Value, defs: &Map<String, Value>, depth: usize) -> Value {
+        let Some(object) = schema.as_object() else {
+            return Value::Null;
+        };
+        if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+            return example(definition(defs, reference), defs, depth);
+        }
+        if let Some(value) = object.get("const") {
+            return value.clone();
+        }
+        if let Some(first) = object.get("enum").and_then(|e| e.get(0)) {
+            return first.clone();
+        }
+        let mut value = match object.get("type") {
+            Some(Value::String(kind)) => example_of(kind, object, defs, depth),
+            Some(Value::Array(kinds)) => {
+                let kind = kinds
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .find(|kind| *kind != "null" && depth < EXAMPLE_DEPTH)
+                    .unwrap_or("null");
+                example_of(kind, object, defs, depth)
+            }
+            _ => Value::Null,
+        };
+        for key in ["oneOf", "anyOf"] {
+            let Some(arms) = object.get(key).and_then(Value::as_array) else {
+                continue;
+            };
+            let null = arms
+                .iter()
+                .find(|arm| arm.get("type") == Some(&json!("null")));
+            let arm = match null {
+                Some(null) if depth >= EXAMPLE_DEPTH => null,
+                _ => arms
+                    .iter()
+                    .find(|arm| Some(*arm) != null)
+                    .unwrap_or(&arms[0]),
+            };
+            merge(&mut value, example(arm, defs, depth));
+        }
+        for part in object
+            .get("allOf")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            merge(&mut value, example(part, defs, depth));
+        }
+        value
+    }
+
+    fn example_of(
+        kind: &str,
+        object: &Map<String, Value>,
+        defs: &Map<String, Value>,
+        depth: usize,
+    ) -> Value {
+        match kind {
+            "object" => {
+                let required: Vec<&str> = object
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .collect();
+                let mut out = Map::new();
+                let properties = object.get("properties").and_then(Value::as_object);
+                for (name, property) in properties.into_iter().flatten() {
+                    if depth < EXAMPLE_DEPTH || required.contains(&name.as_str()) {
+                        out.insert(name.clone(), example(property, defs, depth + 1));
+                    }
+                }
+                Value::Object(out)
+            }
+            "array" => match object.get("prefixItems").and_then(Value::as_array) {
+                Some(items) => items
+                    .iter()
+                    .map(|item| example(item, defs, depth + 1))
+                    .collect(),
+                None if depth < EXAMPLE_DEPTH => match object.get("items") {
+                    Some(item) => json!([example(item, defs, depth + 1)]),
+                    None => json!([]),
+                },
+                None => json!([]),
+            },
+            "string" => match object.get("format").and_then(Value::as_str) {
+                Some("date-time") => json!("2026-03-02T09:00:00+01:00"),
+                Some("date") => json!("2026-03-02"),
+                Some("partial-time") => json!("09:00:00"),
+                Some("partial-date-time") => json!("2026-03-02T09:00:00"),
+                _ => json!("x"),
+            },
+            "integer" => object.get("minimum").cloned().unwrap_or(json!(1)),
+            "number" => json!(1.5),
+            "boolean" => json!(true),
+            _ => Value::Null,
+        }
+    }
+
+    /// What `from` adds to an object; anything else replaces `into`.
+    fn merge(into: &mut Value, from: Value) {
+        match (into, from) {
+            (Value::Object(into), Value::Object(from)) => into.extend(from),
+            (_, Value::Null) => {}
+            (into, from) => *into = from,
+        }
+    }
+
+    /// Checks `value` against the parts of JSON Schema that schemars emits.
+    fn validate(
+        value: &Value,
+        schema: &Value,
+        defs: &Map<String, Value>,
+        at: &str,
+    ) -> Result<(), String> {
+        let object = match schema {
+            Value::Bool(true) => return Ok(()),
+            Value::Bool(false) => return Err(format!("{}: nothing is allowed", at)),
+            Value::Object(object) => object,
+            _ => return Err(format!("{}: not a schema", at)),
+        };
+        if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+            validate(value, definition(defs, reference), defs, at)?;
+        }
+        if let Some(expected) = object.get("const") {
+            if value != expected {
+                return Err(format!("{}: {} is not {}", at, value, expected));
+            }
+        }
+        if let Some(options) = object.get("enum").and_then(Value::as_array) {
+            if !options.contains(value) {
+                return Err(format!("{}: {} is not one of {:?}", at, value, options));
+            }
+        }
+        let kinds: Vec<&str> = match object.get("type") {
+            Some(Value::String(kind)) => vec![kind.as_str()],
+            Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !kinds.is_empty() && !kinds.iter().any(|kind| is_kind(value, kind)) {
+            return Err(format!("{}: {} is not {:?}", at, value, kinds));
+        }
+        if let Value::Object(fields) = value {
+            let properties = object.get("properties").and_then(Value::as_object);
+            for name in object
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let name = name.as_str().unwrap_or_default();
+                if !fields.contains_key(name) {
+                    return Err(format!("{}: {} is missing", at, name));
+                }
+            }
+            for (name, field) in fields {
+                let path = format!("{}.{}", at, name);
+                match (
+                    properties.and_then(|p| p.get(name)),
+                    object.get("additionalProperties"),
+                ) {
+                    (Some(property), _) => validate(field, property, defs, &path)?,
+                    (None, Some(extra)) => validate(field, extra, defs, &path)?,
+                    (None, None) => {}
+                }
+            }
+        }
+        if let Value::Array(items) = value {
+            let prefix = object.get("prefixItems").and_then(Value::as_array);
+            for (i, item) in items.iter().enumerate() {
+                let path = format!("{}[{}]", at, i);
+                match (prefix.and_then(|p| p.get(i)), object.get("items")) {
+                    (Some(item_schema), _) | (None, Some(item_schema)) => {
+                        validate(item, item_schema, defs, &path)?
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+        for part in object
+            .get("allOf")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            validate(value, part, defs, at)?;
+        }
+        if let Some(arms) = object.get("anyOf").and_then(Value::as_array) {
+            if !arms
+                .iter()
+                .any(|arm| validate(value, arm, defs, at).is_ok())
+            {
+                return Err(format!("{}: {} matches none of anyOf", at, value));
+            }
+        }
+        if let Some(arms) = object.get("oneOf").and_then(Value::as_array) {
+            let matching = arms
+                .iter()
+                .filter(|arm| validate(value, arm, defs, at).is_ok())
+                .count();
+            if matching != 1 {
+                return Err(format!("{}: {} matches {} of oneOf", at, value, matching));
+            }
+        }
+        Ok(())
+    }
+
+    fn is_kind(value: &Value, kind: &str) -> bool {
+        match kind {
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn every_command_has_its_payloads() {
+        let mut listed: Vec<&str> = PAYLOADS.iter().map(|spec| spec.command).collect();
+        let mut commands = generated::COMMANDS.to_vec();
+        listed.sort();
+        commands.sort();
+        assert_eq!(listed, commands);
+    }
+
+    #[test]
+    fn arguments_round_trip_through_their_schemas() {
+        let manifest = get_api_manifest();
+        for (spec, payload) in PAYLOADS.iter().zip(&manifest.payloads) {
+            let schema = payload.args.as_value();
+            let at = payload.command;
+            let args = example(schema, &manifest.definitions, 0);
+            validate(&args, schema, &manifest.definitions, at)
+                .unwrap_or_else(|e| panic!("example doesn't fit: {}", e));
+            let read = (spec.round_trip)(&args).unwrap_or_else(|e| panic!("{}: {}", at, e));
+            validate(&read, schema, &manifest.definitions, at)
+                .unwrap_or_else(|e| panic!("written back: {}", e));
+            assert_eq!((spec.round_trip)(&read).as_ref(), Ok(&read), "{}", at);
+        }
+    }
+
+    #[test]
+    fn camel_cases_like_tauri() {
+        assert_eq!(camel_case("event_id"), "eventId");
+        assert_eq!(camel_case("include_outside_hours"), "includeOutsideHours");
+        assert_eq!(camel_case("url"), "url");
+    }
+
+    // --- TypeScript ---
+
+    fn type_name(reference: &str) -> String {
+        reference
+            .trim_start_matches("#/$defs/")
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    fn literal(value: &Value) -> String {
+        match value {
+            Value::String(_) | Value::Number(_) | Value::Bool(_) | Value::Null => value.to_string(),
+            _ => "unknown".to_string(),
+        }
+    }
+
+    fn comment(out: &mut String, schema: &Value, indent: &str) {
+        if let Some(text) = schema.get("description").and_then(Value::as_str) {
+            out.push_str(&format!("{}/**\n", indent));
+            for line in text.lines() {
+                out.push_str(&format!("{} * {}\n", indent, line).replace(" \n", "\n"));
+            }
+            out.push_str(&format!("{} */\n", indent));
+        }
+    }
+
+    fn union(parts: Vec<String>) -> String {
+        let mut unique: Vec<String> = Vec::new();
+        for part in parts {
+            if !unique.contains(&part) {
+                unique.push(part);
+            }
+        }
+        match unique.len() {
+            0 => "never".to_string(),
+            1 => unique.remove(0),
+            _ => unique.join(" | "),
+        }
+    }
+
+    fn ts_type(schema: &Value, indent: &str) -> String {
+        let Some(object) = schema.as_object() else {
+            return "unknown".to_string();
+        };
+        if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+            return type_name(reference);
+        }
+        if let Some(value) = object.get("const") {
+            return literal(value);
+        }
+        if let Some(options) = object.get("enum").and_then(Value::as_array) {
+            return union(options.iter().map(literal).collect());
+        }
+        let mut parts = Vec::new();
+        match object.get("type") {
+            Some(Value::String(kind)) => parts.push(ts_kind(kind, object, indent)),
+            Some(Value::Array(kinds)) => {
+                for kind in kinds.iter().filter_map(Value::as_str) {
+                    parts.push(ts_kind(kind, object, indent));
+                }
+            }
+            _ => {}
+        }
+        let base = union(parts);
+        let mut alternatives = Vec::new();
+        for key in ["oneOf", "anyOf"] {
+            for arm in object
+                .get(key)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                alternatives.push(ts_type(arm, indent));
+            }
+        }
+        let mut all: Vec<String> = object
+            .get("allOf")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|part| ts_type(part, indent))
+            .collect();
+        if !alternatives.is_empty() {
+            if base == "never" && all.is_empty() {
+                return union(alternatives);
+            }
+            all.push(format!("({})", union(alternatives)));
+        }
+        match (base.as_str(), all.is_empty()) {
+            ("never", true) => "unknown".to_string(),
+            ("never", false) => all.join(" & "),
+            (_, true) => base,
+            (_, false) => format!("{} & {}", base, all.join(" & ")),
+        }
+    }
+
+    fn ts_kind(kind: &str, object: &Map<String, Value>, indent: &str) -> String {
+        match kind {
+            "null" => "null".to_string(),
+            "boolean" => "boolean".to_string(),
+            "string" => "string".to_string(),
+            "number" | "integer" => "number".to_string(),
+            "array" => match (object.get("prefixItems"), object.get("items")) {
+                (Some(Value::Array(items)), _) => format!(
+                    "[{}]",
+                    items
+                        .iter()
+                        .map(|item| ts_type(item, indent))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                (_, Some(item)) => {
+                    let item = ts_type(item, indent);
+                    if item.contains(' ') {
+                        format!("({})[]", item)
+                    } else {
+                        format!("{}[]", item)
+                    }
+                }
+                _ => "unknown[]".to_string(),
+            },
+            "object" => ts_object(object, indent),
+            _ => "unknown".to_string(),
+        }
+    }
+
+    fn ts_object(object: &Map<String, Value>, indent: &str) -> String {
+        let properties = object.get("properties").and_then(Value::as_object);
+        let extra = object
+            .get("additionalProperties")
+            .filter(|extra| **extra != Value::Bool(false));
+        if properties.is_none_or(|p| p.is_empty()) {
+            return match extra {
+                Some(extra) => format!("{{ [key: string]: {} }}", ts_type(extra, indent)),
+                None if object.contains_key("properties") => "Record<string, never>".to_string(),
+                None => "{ [key: string]: unknown }".to_string(),
+            };
+        }
+        let required: Vec<&str> = object
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let inner = format!("{}  ", indent);
+        let mut out = "{\n".to_string();
+        for (name, property) in properties.into_iter().flatten() {
+            comment(&mut out, property, &inner);
+            let optional = if required.contains(&name.as_str()) {
+                ""
+            } else {
+                "?"
+            };
+            out.push_str(&format!(
+                "{}{}{}: {};\n",
+                inner,
+                name,
+                optional,
+                ts_type(property, &inner)
+            ));
+        }
+        if let Some(extra) = extra {
+            out.push_str(&format!(
+                "{}[key: string]: {};\n",
+                inner,
+                ts_type(extra, &inner)
+            ));
+        }
+        out.push_str(indent);
+        out.push('}');
+        out
+    }
+
+    fn typescript() -> String {
+        let manifest = get_api_manifest();
+        let mut out = format!(
+            "// Generated by `cargo api-types` from the command payloads in\n\
+             // src-tauri/src/api.rs; don't edit.\n\
+             \n\
+             export const API_VERSION = {};\n\
+             export const MIN_CLIENT_VERSION = {};\n",
+            API_VERSION, MIN_CLIENT_VERSION
+        );
+        let mut names: Vec<&String> = manifest.definitions.keys().collect();
+        names.sort();
+        for name in names {
+            let schema = &manifest.definitions[name];
+            out.push('\n');
+            comment(&mut out, schema, "");
+            out.push_str(&format!(
+                "export type {} = {};\n",
+                type_name(name),
+                ts_type(schema, "")
+            ));
+        }
+        out.push_str("\n/** Every command: what it takes, returns, and is rejected with. */\n");
+        out.push_str("export interface Commands {\n");
+        for payload in &manifest.payloads {
+            let error = payload.error.as_ref().map_or("never".to_string(), |error| {
+                ts_type(error.as_value(), "    ")
+            });
+            out.push_str(&format!(
+                "  {}: {{\n    args: {};\n    response: {};\n    error: {};\n  }};\n",
+                payload.command,
+                ts_type(payload.args.as_value(), "    "),
+                ts_type(payload.response.as_value(), "    "),
+                error
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn types_file() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(TYPES_FILE)
+    }
+
+    #[test]
+    fn typescript_types_are_current() {
+        let written = fs::read_to_string(types_file()).unwrap_or_default();
+        assert!(
+            written == typescript(),
+            "{} is out of date; run `cargo api-types`",
+            TYPES_FILE
+        );
+    }
+
+    /// `cargo api-types`.
+    #[test]
+    #[ignore]
+    fn write_typescript_types() {
+        fs::write(types_file(), typescript()).unwrap();
+    }
+}
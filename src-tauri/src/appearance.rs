@@ -0,0 +1,101 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::accessibility;
+use crate::bus::{self, AppEvent};
+
+// --- System Appearance ---
+//
+// Dark or light mode and "Reduce Transparency", which the overlay opacity and
+// the alert's default theme follow. The scheduler polls it every tick, so a
+// switch at sunset is picked up within a second and published as
+// `appearance-changed`.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    #[default]
+    Dark,
+    Light,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, JsonSchema)]
+pub struct SystemAppearance {
+    pub mode: Mode,
+    pub reduce_transparency: bool,
+}
+
+impl SystemAppearance {
+    pub fn is_light(&self) -> bool {
+        self.mode == Mode::Light
+    }
+}
+
+/// The last appearance seen by `poll`.
+static CURRENT: Mutex<Option<SystemAppearance>> = Mutex::new(None);
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use std::ffi::{CStr, CString};
+
+    /// "Dark" is stored while dark mode is on, automatic mode included;
+    /// the key is absent in light mode.
+    pub fn is_dark() -> bool {
+        let Ok(key) = CString::new("AppleInterfaceStyle") else {
+            return false;
+        };
+        // SAFETY: autoreleased Foundation objects used within this call; the
+        // UTF-8 buffer is copied before they go away.
+        unsafe {
+            let key: *mut AnyObject =
+                msg_send![class!(NSString), stringWithUTF8String: key.as_ptr()];
+            let defaults: *mut AnyObject = msg_send![class!(NSUserDefaults), standardUserDefaults];
+            let Some(defaults) = defaults.as_ref() else {
+                return false;
+            };
+            let style: *mut AnyObject = msg_send![defaults, stringForKey: key];
+            let Some(style) = style.as_ref() else {
+                return false;
+            };
+            let utf8: *const std::ffi::c_char = msg_send![style, UTF8String];
+            !utf8.is_null()
+                && CStr::from_ptr(utf8)
+                    .to_bytes()
+                    .eq_ignore_ascii_case(b"dark")
+        }
+    }
+}
+
+fn read() -> SystemAppearance {
+    #[cfg(target_os = "macos")]
+    let dark = macos::is_dark();
+    #[cfg(not(target_os = "macos"))]
+    let dark = true;
+    SystemAppearance {
+        mode: if dark { Mode::Dark } else { Mode::Light },
+        reduce_transparency: accessibility::prefs().reduce_transparency,
+    }
+}
+
+pub fn current() -> SystemAppearance {
+    let mut current = CURRENT.lock().unwrap();
+    *current.get_or_insert_with(read)
+}
+
+/// Publishes `AppearanceChanged` when the system appearance has changed.
+pub fn poll(app: &AppHandle) {
+    let appearance = read();
+    let previous = CURRENT.lock().unwrap().replace(appearance);
+    if previous.is_some_and(|previous| previous != appearance) {
+        bus::publish(app, AppEvent::AppearanceChanged(appearance));
+    }
+}
+
+#[tauri::command]
+pub fn get_system_appearance() -> SystemAppearance {
+    current()
+}
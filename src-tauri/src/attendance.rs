@@ -0,0 +1,779 @@
+use chrono::{DateTime, Duration, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration as StdDuration;
+use tauri::{AppHandle, Manager, State, Window};
+
+use crate::calendar::CalendarEvent;
+use crate::ipc::{self, IpcError};
+use crate::privacy::{self, PrivacyMode};
+use crate::retention::{self, Coverage};
+use crate::{scheduler, settings, shutdown, simulate};
+
+// --- Attendance (persisted as JSON in the app data dir) ---
+//
+// When meetings were actually attended, for timesheets: while the microphone
+// or a camera is in use, or a meeting app's in-call process runs, the app
+// counts that as being in a call, and when the call ends books it against
+// the calendar events it overlapped. A call spanning back-to-back meetings
+// is split where the next one starts; a call outside any event isn't kept.
+// Opt-in; nothing is recorded or listened to, only whether the devices are
+// running.
+
+/// How often the devices are checked.
+const POLL_SECONDS: u64 = 15;
+/// Sessions of one event this close together are one session in the export,
+/// e.g. across a microphone that was briefly released.
+const MERGE_GAP_SECONDS: i64 = 60;
+const MAX_SESSIONS: usize = 5000;
+/// Zoom's in-meeting helper, running only while in a meeting.
+const MEETING_PROCESSES: [&str; 1] = ["CptHost"];
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct AttendanceSettings {
+    pub enabled: bool,
+    /// Sessions shorter than this, after merging, are left out of the
+    /// export as false positives.
+    pub min_session_seconds: i64,
+}
+
+impl Default for AttendanceSettings {
+    fn default() -> Self {
+        AttendanceSettings {
+            enabled: false,
+            min_session_seconds: 60,
+        }
+    }
+}
+
+/// What showed that a call was on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallSource {
+    Microphone,
+    Camera,
+    Process,
+}
+
+impl CallSource {
+    fn name(self) -> &'static str {
+        match self {
+            CallSource::Microphone => "microphone",
+            CallSource::Camera => "camera",
+            CallSource::Process => "process",
+        }
+    }
+}
+
+/// Part of a call, booked against one event.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceSession {
+    pub event_id: String,
+    pub title: String,
+    pub event_start: DateTime<Local>,
+    pub event_end: DateTime<Local>,
+    pub joined_at: DateTime<Local>,
+    pub left_at: DateTime<Local>,
+    pub sources: Vec<CallSource>,
+}
+
+/// Oldest first.
+pub struct AttendanceState(pub Mutex<Vec<AttendanceSession>>);
+
+/// The call going on, if any.
+struct Call {
+    since: DateTime<Local>,
+    last_seen: DateTime<Local>,
+    sources: Vec<CallSource>,
+}
+
+static CALL: Mutex<Option<Call>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AttendanceRange {
+    pub from: DateTime<Local>,
+    pub to: DateTime<Local>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// One line of the timesheet.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AttendanceRow {
+    event_id: String,
+    title: String,
+    event_start: DateTime<Local>,
+    event_end: DateTime<Local>,
+    joined_at: DateTime<Local>,
+    left_at: DateTime<Local>,
+    minutes: i64,
+    sources: Vec<CallSource>,
+}
+
+fn attendance_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("attendance.json"))
+}
+
+pub fn load(app: &AppHandle) -> AttendanceState {
+    let sessions = attendance_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    AttendanceState(Mutex::new(sessions))
+}
+
+fn persist(app: &AppHandle, sessions: &[AttendanceSession]) {
+    let Some(path) = attendance_path(app) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(sessions) {
+        let _ = fs::write(path, json);
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn devices_in_use() -> Vec<CallSource> {
+    use std::ffi::c_void;
+    use std::ptr;
+
+    #[repr(C)]
+    struct PropertyAddress {
+        selector: u32,
+        scope: u32,
+        element: u32,
+    }
+
+    const fn code(name: &[u8; 4]) -> u32 {
+        u32::from_be_bytes(*name)
+    }
+    const SYSTEM_OBJECT: u32 = 1;
+    const SCOPE_GLOBAL: u32 = code(b"glob");
+    const ELEMENT_MAIN: u32 = 0;
+    const DEFAULT_INPUT_DEVICE: u32 = code(b"dIn ");
+    const DEVICES: u32 = code(b"dev#");
+    const RUNNING_SOMEWHERE: u32 = code(b"gone");
+
+    #[link(name = "CoreAudio", kind = "framework")]
+    extern "C" {
+        fn AudioObjectGetPropertyData(
+            object: u32,
+            address: *const PropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            size: *mut u32,
+            data: *mut c_void,
+        ) -> i32;
+    }
+    #[link(name = "CoreMediaIO", kind = "framework")]
+    extern "C" {
+        fn CMIOObjectGetPropertyDataSize(
+            object: u32,
+            address: *const PropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            size: *mut u32,
+        ) -> i32;
+        fn CMIOObjectGetPropertyData(
+            object: u32,
+            address: *const PropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            size: u32,
+            used: *mut u32,
+            data: *mut c_void,
+        ) -> i32;
+    }
+
+    let address = |selector| PropertyAddress {
+        selector,
+        scope: SCOPE_GLOBAL,
+        element: ELEMENT_MAIN,
+    };
+    let mut sources = Vec::new();
+    // SAFETY: every call gets a buffer of the size it is told, u32s for the
+    // device ids and flags asked for, and ids CoreAudio or CoreMediaIO just
+    // returned.
+    unsafe {
+        let mut device = 0u32;
+        let mut size = 4u32;
+        let found = AudioObjectGetPropertyData(
+            SYSTEM_OBJECT,
+            &address(DEFAULT_INPUT_DEVICE),
+            0,
+            ptr::null(),
+            &mut size,
+            (&mut device as *mut u32).cast(),
+        ) == 0;
+        let mut running = 0u32;
+        let mut size = 4u32;
+        if found
+            && device != 0
+            && AudioObjectGetPropertyData(
+                device,
+                &address(RUNNING_SOMEWHERE),
+                0,
+                ptr::null(),
+                &mut size,
+                (&mut running as *mut u32).cast(),
+            ) == 0
+            && running != 0
+        {
+            sources.push(CallSource::Microphone);
+        }
+
+        let mut size = 0u32;
+        if CMIOObjectGetPropertyDataSize(
+            SYSTEM_OBJECT,
+            &address(DEVICES),
+            0,
+            ptr::null(),
+            &mut size,
+        ) != 0
+        {
+            return sources;
+        }
+        let mut cameras = vec![0u32; size as usize / 4];
+        let mut used = 0u32;
+        if CMIOObjectGetPropertyData(
+            SYSTEM_OBJECT,
+            &address(DEVICES),
+            0,
+            ptr::null(),
+            (cameras.len() * 4) as u32,
+            &mut used,
+            cameras.as_mut_ptr().cast(),
+        ) != 0
+        {
+            return sources;
+        }
+        cameras.truncate(used as usize / 4);
+        let camera_on = cameras.iter().any(|&camera| {
+            let mut running = 0u32;
+            let mut used = 0u32;
+            CMIOObjectGetPropertyData(
+                camera,
+                &address(RUNNING_SOMEWHERE),
+                0,
+                ptr::null(),
+                4,
+                &mut used,
+                (&mut running as *mut u32).cast(),
+            ) == 0
+                && running != 0
+        });
+        if camera_on {
+            sources.push(CallSource::Camera);
+        }
+    }
+    sources
+}
+
+#[cfg(not(target_os = "macos"))]
+fn devices_in_use() -> Vec<CallSource> {
+    Vec::new()
+}
+
+fn meeting_process_running() -> bool {
+    MEETING_PROCESSES.iter().any(|name| {
+        Command::new("/usr/bin/pgrep")
+            .args(["-x", name])
+            .output()
+            .is_ok_and(|output| output.status.success())
+    })
+}
+
+/// What shows a call is on right now; empty if none is.
+fn detect() -> Vec<CallSource> {
+    let mut sources = devices_in_use();
+    if meeting_process_running() {
+        sources.push(CallSource::Process);
+    }
+    sources
+}
+
+/// Books a call from `since` to `until` against the timed events it
+/// overlaps, in order of start: each gets the call up to where the next one
+/// starts, the first from when the call began and the last until it ended.
+/// Of two events booked at the same time, the one that starts later wins the
+/// overlap.
+fn split_call(
+    events: &[CalendarEvent],
+    since: DateTime<Local>,
+    until: DateTime<Local>,
+    sources: &[CallSource],
+) -> Vec<AttendanceSession> {
+    let mut overlapped: Vec<(&CalendarEvent, DateTime<Local>, DateTime<Local>)> = events
+        .iter()
+        .filter(|event| !event.is_all_day)
+        .filter_map(|event| Some((event, event.start_time()?, event.end_time()?)))
+        .filter(|(_, start, end)| *start < until && *end > since)
+        .collect();
+    overlapped.sort_by_key(|(_, start, end)| (*start, *end));
+
+    let mut sessions = Vec::new();
+    for (i, (event, start, end)) in overlapped.iter().enumerate() {
+        let joined_at = if i == 0 { since } else { since.max(*start) };
+        let left_at = match overlapped.get(i + 1) {
+            Some((_, next_start, _)) => until.min(*next_start),
+            None => until,
+        };
+        if left_at <= joined_at {
+            continue;
+        }
+        sessions.push(AttendanceSession {
+            event_id: event.id.clone(),
+            title: event.title.clone(),
+            event_start: *start,
+            event_end: *end,
+            joined_at,
+            left_at,
+            sources: sources.to_vec(),
+        });
+    }
+    sessions
+}
+
+/// Books the call that just ended.
+fn record(app: &AppHandle, call: Call) {
+    let events = scheduler::cached_events(app);
+    let sessions = split_call(&events, call.since, call.last_seen, &call.sources);
+    if sessions.is_empty() {
+        return;
+    }
+    let state = app.state::<AttendanceState>();
+    let mut stored = state.0.lock().unwrap();
+    stored.extend(sessions);
+    if stored.len() > MAX_SESSIONS {
+        let overflow = stored.len() - MAX_SESSIONS;
+        stored.drain(..overflow);
+    }
+    persist(app, &stored);
+}
+
+/// Notes what was detected; a call ends at the first check that finds
+/// nothing, as of the last one that found something.
+fn sample(app: &AppHandle, sources: Vec<CallSource>, now: DateTime<Local>) {
+    let ended = {
+        let mut call = CALL.lock().unwrap();
+        if sources.is_empty() {
+            call.take()
+        } else {
+            let call = call.get_or_insert_with(|| Call {
+                since: now,
+                last_seen: now,
+                sources: Vec::new(),
+            });
+            call.last_seen = now;
+            for source in sources {
+                if !call.sources.contains(&source) {
+                    call.sources.push(source);
+                }
+            }
+            call.sources.sort();
+            None
+        }
+    };
+    if let Some(call) = ended {
+        record(app, call);
+    }
+}
+
+/// Drops the sessions that began before `before` and writes the rest again,
+/// returning how many were dropped.
+pub fn prune(app: &AppHandle, before: DateTime<Local>) -> usize {
+    let state = app.state::<AttendanceState>();
+    let mut sessions = state.0.lock().unwrap();
+    let count = sessions.len();
+    sessions.retain(|s| s.joined_at >= before);
+    persist(app, &sessions);
+    count - sessions.len()
+}
+
+/// Books a call still going on; for shutdown.
+pub fn flush(app: &AppHandle) {
+    sample(app, Vec::new(), simulate::now());
+}
+
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        while !shutdown::in_progress() {
+            let sources = if settings::current(&app).attendance.enabled {
+                detect()
+            } else {
+                Vec::new()
+            };
+            sample(&app, sources, simulate::now());
+            thread::sleep(StdDuration::from_secs(POLL_SECONDS));
+        }
+    });
+}
+
+/// One session per stretch of an event attended: sessions of the same event
+/// that overlap or nearly touch are merged, then short ones dropped.
+fn merge(mut sessions: Vec<AttendanceSession>, min_seconds: i64) -> Vec<AttendanceSession> {
+    sessions.sort_by(|a, b| {
+        (&a.event_id, a.event_start, a.joined_at).cmp(&(&b.event_id, b.event_start, b.joined_at))
+    });
+    let gap = Duration::seconds(MERGE_GAP_SECONDS);
+    let mut merged: Vec<AttendanceSession> = Vec::new();
+    for session in sessions {
+        match merged.last_mut() {
+            Some(last)
+                if last.event_id == session.event_id
+                    && last.event_start == session.event_start
+                    && session.joined_at <= last.left_at + gap =>
+            {
+                last.left_at = last.left_at.max(session.left_at);
+                for source in session.sources {
+                    if !last.sources.contains(&source) {
+                        last.sources.push(source);
+                    }
+                }
+                last.sources.sort();
+            }
+            _ => merged.push(session),
+        }
+    }
+    merged.retain(|s| (s.left_at - s.joined_at).num_seconds() >= min_seconds);
+    merged.sort_by_key(|s| s.joined_at);
+    merged
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_csv(rows: &[AttendanceRow]) -> String {
+    let mut csv =
+        String::from("event_id,title,event_start,event_end,joined_at,left_at,minutes,sources\n");
+    for row in rows {
+        let sources: Vec<&str> = row.sources.iter().map(|s| s.name()).collect();
+        let fields = [
+            csv_field(&row.event_id),
+            csv_field(&row.title),
+            row.event_start.to_rfc3339(),
+            row.event_end.to_rfc3339(),
+            row.joined_at.to_rfc3339(),
+            row.left_at.to_rfc3339(),
+            row.minutes.to_string(),
+            sources.join(" "),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// The JSON export: the rows, and how much of the range is still on record.
+#[derive(Serialize)]
+struct AttendanceExport {
+    coverage: Coverage,
+    rows: Vec<AttendanceRow>,
+}
+
+/// Whether an export hashes titles: when asked to, and always while privacy
+/// mode redacts content, since the file leaves the app.
+fn hashes_titles(mode: PrivacyMode, hash_titles: Option<bool>) -> bool {
+    mode == PrivacyMode::RedactContent || hash_titles.unwrap_or(false)
+}
+
+fn export_rows(
+    sessions: Vec<AttendanceSession>,
+    min_seconds: i64,
+    hash: bool,
+) -> Vec<AttendanceRow> {
+    merge(sessions, min_seconds)
+        .into_iter()
+        .map(|s| AttendanceRow {
+            title: if hash {
+                privacy::redact_title(PrivacyMode::RedactContent, &s.title)
+            } else {
+                s.title
+            },
+            minutes: (s.left_at - s.joined_at).num_minutes(),
+            event_id: s.event_id,
+            event_start: s.event_start,
+            event_end: s.event_end,
+            joined_at: s.joined_at,
+            left_at: s.left_at,
+            sources: s.sources,
+        })
+        .collect()
+}
+
+/// Sessions that began within `range`, merged and filtered, as CSV or JSON.
+/// A range reaching back past what retention kept is exported as far as it
+/// goes; the JSON says so in its coverage. Titles are hashed as in privacy
+/// mode per `hashes_titles`.
+#[tauri::command]
+pub fn export_attendance(
+    app: AppHandle,
+    window: Window,
+    state: State<AttendanceState>,
+    range: AttendanceRange,
+    format: ExportFormat,
+    hash_titles: Option<bool>,
+) -> Result<String, IpcError> {
+    ipc::require_dashboard(&window, "export_attendance")?;
+    let settings = settings::current(&app);
+    let hash = hashes_titles(settings.privacy_mode, hash_titles);
+    let sessions: Vec<AttendanceSession> = state
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|s| s.joined_at >= range.from && s.joined_at < range.to)
+        .cloned()
+        .collect();
+    let rows = export_rows(sessions, settings.attendance.min_session_seconds, hash);
+    Ok(match format {
+        ExportFormat::Csv => to_csv(&rows),
+        ExportFormat::Json => serde_json::to_string_pretty(&AttendanceExport {
+            coverage: retention::coverage(&app, retention::Store::Attendance, range.from, range.to),
+            rows,
+        })
+        .map_err(|e| e.to_string())?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{at, event, hostile_text};
+    use proptest::prelude::*;
+
+    fn session(title: &str) -> AttendanceSession {
+        AttendanceSession {
+            event_id: "board".to_string(),
+            title: title.to_string(),
+            event_start: at("2026-03-02 10:00"),
+            event_end: at("2026-03-02 11:00"),
+            joined_at: at("2026-03-02 10:02"),
+            left_at: at("2026-03-02 10:50"),
+            sources: vec![CallSource::Camera],
+        }
+    }
+
+    #[test]
+    fn redact_content_always_hashes_exported_titles() {
+        assert!(hashes_titles(PrivacyMode::RedactContent, Some(false)));
+        assert!(hashes_titles(PrivacyMode::Off, Some(true)));
+        assert!(!hashes_titles(PrivacyMode::Off, None));
+        assert!(!hashes_titles(PrivacyMode::TitlesOnly, None));
+
+        let hash = hashes_titles(PrivacyMode::RedactContent, Some(false));
+        let rows = export_rows(vec![session("Board: layoffs Q3")], 0, hash);
+        let csv = to_csv(&rows);
+        let json = serde_json::to_string(&rows).unwrap();
+        for artifact in [&csv, &json] {
+            assert!(!artifact.contains("layoffs"), "{}", artifact);
+            assert!(artifact.contains("Busy #"));
+        }
+        assert_eq!(rows[0].minutes, 48);
+    }
+
+    /// Who booked what, from when to when.
+    fn booked(sessions: &[AttendanceSession]) -> Vec<(String, String, String)> {
+        sessions
+            .iter()
+            .map(|s| {
+                (
+                    s.title.clone(),
+                    s.joined_at.format("%H:%M").to_string(),
+                    s.left_at.format("%H:%M").to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn row(title: &str, from: &str, to: &str) -> (String, String, String) {
+        (title.to_string(), from.to_string(), to.to_string())
+    }
+
+    #[test]
+    fn a_call_across_two_adjacent_meetings_is_split_at_the_boundary() {
+        let events = [
+            event("Standup", at("2026-03-02 10:00"), 30),
+            event("Planning", at("2026-03-02 10:30"), 30),
+        ];
+        // Joined early, stayed on the same call into the next meeting, and
+        // left late.
+        let sessions = split_call(
+            &events,
+            at("2026-03-02 09:58"),
+            at("2026-03-02 11:05"),
+            &[CallSource::Microphone],
+        );
+        assert_eq!(
+            booked(&sessions),
+            vec![
+                row("Standup", "09:58", "10:30"),
+                row("Planning", "10:30", "11:05"),
+            ]
+        );
+        assert!(sessions
+            .iter()
+            .all(|s| s.sources == [CallSource::Microphone]));
+    }
+
+    #[test]
+    fn a_call_within_one_meeting_books_only_that_one() {
+        let events = [
+            event("Standup", at("2026-03-02 10:00"), 30),
+            event("Planning", at("2026-03-02 10:30"), 30),
+        ];
+        let sessions = split_call(&events, at("2026-03-02 10:31"), at("2026-03-02 10:55"), &[]);
+        assert_eq!(booked(&sessions), vec![row("Planning", "10:31", "10:55")]);
+        assert!(
+            split_call(&events, at("2026-03-02 11:05"), at("2026-03-02 11:30"), &[]).is_empty()
+        );
+    }
+
+    #[test]
+    fn the_later_of_two_overlapping_meetings_wins_the_overlap() {
+        let mut all_day = event("Offsite", at("2026-03-02 00:00"), 24 * 60);
+        all_day.is_all_day = true;
+        let events = [
+            event("Review", at("2026-03-02 14:00"), 60),
+            event("Interview", at("2026-03-02 14:30"), 30),
+            all_day,
+        ];
+        let sessions = split_call(&events, at("2026-03-02 14:00"), at("2026-03-02 15:00"), &[]);
+        assert_eq!(
+            booked(&sessions),
+            vec![
+                row("Review", "14:00", "14:30"),
+                row("Interview", "14:30", "15:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn sessions_of_one_meeting_merge_across_short_drops() {
+        let at_time = |from: &str, to: &str, source| AttendanceSession {
+            joined_at: at(from),
+            left_at: at(to),
+            sources: vec![source],
+            ..session("Board")
+        };
+        let merged = merge(
+            vec![
+                at_time(
+                    "2026-03-02 10:30",
+                    "2026-03-02 10:50",
+                    CallSource::Microphone,
+                ),
+                // Dropped for a minute, rejoined.
+                at_time("2026-03-02 10:02", "2026-03-02 10:29", CallSource::Camera),
+                // Overlapping, from another detector.
+                at_time("2026-03-02 10:10", "2026-03-02 10:20", CallSource::Process),
+            ],
+            60,
+        );
+        assert_eq!(booked(&merged), vec![row("Board", "10:02", "10:50")]);
+        assert_eq!(
+            merged[0].sources,
+            vec![
+                CallSource::Microphone,
+                CallSource::Camera,
+                CallSource::Process
+            ]
+        );
+
+        // A longer break makes two sessions.
+        let apart = merge(
+            vec![
+                at_time("2026-03-02 10:02", "2026-03-02 10:20", CallSource::Camera),
+                at_time("2026-03-02 10:22", "2026-03-02 10:50", CallSource::Camera),
+            ],
+            60,
+        );
+        assert_eq!(apart.len(), 2);
+    }
+
+    #[test]
+    fn short_sessions_are_dropped_after_merging() {
+        let start = at("2026-03-02 10:02");
+        let blip = |from: i64, to: i64| AttendanceSession {
+            joined_at: start + Duration::seconds(from),
+            left_at: start + Duration::seconds(to),
+            ..session("Board")
+        };
+        // Each is a false positive alone; merged, they last 70 seconds.
+        assert!(merge(vec![blip(0, 30)], 60).is_empty());
+        assert_eq!(merge(vec![blip(0, 30), blip(40, 70)], 60).len(), 1);
+        assert_eq!(merge(vec![blip(0, 30)], 0).len(), 1);
+
+        let rows = export_rows(
+            vec![blip(0, 48 * 60), blip(53 * 60, 53 * 60 + 30)],
+            AttendanceSettings::default().min_session_seconds,
+            false,
+        );
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].minutes, 48);
+    }
+
+    /// The records of `csv`, with quoted fields unquoted.
+    fn read_csv(csv: &str) -> Vec<Vec<String>> {
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut chars = csv.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (quoted, c) {
+                (true, '"') if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                (_, '"') => quoted = !quoted,
+                (false, ',') => record.push(std::mem::take(&mut field)),
+                (false, '\n') => {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                _ => field.push(c),
+            }
+        }
+        records
+    }
+
+    proptest! {
+        #[test]
+        fn hostile_titles_stay_in_their_column(title in hostile_text(), id in hostile_text()) {
+            let mut hostile = session(&title);
+            hostile.event_id = id.clone();
+            let csv = to_csv(&export_rows(vec![hostile], 0, false));
+            let records = read_csv(&csv);
+            prop_assert_eq!(records.len(), 2);
+            prop_assert!(records.iter().all(|record| record.len() == 8));
+            prop_assert_eq!(&records[1][0], &id);
+            prop_assert_eq!(&records[1][1], &title);
+            prop_assert_eq!(&records[1][7], "camera");
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// --- Audio Outputs ---
+//
+// Alert sounds go to the system's default output, which is sometimes the
+// wrong one: AirPods left in their case, or a headset on a call. A route,
+// set for all alerts or per rule, says where sounds play instead; it is
+// looked up each time a sound starts, not when the alert is scheduled, so it
+// follows whatever is connected by then. The outputs come from CoreAudio. A
+// Bluetooth output, or the built-in jack with headphones in, counts as a
+// headset; a USB headset can't be told from USB speakers and doesn't.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioRoute {
+    /// Wherever the system sends sound.
+    #[default]
+    FollowSystem,
+    /// The built-in speakers, whatever the default is.
+    BuiltIn,
+    /// The default output and the built-in speakers, once if they're the same.
+    Both,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Transport {
+    BuiltIn,
+    Bluetooth,
+    Usb,
+    /// HDMI, DisplayPort and the speakers of a display.
+    Display,
+    AirPlay,
+    /// Aggregate and software devices.
+    Virtual,
+    Other,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputDevice {
+    /// CoreAudio's UID, stable across reconnects.
+    pub uid: String,
+    pub name: String,
+    pub transport: Transport,
+    pub headset: bool,
+    pub is_default: bool,
+}
+
+#[cfg(target_os = "macos")]
+mod core_audio {
+    use objc2::msg_send;
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use std::ffi::{c_char, c_void, CStr};
+    use std::mem;
+    use std::ptr;
+
+    use super::{OutputDevice, Transport};
+
+    type AudioObjectId = u32;
+
+    #[repr(C)]
+    struct PropertyAddress {
+        selector: u32,
+        scope: u32,
+        element: u32,
+    }
+
+    #[link(name = "CoreAudio", kind = "framework")]
+    extern "C" {
+        fn AudioObjectGetPropertyDataSize(
+            object: AudioObjectId,
+            address: *const PropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            size: *mut u32,
+        ) -> i32;
+        fn AudioObjectGetPropertyData(
+            object: AudioObjectId,
+            address: *const PropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            size: *mut u32,
+            data: *mut c_void,
+        ) -> i32;
+    }
+
+    const fn code(name: &[u8; 4]) -> u32 {
+        u32::from_be_bytes(*name)
+    }
+
+    const SYSTEM_OBJECT: AudioObjectId = 1;
+    const DEVICES: u32 = code(b"dev#");
+    const DEFAULT_OUTPUT: u32 = code(b"dOut");
+    const DEVICE_UID: u32 = code(b"uid ");
+    const NAME: u32 = code(b"lnam");
+    const TRANSPORT: u32 = code(b"tran");
+    const STREAMS: u32 = code(b"stm#");
+    const DATA_SOURCE: u32 = code(b"ssrc");
+    const HEADPHONES: u32 = code(b"hdpn");
+    const SCOPE_GLOBAL: u32 = code(b"glob");
+    const SCOPE_OUTPUT: u32 = code(b"outp");
+    const ELEMENT_MAIN: u32 = 0;
+
+    fn address(selector: u32, scope: u32) -> PropertyAddress {
+        PropertyAddress {
+            selector,
+            scope,
+            element: ELEMENT_MAIN,
+        }
+    }
+
+    fn size(object: AudioObjectId, address: &PropertyAddress) -> Option<u32> {
+        let mut size = 0;
+        // SAFETY: `address` and `size` outlive the call; no qualifier.
+        let status =
+            unsafe { AudioObjectGetPropertyDataSize(object, address, 0, ptr::null(), &mut size) };
+        (status == 0).then_some(size)
+    }
+
+    /// A property that is a single value of `T`.
+    fn read<T: Copy + Default>(object: AudioObjectId, address: &PropertyAddress) -> Option<T> {
+        let mut value = T::default();
+        let mut size = mem::size_of::<T>() as u32;
+        // SAFETY: `value` has room for `size` bytes, the size CoreAudio
+        // writes for these properties at most.
+        let status = unsafe {
+            AudioObjectGetPropertyData(
+                object,
+                address,
+                0,
+                ptr::null(),
+                &mut size,
+                &mut value as *mut T as *mut c_void,
+            )
+        };
+        (status == 0).then_some(value)
+    }
+
+    /// A CFString property; the caller owns the string CoreAudio returns.
+    fn read_string(object: AudioObjectId, selector: u32) -> Option<String> {
+        let string = read::<usize>(object, &address(selector, SCOPE_GLOBAL))?;
+        // SAFETY: the property is a +1 CFStringRef, toll-free bridged to
+        // NSString; `Retained` releases it when dropped.
+        unsafe {
+            let string = Retained::from_raw(string as *mut AnyObject)?;
+            let utf8: *const c_char = msg_send![&*string, UTF8String];
+            (!utf8.is_null()).then(|| CStr::from_ptr(utf8).to_string_lossy().into_owned())
+        }
+    }
+
+    fn devices() -> Vec<AudioObjectId> {
+        let address = address(DEVICES, SCOPE_GLOBAL);
+        let Some(bytes) = size(SYSTEM_OBJECT, &address) else {
+            return Vec::new();
+        };
+        let mut ids = vec![0 as AudioObjectId; bytes as usize / mem::size_of::<AudioObjectId>()];
+        let mut size = bytes;
+        // SAFETY: `ids` has room for the `size` bytes just asked for.
+        let status = unsafe {
+            AudioObjectGetPropertyData(
+                SYSTEM_OBJECT,
+                &address,
+                0,
+                ptr::null(),
+                &mut size,
+                ids.as_mut_ptr() as *mut c_void,
+            )
+        };
+        if status != 0 {
+            return Vec::new();
+        }
+        ids.truncate(size as usize / mem::size_of::<AudioObjectId>());
+        ids
+    }
+
+    fn transport(device: AudioObjectId) -> Transport {
+        let kind = read::<u32>(device, &address(TRANSPORT, SCOPE_GLOBAL)).unwrap_or(0);
+        match &kind.to_be_bytes() {
+            b"bltn" => Transport::BuiltIn,
+            b"blue" | b"blea" => Transport::Bluetooth,
+            b"usb " => Transport::Usb,
+            b"hdmi" | b"dprt" => Transport::Display,
+            b"airp" => Transport::AirPlay,
+            b"virt" | b"grup" | b"agrp" => Transport::Virtual,
+            _ => Transport::Other,
+        }
+    }
+
+    pub fn outputs() -> Vec<OutputDevice> {
+        let default = read::<AudioObjectId>(SYSTEM_OBJECT, &address(DEFAULT_OUTPUT, SCOPE_GLOBAL));
+        devices()
+            .into_iter()
+            .filter(|&device| {
+                size(device, &address(STREAMS, SCOPE_OUTPUT)).is_some_and(|bytes| bytes > 0)
+            })
+            .filter_map(|device| {
+                let transport = transport(device);
+                let headphones =
+                    read::<u32>(device, &address(DATA_SOURCE, SCOPE_OUTPUT)) == Some(HEADPHONES);
+                Some(OutputDevice {
+                    uid: read_string(device, DEVICE_UID)?,
+                    name: read_string(device, NAME).unwrap_or_default(),
+                    headset: transport == Transport::Bluetooth
+                        || (transport == Transport::BuiltIn && headphones),
+                    transport,
+                    is_default: Some(device) == default,
+                })
+            })
+            .collect()
+    }
+}
+
+pub fn outputs() -> Vec<OutputDevice> {
+    #[cfg(target_os = "macos")]
+    return core_audio::outputs();
+    #[cfg(not(target_os = "macos"))]
+    Vec::new()
+}
+
+/// Whether the output `uid` is still connected.
+pub fn is_connected(uid: &str) -> bool {
+    outputs().iter().any(|o| o.uid == uid)
+}
+
+/// Where a sound on `route` plays right now: `None` is the default output,
+/// `Some` a device by UID. The built-in speakers are only named when they
+/// aren't the default already, so nothing plays twice on one output.
+pub fn targets(route: AudioRoute) -> Vec<Option<String>> {
+    if route == AudioRoute::FollowSystem {
+        return vec![None];
+    }
+    let outputs = outputs();
+    let speakers = outputs
+        .iter()
+        .find(|o| o.transport == Transport::BuiltIn && !o.headset)
+        .filter(|o| !o.is_default);
+    match (route, speakers) {
+        (AudioRoute::BuiltIn, Some(speakers)) => vec![Some(speakers.uid.clone())],
+        (AudioRoute::Both, Some(speakers)) => vec![None, Some(speakers.uid.clone())],
+        _ => vec![None],
+    }
+}
+
+#[tauri::command]
+pub fn get_audio_outputs() -> Vec<OutputDevice> {
+    outputs()
+}
@@ -0,0 +1,267 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Window};
+
+use crate::calendar::CalendarEvent;
+use crate::features::{self, Flag};
+use crate::history::{self, HistoryState};
+use crate::ipc::{self, IpcError};
+use crate::rules::{self, AlertRule};
+use crate::settings::{self, Settings};
+use crate::{confidential, notify};
+
+// --- Lead Time Auto-Tuning (opt-in) ---
+//
+// For a series the punctuality insights call out, adds an alert rule with a
+// longer lead time. The rule is an ordinary one (editable and deletable
+// through the alert-rule commands) marked `auto_tuned`, and it is removed
+// again after enough on-time occurrences.
+
+const RULE_PREFIX: &str = "auto-lead:";
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct AutoTuneSettings {
+    pub enabled: bool,
+    /// Most minutes added on top of the series' usual lead time.
+    pub max_extra_minutes: i64,
+    /// Lead time is never raised beyond this.
+    pub max_lead_minutes: i64,
+    /// Consecutive on-time occurrences that undo the adjustment.
+    pub reset_after_on_time: usize,
+}
+
+impl Default for AutoTuneSettings {
+    fn default() -> Self {
+        AutoTuneSettings {
+            enabled: false,
+            max_extra_minutes: 10,
+            max_lead_minutes: 30,
+            reset_after_on_time: 3,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Adjustment {
+    /// Set the series' lead time to this many minutes.
+    Raise(i64),
+    Reset,
+}
+
+/// What to do for one series. `late_seconds` is newest first; `base_minutes`
+/// is the lead time without the auto rule, `current_minutes` the one set by
+/// it, if any. Tuning only ever raises the lead time; once raised, lateness
+/// should drop, and that is not a reason to lower it again.
+pub fn adjustment(
+    late_seconds: &[i64],
+    median_late_minutes: Option<i64>,
+    base_minutes: i64,
+    current_minutes: Option<i64>,
+    tune: &AutoTuneSettings,
+) -> Option<Adjustment> {
+    if let Some(current) = current_minutes {
+        let streak = tune.reset_after_on_time.max(1);
+        let on_time =
+            late_seconds.len() >= streak && late_seconds[..streak].iter().all(|&s| s <= 0);
+        if on_time {
+            return Some(Adjustment::Reset);
+        }
+        let target = target_minutes(median_late_minutes?, base_minutes, tune);
+        return (target > current).then_some(Adjustment::Raise(target));
+    }
+    let target = target_minutes(median_late_minutes?, base_minutes, tune);
+    (target > base_minutes).then_some(Adjustment::Raise(target))
+}
+
+fn target_minutes(median_late: i64, base_minutes: i64, tune: &AutoTuneSettings) -> i64 {
+    let extra = median_late.clamp(1, tune.max_extra_minutes.max(1));
+    (base_minutes + extra)
+        .min(tune.max_lead_minutes)
+        .max(base_minutes)
+}
+
+fn rule_id(event: &CalendarEvent) -> String {
+    series_rule_id(&event.id)
+}
+
+/// Id of the auto rule for the series `series_id`.
+pub fn series_rule_id(series_id: &str) -> String {
+    format!("{}{}", RULE_PREFIX, series_id)
+}
+
+/// Lead time in minutes the rules give `event` without its auto rule.
+fn base_minutes(settings: &Settings, event: &CalendarEvent) -> i64 {
+    let mut without = settings.clone();
+    without.rules.retain(|r| r.id != rule_id(event));
+    rules::evaluate(&without, event).lead_time_seconds / 60
+}
+
+/// Re-evaluates the series after one of its alerts was acknowledged.
+pub fn review(app: &AppHandle, event: &CalendarEvent) {
+    let settings = settings::current(app);
+    if !settings.auto_tune.enabled
+        || !features::enabled(&settings, Flag::AutoTune)
+        || !settings.punctuality.enabled
+        || event.recurrence.is_none()
+    {
+        return;
+    }
+    let (late_seconds, median) = {
+        let state = app.state::<HistoryState>();
+        let entries = state.0.lock().unwrap();
+        let samples: Vec<i64> = entries
+            .iter()
+            .rev()
+            .filter(|e| e.event_id == event.id)
+            .filter_map(|e| e.late_seconds)
+            .collect();
+        let median = history::punctuality_insights(&entries, &settings.punctuality)
+            .into_iter()
+            .find(|insight| insight.event_id == event.id)
+            .map(|insight| insight.median_late_minutes);
+        (samples, median)
+    };
+
+    let id = rule_id(event);
+    let current = settings
+        .rules
+        .iter()
+        .find(|r| r.id == id)
+        .and_then(|r| r.lead_time_seconds)
+        .map(|s| s / 60);
+    let base = base_minutes(&settings, event);
+    let title = confidential::shown(&settings, event).title;
+    match adjustment(&late_seconds, median, base, current, &settings.auto_tune) {
+        Some(Adjustment::Raise(minutes)) => {
+            let rule = AlertRule {
+                id: id.clone(),
+                series_id: Some(event.id.clone()),
+                lead_time_seconds: Some(minutes * 60),
+                auto_tuned: true,
+                ..AlertRule::default()
+            };
+            let saved = settings::update(app, |settings| {
+                match settings.rules.iter_mut().find(|r| r.id == id) {
+                    Some(existing) => *existing = rule,
+                    None => settings.rules.push(rule),
+                }
+            });
+            if saved.is_ok() {
+                let body = format!("Increased lead time for {} to {} minutes", title, minutes);
+                notify::show(app, "Lead time adjusted", &body);
+            }
+        }
+        Some(Adjustment::Reset) => {
+            let removed = settings::update(app, |settings| settings.rules.retain(|r| r.id != id));
+            if removed.is_ok() {
+                let body = format!(
+                    "You've been on time for {}; lead time is back to {} minutes",
+                    title, base
+                );
+                notify::show(app, "Lead time adjusted", &body);
+            }
+        }
+        None => {}
+    }
+}
+
+/// Removes every auto-tuned rule.
+#[tauri::command]
+pub fn reset_auto_tuned_rules(app: AppHandle, window: Window) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "reset_auto_tuned_rules")?;
+    settings::update(&app, |settings| settings.rules.retain(|r| !r.auto_tuned))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{at, event};
+
+    const LATE: i64 = 4 * 60;
+
+    #[test]
+    fn raises_by_the_median_lateness_within_the_bounds() {
+        let tune = AutoTuneSettings::default();
+        assert_eq!(
+            adjustment(&[LATE; 5], Some(4), 2, None, &tune),
+            Some(Adjustment::Raise(6))
+        );
+        // At least a minute, at most `max_extra_minutes`...
+        assert_eq!(
+            adjustment(&[30; 5], Some(0), 2, None, &tune),
+            Some(Adjustment::Raise(3))
+        );
+        assert_eq!(
+            adjustment(&[LATE; 5], Some(25), 2, None, &tune),
+            Some(Adjustment::Raise(12))
+        );
+        // ...and never past `max_lead_minutes`.
+        assert_eq!(
+            adjustment(&[LATE; 5], Some(10), 25, None, &tune),
+            Some(Adjustment::Raise(30))
+        );
+        assert_eq!(adjustment(&[LATE; 5], Some(10), 30, None, &tune), None);
+        assert_eq!(adjustment(&[LATE; 5], Some(10), 45, None, &tune), None);
+    }
+
+    #[test]
+    fn leaves_series_without_an_insight_alone() {
+        let tune = AutoTuneSettings::default();
+        assert_eq!(adjustment(&[LATE; 5], None, 2, None, &tune), None);
+        assert_eq!(adjustment(&[], None, 2, Some(6), &tune), None);
+    }
+
+    #[test]
+    fn only_ever_raises_a_tuned_lead_time() {
+        let tune = AutoTuneSettings::default();
+        // Still late, by less than before: the lead time stays.
+        let samples = [LATE, 0, LATE, LATE];
+        assert_eq!(adjustment(&samples, Some(2), 2, Some(6), &tune), None);
+        assert_eq!(
+            adjustment(&samples, Some(8), 2, Some(6), &tune),
+            Some(Adjustment::Raise(10))
+        );
+    }
+
+    #[test]
+    fn resets_after_enough_on_time_occurrences_in_a_row() {
+        let tune = AutoTuneSettings::default();
+        let on_time = [0, -60, -120, LATE, LATE];
+        assert_eq!(
+            adjustment(&on_time, Some(4), 2, Some(6), &tune),
+            Some(Adjustment::Reset)
+        );
+        // Two on time, then late: not yet.
+        let not_yet = [0, -60, LATE, LATE, LATE];
+        assert_eq!(adjustment(&not_yet, Some(4), 2, Some(6), &tune), None);
+        // Nothing to reset without an auto rule.
+        assert_eq!(adjustment(&on_time, None, 2, None, &tune), None);
+
+        let strict = AutoTuneSettings {
+            reset_after_on_time: 5,
+            ..AutoTuneSettings::default()
+        };
+        assert_eq!(adjustment(&on_time, Some(4), 2, Some(6), &strict), None);
+    }
+
+    #[test]
+    fn the_base_lead_time_leaves_out_the_auto_rule() {
+        let standup = event("Standup", at("2026-03-02 10:00"), 15);
+        let auto = AlertRule {
+            id: rule_id(&standup),
+            series_id: Some(standup.id.clone()),
+            lead_time_seconds: Some(9 * 60),
+            auto_tuned: true,
+            ..AlertRule::default()
+        };
+        let settings = Settings {
+            lead_time_seconds: 120,
+            rules: vec![auto],
+            ..Settings::default()
+        };
+        assert_eq!(rules::evaluate(&settings, &standup).lead_time_seconds, 540);
+        assert_eq!(base_minutes(&settings, &standup), 2);
+    }
+}
@@ -0,0 +1,352 @@
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Window};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::ipc::{self, IpcError};
+use crate::scheduler::SchedulerState;
+use crate::settings::{self, Settings};
+use crate::{notify, rules};
+
+// --- Backup & Restore ---
+//
+// A backup is the settings (which carry the profiles and alert rules) wrapped
+// with a schema version. Secrets are never written out; on import the secrets
+// of hooks and of the MQTT broker that already exist locally are kept. The
+// snapshot taken before an import stays on this machine and keeps them, so
+// that rolling back restores the settings exactly.
+
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct Backup {
+    pub schema_version: u32,
+    pub created_at: DateTime<Local>,
+    pub app_version: String,
+    pub settings: Value,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct ImportResult {
+    /// Backup of the settings as they were before the import, for rollback.
+    pub snapshot_path: PathBuf,
+}
+
+fn strip_secrets(settings: &mut Settings) {
+    settings.mqtt.password = None;
+    for hook in settings.webhooks.iter_mut() {
+        hook.secret = None;
+    }
+    for profile in settings.profiles.iter_mut() {
+        for hook in profile.webhooks.iter_mut() {
+            hook.secret = None;
+        }
+    }
+}
+
+fn keep_local_secrets(imported: &mut Settings, current: &Settings) {
+    let secret_for = |url: &str| {
+        current
+            .webhooks
+            .iter()
+            .chain(current.profiles.iter().flat_map(|p| p.webhooks.iter()))
+            .find(|h| h.url == url)
+            .and_then(|h| h.secret.clone())
+    };
+    let hooks = imported.webhooks.iter_mut().chain(
+        imported
+            .profiles
+            .iter_mut()
+            .flat_map(|p| p.webhooks.iter_mut()),
+    );
+    for hook in hooks {
+        hook.secret = secret_for(&hook.url);
+    }
+    imported.mqtt.password = (imported.mqtt.host == current.mqtt.host)
+        .then(|| current.mqtt.password.clone())
+        .flatten();
+}
+
+fn to_backup(app_version: &str, settings: &Settings) -> Result<Backup, String> {
+    Ok(Backup {
+        schema_version: SCHEMA_VERSION,
+        created_at: Local::now(),
+        app_version: app_version.to_string(),
+        settings: serde_json::to_value(settings).map_err(|e| e.to_string())?,
+    })
+}
+
+fn write_json(path: &Path, backup: &Backup) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(backup).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// An export, for another machine: without secrets.
+fn write_backup(app: &AppHandle, path: &Path, mut settings: Settings) -> Result<(), String> {
+    strip_secrets(&mut settings);
+    write_json(
+        path,
+        &to_backup(&app.package_info().version.to_string(), &settings)?,
+    )
+}
+
+/// A pre-import snapshot, secrets and all.
+fn write_snapshot(app: &AppHandle, path: &Path, settings: &Settings) -> Result<(), String> {
+    write_json(
+        path,
+        &to_backup(&app.package_info().version.to_string(), settings)?,
+    )
+}
+
+/// Brings any supported backup up to the current schema.
+///
+/// Version 0 is a bare `settings.json` copied from another machine.
+fn migrate(raw: Value) -> Result<Value, String> {
+    let version = raw
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as u32;
+    match version {
+        0 => Ok(raw),
+        SCHEMA_VERSION => raw
+            .get("settings")
+            .cloned()
+            .ok_or("Backup has no settings".to_string()),
+        newer => Err(format!(
+            "Backup schema version {} is newer than this app supports ({})",
+            newer, SCHEMA_VERSION
+        )),
+    }
+}
+
+fn parse_backup(json: &str) -> Result<Settings, String> {
+    let raw: Value = serde_json::from_str(json).map_err(|e| format!("Invalid backup: {}", e))?;
+    if !raw.is_object() {
+        return Err("Invalid backup: expected a JSON object".to_string());
+    }
+    serde_json::from_value(migrate(raw)?).map_err(|e| format!("Invalid backup: {}", e))
+}
+
+/// Where the snapshots taken before an import go.
+pub fn snapshots_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("backups"))
+        .map_err(|e| e.to_string())
+}
+
+fn snapshot_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = snapshots_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let name = format!("pre-import-{}.json", Local::now().format("%Y%m%d-%H%M%S"));
+    Ok(dir.join(name))
+}
+
+fn read_backup(path: &Path) -> Result<Settings, String> {
+    let json = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut settings = parse_backup(&json)?;
+    rules::check_all_chains(&settings)?;
+    settings.nag.raise_to_floor();
+    Ok(settings)
+}
+
+fn apply(app: &AppHandle, imported: Settings) -> Result<(), String> {
+    let state = app.state::<SchedulerState>();
+    let mut scheduler = state.0.lock().unwrap();
+    let settings = settings::update(app, |settings| *settings = imported)?;
+    scheduler.reschedule(&settings);
+    Ok(())
+}
+
+pub fn import(app: &AppHandle, path: &Path) -> Result<ImportResult, String> {
+    let mut imported = read_backup(path)?;
+
+    let current = settings::current(app);
+    let snapshot_path = snapshot_path(app)?;
+    write_snapshot(app, &snapshot_path, &current)?;
+    keep_local_secrets(&mut imported, &current);
+
+    apply(app, imported)?;
+    Ok(ImportResult { snapshot_path })
+}
+
+/// The newest pre-import snapshot; their names sort by date.
+fn latest_snapshot(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = snapshots_dir(app)?;
+    fs::read_dir(&dir)
+        .map_err(|_| "There's no import to roll back".to_string())?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("pre-import-") && name.ends_with(".json"))
+        })
+        .max()
+        .ok_or("There's no import to roll back".to_string())
+}
+
+/// Puts back the settings from before an import: from `snapshot_path`,
+/// which must be one of ours, or else the newest snapshot.
+pub fn rollback(app: &AppHandle, snapshot_path: Option<&Path>) -> Result<PathBuf, String> {
+    let path = match snapshot_path {
+        Some(path) => {
+            let dir = fs::canonicalize(snapshots_dir(app)?).map_err(|e| e.to_string())?;
+            let path = fs::canonicalize(path).map_err(|e| e.to_string())?;
+            if !path.starts_with(&dir) {
+                return Err("Not a pre-import snapshot".to_string());
+            }
+            path
+        }
+        None => latest_snapshot(app)?,
+    };
+    apply(app, read_backup(&path)?)?;
+    Ok(path)
+}
+
+pub fn export_with_dialog(app: &AppHandle) {
+    let handle = app.clone();
+    let name = format!(
+        "in-your-face-backup-{}.json",
+        Local::now().format("%Y-%m-%d")
+    );
+    app.dialog()
+        .file()
+        .add_filter("Backup", &["json"])
+        .set_file_name(name)
+        .save_file(move |path| {
+            let Some(Ok(path)) = path.map(|p| p.into_path()) else {
+                return;
+            };
+            match write_backup(&handle, &path, settings::current(&handle)) {
+                Ok(()) => notify::show(&handle, "Backup exported", &path.display().to_string()),
+                Err(e) => notify::show(&handle, "Backup failed", &e),
+            }
+        });
+}
+
+pub fn import_with_dialog(app: &AppHandle) {
+    let handle = app.clone();
+    app.dialog()
+        .file()
+        .add_filter("Backup", &["json"])
+        .pick_file(move |path| {
+            let Some(Ok(path)) = path.map(|p| p.into_path()) else {
+                return;
+            };
+            match import(&handle, &path) {
+                Ok(_) => notify::show(&handle, "Backup restored", "Your settings were imported."),
+                Err(e) => notify::show(&handle, "Import failed", &e),
+            }
+        });
+}
+
+#[tauri::command]
+pub fn export_backup(app: AppHandle, window: Window, path: String) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "export_backup")?;
+    Ok(write_backup(
+        &app,
+        Path::new(&path),
+        settings::current(&app),
+    )?)
+}
+
+#[tauri::command]
+pub fn import_backup(
+    app: AppHandle,
+    window: Window,
+    path: String,
+) -> Result<ImportResult, IpcError> {
+    ipc::require_dashboard(&window, "import_backup")?;
+    Ok(import(&app, Path::new(&path))?)
+}
+
+/// Restores the settings from before an import; the snapshot it used.
+#[tauri::command]
+pub fn rollback_import(
+    app: AppHandle,
+    window: Window,
+    snapshot_path: Option<String>,
+) -> Result<PathBuf, IpcError> {
+    ipc::require_dashboard(&window, "rollback_import")?;
+    Ok(rollback(&app, snapshot_path.as_deref().map(Path::new))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::WebhookConfig;
+    use serde_json::json;
+
+    #[test]
+    fn imports_a_bare_settings_file_as_version_0() {
+        let settings = parse_backup(&json!({ "lead_time_seconds": 120 }).to_string()).unwrap();
+        assert_eq!(settings.lead_time_seconds, 120);
+        // What it doesn't mention keeps its default.
+        assert_eq!(
+            settings.poll_interval_seconds,
+            Settings::default().poll_interval_seconds
+        );
+    }
+
+    #[test]
+    fn imports_version_1() {
+        let backup = json!({
+            "schema_version": 1,
+            "created_at": "2026-01-05T09:00:00+01:00",
+            "app_version": "0.1.0",
+            "settings": { "lead_time_seconds": 300, "rules": [{ "id": "rule-1" }] },
+        });
+        let settings = parse_backup(&backup.to_string()).unwrap();
+        assert_eq!(settings.lead_time_seconds, 300);
+        assert_eq!(settings.rules[0].id, "rule-1");
+    }
+
+    #[test]
+    fn refuses_newer_and_malformed_backups() {
+        let newer = json!({ "schema_version": SCHEMA_VERSION + 1, "settings": {} });
+        assert!(parse_backup(&newer.to_string())
+            .unwrap_err()
+            .contains("newer"));
+        assert!(parse_backup(&json!({ "schema_version": 1 }).to_string()).is_err());
+        assert!(parse_backup("[]").is_err());
+        assert!(parse_backup("{").is_err());
+    }
+
+    #[test]
+    fn round_trips_through_the_current_schema() {
+        let settings = Settings {
+            lead_time_seconds: 42,
+            ..Settings::default()
+        };
+        let json = serde_json::to_string(&to_backup("0.1.0", &settings).unwrap()).unwrap();
+        assert_eq!(parse_backup(&json).unwrap().lead_time_seconds, 42);
+    }
+
+    #[test]
+    fn exports_drop_secrets_that_snapshots_keep() {
+        let hook = WebhookConfig {
+            url: "https://hooks.example.com/a".to_string(),
+            secret: Some("s3cret".to_string()),
+            ..WebhookConfig::default()
+        };
+        let settings = Settings {
+            webhooks: vec![hook],
+            ..Settings::default()
+        };
+
+        let snapshot = to_backup("0.1.0", &settings).unwrap();
+        assert!(snapshot.settings.to_string().contains("s3cret"));
+
+        let mut exported = settings.clone();
+        strip_secrets(&mut exported);
+        assert!(!serde_json::to_string(&exported).unwrap().contains("s3cret"));
+
+        // Importing the export here keeps the local secret.
+        keep_local_secrets(&mut exported, &settings);
+        assert_eq!(exported.webhooks[0].secret.as_deref(), Some("s3cret"));
+    }
+}
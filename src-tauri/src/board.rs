@@ -0,0 +1,151 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::thread;
+use tauri::{
+    AppHandle, Manager, Monitor, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder, Window,
+};
+
+use crate::bus::{self, AppEvent};
+use crate::ipc::{self, IpcError};
+use crate::{notify, retention, settings};
+
+// --- Agenda Board ---
+//
+// For a spare display next to the desk: a window covering it all the time
+// with the day's agenda and a clock, which switches to the alert layout
+// while an alert is up and back after. It never takes focus and sits at the
+// normal window level, so it stays out of the way on the other displays;
+// the dimming overlays leave its display alone. The display is remembered by
+// name. If it disconnects, board mode turns itself off with a notification
+// instead of moving onto another display.
+
+pub const BOARD_WINDOW: &str = "board";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct BoardSettings {
+    pub enabled: bool,
+    /// Name of the display the board covers.
+    pub display: Option<String>,
+}
+
+impl BoardSettings {
+    /// True if the board is on and covers the display named `name`.
+    pub fn covers(&self, name: Option<&String>) -> bool {
+        self.enabled && self.display.is_some() && name == self.display.as_ref()
+    }
+}
+
+fn board_display(app: &AppHandle, settings: &BoardSettings) -> Option<Monitor> {
+    app.available_monitors()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|monitor| settings.covers(monitor.name()))
+}
+
+fn create(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    WebviewWindowBuilder::new(app, BOARD_WINDOW, WebviewUrl::App("index.html".into()))
+        .title("Agenda Board")
+        .visible(false)
+        .decorations(false)
+        .focused(false)
+        .focusable(false)
+        .skip_taskbar(true)
+        .resizable(false)
+        .build()
+}
+
+/// Covers `monitor` with the board, creating its window if needed; moved
+/// and resized without ever being focused.
+fn show_on(app: &AppHandle, monitor: &Monitor) -> tauri::Result<()> {
+    let window = match app.get_webview_window(BOARD_WINDOW) {
+        Some(window) => window,
+        None => create(app)?,
+    };
+    let position = *monitor.position();
+    let size = *monitor.size();
+    window.set_position(PhysicalPosition::new(position.x, position.y))?;
+    window.set_size(PhysicalSize::new(size.width, size.height))?;
+    window.show()
+}
+
+fn close(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(BOARD_WINDOW) {
+        let _ = window.destroy();
+    }
+}
+
+/// Brings the board in line with the settings and the connected displays;
+/// a board whose display is gone is turned off.
+fn reconcile(app: &AppHandle) {
+    let settings = settings::current(app).board;
+    if !settings.enabled {
+        close(app);
+        return;
+    }
+    let Some(monitor) = board_display(app, &settings) else {
+        close(app);
+        let name = settings.display.unwrap_or_default();
+        if settings::update(app, |s| s.board.enabled = false).is_ok() {
+            notify::show(
+                app,
+                "Agenda board turned off",
+                &format!("{} was disconnected.", name),
+            );
+        }
+        return;
+    };
+    if let Err(e) = show_on(app, &monitor) {
+        retention::log!("failed to show the agenda board: {}", e);
+    }
+}
+
+/// Shows the board if it was on, and follows display and settings changes.
+pub fn start(app: &AppHandle) {
+    reconcile(app);
+    let events = bus::subscribe(app);
+    let app = app.clone();
+    thread::spawn(move || {
+        for event in events {
+            if matches!(
+                event,
+                AppEvent::SettingsChanged | AppEvent::DisplaysChanged(_)
+            ) {
+                reconcile(&app);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn enable_board_mode(
+    app: AppHandle,
+    window: Window,
+    display_name: String,
+) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "enable_board_mode")?;
+    let connected = app
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .any(|monitor| monitor.name() == Some(&display_name));
+    if !connected {
+        return Err(format!("No display named \"{}\" is connected", display_name).into());
+    }
+    settings::update(&app, |s| {
+        s.board = BoardSettings {
+            enabled: true,
+            display: Some(display_name),
+        }
+    })?;
+    Ok(())
+}
+
+/// Turns the board off; the display is kept for next time.
+#[tauri::command]
+pub fn disable_board_mode(app: AppHandle, window: Window) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "disable_board_mode")?;
+    settings::update(&app, |s| s.board.enabled = false)?;
+    Ok(())
+}
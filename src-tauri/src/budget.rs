@@ -0,0 +1,118 @@
+use chrono::{DateTime, Days, Duration, Local, NaiveDate};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::CalendarEvent;
+use crate::settings::{self, Settings};
+use crate::week::{busy_minutes, start_of_day};
+use crate::{scheduler, simulate};
+
+// --- Meeting Budget ---
+//
+// A cap on meeting hours per day. Overlapping meetings count once; declined
+// meetings, events marked free and long blocks don't count at all. While a
+// budget is set the scheduler caches through the end of tomorrow, so both
+// days can be judged from its events.
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct BudgetSettings {
+    /// Meeting hours per day; `None` turns budgeting off.
+    pub daily_hours: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DayUsage {
+    pub date: NaiveDate,
+    pub meeting_minutes: i64,
+    pub over_budget: bool,
+    /// e.g. "5.5h of meetings, over your 4h budget".
+    pub summary: String,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    pub daily_hours: Option<f64>,
+    pub today: Option<DayUsage>,
+    pub tomorrow: Option<DayUsage>,
+}
+
+fn counts(settings: &Settings, event: &CalendarEvent) -> bool {
+    !event.is_all_day
+        && !event.is_long_block
+        && !event.is_free
+        && !event.is_declined()
+        && !settings.calendar_filter.excludes(event)
+}
+
+/// "4h", "5.5h".
+fn hours(minutes: f64) -> String {
+    let hours = format!("{:.1}", minutes / 60.0);
+    format!("{}h", hours.trim_end_matches(".0"))
+}
+
+/// Meeting time on `date`, with meetings across midnight split between days.
+pub fn meeting_minutes(settings: &Settings, events: &[CalendarEvent], date: NaiveDate) -> i64 {
+    let (Some(from), Some(to)) = (start_of_day(date), date.succ_opt().and_then(start_of_day))
+    else {
+        return 0;
+    };
+    let mut spans: Vec<_> = events
+        .iter()
+        .filter(|e| counts(settings, e))
+        .filter_map(|e| Some((e.start_time()?.max(from), e.end_time()?.min(to))))
+        .filter(|(start, end)| start < end)
+        .collect();
+    spans.sort();
+    busy_minutes(&spans)
+}
+
+/// `date`'s usage against the budget; `None` while there is no budget.
+pub fn day_usage(
+    settings: &Settings,
+    events: &[CalendarEvent],
+    date: NaiveDate,
+) -> Option<DayUsage> {
+    let budget = settings.budget.daily_hours?;
+    let minutes = meeting_minutes(settings, events, date);
+    let over_budget = minutes as f64 > budget * 60.0;
+    let relation = if over_budget { "over" } else { "within" };
+    Some(DayUsage {
+        date,
+        meeting_minutes: minutes,
+        over_budget,
+        summary: format!(
+            "{} of meetings, {} your {} budget",
+            hours(minutes as f64),
+            relation,
+            hours(budget * 60.0)
+        ),
+    })
+}
+
+/// How far ahead the scheduler has to fetch to see all of tomorrow.
+pub fn horizon(settings: &Settings, now: DateTime<Local>) -> Option<Duration> {
+    settings.budget.daily_hours?;
+    let end = now.date_naive().checked_add_days(Days::new(2))?;
+    Some(start_of_day(end)? - now)
+}
+
+pub fn status(settings: &Settings, events: &[CalendarEvent], now: DateTime<Local>) -> BudgetStatus {
+    let today = now.date_naive();
+    BudgetStatus {
+        daily_hours: settings.budget.daily_hours,
+        today: day_usage(settings, events, today),
+        tomorrow: today
+            .succ_opt()
+            .and_then(|tomorrow| day_usage(settings, events, tomorrow)),
+    }
+}
+
+#[tauri::command]
+pub fn get_meeting_budget_status(app: AppHandle) -> BudgetStatus {
+    let settings = settings::current(&app);
+    status(&settings, &scheduler::cached_events(&app), simulate::now())
+}
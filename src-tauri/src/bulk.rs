@@ -0,0 +1,638 @@
+use chrono::Local;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::{AppHandle, Window};
+
+use crate::calendar::{self, CalendarEvent, CalendarInfo};
+use crate::ipc::{self, IpcError};
+use crate::rules::{self, AlertRule, AlertStyle};
+use crate::{scheduler, settings};
+
+// --- Bulk Changes ---
+//
+// Setting up a new machine, or a whole team, takes many rules at once. Each
+// bulk command checks every item first, against the calendars and events as
+// they are now, and then writes the settings once. In the default
+// `AllOrNothing` mode one bad item means nothing is written; `SkipInvalid`
+// writes the good ones. Either way the report has a result per item.
+//
+// A rule selector picks calendars by title (`rules::calendar_matches`, the
+// same matching a rule's own calendar uses) or by account, and the rule is
+// saved once for each calendar picked. Where selectors overlap, the rule
+// for the narrower selector wins: an exact title over a pattern, and a
+// pattern over an account. Selectors of the same kind win in item order.
+// The saved rules stay tied to the calendar titles. A calendar added later
+// isn't picked up until the batch is applied again.
+// A rule sent with an id is saved under ids made from it, so applying the
+// batch again replaces those rules instead of adding copies; they go last,
+// as if just saved.
+//
+// A series is muted by a silent rule on its series id. Subscriptions are
+// handed to Calendar, which asks before adding each.
+
+const MUTE_PREFIX: &str = "mute-";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkMode {
+    /// Nothing is written unless every item is valid.
+    #[default]
+    AllOrNothing,
+    /// Valid items are written; the rest are reported.
+    SkipInvalid,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RuleSelector {
+    /// Calendar title, or a pattern with `*`.
+    pub calendar: Option<String>,
+    /// Account the calendars are in, by name ("Exchange", "iCloud").
+    pub account: Option<String>,
+}
+
+impl RuleSelector {
+    fn is_empty(&self) -> bool {
+        self.calendar.is_none() && self.account.is_none()
+    }
+
+    /// Higher is narrower; see the precedence above.
+    fn rank(&self) -> u8 {
+        match (&self.calendar, &self.account) {
+            (Some(title), _) if !title.contains('*') => 3,
+            (Some(_), _) => 2,
+            (None, Some(_)) => 1,
+            (None, None) => 0,
+        }
+    }
+
+    pub fn matches(&self, calendar: &CalendarInfo) -> bool {
+        let title = self
+            .calendar
+            .as_deref()
+            .is_none_or(|pattern| rules::calendar_matches(pattern, &calendar.title));
+        let account = self
+            .account
+            .as_deref()
+            .is_none_or(|account| calendar.source.eq_ignore_ascii_case(account.trim()));
+        title && account
+    }
+}
+
+/// A series by id, or every series with this title among the events known now.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SeriesSelector {
+    pub series_id: Option<String>,
+    pub title: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemResult {
+    /// The item's place in the request.
+    pub index: usize,
+    /// Why the item is invalid; `None` if it is fine.
+    pub error: Option<String>,
+    /// The calendars, series or subscription the item picked.
+    pub matched: Vec<String>,
+    /// The rules the item saved, or would have saved.
+    pub rule_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkReport {
+    /// Whether anything was written.
+    pub applied: bool,
+    pub items: Vec<BulkItemResult>,
+}
+
+/// An item after the validation pass: its result, and what it would save.
+struct Planned {
+    result: BulkItemResult,
+    rank: u8,
+    rules: Vec<AlertRule>,
+}
+
+impl Planned {
+    fn invalid(index: usize, error: String) -> Self {
+        Planned {
+            result: BulkItemResult {
+                index,
+                error: Some(error),
+                matched: Vec::new(),
+                rule_ids: Vec::new(),
+            },
+            rank: 0,
+            rules: Vec::new(),
+        }
+    }
+
+    fn valid(index: usize, rank: u8, matched: Vec<String>, rules: Vec<AlertRule>) -> Self {
+        Planned {
+            result: BulkItemResult {
+                index,
+                error: None,
+                matched,
+                rule_ids: rules.iter().map(|r| r.id.clone()).collect(),
+            },
+            rank,
+            rules,
+        }
+    }
+}
+
+/// Lowercase letters and digits, with a dash for each run of anything else.
+fn slug(title: &str) -> String {
+    let lower = title.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.join("-")
+}
+
+fn plan_rule(
+    index: usize,
+    selector: &RuleSelector,
+    mut rule: AlertRule,
+    calendars: &[CalendarInfo],
+    batch: i64,
+) -> Planned {
+    if let Err(error) = rules::check_chains(&rule) {
+        return Planned::invalid(index, error);
+    }
+    if selector.is_empty() {
+        if rule.id.is_empty() {
+            rule.id = format!("rule-{}-{}", batch, index);
+        }
+        let matched = rule.calendar.iter().cloned().collect();
+        return Planned::valid(index, 0, matched, vec![rule]);
+    }
+    if rule.calendar.is_some() {
+        return Planned::invalid(
+            index,
+            "The rule names a calendar; leave that to the selector".to_string(),
+        );
+    }
+    let mut titles: Vec<String> = Vec::new();
+    for calendar in calendars.iter().filter(|c| selector.matches(c)) {
+        if !titles.contains(&calendar.title) {
+            titles.push(calendar.title.clone());
+        }
+    }
+    if titles.is_empty() {
+        return Planned::invalid(index, "The selector matches no calendar".to_string());
+    }
+    let base = if rule.id.is_empty() {
+        format!("rule-{}-{}", batch, index)
+    } else {
+        rule.id.clone()
+    };
+    let single = titles.len() == 1 && !rule.id.is_empty();
+    let saved = titles
+        .iter()
+        .map(|title| AlertRule {
+            id: if single {
+                base.clone()
+            } else {
+                format!("{}-{}", base, slug(title))
+            },
+            calendar: Some(title.clone()),
+            ..rule.clone()
+        })
+        .collect();
+    Planned::valid(index, selector.rank(), titles, saved)
+}
+
+fn plan_mute(index: usize, selector: &SeriesSelector, events: &[CalendarEvent]) -> Planned {
+    let mut ids: Vec<String> = Vec::new();
+    if let Some(id) = selector.series_id.as_deref().map(str::trim) {
+        if !id.is_empty() {
+            ids.push(id.to_string());
+        }
+    }
+    if let Some(title) = selector.title.as_deref().map(str::trim) {
+        let series = events
+            .iter()
+            .filter(|e| e.recurrence_rule.is_some() && e.title.trim().eq_ignore_ascii_case(title));
+        for event in series {
+            if !ids.contains(&event.id) {
+                ids.push(event.id.clone());
+            }
+        }
+    }
+    if ids.is_empty() {
+        let error = match (&selector.series_id, &selector.title) {
+            (None, None) => "A series id or title is required".to_string(),
+            _ => "The selector matches no series".to_string(),
+        };
+        return Planned::invalid(index, error);
+    }
+    let rules = ids
+        .iter()
+        .map(|id| AlertRule {
+            id: format!("{}{}", MUTE_PREFIX, id),
+            series_id: Some(id.clone()),
+            style: Some(AlertStyle::Silent),
+            ..Default::default()
+        })
+        .collect();
+    Planned::valid(index, 0, ids, rules)
+}
+
+/// Marks a later item invalid when it would save a rule an earlier one
+/// already saves, which would be dropped otherwise.
+fn check_duplicates(planned: &mut [Planned]) {
+    let mut owners: HashMap<String, usize> = HashMap::new();
+    for item in planned.iter_mut().filter(|p| p.result.error.is_none()) {
+        let taken = item
+            .rules
+            .iter()
+            .find_map(|r| owners.get(&r.id).map(|owner| (r.id.clone(), *owner)));
+        if let Some((id, owner)) = taken {
+            item.result.error = Some(format!("Rule {} is also saved by item {}", id, owner));
+            continue;
+        }
+        for rule in &item.rules {
+            owners.insert(rule.id.clone(), item.result.index);
+        }
+    }
+}
+
+/// The rules of the valid items, narrowest selector last so that they win.
+fn to_save(planned: &[Planned]) -> Vec<AlertRule> {
+    let mut valid: Vec<&Planned> = planned
+        .iter()
+        .filter(|p| p.result.error.is_none())
+        .collect();
+    valid.sort_by_key(|p| p.rank);
+    valid.iter().flat_map(|p| p.rules.iter().cloned()).collect()
+}
+
+/// Writes the rules of the valid items in one settings update.
+fn commit(
+    app: &AppHandle,
+    mut planned: Vec<Planned>,
+    mode: BulkMode,
+) -> Result<BulkReport, IpcError> {
+    check_duplicates(&mut planned);
+    let invalid = planned.iter().any(|p| p.result.error.is_some());
+    let applied = !(invalid && mode == BulkMode::AllOrNothing)
+        && planned.iter().any(|p| p.result.error.is_none());
+    if applied {
+        let saved = to_save(&planned);
+        settings::update(app, |settings| {
+            settings
+                .rules
+                .retain(|r| !saved.iter().any(|s| s.id == r.id));
+            settings.rules.extend(saved);
+        })?;
+    }
+    Ok(BulkReport {
+        applied,
+        items: planned.into_iter().map(|p| p.result).collect(),
+    })
+}
+
+fn apply_rules(
+    app: &AppHandle,
+    items: Vec<(RuleSelector, AlertRule)>,
+    mode: BulkMode,
+) -> Result<BulkReport, IpcError> {
+    let calendars = if items.iter().all(|(s, _)| s.is_empty()) {
+        Vec::new()
+    } else {
+        calendar::fetch_calendars()
+    };
+    let batch = Local::now().timestamp_millis();
+    let planned = items
+        .into_iter()
+        .enumerate()
+        .map(|(index, (selector, rule))| plan_rule(index, &selector, rule, &calendars, batch))
+        .collect();
+    commit(app, planned, mode)
+}
+
+/// Saves each rule for the calendars its selector picks.
+#[tauri::command]
+pub async fn apply_rules_bulk(
+    app: AppHandle,
+    window: Window,
+    items: Vec<(RuleSelector, AlertRule)>,
+    mode: Option<BulkMode>,
+) -> Result<BulkReport, IpcError> {
+    ipc::require_dashboard(&window, "apply_rules_bulk")?;
+    let mode = mode.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || apply_rules(&app, items, mode))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Silences every series the selectors pick.
+#[tauri::command]
+pub fn mute_series_bulk(
+    app: AppHandle,
+    window: Window,
+    selectors: Vec<SeriesSelector>,
+    mode: Option<BulkMode>,
+) -> Result<BulkReport, IpcError> {
+    ipc::require_dashboard(&window, "mute_series_bulk")?;
+    let events = scheduler::cached_events(&app);
+    let planned = selectors
+        .iter()
+        .enumerate()
+        .map(|(index, selector)| plan_mute(index, selector, &events))
+        .collect();
+    commit(&app, planned, mode.unwrap_or_default())
+}
+
+/// The feed at `url` as a webcal link, once it has been fetched and read as
+/// a calendar.
+fn check_feed(url: &str) -> Result<String, String> {
+    let mut parsed = reqwest::Url::parse(url.trim()).map_err(|e| format!("{}: {}", url, e))?;
+    match parsed.scheme() {
+        "https" | "http" => {}
+        "webcal" => {
+            let _ = parsed.set_scheme("https");
+        }
+        scheme => return Err(format!("Unsupported scheme {}", scheme)),
+    }
+    let client = reqwest::blocking::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(parsed.clone())
+        .send()
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status.as_u16()));
+    }
+    let body = response.text().map_err(|e| e.to_string())?;
+    if !body.trim_start().starts_with("BEGIN:VCALENDAR") {
+        return Err("Not an iCalendar feed".to_string());
+    }
+    let _ = parsed.set_scheme("webcal");
+    Ok(parsed.to_string())
+}
+
+/// Fetches every feed first; then hands the good ones to Calendar to
+/// subscribe to. Their events come in through EventKit like any other.
+fn add_subscriptions(urls: Vec<String>, mode: BulkMode) -> BulkReport {
+    let mut items: Vec<BulkItemResult> = urls
+        .iter()
+        .enumerate()
+        .map(|(index, url)| {
+            let checked = check_feed(url);
+            BulkItemResult {
+                index,
+                matched: checked.iter().cloned().collect(),
+                error: checked.err(),
+                rule_ids: Vec::new(),
+            }
+        })
+        .collect();
+    let invalid = items.iter().any(|i| i.error.is_some());
+    if invalid && mode == BulkMode::AllOrNothing {
+        return BulkReport {
+            applied: false,
+            items,
+        };
+    }
+    let mut applied = false;
+    for item in items.iter_mut().filter(|i| i.error.is_none()) {
+        match open::that(&item.matched[0]) {
+            Ok(()) => applied = true,
+            Err(e) => item.error = Some(format!("Calendar couldn't open it: {}", e)),
+        }
+    }
+    BulkReport { applied, items }
+}
+
+#[tauri::command]
+pub async fn add_ics_subscriptions_bulk(
+    window: Window,
+    urls: Vec<String>,
+    mode: Option<BulkMode>,
+) -> Result<BulkReport, IpcError> {
+    ipc::require_dashboard(&window, "add_ics_subscriptions_bulk")?;
+    let mode = mode.unwrap_or_default();
+    let report = tauri::async_runtime::spawn_blocking(move || add_subscriptions(urls, mode))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+/// Copies the rules and muted series of the profile `name` into the
+/// settings in use, next to the rules already there, through the same
+/// checks as the bulk commands. Webhooks and escalation stay as they are;
+/// switching to the profile replaces those.
+#[tauri::command]
+pub fn copy_from_profile(
+    app: AppHandle,
+    window: Window,
+    name: String,
+    mode: Option<BulkMode>,
+) -> Result<BulkReport, IpcError> {
+    ipc::require_dashboard(&window, "copy_from_profile")?;
+    let profile = settings::current(&app)
+        .profiles
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or(format!("Unknown profile: {}", name))?;
+    let batch = Local::now().timestamp_millis();
+    let planned = profile
+        .rules
+        .into_iter()
+        .enumerate()
+        .map(|(index, rule)| {
+            if rule.id.starts_with(MUTE_PREFIX) {
+                let selector = SeriesSelector {
+                    series_id: rule.series_id,
+                    title: None,
+                };
+                plan_mute(index, &selector, &[])
+            } else {
+                plan_rule(index, &RuleSelector::default(), rule, &[], batch)
+            }
+        })
+        .collect();
+    commit(&app, planned, mode.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+    use crate::testing::{at, event};
+    use proptest::prelude::*;
+
+    fn calendar(title: &str, source: &str) -> CalendarInfo {
+        CalendarInfo {
+            id: slug(title),
+            title: title.to_string(),
+            kind: "caldav".to_string(),
+            source: source.to_string(),
+            source_type: "exchange".to_string(),
+            allows_modifications: true,
+            delegate: false,
+            excluded: false,
+            auto_excluded: false,
+        }
+    }
+
+    /// Overlapping on purpose: "Work Team" is picked by all three selectors
+    /// below, "Work" by two.
+    fn calendars() -> Vec<CalendarInfo> {
+        vec![
+            calendar("Work", "Exchange"),
+            calendar("Work Team", "Exchange"),
+            calendar("Shared", "Exchange"),
+            calendar("Home", "iCloud"),
+        ]
+    }
+
+    fn selector(calendar: Option<&str>, account: Option<&str>) -> RuleSelector {
+        RuleSelector {
+            calendar: calendar.map(str::to_string),
+            account: account.map(str::to_string),
+        }
+    }
+
+    /// A rule that tells which item it came from by its sound.
+    fn sounding(sound: &str) -> AlertRule {
+        AlertRule {
+            sound: Some(sound.to_string()),
+            ..AlertRule::default()
+        }
+    }
+
+    fn plan(items: &[(RuleSelector, AlertRule)]) -> Vec<Planned> {
+        let mut planned: Vec<Planned> = items
+            .iter()
+            .enumerate()
+            .map(|(index, (selector, rule))| {
+                plan_rule(index, selector, rule.clone(), &calendars(), 1)
+            })
+            .collect();
+        check_duplicates(&mut planned);
+        planned
+    }
+
+    /// The sound an event on `calendar` gets once the batch is saved.
+    fn winner(planned: &[Planned], calendar: &str) -> Option<String> {
+        let settings = Settings {
+            rules: to_save(planned),
+            ..Settings::default()
+        };
+        let meeting = CalendarEvent {
+            calendar: calendar.to_string(),
+            ..event("Planning", at("2026-03-02 10:00"), 30)
+        };
+        rules::evaluate(&settings, &meeting).sound
+    }
+
+    fn overlapping() -> Vec<(RuleSelector, AlertRule)> {
+        vec![
+            (selector(Some("Work Team"), None), sounding("exact")),
+            (selector(Some("work*"), None), sounding("pattern")),
+            (selector(None, Some(" exchange ")), sounding("account")),
+        ]
+    }
+
+    #[test]
+    fn selectors_pick_by_title_pattern_and_account() {
+        let planned = plan(&overlapping());
+        let matched: Vec<Vec<String>> = planned.iter().map(|p| p.result.matched.clone()).collect();
+        assert_eq!(
+            matched,
+            vec![
+                vec!["Work Team"],
+                vec!["Work", "Work Team"],
+                vec!["Work", "Work Team", "Shared"],
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn the_narrowest_selector_wins_whatever_the_order(
+            items in Just(overlapping()).prop_shuffle(),
+        ) {
+            let planned = plan(&items);
+            prop_assert!(planned.iter().all(|p| p.result.error.is_none()));
+            prop_assert_eq!(winner(&planned, "Work Team"), Some("exact".to_string()));
+            prop_assert_eq!(winner(&planned, "Work"), Some("pattern".to_string()));
+            prop_assert_eq!(winner(&planned, "Shared"), Some("account".to_string()));
+            // Untouched by any of them.
+            prop_assert_eq!(winner(&planned, "Home"), winner(&[], "Home"));
+        }
+    }
+
+    #[test]
+    fn selectors_of_a_kind_win_in_item_order() {
+        let planned = plan(&[
+            (selector(Some("Work*"), None), sounding("first")),
+            (selector(Some("*Team"), None), sounding("second")),
+        ]);
+        assert_eq!(winner(&planned, "Work Team").as_deref(), Some("second"));
+        assert_eq!(winner(&planned, "Work").as_deref(), Some("first"));
+    }
+
+    #[test]
+    fn a_rule_sent_with_an_id_is_saved_under_ids_made_from_it() {
+        let focus = AlertRule {
+            id: "focus".to_string(),
+            ..AlertRule::default()
+        };
+        let one = plan(&[(selector(Some("Home"), None), focus.clone())]);
+        assert_eq!(one[0].result.rule_ids, ["focus"]);
+        let many = plan(&[(selector(None, Some("Exchange")), focus)]);
+        assert_eq!(
+            many[0].result.rule_ids,
+            ["focus-work", "focus-work-team", "focus-shared"]
+        );
+    }
+
+    #[test]
+    fn overlapping_items_saving_the_same_rule_are_refused() {
+        let focus = AlertRule {
+            id: "focus".to_string(),
+            ..AlertRule::default()
+        };
+        let planned = plan(&[
+            (selector(Some("Work*"), None), focus.clone()),
+            (selector(None, Some("Exchange")), focus),
+        ]);
+        assert!(planned[0].result.error.is_none());
+        assert_eq!(
+            planned[1].result.error.as_deref(),
+            Some("Rule focus-work is also saved by item 0")
+        );
+    }
+
+    #[test]
+    fn a_selector_must_pick_something_and_own_the_calendar() {
+        let planned = plan(&[
+            (selector(Some("Board*"), None), AlertRule::default()),
+            (
+                selector(None, Some("Exchange")),
+                AlertRule {
+                    calendar: Some("Work".to_string()),
+                    ..AlertRule::default()
+                },
+            ),
+        ]);
+        assert_eq!(
+            planned[0].result.error.as_deref(),
+            Some("The selector matches no calendar")
+        );
+        assert!(planned[1].result.error.is_some());
+    }
+}
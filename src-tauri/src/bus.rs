@@ -0,0 +1,290 @@
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::appearance::SystemAppearance;
+use crate::calendar::CalendarEvent;
+use crate::cancellations::MeetingChange;
+use crate::changes::CalendarDiff;
+use crate::displays::DisplayChange;
+use crate::features::Flag;
+use crate::presentation::PresentationStatus;
+use crate::recent::RecentMeeting;
+use crate::scheduler::{AlertPayload, QueuedAlerts};
+use crate::selftest::SelfTestReport;
+use crate::series::SeriesChange;
+use crate::theme::AlertTheme;
+use crate::timezone::ZoneChange;
+use crate::webhooks::LifecycleEvent;
+use crate::{board, window};
+
+// --- Event Bus ---
+//
+// Subsystems publish typed `AppEvent`s instead of emitting strings. Each one
+// is forwarded to the webview under a fixed name (see `webview`), then handed
+// to every Rust subscriber, which receives it on its own channel.
+
+#[derive(Clone, Debug)]
+pub enum AppEvent {
+    /// Every scheduler tick, with the scheduler's clock.
+    Tick(DateTime<Local>),
+    /// A fetch replaced the cached events.
+    CalendarUpdated {
+        events: usize,
+        diff: Box<CalendarDiff>,
+    },
+    SettingsChanged,
+    ProfileChanged(String),
+    AlertFired(Box<AlertPayload>),
+    /// An alert opened from the dashboard; the event as the frontend sent it.
+    AlertPreview(Value),
+    AlertQueued(QueuedAlerts),
+    AlertTheme(AlertTheme),
+    AlertEscalated(CalendarEvent),
+    AlertExpired,
+    /// The alert was acknowledged, expired or its meeting cancelled.
+    AlertCleared,
+    LinkMissing(CalendarEvent),
+    WindowRecreated(String),
+    AppearanceChanged(SystemAppearance),
+    SelfTestFinished(SelfTestReport),
+    /// A meeting about to start was cancelled or moved.
+    MeetingCancelled(Box<MeetingChange>),
+    TimezoneChanged(ZoneChange),
+    SeriesChanged(SeriesChange),
+    /// Our login session came to the front (true) or went to the background.
+    SessionChanged(bool),
+    /// Presenting to a mirrored or TV display started or stopped.
+    PresentationChanged(PresentationStatus),
+    /// Displays were connected, disconnected or rearranged.
+    DisplaysChanged(DisplayChange),
+    /// Picked from the tray's recent meetings, for the dashboard to show.
+    RecentMeetingShown(Box<RecentMeeting>),
+    /// The alert was moved aside into a corner, or back to fullscreen.
+    AlertCornered(bool),
+    /// The joined meeting the alert window now shows as a pill, or `None`.
+    PillMode(Option<CalendarEvent>),
+    /// Startup is done and every command is served.
+    Ready,
+    /// A feature flag was turned on (true) or off from the dashboard.
+    FeatureChanged(Flag, bool),
+    /// For integrations only; the webview isn't told.
+    Lifecycle(LifecycleEvent, CalendarEvent),
+}
+
+enum Target {
+    All,
+    Main,
+    Alert,
+    Board,
+    /// The alert and the board, which shows it too.
+    AlertAndBoard,
+    /// The dashboard and the board, which both list the events.
+    MainAndBoard,
+}
+
+impl Target {
+    /// The windows it reaches; `All` goes to every window instead.
+    fn windows(&self) -> &'static [&'static str] {
+        // The toast stands in for the alert while presenting.
+        match self {
+            Target::All => &[],
+            Target::Main => &[window::MAIN_WINDOW],
+            Target::Alert => &[window::ALERT_WINDOW, window::TOAST_WINDOW],
+            Target::Board => &[board::BOARD_WINDOW],
+            Target::AlertAndBoard => &[
+                window::ALERT_WINDOW,
+                window::TOAST_WINDOW,
+                board::BOARD_WINDOW,
+            ],
+            Target::MainAndBoard => &[window::MAIN_WINDOW, board::BOARD_WINDOW],
+        }
+    }
+}
+
+impl AppEvent {
+    /// Where the event goes in the webview, under which name.
+    fn webview(&self) -> Option<(Target, &'static str, Value)> {
+        fn json(value: impl Serialize) -> Value {
+            serde_json::to_value(value).unwrap_or_default()
+        }
+        let forwarded = match self {
+            AppEvent::Tick(now) => (Target::All, "tick", json(now)),
+            AppEvent::CalendarUpdated { events, diff } => (
+                Target::MainAndBoard,
+                "calendar-updated",
+                serde_json::json!({ "events": events, "diff": diff }),
+            ),
+            AppEvent::SettingsChanged => (Target::All, "settings-changed", Value::Null),
+            AppEvent::ProfileChanged(name) => (Target::All, "profile-changed", json(name)),
+            AppEvent::AlertFired(payload) => (Target::AlertAndBoard, "alert-fired", json(payload)),
+            AppEvent::AlertPreview(event) => (Target::Alert, "alert-fired", event.clone()),
+            AppEvent::AlertQueued(queued) => (Target::Alert, "alert-queued", json(queued)),
+            AppEvent::AlertTheme(theme) => (Target::Alert, "alert-theme", json(theme)),
+            AppEvent::AlertEscalated(event) => {
+                (Target::AlertAndBoard, "alert-escalated", json(event))
+            }
+            AppEvent::AlertExpired => (Target::Alert, "alert-expired", Value::Null),
+            AppEvent::AlertCleared => (Target::Board, "alert-cleared", Value::Null),
+            AppEvent::LinkMissing(event) => (Target::Main, "link-missing", json(event)),
+            AppEvent::WindowRecreated(label) => (Target::All, "window-recreated", json(label)),
+            AppEvent::AppearanceChanged(appearance) => {
+                (Target::All, "appearance-changed", json(appearance))
+            }
+            AppEvent::SelfTestFinished(report) => {
+                (Target::Main, "self-test-finished", json(report))
+            }
+            AppEvent::MeetingCancelled(change) => (Target::Main, "meeting-cancelled", json(change)),
+            AppEvent::TimezoneChanged(change) => (Target::All, "timezone-changed", json(change)),
+            AppEvent::SeriesChanged(change) => (Target::Main, "series-changed", json(change)),
+            AppEvent::SessionChanged(active) => (Target::All, "session-changed", json(active)),
+            AppEvent::PresentationChanged(status) => {
+                (Target::All, "presentation-changed", json(status))
+            }
+            AppEvent::DisplaysChanged(change) => (Target::All, "displays-changed", json(change)),
+            AppEvent::RecentMeetingShown(meeting) => {
+                (Target::Main, "show-recent-meeting", json(meeting))
+            }
+            AppEvent::AlertCornered(cornered) => (Target::Alert, "alert-cornered", json(cornered)),
+            AppEvent::PillMode(event) => (Target::Alert, "pill-mode", json(event)),
+            AppEvent::Ready => (Target::All, "app-ready", Value::Null),
+            AppEvent::FeatureChanged(flag, enabled) => (
+                Target::Main,
+                "feature-flag-changed",
+                serde_json::json!({ "name": flag, "enabled": enabled }),
+            ),
+            AppEvent::Lifecycle(..) => return None,
+        };
+        Some(forwarded)
+    }
+
+    /// What the webview is sent for the event, if anything.
+    #[cfg(test)]
+    pub fn webview_payload(&self) -> Option<Value> {
+        self.webview().map(|(_, _, payload)| payload)
+    }
+}
+
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Mutex<Vec<Sender<AppEvent>>>,
+}
+
+impl EventBus {
+    fn subscribe(&self) -> Receiver<AppEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers.lock().unwrap().push(tx);
+        rx
+    }
+
+    /// Hands `event` to every subscriber; ones that have gone away are
+    /// dropped.
+    fn send(&self, event: &AppEvent) {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+}
+
+fn forward(app: &AppHandle, event: &AppEvent) {
+    let Some((target, name, payload)) = event.webview() else {
+        return;
+    };
+    if let Target::All = target {
+        let _ = app.emit(name, payload);
+        return;
+    }
+    for label in target.windows() {
+        let _ = app.emit_to(*label, name, payload.clone());
+    }
+}
+
+/// Forwards the event to the webview, then to subscribers.
+pub fn publish(app: &AppHandle, event: AppEvent) {
+    forward(app, &event);
+    if let Some(bus) = app.try_state::<EventBus>() {
+        bus.send(&event);
+    }
+}
+
+/// Every event published from now on.
+pub fn subscribe(app: &AppHandle) -> Receiver<AppEvent> {
+    app.state::<EventBus>().subscribe()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{at, event};
+
+    /// The windows and name the webview gets `event` under.
+    fn forwarded(event: &AppEvent) -> Option<(&'static [&'static str], &'static str)> {
+        let (target, name, _) = event.webview()?;
+        let windows = match target {
+            Target::All => &["all"][..],
+            other => other.windows(),
+        };
+        Some((windows, name))
+    }
+
+    #[test]
+    fn forwards_under_stable_names_to_the_right_window() {
+        let standup = event("Standup", at("2026-03-02 10:00"), 15);
+        let alert: &[&str] = &[window::ALERT_WINDOW, window::TOAST_WINDOW];
+        assert_eq!(
+            forwarded(&AppEvent::Tick(at("2026-03-02 09:00"))),
+            Some((&["all"][..], "tick"))
+        );
+        assert_eq!(
+            forwarded(&AppEvent::AlertEscalated(standup.clone())),
+            Some((
+                &[alert, &[board::BOARD_WINDOW]].concat()[..],
+                "alert-escalated"
+            ))
+        );
+        assert_eq!(
+            forwarded(&AppEvent::AlertExpired),
+            Some((alert, "alert-expired"))
+        );
+        assert_eq!(
+            forwarded(&AppEvent::AlertCleared),
+            Some((&[board::BOARD_WINDOW][..], "alert-cleared"))
+        );
+        assert_eq!(
+            forwarded(&AppEvent::LinkMissing(standup.clone())),
+            Some((&[window::MAIN_WINDOW][..], "link-missing"))
+        );
+        assert_eq!(
+            forwarded(&AppEvent::Ready),
+            Some((&["all"][..], "app-ready"))
+        );
+        // Integrations only.
+        assert_eq!(
+            forwarded(&AppEvent::Lifecycle(LifecycleEvent::AlertFired, standup)),
+            None
+        );
+    }
+
+    #[test]
+    fn every_subscriber_gets_every_event_in_order() {
+        let bus = EventBus::default();
+        let first = bus.subscribe();
+        let second = bus.subscribe();
+        let gone = bus.subscribe();
+        drop(gone);
+        bus.send(&AppEvent::SettingsChanged);
+        bus.send(&AppEvent::AlertExpired);
+        assert_eq!(bus.subscribers.lock().unwrap().len(), 2);
+        for events in [first, second] {
+            let received: Vec<AppEvent> = events.try_iter().collect();
+            assert!(matches!(
+                received[..],
+                [AppEvent::SettingsChanged, AppEvent::AlertExpired]
+            ));
+        }
+    }
+}
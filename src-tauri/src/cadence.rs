@@ -0,0 +1,221 @@
+use chrono::{DateTime, Duration, Local};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::power::{self, LOW_POWER_POLL_SECONDS};
+use crate::settings::Settings;
+
+// --- Poll Cadence ---
+//
+// How often the calendar is refetched, from what is coming up: every 15
+// seconds in the 10 minutes before an alert or an event starting or ending,
+// so a last-minute room change shows up in time; every few minutes during
+// working hours; every 15 to 30 minutes at night and on days off. On battery
+// the low-power interval is the fastest it goes. Anything that invalidates
+// the cache, like a change `calendar_watch` saw, a manual refresh or a wake
+// from sleep, still refetches right away. With `adaptive_polling` off the
+// calendar is polled every `poll_interval_seconds`, as before.
+
+/// Before a boundary: alert times, event starts and event ends.
+const NEAR_MINUTES: i64 = 10;
+const NEAR_SECONDS: u64 = 15;
+/// A boundary this close makes the working-hours and off-hours intervals
+/// the shorter of their two.
+const SOON_MINUTES: i64 = 60;
+const WORKING_SOON_SECONDS: u64 = 120;
+const WORKING_SECONDS: u64 = 300;
+const OFF_HOURS_SOON_SECONDS: u64 = 900;
+const OFF_HOURS_SECONDS: u64 = 1800;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CadenceReason {
+    /// `adaptive_polling` is off.
+    Fixed,
+    NearBoundary,
+    WorkingHours,
+    OffHours,
+    /// On battery, slower than the schedule asked for.
+    LowPower,
+}
+
+/// The poll interval in effect and why; in the health report.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Cadence {
+    pub seconds: u64,
+    pub reason: CadenceReason,
+    /// The alert or event boundary it is tightening for, if any is near.
+    pub next_boundary: Option<DateTime<Local>>,
+}
+
+/// The poll interval at `now`, given the upcoming `boundaries` (in any
+/// order; past ones are ignored) and whether the low-power profile is on.
+pub fn interval(
+    settings: &Settings,
+    boundaries: &[DateTime<Local>],
+    now: DateTime<Local>,
+    low_power: bool,
+) -> Cadence {
+    let next_boundary = boundaries.iter().copied().filter(|b| *b >= now).min();
+    let within = |minutes| next_boundary.is_some_and(|b| b - now <= Duration::minutes(minutes));
+    let (seconds, reason) = if !settings.adaptive_polling {
+        (settings.poll_interval_seconds, CadenceReason::Fixed)
+    } else if within(NEAR_MINUTES) {
+        (NEAR_SECONDS, CadenceReason::NearBoundary)
+    } else if in_working_hours(settings, now) {
+        let seconds = if within(SOON_MINUTES) {
+            WORKING_SOON_SECONDS
+        } else {
+            WORKING_SECONDS
+        };
+        (seconds, CadenceReason::WorkingHours)
+    } else {
+        let seconds = if within(SOON_MINUTES) {
+            OFF_HOURS_SOON_SECONDS
+        } else {
+            OFF_HOURS_SECONDS
+        };
+        (seconds, CadenceReason::OffHours)
+    };
+    let (seconds, reason) = if low_power && seconds < LOW_POWER_POLL_SECONDS {
+        (LOW_POWER_POLL_SECONDS, CadenceReason::LowPower)
+    } else {
+        (seconds, reason)
+    };
+    Cadence {
+        seconds,
+        reason,
+        next_boundary: next_boundary.filter(|_| reason != CadenceReason::Fixed),
+    }
+}
+
+fn in_working_hours(settings: &Settings, now: DateTime<Local>) -> bool {
+    settings
+        .working_hours
+        .hours_on(now.date_naive())
+        .is_some_and(|(start, end)| start <= now && now < end)
+}
+
+/// The longest any cadence goes between polls, for how far ahead a fetch
+/// has to reach.
+pub fn longest_seconds(settings: &Settings) -> u64 {
+    if settings.adaptive_polling {
+        OFF_HOURS_SECONDS
+    } else {
+        power::poll_interval_seconds(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::at;
+
+    /// Nine to five on weekdays.
+    fn office_hours() -> Settings {
+        let mut settings = Settings::default();
+        settings.working_hours.enabled = true;
+        settings
+    }
+
+    fn at_point(settings: &Settings, boundaries: &[&str], now: &str) -> (u64, CadenceReason) {
+        let boundaries: Vec<_> = boundaries.iter().map(|b| at(b)).collect();
+        let cadence = interval(settings, &boundaries, at(now), false);
+        (cadence.seconds, cadence.reason)
+    }
+
+    #[test]
+    fn follows_a_day_of_meetings() {
+        let settings = office_hours();
+        // Monday: a standup at 10:00 alerted at 09:59, and drinks at 17:30.
+        let day = [
+            "2026-03-02 09:59",
+            "2026-03-02 10:00",
+            "2026-03-02 10:15",
+            "2026-03-02 17:29",
+            "2026-03-02 17:30",
+            "2026-03-02 18:30",
+        ];
+        use CadenceReason::*;
+        for (now, expected) in [
+            ("2026-03-02 07:00", (OFF_HOURS_SECONDS, OffHours)),
+            ("2026-03-02 08:45", (OFF_HOURS_SECONDS, OffHours)),
+            ("2026-03-02 09:00", (WORKING_SOON_SECONDS, WorkingHours)),
+            ("2026-03-02 09:48", (WORKING_SOON_SECONDS, WorkingHours)),
+            ("2026-03-02 09:49", (NEAR_SECONDS, NearBoundary)),
+            ("2026-03-02 10:05", (NEAR_SECONDS, NearBoundary)),
+            ("2026-03-02 10:15", (NEAR_SECONDS, NearBoundary)),
+            ("2026-03-02 10:16", (WORKING_SECONDS, WorkingHours)),
+            ("2026-03-02 16:28", (WORKING_SECONDS, WorkingHours)),
+            ("2026-03-02 16:30", (WORKING_SOON_SECONDS, WorkingHours)),
+            ("2026-03-02 17:00", (OFF_HOURS_SOON_SECONDS, OffHours)),
+            ("2026-03-02 17:20", (NEAR_SECONDS, NearBoundary)),
+            ("2026-03-02 18:45", (OFF_HOURS_SECONDS, OffHours)),
+        ] {
+            assert_eq!(at_point(&settings, &day, now), expected, "at {}", now);
+        }
+    }
+
+    #[test]
+    fn the_weekend_is_off_hours() {
+        let settings = office_hours();
+        let saturday = ["2026-03-07 12:30"];
+        assert_eq!(
+            at_point(&settings, &saturday, "2026-03-07 10:00"),
+            (OFF_HOURS_SECONDS, CadenceReason::OffHours)
+        );
+        assert_eq!(
+            at_point(&settings, &saturday, "2026-03-07 12:00"),
+            (OFF_HOURS_SOON_SECONDS, CadenceReason::OffHours)
+        );
+        assert_eq!(
+            at_point(&settings, &saturday, "2026-03-07 12:25"),
+            (NEAR_SECONDS, CadenceReason::NearBoundary)
+        );
+    }
+
+    #[test]
+    fn past_boundaries_are_ignored() {
+        let settings = office_hours();
+        let boundaries = [at("2026-03-02 10:55"), at("2026-03-02 12:30")];
+        let cadence = interval(&settings, &boundaries, at("2026-03-02 11:00"), false);
+        assert_eq!(cadence.seconds, WORKING_SECONDS);
+        assert_eq!(cadence.next_boundary, Some(at("2026-03-02 12:30")));
+        let cadence = interval(&settings, &boundaries, at("2026-03-02 13:00"), false);
+        assert_eq!(cadence.next_boundary, None);
+    }
+
+    #[test]
+    fn fixed_when_adaptive_polling_is_off() {
+        let settings = Settings {
+            adaptive_polling: false,
+            poll_interval_seconds: 90,
+            ..office_hours()
+        };
+        let boundaries = [at("2026-03-02 10:00")];
+        let cadence = interval(&settings, &boundaries, at("2026-03-02 09:55"), false);
+        assert_eq!(
+            cadence,
+            Cadence {
+                seconds: 90,
+                reason: CadenceReason::Fixed,
+                next_boundary: None,
+            }
+        );
+    }
+
+    #[test]
+    fn low_power_sets_a_floor() {
+        let settings = office_hours();
+        let boundaries = [at("2026-03-02 10:00")];
+        let near = interval(&settings, &boundaries, at("2026-03-02 09:55"), true);
+        assert_eq!(near.seconds, LOW_POWER_POLL_SECONDS);
+        assert_eq!(near.reason, CadenceReason::LowPower);
+        assert_eq!(near.next_boundary, Some(at("2026-03-02 10:00")));
+        // Slower than the floor already, the schedule stands.
+        let night = interval(&settings, &boundaries, at("2026-03-02 02:00"), true);
+        assert_eq!(night.seconds, OFF_HOURS_SECONDS);
+        assert_eq!(night.reason, CadenceReason::OffHours);
+    }
+}
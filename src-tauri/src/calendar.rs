@@ -0,0 +1,1323 @@
+use chrono::{DateTime, Datelike, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use crate::counterparts::CounterpartTime;
+use crate::locale::{self, FormatSettings};
+use crate::location::{self, LocationKind};
+use crate::{children, helper, simulate, untrusted};
+
+// --- Calendar Logic (Powered by Swift) ---
+
+// We use Swift's EventKit because JXA (AppleScript) fails to fetch
+// recurring events (like Daily Standups) properly.
+//
+// Every helper script is this prelude followed by a body defining `run()`.
+const SWIFT_PRELUDE: &str = r#"
+import EventKit
+import Foundation
+
+let store = EKEventStore()
+let semaphore = DispatchSemaphore(value: 0)
+
+// Request Access
+if #available(macOS 14.0, *) {
+    store.requestFullAccessToEvents { (granted, error) in
+        if !granted {
+            fail("Calendar access denied")
+        }
+        run()
+    }
+} else {
+    store.requestAccess(to: .event) { (granted, error) in
+        if !granted {
+            fail("Calendar access denied")
+        }
+        run()
+    }
+}
+
+// Prints the JSON payload between marker lines, so the app can find it
+// among anything else that ends up on stdout.
+func emit(_ json: String) {
+    print("<<<IN-YOUR-FACE-JSON")
+    print(json)
+    print("IN-YOUR-FACE-JSON>>>")
+}
+
+// Exits non-zero, so the app can tell a failed read from an empty calendar.
+func fail(_ message: String) -> Never {
+    FileHandle.standardError.write((message + "\n").data(using: .utf8)!)
+    exit(1)
+}
+
+func calendarType(_ calendar: EKCalendar?) -> String {
+    switch calendar?.type {
+    case .local: return "local"
+    case .calDAV: return "caldav"
+    case .exchange: return "exchange"
+    case .subscription: return "subscription"
+    case .birthday: return "birthday"
+    default: return "other"
+    }
+}
+
+func sourceType(_ source: EKSource?) -> String {
+    switch source?.sourceType {
+    case .local: return "local"
+    case .calDAV: return "caldav"
+    case .exchange: return "exchange"
+    case .mobileMe: return "icloud"
+    case .subscribed: return "subscribed"
+    case .birthdays: return "birthday"
+    default: return "other"
+    }
+}
+
+func participantEmail(_ participant: EKParticipant) -> String? {
+    let url = participant.url.absoluteString
+    return url.hasPrefix("mailto:") ? String(url.dropFirst(7)) : nil
+}
+
+func participantStatus(_ participant: EKParticipant) -> String {
+    switch participant.participantStatus {
+    case .accepted: return "accepted"
+    case .declined: return "declined"
+    case .tentative: return "tentative"
+    case .pending: return "pending"
+    default: return "unknown"
+    }
+}
+
+func frequencyName(_ frequency: EKRecurrenceFrequency) -> String {
+    switch frequency {
+    case .daily: return "daily"
+    case .weekly: return "weekly"
+    case .monthly: return "monthly"
+    case .yearly: return "yearly"
+    @unknown default: return "daily"
+    }
+}
+
+struct JsonRecurrenceDay: Codable {
+    let weekday: Int
+    let weekNumber: Int
+}
+
+struct JsonRecurrence: Codable {
+    let frequency: String
+    let interval: Int
+    let days: [JsonRecurrenceDay]
+    let daysOfMonth: [Int]
+    let endDate: String?
+    let occurrenceCount: Int?
+}
+
+func jsonRecurrence(_ rule: EKRecurrenceRule, _ formatter: ISO8601DateFormatter) -> JsonRecurrence {
+    return JsonRecurrence(
+        frequency: frequencyName(rule.frequency),
+        interval: rule.interval,
+        days: (rule.daysOfTheWeek ?? []).map { day in
+            JsonRecurrenceDay(weekday: day.dayOfTheWeek.rawValue, weekNumber: day.weekNumber)
+        },
+        daysOfMonth: (rule.daysOfTheMonth ?? []).map { $0.intValue },
+        endDate: rule.recurrenceEnd?.endDate.map { formatter.string(from: $0) },
+        occurrenceCount: rule.recurrenceEnd.flatMap { $0.occurrenceCount > 0 ? $0.occurrenceCount : nil }
+    )
+}
+
+struct JsonAttendee: Codable {
+    let name: String?
+    let email: String?
+    let status: String
+    let isCurrentUser: Bool
+}
+
+struct JsonEvent: Codable {
+    let id: String
+    let calendar: String
+    let calendarType: String
+    let title: String
+    let start: String
+    let end: String
+    let location: String?
+    let description: String?
+    let url: String?
+    let isAllDay: Bool
+    let hasCoordinates: Bool
+    let organizer: String?
+    let attendees: [JsonAttendee]
+    let recurrenceRule: JsonRecurrence?
+    let readOnly: Bool
+    let alarmOffsets: [Int]
+    let isFree: Bool
+    let timeZone: String?
+}
+
+func jsonEvent(_ event: EKEvent) -> JsonEvent {
+    let formatter = ISO8601DateFormatter()
+    // Ensure we use local time for string output to match user expectation
+    formatter.timeZone = TimeZone.current
+
+    return JsonEvent(
+        id: event.eventIdentifier ?? "",
+        calendar: event.calendar?.title ?? "",
+        calendarType: calendarType(event.calendar),
+        title: event.title,
+        start: formatter.string(from: event.startDate),
+        end: formatter.string(from: event.endDate),
+        location: event.location,
+        description: event.notes,
+        url: event.url?.absoluteString,
+        isAllDay: event.isAllDay,
+        hasCoordinates: event.structuredLocation?.geoLocation != nil,
+        organizer: event.organizer.flatMap(participantEmail),
+        attendees: (event.attendees ?? []).map { attendee in
+            JsonAttendee(
+                name: attendee.name,
+                email: participantEmail(attendee),
+                status: participantStatus(attendee),
+                isCurrentUser: attendee.isCurrentUser
+            )
+        },
+        recurrenceRule: event.recurrenceRules?.first.map { jsonRecurrence($0, formatter) },
+        readOnly: !(event.calendar?.allowsContentModifications ?? true),
+        // Seconds before the start; absolute alarms are converted.
+        alarmOffsets: (event.alarms ?? []).map { alarm in
+            if let date = alarm.absoluteDate {
+                return Int(event.startDate.timeIntervalSince(date))
+            }
+            return Int(-alarm.relativeOffset)
+        },
+        isFree: event.availability == .free,
+        // Floating events have none.
+        timeZone: event.timeZone?.identifier
+    )
+}
+"#;
+
+/// The next 24 hours, or the range given as Unix seconds in arguments 1 and 2.
+const EVENTS_SCRIPT: &str = r#"
+func run() {
+    let args = CommandLine.arguments
+    var now = Date()
+    // Look ahead 24 hours
+    var end = Date().addingTimeInterval(24 * 3600)
+    if args.count > 2, let from = Double(args[1]), let to = Double(args[2]) {
+        now = Date(timeIntervalSince1970: from)
+        end = Date(timeIntervalSince1970: to)
+    }
+
+    // Fetch all calendars
+    let predicate = store.predicateForEvents(withStart: now, end: end, calendars: nil)
+    let events = store.events(matching: predicate)
+
+    let encoder = JSONEncoder()
+    if let data = try? encoder.encode(events.map(jsonEvent)),
+       let string = String(data: data, encoding: .utf8) {
+        emit(string)
+    } else {
+        fail("Events couldn't be encoded")
+    }
+    semaphore.signal()
+}
+"#;
+
+/// Looks up one event by identifier; with an occurrence start (arguments 1
+/// and 2) it searches just around that start so recurring events resolve to
+/// the right instance.
+const DETAILS_SCRIPT: &str = r#"
+struct JsonDetails: Codable {
+    let event: JsonEvent
+    let isDetached: Bool
+}
+
+struct JsonError: Codable {
+    let error: String
+}
+
+func printJson<T: Encodable>(_ value: T) {
+    if let data = try? JSONEncoder().encode(value),
+       let string = String(data: data, encoding: .utf8) {
+        emit(string)
+    }
+}
+
+func run() {
+    let args = CommandLine.arguments
+    let id = args.count > 1 ? args[1] : ""
+    var found: EKEvent? = nil
+
+    if args.count > 2, let start = ISO8601DateFormatter().date(from: args[2]) {
+        let predicate = store.predicateForEvents(
+            withStart: start.addingTimeInterval(-60),
+            end: start.addingTimeInterval(60),
+            calendars: nil
+        )
+        found = store.events(matching: predicate).first { event in
+            event.eventIdentifier == id && abs(event.startDate.timeIntervalSince(start)) < 1
+        }
+        if found == nil && store.event(withIdentifier: id) != nil {
+            printJson(JsonError(error: "occurrence_not_found"))
+            semaphore.signal()
+            return
+        }
+    } else {
+        found = store.event(withIdentifier: id)
+    }
+
+    guard let event = found else {
+        printJson(JsonError(error: "not_found"))
+        semaphore.signal()
+        return
+    }
+
+    printJson(JsonDetails(event: jsonEvent(event), isDetached: event.isDetached))
+    semaphore.signal()
+}
+"#;
+
+/// Appends a line to the notes of one occurrence, only that occurrence and
+/// only if the line isn't there yet. Events the user organizes for others are
+/// left alone, since their notes are shared. Prints "added" or "present".
+const ANNOTATE_SCRIPT: &str = r#"
+func run() {
+    let args = CommandLine.arguments
+    guard args.count > 3, let start = ISO8601DateFormatter().date(from: args[2]) else {
+        fail("Usage: annotate <id> <start> <line>")
+    }
+    let (id, line) = (args[1], args[3])
+    let predicate = store.predicateForEvents(
+        withStart: start.addingTimeInterval(-60),
+        end: start.addingTimeInterval(60),
+        calendars: nil
+    )
+    let found = store.events(matching: predicate).first { event in
+        event.eventIdentifier == id && abs(event.startDate.timeIntervalSince(start)) < 1
+    }
+    guard let event = found else {
+        fail("Occurrence not found")
+    }
+    guard event.calendar?.allowsContentModifications ?? false else {
+        fail("Calendar is read-only")
+    }
+    // The organizer's notes go out to everyone invited.
+    if event.hasAttendees && (event.organizer?.isCurrentUser ?? false) {
+        fail("Shared event organized by you")
+    }
+    let notes = event.notes ?? ""
+    if notes.contains(line) {
+        print("present")
+    } else {
+        event.notes = notes.isEmpty ? line : notes + "\n" + line
+        do {
+            try store.save(event, span: .thisEvent, commit: true)
+        } catch {
+            fail("Not saved: \(error.localizedDescription)")
+        }
+        print("added")
+    }
+    semaphore.signal()
+}
+"#;
+
+/// Prints `WATCHING` once it listens, then `CHANGED` a second after each
+/// burst of changes to the calendar database, since a sync posts several.
+/// Runs until it is killed.
+const WATCH_SCRIPT: &str = r#"
+func run() {
+    var pending: DispatchWorkItem?
+    _ = NotificationCenter.default.addObserver(
+        forName: .EKEventStoreChanged, object: store, queue: .main
+    ) { _ in
+        pending?.cancel()
+        let work = DispatchWorkItem {
+            print("IN-YOUR-FACE-CHANGED")
+            fflush(stdout)
+        }
+        pending = work
+        DispatchQueue.main.asyncAfter(deadline: .now() + 1, execute: work)
+    }
+    print("IN-YOUR-FACE-WATCHING")
+    fflush(stdout)
+}
+"#;
+
+/// The lines `WATCH_SCRIPT` prints.
+pub const WATCHING: &str = "IN-YOUR-FACE-WATCHING";
+pub const CHANGED: &str = "IN-YOUR-FACE-CHANGED";
+
+/// Standalone (no prelude): reports the authorization without prompting.
+const PERMISSION_SCRIPT: &str = r#"
+import EventKit
+
+switch EKEventStore.authorizationStatus(for: .event) {
+case .notDetermined: print("not_determined")
+case .restricted: print("restricted")
+case .denied: print("denied")
+case .fullAccess: print("full_access")
+case .writeOnly: print("write_only")
+default: print("authorized")
+}
+"#;
+
+const CALENDARS_SCRIPT: &str = r#"
+func run() {
+    struct JsonCalendar: Codable {
+        let id: String
+        let title: String
+        let kind: String
+        let source: String
+        let sourceType: String
+        let allowsModifications: Bool
+        let delegate: Bool
+
+        enum CodingKeys: String, CodingKey {
+            case id, title, kind, source, delegate
+            case sourceType = "source_type"
+            case allowsModifications = "allows_modifications"
+        }
+    }
+
+    func jsonCalendar(_ calendar: EKCalendar, delegate: Bool) -> JsonCalendar {
+        return JsonCalendar(
+            id: calendar.calendarIdentifier,
+            title: calendar.title,
+            kind: calendarType(calendar),
+            source: calendar.source?.title ?? "",
+            sourceType: sourceType(calendar.source),
+            allowsModifications: calendar.allowsContentModifications,
+            delegate: delegate
+        )
+    }
+
+    var jsonCalendars = store.calendars(for: .event).map { jsonCalendar($0, delegate: false) }
+    // Calendars shared by delegation (e.g. an assistant-managed Exchange
+    // calendar) aren't in the default list.
+    if #available(macOS 12.0, *), !store.delegateSources.isEmpty {
+        let delegateStore = EKEventStore(sources: store.delegateSources)
+        jsonCalendars += delegateStore.calendars(for: .event).map { jsonCalendar($0, delegate: true) }
+    }
+
+    let encoder = JSONEncoder()
+    if let data = try? encoder.encode(jsonCalendars),
+       let string = String(data: data, encoding: .utf8) {
+        emit(string)
+    } else {
+        emit("[]")
+    }
+    semaphore.signal()
+}
+"#;
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEvent {
+    #[serde(default)]
+    pub id: String,
+    /// Title of the calendar the event belongs to.
+    #[serde(default)]
+    pub calendar: String,
+    /// EKCalendar type: local, caldav, exchange, subscription, birthday.
+    #[serde(default)]
+    pub calendar_type: String,
+    pub title: String,
+    pub start: String,
+    pub end: String,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub is_all_day: bool,
+    /// The location carries map coordinates (picked from Maps in Calendar).
+    #[serde(default)]
+    pub has_coordinates: bool,
+    /// Organizer email address.
+    #[serde(default)]
+    pub organizer: Option<String>,
+    #[serde(default)]
+    pub attendees: Vec<Attendee>,
+    /// Why the event won't alert; only filled in for debugging.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filtered_reason: Option<String>,
+    /// Video call link found in the event; kept when the notes are pruned.
+    #[serde(default)]
+    pub video_link: Option<String>,
+    /// Rule components as EventKit reports them; see `recurrence`.
+    #[serde(default)]
+    pub recurrence_rule: Option<Recurrence>,
+    /// e.g. "Weekly on Tue, Thu"; `None` for one-off events.
+    #[serde(default)]
+    pub recurrence: Option<String>,
+    #[serde(default)]
+    pub location_kind: LocationKind,
+    /// The location for display; room names lose their capacity/AV tags.
+    #[serde(default)]
+    pub location_display: Option<String>,
+    /// Apple Maps link when the location is an address.
+    #[serde(default)]
+    pub map_url: Option<String>,
+    /// The user muted this occurrence from the dashboard.
+    #[serde(default)]
+    pub muted: bool,
+    /// The event's calendar doesn't allow modifications.
+    #[serde(default)]
+    pub read_only: bool,
+    /// The event's own alarms, in seconds before the start.
+    #[serde(default)]
+    pub alarm_offsets: Vec<i64>,
+    /// Marked as free time rather than busy.
+    #[serde(default)]
+    pub is_free: bool,
+    /// Long enough not to count as a meeting; see `long_events`.
+    #[serde(default)]
+    pub is_long_block: bool,
+    /// The IANA zone the event was created in, e.g. by its organizer.
+    #[serde(default)]
+    pub time_zone: Option<String>,
+    /// Shown under a confidential display policy; see `confidential`.
+    #[serde(default)]
+    pub redacted: bool,
+    /// Snapshots taken when the event was returned; the `tick` event is what
+    /// keeps a countdown live.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seconds_until_start: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_start: Option<String>,
+}
+
+/// Everything known about one event, for the alert and the detail pane.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEventDetails {
+    #[serde(flatten)]
+    pub event: CalendarEvent,
+    /// This occurrence was edited separately from its series.
+    pub is_detached: bool,
+    /// The start for attendees in other zones; see `counterparts`.
+    pub counterpart_local_times: Vec<CounterpartTime>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Recurrence {
+    /// daily, weekly, monthly or yearly.
+    pub frequency: String,
+    pub interval: i64,
+    pub days: Vec<RecurrenceDay>,
+    /// 1 to 31, negative counting from the end of the month.
+    pub days_of_month: Vec<i64>,
+    pub end_date: Option<String>,
+    pub occurrence_count: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceDay {
+    /// EventKit weekday number, 1 = Sunday through 7 = Saturday.
+    pub weekday: u8,
+    /// Week of the month ("second Tuesday"); 0 means every week, -1 the last.
+    pub week_number: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum DetailsError {
+    NotFound(String),
+    OccurrenceNotFound { id: String, start: String },
+    AccessDenied,
+    Failed(String),
+}
+
+/// Why a change to an event wasn't saved.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum WriteError {
+    /// A subscription or delegate calendar that doesn't allow changes.
+    ReadOnlyCalendar,
+    Failed(String),
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WriteError::ReadOnlyCalendar => f.write_str(READ_ONLY),
+            WriteError::Failed(reason) => f.write_str(reason),
+        }
+    }
+}
+
+const WEEKDAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const WORK_WEEK: [u8; 5] = [2, 3, 4, 5, 6];
+
+fn weekday_name(day: u8) -> Option<&'static str> {
+    WEEKDAY_NAMES.get((day as usize).checked_sub(1)?).copied()
+}
+
+fn ordinal(n: i64) -> String {
+    match n {
+        -1 => "last".to_string(),
+        1 => "first".to_string(),
+        2 => "second".to_string(),
+        3 => "third".to_string(),
+        4 => "fourth".to_string(),
+        5 => "fifth".to_string(),
+        n if n < 0 => format!("{} from last", ordinal(-n)),
+        n => format!("{}th", n),
+    }
+}
+
+fn day_of_month(n: i64) -> String {
+    match n {
+        -1 => "the last day".to_string(),
+        n if n < 0 => format!("{} day from the end", ordinal(-n)),
+        n => format!("day {}", n),
+    }
+}
+
+impl Recurrence {
+    /// "Every weekday", "Weekly on Tue, Thu", "Every 2 weeks on Mon",
+    /// "Monthly on the second Tue", "Daily until Jun 30", "Yearly, 5 times".
+    pub fn describe(&self) -> String {
+        let every = |unit: &str, adverb: &str| {
+            if self.interval <= 1 {
+                adverb.to_string()
+            } else {
+                format!("Every {} {}s", self.interval, unit)
+            }
+        };
+        let mut weekdays: Vec<u8> = self
+            .days
+            .iter()
+            .filter(|d| d.week_number == 0)
+            .map(|d| d.weekday)
+            .collect();
+        weekdays.sort_unstable();
+        weekdays.dedup();
+        let weekday_list = || {
+            let names: Vec<&str> = weekdays.iter().filter_map(|d| weekday_name(*d)).collect();
+            names.join(", ")
+        };
+
+        let mut text = match self.frequency.as_str() {
+            "weekly" | "daily" if weekdays == WORK_WEEK && self.interval <= 1 => {
+                "Every weekday".to_string()
+            }
+            "weekly" if !weekdays.is_empty() => {
+                format!("{} on {}", every("week", "Weekly"), weekday_list())
+            }
+            "weekly" => every("week", "Weekly"),
+            "monthly" => {
+                let mut text = every("month", "Monthly");
+                let nth: Vec<String> = self
+                    .days
+                    .iter()
+                    .filter(|d| d.week_number != 0)
+                    .filter_map(|d| {
+                        Some(format!(
+                            "the {} {}",
+                            ordinal(d.week_number),
+                            weekday_name(d.weekday)?
+                        ))
+                    })
+                    .collect();
+                if !self.days_of_month.is_empty() {
+                    let days: Vec<String> = self
+                        .days_of_month
+                        .iter()
+                        .map(|d| day_of_month(*d))
+                        .collect();
+                    text.push_str(&format!(" on {}", days.join(", ")));
+                } else if !nth.is_empty() {
+                    text.push_str(&format!(" on {}", nth.join(", ")));
+                }
+                text
+            }
+            "yearly" => every("year", "Yearly"),
+            _ => every("day", "Daily"),
+        };
+
+        if let Some(end) = self.end_date.as_deref().and_then(parse_time) {
+            let format = if end.year() == Local::now().year() {
+                "%b %-d"
+            } else {
+                "%b %-d, %Y"
+            };
+            text.push_str(&format!(" until {}", end.format(format)));
+        } else if let Some(count) = self.occurrence_count {
+            text.push_str(&format!(", {} times", count));
+        }
+        text
+    }
+}
+
+/// One page of the dashboard's event list.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPage {
+    pub events: Vec<CalendarEvent>,
+    pub total: usize,
+    pub next_offset: Option<usize>,
+    /// EventKit couldn't be read; these are the scheduler's last good events.
+    pub stale: bool,
+    pub last_success_at: Option<DateTime<Local>>,
+}
+
+/// The most a page of events may weigh as JSON. A default page of pruned
+/// events is far under it; a few hundred with their notes and attendees may
+/// not be, and is refused rather than sent.
+pub const MAX_PAGE_BYTES: usize = 512 * 1024;
+
+impl EventPage {
+    /// The page as JSON, or why it can't be sent.
+    pub fn to_json(&self) -> Result<String, String> {
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        if json.len() > MAX_PAGE_BYTES {
+            return Err(format!(
+                "Event page too large ({} KB); ask for fewer events or leave out the details",
+                json.len() / 1024
+            ));
+        }
+        Ok(json)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Attendee {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    /// accepted, declined, tentative, pending or unknown.
+    pub status: String,
+    #[serde(default)]
+    pub is_current_user: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CalendarInfo {
+    pub id: String,
+    pub title: String,
+    pub kind: String,
+    pub source: String,
+    /// EKSource type: local, caldav, exchange, icloud, subscribed, birthday.
+    #[serde(default)]
+    pub source_type: String,
+    /// False for read-only calendars (subscriptions, some delegates): their
+    /// events can't be responded to or edited.
+    #[serde(default = "default_true")]
+    pub allows_modifications: bool,
+    /// Shared with the user by delegation rather than owned.
+    #[serde(default)]
+    pub delegate: bool,
+    /// Excluded from alerts and the dashboard, explicitly or automatically.
+    #[serde(default)]
+    pub excluded: bool,
+    /// Detected as a holiday/birthday calendar and excluded by default.
+    #[serde(default)]
+    pub auto_excluded: bool,
+}
+
+/// Which calendars to hide: the explicit list plus auto-detected noise.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CalendarFilter {
+    pub excluded: Vec<String>,
+    /// Exclude birthday calendars and subscribed holiday calendars.
+    pub auto_exclude: bool,
+    /// Case-insensitive title fragments identifying holiday calendars.
+    pub holiday_patterns: Vec<String>,
+    /// Auto-excluded calendars the user re-enabled.
+    pub included: Vec<String>,
+    pub min_duration_minutes: Option<i64>,
+    pub max_duration_minutes: Option<i64>,
+    /// Per-calendar limits, overriding the global ones.
+    pub durations: Vec<CalendarDurations>,
+    /// Exclude timed events whose end equals their start.
+    pub exclude_zero_length: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CalendarDurations {
+    pub calendar: String,
+    pub min_duration_minutes: Option<i64>,
+    pub max_duration_minutes: Option<i64>,
+}
+
+impl Default for CalendarFilter {
+    fn default() -> Self {
+        let patterns = [
+            "holiday",
+            "feiertage",
+            "libur",
+            "fériés",
+            "festivos",
+            "feriados",
+        ];
+        CalendarFilter {
+            excluded: Vec::new(),
+            auto_exclude: true,
+            holiday_patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            included: Vec::new(),
+            min_duration_minutes: None,
+            max_duration_minutes: None,
+            durations: Vec::new(),
+            exclude_zero_length: true,
+        }
+    }
+}
+
+impl CalendarFilter {
+    pub fn is_auto_excluded(&self, title: &str, kind: &str) -> bool {
+        let lower = title.to_lowercase();
+        let holiday = kind == "subscription"
+            && self
+                .holiday_patterns
+                .iter()
+                .any(|p| !p.is_empty() && lower.contains(&p.to_lowercase()));
+        self.auto_exclude && (kind == "birthday" || holiday)
+    }
+
+    pub fn is_excluded(&self, title: &str, kind: &str) -> bool {
+        if self.excluded.iter().any(|c| c == title) {
+            return true;
+        }
+        self.is_auto_excluded(title, kind) && !self.included.iter().any(|c| c == title)
+    }
+
+    fn duration_limits(&self, calendar: &str) -> (Option<i64>, Option<i64>) {
+        let own = self.durations.iter().find(|d| d.calendar == calendar);
+        (
+            own.and_then(|d| d.min_duration_minutes)
+                .or(self.min_duration_minutes),
+            own.and_then(|d| d.max_duration_minutes)
+                .or(self.max_duration_minutes),
+        )
+    }
+
+    /// Why the event is filtered out, or `None` if it should alert.
+    pub fn reason(&self, event: &CalendarEvent) -> Option<String> {
+        if self.excluded.iter().any(|c| c == &event.calendar) {
+            return Some(format!("calendar \"{}\" is excluded", event.calendar));
+        }
+        if self.is_excluded(&event.calendar, &event.calendar_type) {
+            return Some(format!("calendar \"{}\" is auto-excluded", event.calendar));
+        }
+        if event.is_all_day {
+            return None;
+        }
+
+        let (start, end) = (event.start_time()?, event.end_time()?);
+        let minutes = (end - start).num_minutes();
+        if end <= start {
+            return self
+                .exclude_zero_length
+                .then(|| "event has no duration".to_string());
+        }
+        let (min, max) = self.duration_limits(&event.calendar);
+        if let Some(min) = min.filter(|&min| minutes < min) {
+            return Some(format!("shorter than {} minutes", min));
+        }
+        if let Some(max) = max.filter(|&max| minutes > max) {
+            return Some(format!("longer than {} minutes", max));
+        }
+        None
+    }
+
+    pub fn excludes(&self, event: &CalendarEvent) -> bool {
+        self.reason(event).is_some()
+    }
+}
+
+impl CalendarEvent {
+    /// Fills in `seconds_until_start` and `relative_start` as of `now`.
+    pub fn stamp(&mut self, now: DateTime<Local>, format: &FormatSettings) {
+        let Some(start) = self.start_time() else {
+            return;
+        };
+        self.seconds_until_start = Some((start - now).num_seconds());
+        self.relative_start = Some(locale::relative_start(format, start, now));
+    }
+
+    pub fn start_time(&self) -> Option<DateTime<Local>> {
+        parse_time(&self.start)
+    }
+
+    pub fn end_time(&self) -> Option<DateTime<Local>> {
+        parse_time(&self.end)
+    }
+
+    /// The user is invited and said no.
+    pub fn is_declined(&self) -> bool {
+        self.attendees
+            .iter()
+            .any(|a| a.is_current_user && a.status == "declined")
+    }
+
+    /// Fills in the fields derived from the raw EventKit data.
+    pub fn described(mut self) -> Self {
+        self.recurrence = self.recurrence_rule.as_ref().map(Recurrence::describe);
+        location::annotate(&mut self);
+        self
+    }
+
+    /// Drops the notes and attendee list, which dominate the payload for
+    /// busy calendars. The video link is extracted first so it survives.
+    pub fn prune(&mut self) {
+        if self.video_link.is_none() {
+            self.video_link = self.find_video_link();
+        }
+        self.description = None;
+        self.attendees.clear();
+        self.recurrence_rule = None;
+    }
+
+    pub fn find_video_link(&self) -> Option<String> {
+        [&self.description, &self.location, &self.url]
+            .into_iter()
+            .flatten()
+            .flat_map(|text| text.split_whitespace())
+            .map(|word| word.trim_matches(|c| c == '<' || c == '>' || c == '"'))
+            .find(|word| is_video_link(word))
+            .map(str::to_string)
+    }
+
+    /// Identifies the alert for an occurrence: stays the same when the event
+    /// is moved within its day, so a rescheduled meeting alerts only once.
+    pub fn alert_key(&self) -> String {
+        let date = self
+            .start_time()
+            .map(|t| t.date_naive().to_string())
+            .unwrap_or_else(|| self.start.clone());
+        format!("{}@{}", self.id, date)
+    }
+
+    /// Identifies a single occurrence; recurring events share their `id`.
+    pub fn occurrence_key(&self) -> String {
+        format!("{}@{}", self.id, self.start)
+    }
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Local))
+}
+
+/// Runs a Swift helper and returns its stdout ("[]" on failure); JSON in it
+/// is read through `helper`.
+fn run_script(file_name: &str, body: &str, args: &[&str]) -> String {
+    try_run_script(file_name, body, args).unwrap_or("[]".to_string())
+}
+
+fn try_run_script(file_name: &str, body: &str, args: &[&str]) -> Result<String, String> {
+    let script = format!("{}{}\nsemaphore.wait()\n", SWIFT_PRELUDE, body);
+    try_run_swift(file_name, &script, args)
+}
+
+fn run_swift(file_name: &str, script: &str, args: &[&str]) -> String {
+    try_run_swift(file_name, script, args).unwrap_or("[]".to_string())
+}
+
+/// `swift` running the script at `script_path`; `args` are passed as argv,
+/// one each, whatever is in them.
+fn swift_command(script_path: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new("/usr/bin/swift");
+    command.arg(script_path).args(args);
+    command
+}
+
+/// What the prelude's `fail` prints when calendar access was refused.
+const ACCESS_DENIED: &str = "Calendar access denied";
+/// What the annotate helper's `fail` prints for a calendar it can't write.
+const READ_ONLY: &str = "Calendar is read-only";
+
+/// Like `run_swift`, but says why the helper didn't produce output:
+/// `ACCESS_DENIED` or `READ_ONLY` as is when that is what it reported.
+fn try_run_swift(file_name: &str, script: &str, args: &[&str]) -> Result<String, String> {
+    // 1. Write Swift script to a temporary file
+    let script_path = children::script_path(file_name).map_err(|e| e.to_string())?;
+    fs::write(&script_path, script).map_err(|e| e.to_string())?;
+
+    // 2. Run the Swift script
+    let output = children::output(&mut swift_command(&script_path, args));
+
+    // 3. Cleanup (Optional, but good practice)
+    let _ = fs::remove_file(script_path);
+
+    let output = output.map_err(|e| format!("Swift helper didn't start: {}", e))?;
+    helper::log_stderr(file_name, &output.stderr);
+    if !output.status.success() {
+        return Err(failure(&output));
+    }
+    helper::stdout_text(&output.stdout)
+}
+
+/// Why a helper exited non-zero: `ACCESS_DENIED` or `READ_ONLY` as is when
+/// that is what it reported, else the first line it printed to stderr.
+pub fn failure(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if let Some(known) = [ACCESS_DENIED, READ_ONLY]
+        .into_iter()
+        .find(|known| stderr.lines().any(|line| line.trim() == *known))
+    {
+        return known.to_string();
+    }
+    let reason = stderr.lines().next().unwrap_or("no output");
+    format!("Swift helper failed ({}): {}", output.status, reason)
+}
+
+/// The change watcher (`calendar_watch`), ready to spawn. Its script stays
+/// at the returned path until the caller removes it.
+pub fn watch_command() -> Result<(Command, PathBuf), String> {
+    let script = format!("{}{}\ndispatchMain()\n", SWIFT_PRELUDE, WATCH_SCRIPT);
+    let script_path = children::script_path("watch_calendar.swift").map_err(|e| e.to_string())?;
+    fs::write(&script_path, script).map_err(|e| e.to_string())?;
+    Ok((swift_command(&script_path, &[]), script_path))
+}
+
+/// Events overlapping `from..to`, in one EventKit query.
+pub fn fetch_events_between(from: DateTime<Local>, to: DateTime<Local>) -> Vec<CalendarEvent> {
+    try_fetch_events(from, to).unwrap_or_default()
+}
+
+/// Like `fetch_events_between`, but says why EventKit couldn't be read.
+pub fn try_fetch_events(
+    from: DateTime<Local>,
+    to: DateTime<Local>,
+) -> Result<Vec<CalendarEvent>, String> {
+    if let Some(events) = simulate::events_between(from, to) {
+        return Ok(events);
+    }
+    let (from, to) = (from.timestamp().to_string(), to.timestamp().to_string());
+    let output = try_run_script("fetch_calendar.swift", EVENTS_SCRIPT, &[&from, &to])?;
+    let events = helper::parse_items("fetch_calendar.swift", &output, helper::check_event)
+        .map_err(|e| format!("Unreadable events: {}", e))?;
+    Ok(events.into_iter().map(CalendarEvent::described).collect())
+}
+
+/// Hosts of video calls; a link's host must be one of them or a subdomain
+/// ("us02web.zoom.us"), so a document on google.com or a "?zoom.us" query
+/// isn't taken for a meeting.
+const VIDEO_HOSTS: [&str; 4] = [
+    "zoom.us",
+    "meet.google.com",
+    "teams.microsoft.com",
+    "webex.com",
+];
+
+pub fn is_video_link(word: &str) -> bool {
+    let Ok(url) = reqwest::Url::parse(word) else {
+        return false;
+    };
+    let Some(host) = url.host_str().map(str::to_ascii_lowercase) else {
+        return false;
+    };
+    matches!(url.scheme(), "https" | "http")
+        && VIDEO_HOSTS.iter().any(|video| {
+            host == *video
+                || host
+                    .strip_suffix(video)
+                    .is_some_and(|sub| sub.ends_with('.'))
+        })
+}
+
+/// Opens the event in Calendar.app.
+pub fn calendar_app_url(event_id: &str) -> String {
+    format!(
+        "ical://ekevent/{}?method=show&options=more",
+        untrusted::escape_in_link(event_id)
+    )
+}
+
+/// EventKit authorization for events, e.g. "full_access" or "denied";
+/// "unknown" if the helper didn't run.
+pub fn permission_state() -> String {
+    if simulate::is_active() {
+        return "simulated".to_string();
+    }
+    let output = run_swift("calendar_permission.swift", PERMISSION_SCRIPT, &[]);
+    match helper::last_line(&output) {
+        "[]" | "" => "unknown".to_string(),
+        state => state.to_string(),
+    }
+}
+
+/// Appends `line` to the notes of the occurrence of `event_id` starting at
+/// `start`; false if it was there already. Simulated events are left alone.
+pub fn append_to_notes(event_id: &str, start: &str, line: &str) -> Result<bool, WriteError> {
+    if simulate::is_active() {
+        return Ok(false);
+    }
+    let output = try_run_script(
+        "annotate_event.swift",
+        ANNOTATE_SCRIPT,
+        &[event_id, start, line],
+    )
+    .map_err(|error| match error.as_str() {
+        READ_ONLY => WriteError::ReadOnlyCalendar,
+        _ => WriteError::Failed(error),
+    })?;
+    Ok(helper::last_line(&output) == "added")
+}
+
+pub fn fetch_calendars() -> Vec<CalendarInfo> {
+    if let Some(calendars) = simulate::calendars() {
+        return calendars;
+    }
+    let output = run_script("fetch_calendars.swift", CALENDARS_SCRIPT, &[]);
+    helper::parse_items("fetch_calendars.swift", &output, helper::check_calendar)
+        .unwrap_or_default()
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DetailsOutput {
+    Found(Box<RawDetails>),
+    Error { error: String },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawDetails {
+    event: CalendarEvent,
+    is_detached: bool,
+}
+
+/// `occurrence_start` picks the instance of a recurring event; without it the
+/// first occurrence is returned.
+pub fn fetch_event_details(
+    event_id: &str,
+    occurrence_start: Option<&str>,
+) -> Result<CalendarEventDetails, DetailsError> {
+    if let Some(found) = simulate::find(event_id) {
+        let event = found.ok_or_else(|| DetailsError::NotFound(event_id.to_string()))?;
+        return Ok(CalendarEventDetails {
+            event,
+            is_detached: false,
+            counterpart_local_times: Vec::new(),
+        });
+    }
+    let mut args = vec![event_id];
+    args.extend(occurrence_start);
+    let output =
+        try_run_script("fetch_event_details.swift", DETAILS_SCRIPT, &args).map_err(|error| {
+            match error.as_str() {
+                ACCESS_DENIED => DetailsError::AccessDenied,
+                _ => DetailsError::Failed(error),
+            }
+        })?;
+    let parsed: DetailsOutput = helper::parse(&output).map_err(DetailsError::Failed)?;
+    let raw = match parsed {
+        DetailsOutput::Found(raw) => RawDetails {
+            event: helper::check_event(raw.event).map_err(DetailsError::Failed)?,
+            ..*raw
+        },
+        DetailsOutput::Error { error } => {
+            return Err(match error.as_str() {
+                "not_found" => DetailsError::NotFound(event_id.to_string()),
+                "occurrence_not_found" => DetailsError::OccurrenceNotFound {
+                    id: event_id.to_string(),
+                    start: occurrence_start.unwrap_or_default().to_string(),
+                },
+                _ => DetailsError::Failed(error),
+            })
+        }
+    };
+
+    let mut event = raw.event.described();
+    event.video_link = event.find_video_link();
+    Ok(CalendarEventDetails {
+        event,
+        is_detached: raw.is_detached,
+        counterpart_local_times: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{at, event, hostile_text};
+    use proptest::prelude::*;
+
+    /// `count` events a quarter hour apart, each with long notes and a
+    /// full attendee list.
+    fn busy_day(count: usize) -> Vec<CalendarEvent> {
+        (0..count)
+            .map(|i| {
+                let start = at("2026-03-02 00:00") + chrono::Duration::minutes(15 * i as i64);
+                let mut meeting = event(&format!("On-call {}", i), start, 15);
+                meeting.description = Some(format!(
+                    "https://zoom.us/j/{} {}",
+                    i,
+                    "Runbook and handover notes. ".repeat(150)
+                ));
+                meeting.attendees = (0..40)
+                    .map(|n| Attendee {
+                        name: Some(format!("Engineer {}", n)),
+                        email: Some(format!("engineer{}@example.com", n)),
+                        status: "accepted".to_string(),
+                        is_current_user: false,
+                    })
+                    .collect();
+                meeting
+            })
+            .collect()
+    }
+
+    fn page(events: Vec<CalendarEvent>) -> EventPage {
+        EventPage {
+            total: events.len(),
+            events,
+            next_offset: None,
+            stale: false,
+            last_success_at: None,
+        }
+    }
+
+    #[test]
+    fn an_oversized_page_is_refused() {
+        let page = page(busy_day(200));
+        let error = page.to_json().unwrap_err();
+        assert!(error.starts_with("Event page too large"), "{}", error);
+    }
+
+    #[test]
+    fn a_pruned_page_stays_small() {
+        let mut events = busy_day(200);
+        events.iter_mut().for_each(CalendarEvent::prune);
+        let json = page(events).to_json().unwrap();
+        assert!(json.len() < MAX_PAGE_BYTES / 2, "{} bytes", json.len());
+        assert!(json.contains("https://zoom.us/j/199"));
+    }
+
+    #[test]
+    fn video_links_match_by_host() {
+        assert!(is_video_link("https://us02web.zoom.us/j/123"));
+        assert!(is_video_link("https://zoom.us/j/123"));
+        assert!(is_video_link("https://meet.google.com/abc-defg-hij"));
+        assert!(is_video_link("http://Teams.Microsoft.com/l/meetup-join/x"));
+        assert!(!is_video_link("https://docs.google.com/document/d/1"));
+        assert!(!is_video_link("https://example.com/?next=zoom.us"));
+        assert!(!is_video_link("https://notzoom.us/j/1"));
+        assert!(!is_video_link("https://zoom.us.example.com/j/1"));
+        assert!(!is_video_link("ftp://zoom.us/j/1"));
+        assert!(!is_video_link("zoom.us/j/1"));
+    }
+
+    fn rule(frequency: &str, interval: i64, days: &[(u8, i64)]) -> Recurrence {
+        Recurrence {
+            frequency: frequency.to_string(),
+            interval,
+            days: days
+                .iter()
+                .map(|&(weekday, week_number)| RecurrenceDay {
+                    weekday,
+                    week_number,
+                })
+                .collect(),
+            days_of_month: Vec::new(),
+            end_date: None,
+            occurrence_count: None,
+        }
+    }
+
+    #[test]
+    fn describes_recurrence_rules() {
+        let cases = [
+            (rule("daily", 1, &[]), "Daily"),
+            (rule("daily", 3, &[]), "Every 3 days"),
+            (rule("weekly", 1, &[]), "Weekly"),
+            (
+                rule("weekly", 1, &[(5, 0), (3, 0), (5, 0)]),
+                "Weekly on Tue, Thu",
+            ),
+            (rule("weekly", 2, &[(2, 0)]), "Every 2 weeks on Mon"),
+            (
+                rule("weekly", 1, &[(2, 0), (3, 0), (4, 0), (5, 0), (6, 0)]),
+                "Every weekday",
+            ),
+            (
+                rule("weekly", 2, &[(2, 0), (3, 0), (4, 0), (5, 0), (6, 0)]),
+                "Every 2 weeks on Mon, Tue, Wed, Thu, Fri",
+            ),
+            (rule("monthly", 1, &[(3, 2)]), "Monthly on the second Tue"),
+            (
+                rule("monthly", 3, &[(6, -1)]),
+                "Every 3 months on the last Fri",
+            ),
+            (rule("yearly", 1, &[]), "Yearly"),
+        ];
+        for (rule, text) in cases {
+            assert_eq!(rule.describe(), text, "{:?}", rule);
+        }
+
+        let by_day = Recurrence {
+            days_of_month: vec![1, 15, -1],
+            ..rule("monthly", 1, &[])
+        };
+        assert_eq!(by_day.describe(), "Monthly on day 1, day 15, the last day");
+    }
+
+    #[test]
+    fn describes_how_recurrence_ends() {
+        let until = Recurrence {
+            end_date: Some("2099-06-30T23:59:00+00:00".to_string()),
+            ..rule("daily", 1, &[])
+        };
+        assert_eq!(until.describe(), "Daily until Jun 30, 2099");
+        let this_year = format!("{}-12-30T12:00:00+00:00", Local::now().year());
+        let until = Recurrence {
+            end_date: Some(this_year),
+            ..rule("weekly", 1, &[(2, 0)])
+        };
+        assert_eq!(until.describe(), "Weekly on Mon until Dec 30");
+        let counted = Recurrence {
+            occurrence_count: Some(5),
+            ..rule("yearly", 1, &[])
+        };
+        assert_eq!(counted.describe(), "Yearly, 5 times");
+    }
+
+    #[test]
+    fn one_off_events_have_no_recurrence() {
+        let one_off = event("Dentist", at("2026-03-02 10:00"), 30).described();
+        assert_eq!(one_off.recurrence, None);
+        let mut standup = event("Standup", at("2026-03-02 10:00"), 15);
+        standup.recurrence_rule = Some(rule("weekly", 1, &[(2, 0)]));
+        assert_eq!(
+            standup.described().recurrence.as_deref(),
+            Some("Weekly on Mon")
+        );
+    }
+
+    /// A NUL can't be passed in argv; spawning refuses such an argument
+    /// rather than cutting it short.
+    fn argument() -> impl Strategy<Value = String> {
+        hostile_text().prop_map(|text| text.replace('\0', ""))
+    }
+
+    proptest! {
+        /// What goes into the annotate helper: an id, a start and a line of
+        /// notes, each arriving as one argument with nothing a shell reads.
+        #[test]
+        fn helper_arguments_stay_whole(
+            id in argument(),
+            start in argument(),
+            line in argument(),
+        ) {
+            let script = Path::new("/tmp/in-your-face-helpers/1-0-annotate_event.swift");
+            let command = swift_command(script, &[&id, &start, &line]);
+            prop_assert_eq!(command.get_program(), "/usr/bin/swift");
+            let args: Vec<_> = command.get_args().collect();
+            prop_assert_eq!(args, [script.as_os_str(), id.as_ref(), start.as_ref(), line.as_ref()]);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::{calendar, children, helper, shutdown, sources};
+
+// --- Calendar Change Watcher ---
+//
+// Polling sees an edit at the next poll, which outside the run-up to a
+// meeting is minutes away (see `cadence`). A Swift helper that keeps running
+// listens for EventKit's change notification instead, and each change it
+// reports refetches EventKit on the next tick, skipping any backoff, as a
+// manual refresh does. Polling goes on underneath: a watcher that died or
+// was refused calendar access only costs latency. It is restarted after a
+// wait that doubles while it keeps failing, and a restart refetches, since
+// changes made in between went unseen.
+
+const HELPER: &str = "watch_calendar.swift";
+const RESTART_BASE: Duration = Duration::from_secs(5);
+const RESTART_MAX: Duration = Duration::from_secs(600);
+/// A watcher that ran this long worked; the wait starts over.
+const HEALTHY_RUN: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WatcherStatus {
+    /// The helper is running and listening for changes.
+    pub listening: bool,
+    pub last_change_at: Option<DateTime<Local>>,
+    pub restarts: u32,
+    /// Why the helper last stopped.
+    pub last_error: Option<String>,
+}
+
+static STATUS: Mutex<WatcherStatus> = Mutex::new(WatcherStatus {
+    listening: false,
+    last_change_at: None,
+    restarts: 0,
+    last_error: None,
+});
+
+pub fn status() -> WatcherStatus {
+    STATUS.lock().unwrap().clone()
+}
+
+/// The wait before restarting after `failures` runs in a row that ended
+/// early.
+fn restart_wait(failures: u32) -> Duration {
+    RESTART_BASE
+        .saturating_mul(1 << failures.clamp(1, 16).saturating_sub(1))
+        .min(RESTART_MAX)
+}
+
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        let mut failures = 0;
+        while !shutdown::in_progress() {
+            let started = Instant::now();
+            let error = watch(&app);
+            if shutdown::in_progress() {
+                return;
+            }
+            failures = if started.elapsed() >= HEALTHY_RUN {
+                1
+            } else {
+                failures + 1
+            };
+            {
+                let mut status = STATUS.lock().unwrap();
+                status.listening = false;
+                status.restarts += 1;
+                status.last_error = Some(error);
+            }
+            thread::sleep(restart_wait(failures));
+        }
+    });
+}
+
+/// Runs the helper until it ends, refetching on each change it reports;
+/// why it ended.
+fn watch(app: &AppHandle) -> String {
+    let (mut command, script_path) = match calendar::watch_command() {
+        Ok(command) => command,
+        Err(error) => return error,
+    };
+    let error = match children::spawn(&mut command) {
+        Ok(mut child) => {
+            if let Some(stdout) = child.stdout.take() {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    match line.trim() {
+                        calendar::WATCHING => listening(app),
+                        calendar::CHANGED => changed(app),
+                        _ => {}
+                    }
+                }
+            }
+            match children::reap(child) {
+                Ok(output) if output.status.success() => "The watcher quit".to_string(),
+                Ok(output) => {
+                    helper::log_stderr(HELPER, &output.stderr);
+                    calendar::failure(&output)
+                }
+                Err(e) => e.to_string(),
+            }
+        }
+        Err(e) => format!("Swift helper didn't start: {}", e),
+    };
+    let _ = fs::remove_file(script_path);
+    error
+}
+
+fn listening(app: &AppHandle) {
+    let restarted = {
+        let mut status = STATUS.lock().unwrap();
+        status.listening = true;
+        status.restarts > 0
+    };
+    if restarted {
+        let _ = sources::refresh(app, sources::EVENTKIT);
+    }
+}
+
+fn changed(app: &AppHandle) {
+    STATUS.lock().unwrap().last_change_at = Some(Local::now());
+    let _ = sources::refresh(app, sources::EVENTKIT);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restarts_wait_longer_while_failing() {
+        assert_eq!(restart_wait(0), RESTART_BASE);
+        assert_eq!(restart_wait(1), RESTART_BASE);
+        assert_eq!(restart_wait(2), RESTART_BASE * 2);
+        assert_eq!(restart_wait(4), RESTART_BASE * 8);
+        assert_eq!(restart_wait(8), RESTART_MAX);
+        assert_eq!(restart_wait(u32::MAX), RESTART_MAX);
+    }
+}
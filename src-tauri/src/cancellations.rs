@@ -0,0 +1,139 @@
+use chrono::{DateTime, Duration, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::bus::{self, AppEvent};
+use crate::calendar::CalendarEvent;
+use crate::changes::CalendarDiff;
+use crate::history::{self, HistoryKind};
+use crate::settings::Settings;
+use crate::{confidential, locale, notify};
+
+// --- Cancelled Meetings ---
+//
+// A meeting about to start that disappears between two fetches is reported
+// instead of vanishing silently. If the fetch's diff (see `changes`) has it
+// at a new time it was moved, not cancelled, and the message says where it
+// went.
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CancellationSettings {
+    pub enabled: bool,
+    /// Only meetings starting this soon are reported.
+    pub window_minutes: i64,
+    /// Also post a system notification, not just the dashboard banner.
+    pub notify: bool,
+}
+
+impl Default for CancellationSettings {
+    fn default() -> Self {
+        CancellationSettings {
+            enabled: true,
+            window_minutes: 60,
+            notify: true,
+        }
+    }
+}
+
+/// Sent to the dashboard as `meeting-cancelled`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingChange {
+    pub event: CalendarEvent,
+    /// The event at its new time, if it was moved rather than cancelled.
+    pub moved_to: Option<CalendarEvent>,
+    pub message: String,
+}
+
+impl MeetingChange {
+    pub fn is_cancelled(&self) -> bool {
+        self.moved_to.is_none()
+    }
+}
+
+fn moved_message(settings: &Settings, event: &CalendarEvent, to: DateTime<Local>) -> String {
+    let format = &settings.format;
+    let same_day = event
+        .start_time()
+        .is_some_and(|from| from.date_naive() == to.date_naive());
+    let when = if same_day {
+        locale::format_time(format, to)
+    } else {
+        format!(
+            "{} {}",
+            locale::format_date(format, to.date_naive()),
+            locale::format_time(format, to)
+        )
+    };
+    format!(
+        "{} moved to {}",
+        confidential::shown(settings, event).title,
+        when
+    )
+}
+
+/// Meetings starting within the window that the fetch removed or moved.
+pub fn detect(
+    diff: &CalendarDiff,
+    now: DateTime<Local>,
+    settings: &Settings,
+) -> Vec<MeetingChange> {
+    let config = &settings.cancellations;
+    if !config.enabled {
+        return Vec::new();
+    }
+    let until = now + Duration::minutes(config.window_minutes);
+    let soon = |event: &CalendarEvent| {
+        !event.is_all_day
+            && !settings.calendar_filter.excludes(event)
+            && event
+                .start_time()
+                .is_some_and(|start| start >= now && start <= until)
+    };
+    let cancelled = diff
+        .removed
+        .iter()
+        .filter(|event| soon(event))
+        .map(|event| MeetingChange {
+            event: event.clone(),
+            moved_to: None,
+            message: format!(
+                "{} was cancelled",
+                confidential::shown(settings, event).title
+            ),
+        });
+    let moved = diff
+        .changed
+        .iter()
+        .filter(|change| change.moved() && soon(&change.before))
+        .filter_map(|change| {
+            let to = change.after.start_time()?;
+            (change.before.start_time() != Some(to)).then(|| MeetingChange {
+                event: change.before.clone(),
+                moved_to: Some(change.after.clone()),
+                message: moved_message(settings, &change.before, to),
+            })
+        });
+    cancelled.chain(moved).collect()
+}
+
+pub fn announce(app: &AppHandle, settings: &Settings, mut change: MeetingChange) {
+    if change.is_cancelled() {
+        history::record(app, &change.event, HistoryKind::Cancelled);
+    }
+    change.event = confidential::shown(settings, &change.event);
+    change.moved_to = change
+        .moved_to
+        .map(|event| confidential::shown(settings, &event));
+    if settings.cancellations.notify {
+        let title = if change.is_cancelled() {
+            "Meeting cancelled"
+        } else {
+            "Meeting moved"
+        };
+        notify::show(app, title, &change.message);
+    }
+    bus::publish(app, AppEvent::MeetingCancelled(Box::new(change)));
+}
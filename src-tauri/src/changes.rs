@@ -0,0 +1,405 @@
+use chrono::{DateTime, Local, NaiveDate};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::calendar::CalendarEvent;
+use crate::locale;
+use crate::privacy;
+use crate::settings::Settings;
+
+// --- Calendar Changes ---
+//
+// What one fetch changed in the cache: events added, removed, moved,
+// renamed or given a different link. Sent with `calendar-updated`, read by
+// the cancellation notices and kept as a short log for the diagnostics.
+//
+// Occurrences are matched by event id and start first. What is left is
+// matched within the same event id and day, then within the same event id
+// if as many are left on both sides, so a moved occurrence is a change, not
+// a removal plus an addition. Each previous and current event ends up in
+// exactly one place: unchanged, changed, removed or added. Events that ended
+// before the fetch only aged out of the window and aren't counted as removed.
+
+/// Fetches with changes kept for the diagnostics.
+const LOG_ENTRIES: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangedField {
+    /// The start or the end moved.
+    Time,
+    Title,
+    Link,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventChange {
+    pub before: CalendarEvent,
+    pub after: CalendarEvent,
+    pub fields: Vec<ChangedField>,
+}
+
+impl EventChange {
+    pub fn moved(&self) -> bool {
+        self.fields.contains(&ChangedField::Time)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarDiff {
+    pub added: Vec<CalendarEvent>,
+    pub removed: Vec<CalendarEvent>,
+    pub changed: Vec<EventChange>,
+}
+
+impl CalendarDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// One fetch's changes as lines for the diagnostics, titles redacted per
+/// the privacy mode.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeLogEntry {
+    pub at: DateTime<Local>,
+    pub lines: Vec<String>,
+}
+
+static LOG: Mutex<VecDeque<ChangeLogEntry>> = Mutex::new(VecDeque::new());
+
+fn fields(before: &CalendarEvent, after: &CalendarEvent) -> Vec<ChangedField> {
+    let mut fields = Vec::new();
+    if before.start_time() != after.start_time() || before.end_time() != after.end_time() {
+        fields.push(ChangedField::Time);
+    }
+    if before.title != after.title {
+        fields.push(ChangedField::Title);
+    }
+    if before.video_link != after.video_link {
+        fields.push(ChangedField::Link);
+    }
+    fields
+}
+
+fn day(event: &CalendarEvent) -> Option<NaiveDate> {
+    event.start_time().map(|start| start.date_naive())
+}
+
+fn by_start(events: &[CalendarEvent]) -> Vec<&CalendarEvent> {
+    let mut events: Vec<&CalendarEvent> = events.iter().collect();
+    events.sort_by_key(|e| e.start_time());
+    events
+}
+
+/// Pairs up the unmatched events of both sides for which `same` holds, one
+/// to one and in order of start, taking them out of `previous` and
+/// `current`.
+fn pair<'a>(
+    previous: &mut Vec<&'a CalendarEvent>,
+    current: &mut Vec<&'a CalendarEvent>,
+    same: impl Fn(&CalendarEvent, &CalendarEvent) -> bool,
+) -> Vec<(&'a CalendarEvent, &'a CalendarEvent)> {
+    let mut pairs = Vec::new();
+    let mut i = 0;
+    while i < previous.len() {
+        match current.iter().position(|c| same(previous[i], c)) {
+            Some(j) => pairs.push((previous.remove(i), current.remove(j))),
+            None => i += 1,
+        }
+    }
+    pairs
+}
+
+/// What changed from `previous` to `current`, as of a fetch at `now`.
+pub fn diff(
+    previous: &[CalendarEvent],
+    current: &[CalendarEvent],
+    now: DateTime<Local>,
+) -> CalendarDiff {
+    let mut previous = by_start(previous);
+    let mut current = by_start(current);
+
+    let mut pairs = pair(&mut previous, &mut current, |p, c| {
+        p.occurrence_key() == c.occurrence_key()
+    });
+    pairs.extend(pair(&mut previous, &mut current, |p, c| {
+        p.id == c.id && day(p) == day(c)
+    }));
+    // Left over with the same id on both sides: moved to another day, if
+    // there are as many of them either way.
+    let ids: Vec<String> = previous.iter().map(|p| p.id.clone()).collect();
+    for id in ids {
+        let before = previous.iter().filter(|p| p.id == id).count();
+        let after = current.iter().filter(|c| c.id == id).count();
+        if before == after {
+            pairs.extend(pair(&mut previous, &mut current, |p, c| {
+                p.id == id && c.id == id
+            }));
+        }
+    }
+
+    let changed = pairs
+        .into_iter()
+        .filter_map(|(before, after)| {
+            let fields = fields(before, after);
+            (!fields.is_empty()).then(|| EventChange {
+                before: before.clone(),
+                after: after.clone(),
+                fields,
+            })
+        })
+        .collect();
+    CalendarDiff {
+        added: current.into_iter().cloned().collect(),
+        removed: previous
+            .into_iter()
+            .filter(|p| p.end_time().is_none_or(|end| end > now))
+            .cloned()
+            .collect(),
+        changed,
+    }
+}
+
+fn when(settings: &Settings, event: &CalendarEvent) -> String {
+    event
+        .start_time()
+        .map(|start| {
+            format!(
+                "{} {}",
+                locale::format_date(&settings.format, start.date_naive()),
+                locale::format_time(&settings.format, start)
+            )
+        })
+        .unwrap_or_else(|| event.start.clone())
+}
+
+fn lines(settings: &Settings, diff: &CalendarDiff) -> Vec<String> {
+    let title = |event: &CalendarEvent| privacy::redact_title(settings.privacy_mode, &event.title);
+    let mut lines = Vec::new();
+    for event in &diff.added {
+        lines.push(format!(
+            "Added {} at {}",
+            title(event),
+            when(settings, event)
+        ));
+    }
+    for event in &diff.removed {
+        lines.push(format!(
+            "Removed {} at {}",
+            title(event),
+            when(settings, event)
+        ));
+    }
+    for change in &diff.changed {
+        let name = title(&change.before);
+        for field in &change.fields {
+            lines.push(match field {
+                ChangedField::Time if change.before.start == change.after.start => {
+                    format!("Changed when {} ends", name)
+                }
+                ChangedField::Time => format!(
+                    "Moved {} from {} to {}",
+                    name,
+                    when(settings, &change.before),
+                    when(settings, &change.after)
+                ),
+                ChangedField::Title => format!("Renamed {} to {}", name, title(&change.after)),
+                ChangedField::Link => format!("Changed the link of {}", name),
+            });
+        }
+    }
+    lines
+}
+
+/// Keeps the diff in the log, unless nothing changed.
+pub fn log(settings: &Settings, diff: &CalendarDiff, at: DateTime<Local>) {
+    if diff.is_empty() {
+        return;
+    }
+    let mut log = LOG.lock().unwrap();
+    log.push_back(ChangeLogEntry {
+        at,
+        lines: lines(settings, diff),
+    });
+    if log.len() > LOG_ENTRIES {
+        log.pop_front();
+    }
+}
+
+/// The latest changes, newest first.
+pub fn recent() -> Vec<ChangeLogEntry> {
+    LOG.lock().unwrap().iter().rev().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{at, event};
+    use chrono::Duration;
+    use proptest::prelude::*;
+
+    #[derive(Clone, Copy, Debug)]
+    enum Mutation {
+        Keep,
+        /// Later the same day, by this many minutes.
+        Retime(i64),
+        /// To another day, this many days later.
+        MoveDay(i64),
+        Rename,
+        Relink,
+        Remove,
+    }
+
+    fn mutation() -> impl Strategy<Value = Mutation> {
+        prop_oneof![
+            Just(Mutation::Keep),
+            (1..=360_i64).prop_map(Mutation::Retime),
+            (1..=5_i64).prop_map(Mutation::MoveDay),
+            Just(Mutation::Rename),
+            Just(Mutation::Relink),
+            Just(Mutation::Remove),
+        ]
+    }
+
+    /// Occurrence `k` of series `i`: weekly, one series a minute apart.
+    fn occurrence(i: usize, k: usize) -> CalendarEvent {
+        let start =
+            at("2026-03-02 09:00") + Duration::days(7 * k as i64) + Duration::minutes(i as i64);
+        CalendarEvent {
+            id: format!("E{}", i),
+            ..event(&format!("Series {}", i), start, 30)
+        }
+    }
+
+    fn keys(events: &[CalendarEvent]) -> Vec<String> {
+        let mut keys: Vec<String> = events.iter().map(|e| e.occurrence_key()).collect();
+        keys.sort();
+        keys
+    }
+
+    proptest! {
+        #[test]
+        fn every_mutation_lands_where_it_belongs(
+            series in prop::collection::vec(prop::collection::vec(mutation(), 1..=3), 0..12),
+            added in 0..4_usize,
+        ) {
+            let mut previous = Vec::new();
+            let mut current = Vec::new();
+            let mut removed = Vec::new();
+            let mut new = Vec::new();
+            let mut changed = Vec::new();
+            for (i, mutations) in series.iter().enumerate() {
+                // With one of them gone, what moved to another day can't be
+                // told from what was removed: as many aren't left either way.
+                let ambiguous = mutations.iter().any(|m| matches!(m, Mutation::Remove));
+                for (k, mutation) in mutations.iter().enumerate() {
+                    let before = occurrence(i, k);
+                    let mut after = before.clone();
+                    let field = match *mutation {
+                        Mutation::Keep => None,
+                        Mutation::Retime(minutes) | Mutation::MoveDay(minutes) => {
+                            let by = match mutation {
+                                Mutation::Retime(_) => Duration::minutes(minutes),
+                                _ => Duration::days(minutes),
+                            };
+                            let start = before.start_time().unwrap() + by;
+                            after.start = start.to_rfc3339();
+                            after.end = (start + Duration::minutes(30)).to_rfc3339();
+                            Some(ChangedField::Time)
+                        }
+                        Mutation::Rename => {
+                            after.title = format!("{} (moved to Thursdays)", before.title);
+                            Some(ChangedField::Title)
+                        }
+                        Mutation::Relink => {
+                            after.video_link = Some(format!("https://zoom.us/j/{}{}", i, k));
+                            Some(ChangedField::Link)
+                        }
+                        Mutation::Remove => {
+                            removed.push(before.clone());
+                            previous.push(before);
+                            continue;
+                        }
+                    };
+                    match field {
+                        Some(_) if ambiguous && matches!(mutation, Mutation::MoveDay(_)) => {
+                            removed.push(before.clone());
+                            new.push(after.clone());
+                        }
+                        Some(field) => changed.push((before.occurrence_key(), field)),
+                        None => {}
+                    }
+                    previous.push(before);
+                    current.push(after);
+                }
+            }
+            for n in 0..added {
+                let extra = CalendarEvent {
+                    id: format!("New{}", n),
+                    ..event(&format!("New {}", n), at("2026-03-04 14:00"), 30)
+                };
+                new.push(extra.clone());
+                current.push(extra);
+            }
+
+            let diff = diff(&previous, &current, at("2026-03-01 12:00"));
+            prop_assert_eq!(keys(&diff.added), keys(&new));
+            prop_assert_eq!(keys(&diff.removed), keys(&removed));
+            let mut found: Vec<(String, ChangedField)> = diff
+                .changed
+                .iter()
+                .map(|c| {
+                    prop_assert_eq!(c.fields.len(), 1);
+                    Ok((c.before.occurrence_key(), c.fields[0]))
+                })
+                .collect::<Result<_, TestCaseError>>()?;
+            found.sort_by(|a, b| a.0.cmp(&b.0));
+            changed.sort_by(|a, b| a.0.cmp(&b.0));
+            prop_assert_eq!(found, changed);
+            // No event ends up in two places.
+            for change in &diff.changed {
+                prop_assert!(!keys(&diff.added).contains(&change.after.occurrence_key()));
+                prop_assert!(!keys(&diff.removed).contains(&change.before.occurrence_key()));
+            }
+        }
+
+        #[test]
+        fn a_shuffled_copy_is_no_change(
+            count in 0..20_usize,
+            seed in any::<u64>(),
+        ) {
+            let events: Vec<CalendarEvent> = (0..count).map(|i| occurrence(i % 5, i / 5)).collect();
+            let mut shuffled = events.clone();
+            shuffled.rotate_left((seed as usize) % count.max(1));
+            prop_assert!(diff(&events, &shuffled, at("2026-03-01 12:00")).is_empty());
+        }
+    }
+
+    #[test]
+    fn a_moved_occurrence_is_one_change() {
+        let standup = occurrence(0, 0);
+        let moved = CalendarEvent {
+            start: at("2026-03-03 11:00").to_rfc3339(),
+            end: at("2026-03-03 11:30").to_rfc3339(),
+            ..standup.clone()
+        };
+        let diff = diff(&[standup], &[moved], at("2026-03-01 12:00"));
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert!(diff.changed[0].moved());
+    }
+
+    #[test]
+    fn what_already_ended_only_aged_out() {
+        let earlier = event("Earlier", at("2026-03-02 08:00"), 30);
+        let later = event("Later", at("2026-03-02 11:00"), 30);
+        let diff = diff(&[earlier, later.clone()], &[], at("2026-03-02 09:00"));
+        assert_eq!(keys(&diff.removed), keys(&[later]));
+    }
+}
@@ -0,0 +1,87 @@
+use std::env;
+use std::fs;
+use std::io;
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+use std::path::PathBuf;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+// --- Helper Processes ---
+//
+// The Swift helpers run in their own process group, so killing the group
+// takes the compiler and the script it spawned down together. Running ones
+// are killed on exit; after a crash or force-quit, the next launch kills any
+// that are left, found by the helper directory in their command line.
+
+const HELPER_DIR: &str = "in-your-face-helpers";
+
+/// Process group ids (equal to the child's pid) of running helpers.
+static CHILDREN: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+static NEXT_SCRIPT: AtomicU64 = AtomicU64::new(0);
+
+fn helper_dir() -> PathBuf {
+    env::temp_dir().join(HELPER_DIR)
+}
+
+/// A fresh path for a helper script, so concurrent runs don't share a file.
+pub fn script_path(file_name: &str) -> io::Result<PathBuf> {
+    let dir = helper_dir();
+    fs::create_dir_all(&dir)?;
+    let n = NEXT_SCRIPT.fetch_add(1, Ordering::Relaxed);
+    Ok(dir.join(format!("{}-{}-{}", std::process::id(), n, file_name)))
+}
+
+/// Like `Command::output`, but tracked so the child can be killed on exit.
+pub fn output(command: &mut Command) -> io::Result<Output> {
+    reap(spawn(command)?)
+}
+
+/// Starts a helper with its output piped, for one that keeps running and is
+/// read as it goes; tracked until `reap` has waited for it.
+pub fn spawn(command: &mut Command) -> io::Result<Child> {
+    #[cfg(unix)]
+    command.process_group(0);
+    let child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    CHILDREN.lock().unwrap().push(child.id());
+    Ok(child)
+}
+
+/// Waits for a helper from `spawn` to end; what is left of its output.
+pub fn reap(child: Child) -> io::Result<Output> {
+    let pid = child.id();
+    let output = child.wait_with_output();
+    CHILDREN.lock().unwrap().retain(|&p| p != pid);
+    output
+}
+
+fn kill_group(pgid: u32) {
+    let _ = Command::new("/bin/kill")
+        .args(["-KILL", &format!("-{}", pgid)])
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// Kills every helper still running; called when the app exits.
+pub fn kill_all() {
+    let children: Vec<u32> = CHILDREN.lock().unwrap().drain(..).collect();
+    for pgid in children {
+        kill_group(pgid);
+    }
+}
+
+/// Kills helpers left behind by a previous run and removes their scripts.
+pub fn clean_stale() {
+    let dir = helper_dir();
+    let _ = Command::new("/usr/bin/pkill")
+        .args(["-KILL", "-f"])
+        .arg(&dir)
+        .stderr(Stdio::null())
+        .status();
+    let _ = fs::remove_dir_all(dir);
+}
@@ -0,0 +1,313 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Window};
+
+use crate::calendar::CalendarEvent;
+use crate::changes::{CalendarDiff, EventChange};
+use crate::ipc::{self, IpcError};
+use crate::settings::Settings;
+use crate::{rules, scheduler, settings, simulate};
+
+// --- Confidential Meetings ---
+//
+// Some calendars (HR, legal, a board) shouldn't put their meetings in front
+// of whoever can see the screen. A display policy per calendar, which an
+// alert rule can override, says how much of an event shows: all of it, the
+// title alone, or just "Busy". Events are redacted in the backend before
+// the alert, the queue, notifications, speech, the tray's recent meetings
+// and the event list are built from them, so nothing withheld reaches a
+// window. The join link stays so the alert can still join; the dashboard
+// gets the rest with `reveal_event`. Privacy mode is separate: it covers
+// what leaves the app, this covers what shows on it.
+
+const BUSY_TITLE: &str = "Busy";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayPolicy {
+    #[default]
+    ShowAll,
+    /// The title shows; location, notes, organizer and attendees don't.
+    TitleOnly,
+    /// The title becomes "Busy" too, and the user's own note is hidden.
+    BusyOnly,
+}
+
+impl DisplayPolicy {
+    pub fn shows_note(self) -> bool {
+        self != DisplayPolicy::BusyOnly
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ConfidentialSettings {
+    pub calendars: Vec<CalendarPolicy>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CalendarPolicy {
+    pub calendar: String,
+    pub policy: DisplayPolicy,
+}
+
+impl ConfidentialSettings {
+    /// The policy of `calendar`, before any rule.
+    pub fn calendar_policy(&self, calendar: &str) -> DisplayPolicy {
+        self.calendars
+            .iter()
+            .find(|c| c.calendar.eq_ignore_ascii_case(calendar))
+            .map(|c| c.policy)
+            .unwrap_or_default()
+    }
+}
+
+/// The event with what `policy` withholds left out.
+pub fn redact(policy: DisplayPolicy, event: &CalendarEvent) -> CalendarEvent {
+    let title = match policy {
+        DisplayPolicy::ShowAll => return event.clone(),
+        DisplayPolicy::TitleOnly => event.title.clone(),
+        DisplayPolicy::BusyOnly => BUSY_TITLE.to_string(),
+    };
+    CalendarEvent {
+        title,
+        location: None,
+        description: None,
+        url: None,
+        has_coordinates: false,
+        organizer: None,
+        attendees: Vec::new(),
+        location_display: None,
+        map_url: None,
+        redacted: true,
+        ..event.clone()
+    }
+}
+
+/// The policy for `event`: its calendar's, or the last matching rule's.
+pub fn policy(settings: &Settings, event: &CalendarEvent) -> DisplayPolicy {
+    rules::evaluate(settings, event).display_policy
+}
+
+/// The event as its policy lets it show.
+pub fn shown(settings: &Settings, event: &CalendarEvent) -> CalendarEvent {
+    redact(policy(settings, event), event)
+}
+
+/// `diff` with each event as `shown` shows it.
+pub fn shown_diff(settings: &Settings, diff: &CalendarDiff) -> CalendarDiff {
+    let shown_all = |events: &[CalendarEvent]| {
+        events
+            .iter()
+            .map(|event| shown(settings, event))
+            .collect::<Vec<_>>()
+    };
+    CalendarDiff {
+        added: shown_all(&diff.added),
+        removed: shown_all(&diff.removed),
+        changed: diff
+            .changed
+            .iter()
+            .map(|change| EventChange {
+                before: shown(settings, &change.before),
+                after: shown(settings, &change.after),
+                fields: change.fields.clone(),
+            })
+            .collect(),
+    }
+}
+
+/// The whole event, for the dashboard to show a redacted one on request.
+#[tauri::command]
+pub fn reveal_event(
+    app: AppHandle,
+    window: Window,
+    event_id: String,
+    occurrence_start: Option<String>,
+) -> Result<CalendarEvent, IpcError> {
+    ipc::require_dashboard(&window, "reveal_event")?;
+    let mut event = scheduler::cached_events(&app)
+        .into_iter()
+        .find(|e| {
+            e.id == event_id
+                && occurrence_start
+                    .as_deref()
+                    .is_none_or(|start| e.start == start)
+        })
+        .ok_or_else(|| format!("No upcoming event {}", event_id))?;
+    event.stamp(simulate::now(), &settings::current(&app).format);
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::Attendee;
+    use crate::rules::AlertRule;
+    use crate::testing::{at, event, hostile_text};
+    use crate::{cancellations, counterparts, templates};
+    use proptest::prelude::*;
+
+    /// Every placeholder, so a template can't leave out what it could leak.
+    const EVERYTHING: &str = "{title} {start_time} {end_time} {minutes_until} \
+        {relative_start} {duration_minutes} {location} {calendar} {organizer} {link}";
+
+    fn confidential(policy: DisplayPolicy) -> Settings {
+        Settings {
+            confidential: ConfidentialSettings {
+                calendars: vec![CalendarPolicy {
+                    calendar: "work".to_string(),
+                    policy,
+                }],
+            },
+            templates: templates::TemplateSettings {
+                alert: Some(EVERYTHING.to_string()),
+                speech: Some(EVERYTHING.to_string()),
+                notification: Some(EVERYTHING.to_string()),
+                status: Some(EVERYTHING.to_string()),
+            },
+            ..Settings::default()
+        }
+    }
+
+    /// The meeting with `secret` in every field a policy can withhold, each
+    /// marked so that one can't pass for another.
+    fn sensitive(title: &str, secret: &str) -> CalendarEvent {
+        CalendarEvent {
+            location: Some(format!("location ⟦{}⟧", secret)),
+            location_display: Some(format!("location ⟦{}⟧", secret)),
+            description: Some(format!("notes ⟦{}⟧", secret)),
+            url: Some(format!("https://hr.example.com/⟦{}⟧", secret)),
+            organizer: Some(format!("⟦{}⟧@hr.example.com", secret)),
+            attendees: vec![Attendee {
+                name: Some(format!("attendee ⟦{}⟧", secret)),
+                email: Some(format!("⟦{}⟧@example.com", secret)),
+                status: "accepted".to_string(),
+                is_current_user: false,
+            }],
+            map_url: Some(format!("https://maps.apple.com/?q=⟦{}⟧", secret)),
+            video_link: Some("https://zoom.us/j/1".to_string()),
+            ..event(title, at("2026-03-02 10:00"), 30)
+        }
+    }
+
+    /// Everything built from the event for a window, a notification, speech
+    /// or a cancellation notice.
+    fn emitted(settings: &Settings, event: &CalendarEvent) -> Vec<String> {
+        let now = at("2026-03-02 09:50");
+        let shown = shown(settings, event);
+        let diff = CalendarDiff {
+            removed: vec![event.clone()],
+            ..CalendarDiff::default()
+        };
+        let mut emitted = vec![
+            serde_json::to_string(&shown).unwrap(),
+            templates::display_text(settings, &shown),
+            templates::speech(settings, &shown),
+            templates::status_line(settings, &shown, now),
+            templates::render(EVERYTHING, &shown, now, &settings.format),
+            serde_json::to_string(&counterparts::for_event(settings, &shown)).unwrap(),
+        ];
+        emitted.extend(
+            cancellations::detect(&diff, now, settings)
+                .into_iter()
+                .map(|change| change.message),
+        );
+        #[cfg(feature = "test-harness")]
+        emitted.extend(sent_to_the_webview(settings, event));
+        emitted
+    }
+
+    /// What the scheduler sends the webview as `event` fires, escalates and,
+    /// once joined, shows as the pill.
+    #[cfg(feature = "test-harness")]
+    fn sent_to_the_webview(settings: &Settings, event: &CalendarEvent) -> Vec<String> {
+        use crate::bus::AppEvent;
+        use crate::scheduler::harness::Harness;
+        use crate::scheduler::Acknowledgement;
+
+        let mut settings = settings.clone();
+        settings.lead_time_seconds = 60;
+        settings.escalation.enabled = true;
+        settings.escalation.after_minutes = 1;
+        settings.rules.push(AlertRule {
+            id: "pill".to_string(),
+            pill_after_join: Some(true),
+            ..AlertRule::default()
+        });
+        let mut app = Harness::new(settings, "2026-03-02 09:58").with_event(event.clone());
+        app.run_until("2026-03-02 10:01");
+        app.answer(Acknowledgement::Joined);
+        app.show_pill();
+        let published = app.take_sent();
+
+        let sent = |pattern: fn(&AppEvent) -> bool| published.iter().any(pattern);
+        assert!(sent(|e| matches!(e, AppEvent::AlertFired(_))));
+        assert!(sent(|e| matches!(e, AppEvent::AlertEscalated(_))));
+        assert!(sent(|e| matches!(e, AppEvent::PillMode(Some(_)))));
+        published
+            .iter()
+            .filter_map(AppEvent::webview_payload)
+            .map(|payload| payload.to_string())
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn nothing_withheld_is_emitted(
+            title in hostile_text(),
+            secret in hostile_text(),
+            busy in any::<bool>(),
+        ) {
+            let policy = if busy { DisplayPolicy::BusyOnly } else { DisplayPolicy::TitleOnly };
+            let settings = confidential(policy);
+            let title = format!("title ⟪{}⟫", title);
+            let event = sensitive(&title, &secret);
+            for text in emitted(&settings, &event) {
+                prop_assert!(!text.contains(&format!("⟦{}⟧", secret)), "{}", text);
+                if busy {
+                    prop_assert!(!text.contains(&title), "{}", text);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn title_only_keeps_the_title_and_the_link() {
+        let shown = shown(
+            &confidential(DisplayPolicy::TitleOnly),
+            &sensitive("Offsite planning", "budget"),
+        );
+        assert_eq!(shown.title, "Offsite planning");
+        assert_eq!(shown.video_link.as_deref(), Some("https://zoom.us/j/1"));
+        assert!(shown.redacted);
+    }
+
+    #[test]
+    fn an_unlisted_calendar_shows_all() {
+        let event = CalendarEvent {
+            calendar: "Personal".to_string(),
+            ..sensitive("Dentist", "molar")
+        };
+        let shown = shown(&confidential(DisplayPolicy::BusyOnly), &event);
+        assert!(!shown.redacted);
+        assert_eq!(shown.title, "Dentist");
+    }
+
+    #[test]
+    fn a_rule_overrides_the_calendar() {
+        let settings = Settings {
+            rules: vec![AlertRule {
+                id: "hr".to_string(),
+                title_contains: Some("review".to_string()),
+                display_policy: Some(DisplayPolicy::BusyOnly),
+                ..AlertRule::default()
+            }],
+            ..Settings::default()
+        };
+        let shown = shown(&settings, &sensitive("Performance review", "pip"));
+        assert_eq!(shown.title, BUSY_TITLE);
+        assert!(shown.location.is_none() && shown.attendees.is_empty());
+    }
+}
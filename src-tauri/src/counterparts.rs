@@ -0,0 +1,243 @@
+use chrono::{DateTime, FixedOffset, Offset, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Window};
+
+use crate::calendar::CalendarEvent;
+use crate::ipc::{self, IpcError};
+use crate::locale;
+use crate::settings::{self, Settings};
+
+// --- Counterpart Local Times ---
+//
+// For a meeting with people in other zones, its start as it is for each of
+// them ("6:55 AM for Priya"), flagged when that is outside their working
+// day and with their date where it isn't ours. Someone's zone comes from
+// the contact zones in the settings (`set_contact_timezone`); for the
+// organizer it falls back to the zone the event was created in. Zones are
+// IANA names looked up in the tz database bundled with `chrono-tz`, so the
+// answer is the same on every machine and in tests. People without a known
+// zone, or in one at our offset then, are left out, and so is everyone
+// in a redacted event, which has no attendees.
+
+/// Their working day, in their local hours: from 08:00 until 18:00.
+const DAY_STARTS: u32 = 8;
+const DAY_ENDS: u32 = 18;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ContactZone {
+    pub email: String,
+    /// IANA name such as "Asia/Kolkata".
+    pub zone: String,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterpartTime {
+    /// Their name, or their address where the invite has none.
+    pub name: String,
+    pub email: String,
+    pub zone: String,
+    /// The start in their zone, RFC 3339.
+    pub local_start: String,
+    /// "06:55" or "6:55 AM", per the clock setting.
+    pub local_time: String,
+    /// Their date minus ours: -1 while it is still the day before for them.
+    pub day_offset: i64,
+    /// Before 08:00 or from 18:00 for them.
+    pub outside_hours: bool,
+}
+
+/// The zone `name`'s offset at `at`.
+fn offset_at(name: &str, at: DateTime<Utc>) -> Option<FixedOffset> {
+    let zone: Tz = name.parse().ok()?;
+    Some(zone.offset_from_utc_datetime(&at.naive_utc()).fix())
+}
+
+/// Whether the tz database has the zone `name`.
+pub fn is_known_zone(name: &str) -> bool {
+    name.parse::<Tz>().is_ok()
+}
+
+fn contact_zone<'a>(settings: &'a Settings, email: &str) -> Option<&'a str> {
+    settings
+        .contact_time_zones
+        .iter()
+        .find(|c| c.email.eq_ignore_ascii_case(email))
+        .map(|c| c.zone.as_str())
+}
+
+/// `start` as it is in `zone`, compared with our own date and time.
+fn local_time<Ours: TimeZone>(
+    settings: &Settings,
+    start: DateTime<Ours>,
+    name: String,
+    email: String,
+    zone: &str,
+) -> Option<CounterpartTime> {
+    let offset = offset_at(zone, start.with_timezone(&Utc))?;
+    if offset == start.offset().fix() {
+        return None;
+    }
+    let theirs = start.with_timezone(&offset);
+    let hour = theirs.hour();
+    Some(CounterpartTime {
+        name,
+        email,
+        zone: zone.to_string(),
+        local_start: theirs.to_rfc3339(),
+        local_time: locale::format_time(&settings.format, theirs),
+        day_offset: (theirs.date_naive() - start.date_naive()).num_days(),
+        outside_hours: !(DAY_STARTS..DAY_ENDS).contains(&hour),
+    })
+}
+
+/// The start for everyone else in `event` whose zone is known.
+pub fn for_event(settings: &Settings, event: &CalendarEvent) -> Vec<CounterpartTime> {
+    let Some(start) = event.start_time().filter(|_| !event.is_all_day) else {
+        return Vec::new();
+    };
+    let mut people: Vec<(String, String)> = event
+        .attendees
+        .iter()
+        .filter(|a| !a.is_current_user)
+        .filter_map(|a| {
+            let email = a.email.clone()?;
+            Some((a.name.clone().unwrap_or_else(|| email.clone()), email))
+        })
+        .collect();
+    let organizer = event.organizer.as_deref();
+    if let Some(organizer) = organizer {
+        if !people
+            .iter()
+            .any(|(_, e)| e.eq_ignore_ascii_case(organizer))
+        {
+            people.push((organizer.to_string(), organizer.to_string()));
+        }
+    }
+    people
+        .into_iter()
+        .filter_map(|(name, email)| {
+            let is_organizer = organizer.is_some_and(|o| o.eq_ignore_ascii_case(&email));
+            let zone = contact_zone(settings, &email)
+                .or_else(|| event.time_zone.as_deref().filter(|_| is_organizer))?
+                .to_string();
+            local_time(settings, start, name, email, &zone)
+        })
+        .collect()
+}
+
+/// Sets the zone `zone` for `email`, or forgets it when `zone` is empty.
+#[tauri::command]
+pub fn set_contact_timezone(
+    app: AppHandle,
+    window: Window,
+    email: String,
+    zone: String,
+) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "set_contact_timezone")?;
+    let (email, zone) = (email.trim().to_string(), zone.trim().to_string());
+    if email.is_empty() {
+        return Err("An email address is required".to_string().into());
+    }
+    if !zone.is_empty() && !is_known_zone(&zone) {
+        return Err(format!("Unknown time zone {}", zone).into());
+    }
+    settings::update(&app, |settings| {
+        let zones = &mut settings.contact_time_zones;
+        zones.retain(|c| !c.email.eq_ignore_ascii_case(&email));
+        if !zone.is_empty() {
+            zones.push(ContactZone { email, zone });
+        }
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(text: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(text).unwrap()
+    }
+
+    fn time_in(zone: &str, ours: &str) -> CounterpartTime {
+        let settings = Settings::default();
+        local_time(&settings, utc(ours), String::new(), String::new(), zone).unwrap()
+    }
+
+    #[test]
+    fn knows_iana_zones() {
+        assert!(is_known_zone("Asia/Kolkata"));
+        assert!(is_known_zone("Pacific/Kiritimati"));
+        assert!(!is_known_zone("Mars/Olympus_Mons"));
+        assert!(!is_known_zone(""));
+    }
+
+    #[test]
+    fn across_the_date_line_the_day_differs() {
+        // Monday 09:00 in American Samoa (UTC-11) is already Tuesday 10:00
+        // on Kiritimati (UTC+14), across the date line.
+        let ours = "2026-03-02T09:00:00-11:00";
+        let line_islands = time_in("Pacific/Kiritimati", ours);
+        assert_eq!(line_islands.day_offset, 1);
+        assert_eq!(line_islands.local_time, "10:00");
+        assert!(!line_islands.outside_hours);
+        assert!(line_islands
+            .local_start
+            .starts_with("2026-03-03T10:00:00+14:00"));
+
+        let pago_pago = time_in("Pacific/Pago_Pago", "2026-03-03T10:00:00+14:00");
+        assert_eq!(pago_pago.day_offset, -1);
+        assert_eq!(pago_pago.local_time, "09:00");
+    }
+
+    #[test]
+    fn samoa_skipped_a_day_in_2011() {
+        // Apia moved from UTC-10 to UTC+14 at the end of 29 December 2011.
+        assert_eq!(
+            offset_at("Pacific/Apia", utc("2011-12-29T12:00:00Z").into()),
+            FixedOffset::west_opt(10 * 3600)
+        );
+        assert_eq!(
+            offset_at("Pacific/Apia", utc("2011-12-31T12:00:00Z").into()),
+            FixedOffset::east_opt(14 * 3600)
+        );
+    }
+
+    #[test]
+    fn flags_hours_outside_their_day_and_follows_dst() {
+        // 14:00 in London in summer is 06:00 in Los Angeles (UTC-7).
+        let summer = time_in("America/Los_Angeles", "2026-07-01T14:00:00+01:00");
+        assert_eq!(summer.local_time, "06:00");
+        assert!(summer.outside_hours);
+        // In winter both have left DST, so it is 06:00 again.
+        let winter = time_in("America/Los_Angeles", "2026-01-05T14:00:00+00:00");
+        assert_eq!(winter.local_time, "06:00");
+        assert_eq!(winter.day_offset, 0);
+    }
+
+    #[test]
+    fn leaves_out_people_at_our_offset() {
+        let settings = Settings::default();
+        let start = utc("2026-03-02T09:00:00+05:30");
+        assert!(local_time(
+            &settings,
+            start,
+            String::new(),
+            String::new(),
+            "Asia/Kolkata"
+        )
+        .is_none());
+        assert!(local_time(
+            &settings,
+            start,
+            String::new(),
+            String::new(),
+            "Nowhere/Else"
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::audio::AudioRoute;
+use crate::bus::{self, AppEvent};
+use crate::features::Flag;
+use crate::scheduler::SchedulerState;
+use crate::{shutdown, simulate, sound};
+
+// --- Count-In ---
+//
+// For the last seconds before a meeting starts, a quiet tick every second
+// and a chime on the start itself, so a sentence can be finished and Join
+// clicked right on time. The scheduler arms it once per alert, from its
+// one-second tick, while the alert is showing with sound; the sequence then
+// runs on its own thread against the monotonic clock, so each sound lands
+// on its second rather than whenever the next tick comes round. Stopping
+// the alert's sound (dismissing, snoozing or joining), the alert going away
+// or the `count_in` flag being turned off ends it before the next sound.
+
+/// Goes up whenever the `count_in` flag is turned off.
+static STOPPED: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CountInSettings {
+    /// Off unless turned on here or by a rule.
+    pub enabled: bool,
+    /// Ticks before the start.
+    pub seconds: u32,
+    pub tick_sound: String,
+    pub chime_sound: String,
+    /// 0 to 1.
+    pub volume: f32,
+}
+
+impl Default for CountInSettings {
+    fn default() -> Self {
+        CountInSettings {
+            enabled: false,
+            seconds: 10,
+            tick_sound: "Tink".to_string(),
+            chime_sound: "Glass".to_string(),
+            volume: 0.3,
+        }
+    }
+}
+
+/// Whether the alert for `key` is still the one showing and may be heard.
+fn live(app: &AppHandle, key: &str, generation: u64, stopped: u64) -> bool {
+    sound::generation() == generation
+        && STOPPED.load(Ordering::SeqCst) == stopped
+        && !shutdown::in_progress()
+        && app
+            .state::<SchedulerState>()
+            .0
+            .lock()
+            .unwrap()
+            .counts_in(key)
+}
+
+fn sleep_until(at: Instant) {
+    thread::sleep(at.saturating_duration_since(Instant::now()));
+}
+
+/// Runs the count-in for the occurrence `key` starting at `start`, on the
+/// outputs `route` picks at each sound; ticks already past when it begins
+/// are skipped.
+pub fn start(
+    app: &AppHandle,
+    key: String,
+    start: DateTime<Local>,
+    route: AudioRoute,
+    settings: &CountInSettings,
+) {
+    let Ok(lead) = (start - simulate::now()).to_std() else {
+        return;
+    };
+    let at = Instant::now() + lead;
+    let generation = sound::generation();
+    let stopped = STOPPED.load(Ordering::SeqCst);
+    sound::preload(app, &settings.tick_sound);
+    sound::preload(app, &settings.chime_sound);
+    let app = app.clone();
+    let settings = settings.clone();
+    thread::spawn(move || {
+        for before in (1..=settings.seconds).rev() {
+            let Some(tick) = at.checked_sub(Duration::from_secs(before.into())) else {
+                continue;
+            };
+            if tick < Instant::now() {
+                continue;
+            }
+            sleep_until(tick);
+            if !live(&app, &key, generation, stopped) {
+                return;
+            }
+            sound::play_sample(&app, &settings.tick_sound, settings.volume, route);
+        }
+        sleep_until(at);
+        if live(&app, &key, generation, stopped) {
+            sound::play_sample(&app, &settings.chime_sound, settings.volume, route);
+        }
+    });
+}
+
+/// Ends a running count-in as soon as the `count_in` flag goes off.
+pub fn watch(app: &AppHandle) {
+    let events = bus::subscribe(app);
+    thread::spawn(move || {
+        for event in events {
+            if let AppEvent::FeatureChanged(Flag::CountIn, false) = event {
+                STOPPED.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    });
+}
@@ -0,0 +1,110 @@
+use chrono::{Local, NaiveDate};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::calendar::CalendarEvent;
+use crate::settings::{self, Settings};
+use crate::tray;
+
+// --- Days Off ---
+//
+// Weekends and public holidays shouldn't start with a digest of the week's
+// meetings. A day off is a day without working hours (when they are set
+// up), or one an all-day event in a holiday calendar covers; the holiday
+// calendars are named in `DaysOffSettings`, and their days are taken from
+// each fetch. On a day off there is no morning digest, and only meetings
+// with other people alert, besides events forced from the dashboard and
+// ones a rule lets alert outside working hours. "I'm Working Today" in the
+// tray turns that off until midnight.
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DaysOffSettings {
+    pub enabled: bool,
+    /// Calendars whose all-day events are days off, e.g. a subscribed
+    /// national-holiday calendar.
+    pub holiday_calendars: Vec<String>,
+    /// The day "I'm Working Today" was turned on; it ends at midnight.
+    pub working_on: Option<NaiveDate>,
+}
+
+/// Days taken from one event, so one spanning years by mistake stays small.
+const MAX_HOLIDAY_DAYS: usize = 31;
+
+/// Days covered by holiday-calendar events, as of the last fetch.
+static HOLIDAYS: Mutex<BTreeSet<NaiveDate>> = Mutex::new(BTreeSet::new());
+/// The day `poll` last saw, so the tray changes at midnight.
+static TODAY: Mutex<Option<NaiveDate>> = Mutex::new(None);
+
+fn holiday_dates(event: &CalendarEvent) -> Vec<NaiveDate> {
+    let (Some(start), Some(end)) = (event.start_time(), event.end_time()) else {
+        return Vec::new();
+    };
+    let (first, end) = (start.date_naive(), end.date_naive());
+    // All-day events end at the midnight after their last day.
+    first
+        .iter_days()
+        .take_while(|date| *date < end || *date == first)
+        .take(MAX_HOLIDAY_DAYS)
+        .collect()
+}
+
+/// Takes the holidays from a fetch; the tray follows when they change.
+pub fn observe(app: &AppHandle, settings: &Settings, events: &[CalendarEvent]) {
+    let days_off = &settings.days_off;
+    let holidays = events
+        .iter()
+        .filter(|e| e.is_all_day)
+        .filter(|e| {
+            days_off
+                .holiday_calendars
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(&e.calendar))
+        })
+        .flat_map(holiday_dates)
+        .collect();
+    let previous = std::mem::replace(&mut *HOLIDAYS.lock().unwrap(), holidays);
+    if previous != *HOLIDAYS.lock().unwrap() {
+        tray::refresh(app);
+    }
+}
+
+/// Off by the working hours or a holiday calendar, before the override.
+fn scheduled_off(settings: &Settings, date: NaiveDate) -> bool {
+    let hours = &settings.working_hours;
+    (hours.enabled && hours.hours_on(date).is_none()) || HOLIDAYS.lock().unwrap().contains(&date)
+}
+
+pub fn is_day_off(settings: &Settings, date: NaiveDate) -> bool {
+    let days_off = &settings.days_off;
+    days_off.enabled && days_off.working_on != Some(date) && scheduled_off(settings, date)
+}
+
+/// Whether the tray offers "I'm Working Today", and whether it is on.
+pub fn working_today_item(settings: &Settings) -> Option<bool> {
+    let today = Local::now().date_naive();
+    let days_off = &settings.days_off;
+    (days_off.enabled && scheduled_off(settings, today)).then(|| days_off.working_on == Some(today))
+}
+
+/// Flips "I'm Working Today".
+pub fn toggle_working_today(app: &AppHandle) -> Result<(), String> {
+    let today = Local::now().date_naive();
+    settings::update(app, |settings| {
+        let days_off = &mut settings.days_off;
+        days_off.working_on = (days_off.working_on != Some(today)).then_some(today);
+    })
+    .map(|_| ())
+}
+
+/// Rebuilds the tray menu when the day changes; called every tick.
+pub fn poll(app: &AppHandle) {
+    let today = Local::now().date_naive();
+    let changed = TODAY.lock().unwrap().replace(today) != Some(today);
+    if changed {
+        tray::refresh(app);
+    }
+}
@@ -0,0 +1,343 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use crate::calendar::CalendarEvent;
+use crate::locale::{self, FormatSettings};
+use crate::scheduler::{self, Acknowledgement, Scheduler, SchedulerState};
+use crate::settings::Settings;
+use crate::{focus, rules, settings};
+
+// --- Hard Dismiss ---
+//
+// For alerts that get dismissed on reflex: under a rule with `hard_dismiss`,
+// Dismiss first hands out a challenge (type the start time, or a two-digit
+// code) and only the right answer closes the alert. Wrong answers lock the
+// challenge for a growing while. Snooze and Join stay one click, and holding
+// Escape in the alert for three seconds always gets out.
+
+const ESCAPE_HOLD: Duration = Duration::from_secs(3);
+const LOCKOUT_BASE_SECONDS: u64 = 2;
+const LOCKOUT_MAX_SECONDS: u64 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DismissChallenge {
+    /// Type the meeting's start time, as the alert shows it.
+    StartTime,
+    /// Type back a two-digit code.
+    Code,
+}
+
+/// What the alert shows for a challenge; the answer stays in the backend.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengePrompt {
+    pub kind: DismissChallenge,
+    pub prompt: String,
+    /// The code to type, for `code` challenges.
+    pub code: Option<String>,
+}
+
+struct Pending {
+    event_id: String,
+    prompt: ChallengePrompt,
+    answer: String,
+    failures: u32,
+    locked_until: Option<Instant>,
+}
+
+impl Pending {
+    /// Checks `answer` at `now`; a wrong one locks the challenge.
+    fn check(&mut self, answer: &str, now: Instant) -> Result<(), String> {
+        if let Some(until) = self.locked_until.filter(|until| now < *until) {
+            let wait = (until - now).as_secs() + 1;
+            return Err(format!("Too many wrong answers; try again in {}s", wait));
+        }
+        if !is_correct(&self.answer, answer) {
+            self.failures += 1;
+            self.locked_until = Some(now + lockout(self.failures));
+            return Err("That's not it".to_string());
+        }
+        Ok(())
+    }
+}
+
+static PENDING: Mutex<Option<Pending>> = Mutex::new(None);
+/// When Escape went down in the alert.
+static ESCAPE_DOWN: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// The prompt and expected answer; `seed` picks the code.
+pub fn challenge(
+    kind: DismissChallenge,
+    event: &CalendarEvent,
+    format: &FormatSettings,
+    seed: u32,
+) -> (ChallengePrompt, String) {
+    match kind {
+        DismissChallenge::StartTime => {
+            let answer = event
+                .start_time()
+                .map(|start| locale::format_time(format, start))
+                .unwrap_or_default();
+            let prompt = ChallengePrompt {
+                kind,
+                prompt: "Type the start time to dismiss".to_string(),
+                code: None,
+            };
+            (prompt, answer)
+        }
+        DismissChallenge::Code => {
+            let code = (10 + seed % 90).to_string();
+            let prompt = ChallengePrompt {
+                kind,
+                prompt: format!("Type {} to dismiss", code),
+                code: Some(code.clone()),
+            };
+            (prompt, code)
+        }
+    }
+}
+
+/// Letters and digits only, so "9:30 AM", "9.30am" and "930am" are the same.
+fn normalize(answer: &str) -> String {
+    answer
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+pub fn is_correct(expected: &str, answer: &str) -> bool {
+    let expected = normalize(expected);
+    !expected.is_empty() && normalize(answer) == expected
+}
+
+/// How long the challenge is locked after `failures` wrong answers.
+pub fn lockout(failures: u32) -> Duration {
+    let seconds = LOCKOUT_BASE_SECONDS
+        .saturating_mul(1 << failures.clamp(1, 10).saturating_sub(1))
+        .min(LOCKOUT_MAX_SECONDS);
+    Duration::from_secs(seconds)
+}
+
+fn seed() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos())
+}
+
+/// The active alert and its challenge, if its rules ask for one.
+fn required(app: &AppHandle) -> Option<(CalendarEvent, DismissChallenge)> {
+    let state = app.state::<SchedulerState>();
+    let scheduler = state.0.lock().unwrap();
+    challenge_for(&settings::current(app), &scheduler)
+}
+
+/// `required`, from the settings and the scheduler themselves.
+pub fn challenge_for(
+    settings: &Settings,
+    scheduler: &Scheduler,
+) -> Option<(CalendarEvent, DismissChallenge)> {
+    let event = scheduler.active_event()?.clone();
+    let kind = rules::evaluate(settings, &event).hard_dismiss?;
+    Some((event, kind))
+}
+
+/// Whether the active alert may only be dismissed through a challenge.
+pub fn is_required(app: &AppHandle) -> bool {
+    required(app).is_some()
+}
+
+/// Forgets the challenge, once the alert is handled some other way.
+pub fn clear() {
+    *PENDING.lock().unwrap() = None;
+}
+
+/// Starts a dismiss of the active alert. `None` means it needs no challenge
+/// and `dismiss_alert` will do; asking again repeats the same challenge.
+#[tauri::command]
+pub fn request_dismiss(
+    app: AppHandle,
+    event_id: String,
+) -> Result<Option<ChallengePrompt>, String> {
+    focus::check_grace()?;
+    let Some((event, kind)) = required(&app) else {
+        return Ok(None);
+    };
+    if event.id != event_id {
+        return Err("That alert isn't showing".to_string());
+    }
+    let mut pending = PENDING.lock().unwrap();
+    if let Some(current) = pending.as_ref().filter(|p| p.event_id == event_id) {
+        return Ok(Some(current.prompt.clone()));
+    }
+    let settings = settings::current(&app);
+    let (prompt, answer) = challenge(kind, &event, &settings.format, seed());
+    *pending = Some(Pending {
+        event_id,
+        prompt: prompt.clone(),
+        answer,
+        failures: 0,
+        locked_until: None,
+    });
+    Ok(Some(prompt))
+}
+
+#[tauri::command]
+pub fn confirm_dismiss(app: AppHandle, event_id: String, answer: String) -> Result<(), String> {
+    {
+        let mut pending = PENDING.lock().unwrap();
+        let Some(current) = pending.as_mut().filter(|p| p.event_id == event_id) else {
+            return Err("Request a dismiss first".to_string());
+        };
+        current.check(&answer, Instant::now())?;
+    }
+    scheduler::acknowledge(&app, Acknowledgement::Dismissed);
+    Ok(())
+}
+
+/// Escape went down in the alert window.
+#[tauri::command]
+pub fn begin_escape() {
+    ESCAPE_DOWN.lock().unwrap().get_or_insert_with(Instant::now);
+}
+
+/// Escape came back up, or has been held for the full time; after a long
+/// enough hold the alert is dismissed, challenge or not. Returns whether it
+/// was.
+#[tauri::command]
+pub fn end_escape(app: AppHandle) -> bool {
+    let held = ESCAPE_DOWN.lock().unwrap().take();
+    if held.is_none_or(|down| down.elapsed() < ESCAPE_HOLD) {
+        return false;
+    }
+    scheduler::acknowledge(&app, Acknowledgement::Dismissed);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::ClockStyle;
+    use crate::testing::{at, event};
+
+    fn clock(clock: ClockStyle) -> FormatSettings {
+        FormatSettings {
+            clock,
+            ..FormatSettings::default()
+        }
+    }
+
+    #[test]
+    fn the_start_time_challenge_takes_it_as_the_alert_shows_it() {
+        let review = event("Review", at("2026-03-02 09:30"), 30);
+        let (prompt, answer) = challenge(
+            DismissChallenge::StartTime,
+            &review,
+            &clock(ClockStyle::TwelveHour),
+            7,
+        );
+        assert_eq!(prompt.kind, DismissChallenge::StartTime);
+        assert_eq!(prompt.code, None);
+        assert!(!prompt.prompt.contains("9:30"));
+        assert_eq!(answer, "9:30 AM");
+        for typed in ["9:30 AM", "9.30am", "930am", " 9:30 am "] {
+            assert!(is_correct(&answer, typed), "{}", typed);
+        }
+        for typed in ["9:30", "09:30 AM", "9:31 AM", ""] {
+            assert!(!is_correct(&answer, typed), "{}", typed);
+        }
+
+        let (_, answer) = challenge(
+            DismissChallenge::StartTime,
+            &review,
+            &clock(ClockStyle::TwentyFourHour),
+            7,
+        );
+        assert!(is_correct(&answer, "09:30"));
+        assert!(is_correct(&answer, "0930"));
+    }
+
+    #[test]
+    fn codes_have_two_digits_and_follow_the_seed() {
+        let review = event("Review", at("2026-03-02 09:30"), 30);
+        for seed in [0, 1, 89, 90, 12_345, u32::MAX] {
+            let (prompt, answer) = challenge(
+                DismissChallenge::Code,
+                &review,
+                &FormatSettings::default(),
+                seed,
+            );
+            assert_eq!(answer.len(), 2, "{}", answer);
+            assert!(answer.chars().all(|c| c.is_ascii_digit()));
+            assert!(!answer.starts_with('0'));
+            assert_eq!(prompt.code.as_deref(), Some(answer.as_str()));
+            assert_eq!(prompt.prompt, format!("Type {} to dismiss", answer));
+        }
+        let code = |seed| {
+            challenge(
+                DismissChallenge::Code,
+                &review,
+                &FormatSettings::default(),
+                seed,
+            )
+            .1
+        };
+        assert_eq!(code(0), "10");
+        assert_eq!(code(89), "99");
+        assert_eq!(code(90), "10");
+    }
+
+    #[test]
+    fn an_event_without_a_start_cannot_be_answered() {
+        let mut broken = event("Review", at("2026-03-02 09:30"), 30);
+        broken.start = "not a time".to_string();
+        let (_, answer) = challenge(
+            DismissChallenge::StartTime,
+            &broken,
+            &FormatSettings::default(),
+            0,
+        );
+        assert!(!is_correct(&answer, ""));
+        assert!(!is_correct(&answer, "   "));
+    }
+
+    #[test]
+    fn lockouts_double_up_to_the_cap() {
+        let seconds: Vec<u64> = (0..8).map(|n| lockout(n).as_secs()).collect();
+        assert_eq!(seconds, vec![2, 2, 4, 8, 16, 30, 30, 30]);
+        assert_eq!(lockout(u32::MAX).as_secs(), LOCKOUT_MAX_SECONDS);
+    }
+
+    #[test]
+    fn wrong_answers_lock_the_challenge_for_longer_each_time() {
+        let mut pending = Pending {
+            event_id: "review".to_string(),
+            prompt: ChallengePrompt {
+                kind: DismissChallenge::Code,
+                prompt: "Type 42 to dismiss".to_string(),
+                code: Some("42".to_string()),
+            },
+            answer: "42".to_string(),
+            failures: 0,
+            locked_until: None,
+        };
+        let start = Instant::now();
+        let after = |seconds: f64| start + Duration::from_secs_f64(seconds);
+
+        assert_eq!(pending.check("24", start), Err("That's not it".to_string()));
+        // Locked for 2s: even the right answer is refused.
+        let locked = pending.check("42", after(1.5)).unwrap_err();
+        assert_eq!(locked, "Too many wrong answers; try again in 1s");
+        assert_eq!(pending.failures, 1);
+
+        assert!(pending.check("43", after(2.0)).is_err());
+        // Now for 4s.
+        assert!(pending.check("42", after(5.5)).is_err());
+        assert_eq!(pending.failures, 2);
+        assert_eq!(pending.check("42", after(6.0)), Ok(()));
+    }
+}
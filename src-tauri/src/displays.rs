@@ -0,0 +1,471 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::{
+    AppHandle, LogicalPosition, Manager, Monitor, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+};
+
+use crate::appearance;
+use crate::board::BoardSettings;
+use crate::bus::{self, AppEvent};
+use crate::power::{self, PowerProfile};
+use crate::retention;
+use crate::settings;
+use crate::window;
+
+// --- Displays ---
+//
+// The alert shows on one display. Depending on the mode the other displays
+// are left alone, dimmed, or covered by overlay windows, one per display.
+// Overlay windows are created the first time their display needs one and
+// then only hidden between alerts, since a webview takes about a second to
+// start. The scheduler calls `sync` every tick, so a display plugged in
+// mid-alert gets an overlay and one removed loses its window. Any change of
+// the connected displays is published as `DisplaysChanged`, and an alert
+// left on a display that went away moves to the alert display. The agenda
+// board's display gets no overlay; the board shows the alert itself.
+
+const OVERLAY_PREFIX: &str = "overlay-";
+
+static OVERLAYS_UP: AtomicBool = AtomicBool::new(false);
+/// Overlay webviews created since launch.
+static OVERLAYS_CREATED: AtomicU64 = AtomicU64::new(0);
+/// The displays as of the last `sync`; `None` before the first.
+static CONNECTED: Mutex<Option<Vec<DisplayInfo>>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayMode {
+    /// Only the alert display is taken over.
+    Single,
+    /// Other displays get a click-through dark overlay.
+    Dim,
+    /// Other displays are blacked out.
+    Cover,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Corner {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub mode: DisplayMode,
+    /// Name of the display to alert on; the primary display when unset or
+    /// not connected.
+    pub monitor: Option<String>,
+    /// Where an alert moved aside docks.
+    pub corner: Corner,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        DisplaySettings {
+            mode: DisplayMode::Single,
+            monitor: None,
+            corner: Corner::default(),
+        }
+    }
+}
+
+/// A connected display, in physical pixels.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    pub name: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayChange {
+    pub displays: Vec<DisplayInfo>,
+    pub added: Vec<DisplayInfo>,
+    pub removed: Vec<DisplayInfo>,
+}
+
+impl DisplayInfo {
+    pub fn of(monitor: &Monitor) -> Self {
+        DisplayInfo {
+            name: monitor.name().cloned(),
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+            scale_factor: monitor.scale_factor(),
+        }
+    }
+
+    /// Whether the physical point is on this display.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        (self.x..self.x + self.width as i32).contains(&x)
+            && (self.y..self.y + self.height as i32).contains(&y)
+    }
+
+    fn same_place(&self, other: &DisplayInfo) -> bool {
+        (self.x, self.y, self.width, self.height) == (other.x, other.y, other.width, other.height)
+    }
+}
+
+/// What changed from `old` to `new`; `None` if nothing did. A display that
+/// moved or changed resolution counts as removed and added.
+pub fn diff(old: &[DisplayInfo], new: &[DisplayInfo]) -> Option<DisplayChange> {
+    let added: Vec<DisplayInfo> = new.iter().filter(|d| !old.contains(d)).cloned().collect();
+    let removed: Vec<DisplayInfo> = old.iter().filter(|d| !new.contains(d)).cloned().collect();
+    if added.is_empty() && removed.is_empty() {
+        return None;
+    }
+    Some(DisplayChange {
+        displays: new.to_vec(),
+        added,
+        removed,
+    })
+}
+
+pub fn alert_display(app: &AppHandle, settings: &DisplaySettings) -> Option<Monitor> {
+    let monitors = app.available_monitors().unwrap_or_default();
+    settings
+        .monitor
+        .as_ref()
+        .and_then(|name| monitors.into_iter().find(|m| m.name() == Some(name)))
+        .or_else(|| app.primary_monitor().ok().flatten())
+}
+
+/// Top-left point of a window of `size` (in points) in `corner` of the
+/// monitor's work area, clear of the menu bar and the Dock.
+pub fn corner_position(
+    monitor: &Monitor,
+    size: (f64, f64),
+    corner: Corner,
+    margin: f64,
+) -> LogicalPosition<f64> {
+    let scale = monitor.scale_factor();
+    let area = monitor.work_area();
+    let left = f64::from(area.position.x) / scale;
+    let top = f64::from(area.position.y) / scale;
+    let right = left + f64::from(area.size.width) / scale;
+    let bottom = top + f64::from(area.size.height) / scale;
+    let x = match corner {
+        Corner::TopLeft | Corner::BottomLeft => left + margin,
+        Corner::TopRight | Corner::BottomRight => right - size.0 - margin,
+    };
+    let y = match corner {
+        Corner::TopLeft | Corner::TopRight => top + margin,
+        Corner::BottomLeft | Corner::BottomRight => bottom - size.1 - margin,
+    };
+    LogicalPosition::new(x, y)
+}
+
+fn overlay_label(display: &DisplayInfo) -> String {
+    format!("{}{}_{}", OVERLAY_PREFIX, display.x, display.y)
+}
+
+/// Labels of the overlays `mode` wants over `displays`: one for each but the
+/// alert's and the board's.
+fn wanted_overlays(
+    displays: &[DisplayInfo],
+    alert: Option<&DisplayInfo>,
+    mode: DisplayMode,
+    board: &BoardSettings,
+) -> Vec<String> {
+    displays
+        .iter()
+        .filter(|_| mode != DisplayMode::Single)
+        .filter(|d| !alert.is_some_and(|a| a.same_place(d)))
+        .filter(|d| !board.covers(d.name.as_ref()))
+        .map(overlay_label)
+        .collect()
+}
+
+/// Moves the alert window onto the configured display before it goes
+/// fullscreen there.
+pub fn place_alert(window: &WebviewWindow) {
+    let settings = settings::current(window.app_handle()).display;
+    if let Some(monitor) = alert_display(window.app_handle(), &settings) {
+        let _ = window.set_position(*monitor.position());
+    }
+}
+
+fn create_overlay(app: &AppHandle, monitor: &Monitor) -> tauri::Result<WebviewWindow> {
+    OVERLAYS_CREATED.fetch_add(1, Ordering::SeqCst);
+    WebviewWindowBuilder::new(
+        app,
+        overlay_label(&DisplayInfo::of(monitor)),
+        WebviewUrl::App("index.html".into()),
+    )
+    .title("Overlay")
+    .visible(false)
+    .decorations(false)
+    .transparent(true)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .resizable(false)
+    .focused(false)
+    .build()
+}
+
+/// Fits a new or pooled overlay to its display, which may have changed size
+/// since, and shows it.
+fn show_overlay(window: &WebviewWindow, monitor: &Monitor, mode: DisplayMode) -> tauri::Result<()> {
+    window.set_position(*monitor.position())?;
+    window.set_size(*monitor.size())?;
+    window.set_ignore_cursor_events(mode == DisplayMode::Dim)?;
+    if !window.is_visible()? {
+        window.show()?;
+    }
+    Ok(())
+}
+
+fn overlay_windows(app: &AppHandle) -> Vec<(String, WebviewWindow)> {
+    app.webview_windows()
+        .into_iter()
+        .filter(|(label, _)| label.starts_with(OVERLAY_PREFIX))
+        .collect()
+}
+
+/// Publishes a change of the connected displays, and moves the alert off a
+/// display that is gone.
+fn watch(app: &AppHandle) {
+    let displays: Vec<DisplayInfo> = app
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(DisplayInfo::of)
+        .collect();
+    let change = {
+        let mut connected = CONNECTED.lock().unwrap();
+        let change = connected.as_deref().and_then(|old| diff(old, &displays));
+        *connected = Some(displays);
+        change
+    };
+    let Some(change) = change else {
+        return;
+    };
+    if !change.removed.is_empty() {
+        window::rehome_alert(app);
+    }
+    bus::publish(app, AppEvent::DisplaysChanged(change));
+}
+
+/// Brings the overlay pool in line with the connected displays, and shows
+/// the overlays while they are up.
+pub fn sync(app: &AppHandle) {
+    watch(app);
+    let up = OVERLAYS_UP.load(Ordering::SeqCst);
+    let pooled = overlay_windows(app);
+    if !up && pooled.is_empty() {
+        return;
+    }
+    let all = settings::current(app);
+    let settings = &all.display;
+    let monitors = app.available_monitors().unwrap_or_default();
+    let displays: Vec<DisplayInfo> = monitors.iter().map(DisplayInfo::of).collect();
+    let alert = alert_display(app, settings).map(|m| DisplayInfo::of(&m));
+    let wanted = wanted_overlays(&displays, alert.as_ref(), settings.mode, &all.board);
+
+    // Gone displays, and one that became the alert display.
+    for (label, window) in pooled {
+        if !wanted.contains(&label) {
+            let _ = window.destroy();
+        }
+    }
+    if !up {
+        return;
+    }
+    for (monitor, display) in monitors.iter().zip(&displays) {
+        let label = overlay_label(display);
+        if !wanted.contains(&label) {
+            continue;
+        }
+        let window = match app.get_webview_window(&label) {
+            Some(window) => Ok(window),
+            None => create_overlay(app, monitor),
+        };
+        if let Err(e) = window.and_then(|window| show_overlay(&window, monitor, settings.mode)) {
+            retention::log!("failed to show display overlay: {}", e);
+        }
+    }
+}
+
+/// Overlay windows existing now, and how many of them are showing.
+pub fn pooled_overlays(app: &AppHandle) -> (usize, usize) {
+    let pooled = overlay_windows(app);
+    let visible = pooled
+        .iter()
+        .filter(|(_, window)| window.is_visible().unwrap_or(false))
+        .count();
+    (pooled.len(), visible)
+}
+
+pub fn overlays_created() -> u64 {
+    OVERLAYS_CREATED.load(Ordering::SeqCst)
+}
+
+pub fn show_overlays(app: &AppHandle) {
+    let settings = settings::current(app);
+    let skip = match settings.display.mode {
+        DisplayMode::Single => true,
+        DisplayMode::Dim => power::profile(&settings) == PowerProfile::LowPower,
+        DisplayMode::Cover => false,
+    };
+    if skip {
+        return;
+    }
+    OVERLAYS_UP.store(true, Ordering::SeqCst);
+    sync(app);
+}
+
+/// How opaque an overlay is: dimming is lighter in light mode, and solid
+/// when the system asks for reduced transparency.
+#[tauri::command]
+pub fn get_overlay_opacity(app: AppHandle) -> f64 {
+    let appearance = appearance::current();
+    match settings::current(&app).display.mode {
+        DisplayMode::Cover => 1.0,
+        _ if appearance.reduce_transparency => 1.0,
+        _ if appearance.is_light() => 0.45,
+        _ => 0.6,
+    }
+}
+
+/// Hides the overlays, keeping their windows for the next alert.
+pub fn hide_overlays(app: &AppHandle) {
+    OVERLAYS_UP.store(false, Ordering::SeqCst);
+    for (_, window) in overlay_windows(app) {
+        let _ = window.hide();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(name: &str, x: i32, y: i32, width: u32, height: u32) -> DisplayInfo {
+        DisplayInfo {
+            name: Some(name.to_string()),
+            x,
+            y,
+            width,
+            height,
+            scale_factor: 2.0,
+        }
+    }
+
+    /// A laptop with an external display to its right.
+    fn desk() -> Vec<DisplayInfo> {
+        vec![
+            display("Built-in Retina Display", 0, 0, 3024, 1964),
+            display("DELL U2720Q", 3024, 0, 3840, 2160),
+        ]
+    }
+
+    #[test]
+    fn nothing_changes_while_the_same_displays_stay() {
+        assert!(diff(&desk(), &desk()).is_none());
+        let mut reordered = desk();
+        reordered.reverse();
+        assert!(diff(&desk(), &reordered).is_none());
+    }
+
+    #[test]
+    fn unplugging_and_plugging_in_are_removals_and_additions() {
+        let laptop = vec![desk()[0].clone()];
+        let unplugged = diff(&desk(), &laptop).unwrap();
+        assert_eq!(unplugged.removed, vec![desk()[1].clone()]);
+        assert!(unplugged.added.is_empty());
+        assert_eq!(unplugged.displays, laptop);
+
+        let plugged = diff(&laptop, &desk()).unwrap();
+        assert_eq!(plugged.added, vec![desk()[1].clone()]);
+        assert!(plugged.removed.is_empty());
+    }
+
+    #[test]
+    fn a_display_that_moved_or_changed_resolution_counts_as_replaced() {
+        let mut rearranged = desk();
+        rearranged[1].x = -3840;
+        let change = diff(&desk(), &rearranged).unwrap();
+        assert_eq!(change.removed, vec![desk()[1].clone()]);
+        assert_eq!(change.added, vec![rearranged[1].clone()]);
+
+        let mut scaled = desk();
+        scaled[0].width = 2560;
+        scaled[0].height = 1664;
+        let change = diff(&desk(), &scaled).unwrap();
+        assert_eq!((change.removed.len(), change.added.len()), (1, 1));
+    }
+
+    #[test]
+    fn an_alert_left_on_a_gone_display_is_off_screen() {
+        // Where the alert sat on the external display.
+        let (x, y) = (3024 + 100, 50);
+        assert!(desk().iter().any(|d| d.contains(x, y)));
+        let laptop = [desk()[0].clone()];
+        assert!(!laptop.iter().any(|d| d.contains(x, y)));
+        // Edges: the origin is on a display, its far corner isn't.
+        assert!(laptop[0].contains(0, 0));
+        assert!(!laptop[0].contains(3024, 1964));
+    }
+
+    #[test]
+    fn overlays_cover_every_display_but_the_alerts_and_the_boards() {
+        let mut displays = desk();
+        displays.push(display("LG HDR 4K", -3840, 0, 3840, 2160));
+        let alert = displays[0].clone();
+        let no_board = BoardSettings::default();
+
+        assert!(
+            wanted_overlays(&displays, Some(&alert), DisplayMode::Single, &no_board).is_empty()
+        );
+        assert_eq!(
+            wanted_overlays(&displays, Some(&alert), DisplayMode::Dim, &no_board),
+            vec!["overlay-3024_0", "overlay--3840_0"]
+        );
+        // No alert display known: every display gets one.
+        assert_eq!(
+            wanted_overlays(&displays, None, DisplayMode::Cover, &no_board).len(),
+            3
+        );
+
+        let board = BoardSettings {
+            enabled: true,
+            display: Some("LG HDR 4K".to_string()),
+        };
+        assert_eq!(
+            wanted_overlays(&displays, Some(&alert), DisplayMode::Cover, &board),
+            vec!["overlay-3024_0"]
+        );
+        let board_off = BoardSettings {
+            enabled: false,
+            ..board
+        };
+        assert_eq!(
+            wanted_overlays(&displays, Some(&alert), DisplayMode::Cover, &board_off).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn a_display_plugged_in_mid_alert_gets_an_overlay_and_a_gone_one_loses_it() {
+        let laptop = vec![desk()[0].clone()];
+        let alert = laptop[0].clone();
+        let board = BoardSettings::default();
+        assert!(wanted_overlays(&laptop, Some(&alert), DisplayMode::Dim, &board).is_empty());
+        assert_eq!(
+            wanted_overlays(&desk(), Some(&alert), DisplayMode::Dim, &board),
+            vec!["overlay-3024_0"]
+        );
+    }
+}
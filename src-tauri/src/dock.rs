@@ -0,0 +1,99 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+// --- Dock ---
+//
+// A quieter nudge than the takeover: minutes until the next meeting on the
+// Dock badge, and a bounce for notification-only alerts. Neither shows when
+// the app runs as an accessory (no Dock icon), so both are skipped then.
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DockSettings {
+    pub badge: bool,
+    /// The badge is cleared when the next meeting is further away than this.
+    pub badge_within_minutes: i64,
+    /// Bounce the Dock icon until focused when a notification alert fires.
+    pub bounce_on_notification: bool,
+}
+
+impl Default for DockSettings {
+    fn default() -> Self {
+        DockSettings {
+            badge: false,
+            badge_within_minutes: 60,
+            bounce_on_notification: false,
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use std::ffi::CString;
+
+    /// NSApplicationActivationPolicyRegular.
+    const POLICY_REGULAR: isize = 0;
+    /// NSCriticalRequest.
+    const CRITICAL_REQUEST: usize = 0;
+
+    /// The shared NSApplication, if the app shows in the Dock.
+    ///
+    /// SAFETY: call on the main thread only.
+    unsafe fn dock_app() -> Option<&'static AnyObject> {
+        let app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
+        let app = app.as_ref()?;
+        let policy: isize = msg_send![app, activationPolicy];
+        (policy == POLICY_REGULAR).then_some(app)
+    }
+
+    pub fn set_badge(text: Option<&str>) {
+        let label = text.and_then(|t| CString::new(t).ok());
+        // SAFETY: runs on the main thread; the label is an autoreleased
+        // NSString, nil clears the badge.
+        unsafe {
+            let Some(app) = dock_app() else {
+                return;
+            };
+            let tile: *mut AnyObject = msg_send![app, dockTile];
+            let Some(tile) = tile.as_ref() else {
+                return;
+            };
+            let label: *mut AnyObject = match &label {
+                Some(label) => msg_send![class!(NSString), stringWithUTF8String: label.as_ptr()],
+                None => std::ptr::null_mut(),
+            };
+            let _: () = msg_send![tile, setBadgeLabel: label];
+        }
+    }
+
+    pub fn request_attention() {
+        // SAFETY: runs on the main thread.
+        unsafe {
+            if let Some(app) = dock_app() {
+                let _: isize = msg_send![app, requestUserAttention: CRITICAL_REQUEST];
+            }
+        }
+    }
+}
+
+/// Sets or, with `None`, clears the Dock badge.
+pub fn set_badge(app: &AppHandle, text: Option<String>) {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = app.run_on_main_thread(move || macos::set_badge(text.as_deref()));
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = (app, text);
+}
+
+pub fn request_attention(app: &AppHandle) {
+    #[cfg(target_os = "macos")]
+    {
+        let _ = app.run_on_main_thread(macos::request_attention);
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = app;
+}
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::rules::{AlertStyle, StepOutcome, TraceStep};
+use crate::scheduler::{self, SchedulerState};
+use crate::{history, overrides, settings, simulate};
+
+// --- Explain ---
+//
+// "Why didn't it alert?": a dry run of every decision about one occurrence,
+// from the override and filters through each matching rule to the
+// scheduler's state, in the order they apply. Nothing is changed; the same
+// steps are kept in the history for alerts that were missed or silent.
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Explanation {
+    pub steps: Vec<TraceStep>,
+    /// `None` when the occurrence doesn't alert at all.
+    pub style: Option<AlertStyle>,
+    /// When the first alert is (or was) due.
+    pub alert_at: Option<DateTime<Local>>,
+    /// Why it won't alert, from the first step that stopped it.
+    pub suppressed: Option<String>,
+}
+
+impl Explanation {
+    pub fn new(
+        steps: Vec<TraceStep>,
+        style: Option<AlertStyle>,
+        alert_at: Option<DateTime<Local>>,
+    ) -> Self {
+        let suppressed = steps
+            .iter()
+            .find(|step| step.outcome == StepOutcome::Suppressed)
+            .map(|step| step.detail.clone());
+        Explanation {
+            steps,
+            style,
+            alert_at,
+            suppressed,
+        }
+    }
+}
+
+#[tauri::command]
+pub fn explain_event(
+    app: AppHandle,
+    state: State<SchedulerState>,
+    event_id: String,
+    occurrence_start: String,
+) -> Result<Explanation, String> {
+    let start = overrides::parse_start(&occurrence_start)?;
+    let event = scheduler::cached_events(&app)
+        .into_iter()
+        .find(|e| e.id == event_id && e.start_time() == Some(start))
+        .ok_or("Not in the part of the calendar being read".to_string())?;
+    let settings = settings::current(&app);
+    let alerted = history::was_alerted(&app, &event);
+    let scheduler = state.0.lock().unwrap();
+    Ok(scheduler.explain(&settings, &event, simulate::now(), alerted))
+}
@@ -0,0 +1,129 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::WebviewWindow;
+
+// --- Alert Fade ---
+//
+// The alert window fades by animating the native window's alpha, which CSS
+// can't reach. Each new fade bumps the generation so a dismiss halfway
+// through a fade-in takes over from the current alpha instead of racing it.
+
+const FRAME: Duration = Duration::from_millis(16);
+
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Last alpha applied, stored as `f64` bits.
+static ALPHA: AtomicU64 = AtomicU64::new(0x3FF0_0000_0000_0000);
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct FadeSettings {
+    pub duration_ms: u64,
+    /// Skip the animation and show or hide immediately.
+    pub instant: bool,
+}
+
+impl Default for FadeSettings {
+    fn default() -> Self {
+        FadeSettings {
+            duration_ms: 400,
+            instant: false,
+        }
+    }
+}
+
+fn current_alpha() -> f64 {
+    f64::from_bits(ALPHA.load(Ordering::SeqCst))
+}
+
+#[cfg(target_os = "macos")]
+fn apply_alpha(window: &WebviewWindow, alpha: f64) {
+    use objc2::{msg_send, runtime::AnyObject, MainThreadMarker};
+
+    fn set(window: &WebviewWindow, alpha: f64) {
+        let Ok(ns_window) = window.ns_window() else {
+            return;
+        };
+        // SAFETY: `ns_window` is the live NSWindow behind this window and
+        // we are on the main thread.
+        unsafe {
+            let ns_window = &*(ns_window as *const AnyObject);
+            let _: () = msg_send![ns_window, setAlphaValue: alpha];
+        }
+    }
+
+    if MainThreadMarker::new().is_some() {
+        set(window, alpha);
+    } else {
+        let target = window.clone();
+        let _ = window.run_on_main_thread(move || set(&target, alpha));
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn apply_alpha(_window: &WebviewWindow, _alpha: f64) {}
+
+fn set_alpha(window: &WebviewWindow, alpha: f64) {
+    ALPHA.store(alpha.to_bits(), Ordering::SeqCst);
+    apply_alpha(window, alpha);
+}
+
+/// Animates from the current alpha to `to`, then runs `done` unless another
+/// fade started in the meantime.
+fn animate<F>(window: &WebviewWindow, to: f64, settings: &FadeSettings, done: F)
+where
+    F: FnOnce(&WebviewWindow) + Send + 'static,
+{
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if settings.instant || settings.duration_ms == 0 {
+        set_alpha(window, to);
+        done(window);
+        return;
+    }
+
+    let window = window.clone();
+    let from = current_alpha();
+    let duration = Duration::from_millis(settings.duration_ms).mul_f64((to - from).abs());
+    thread::spawn(move || {
+        let started = Instant::now();
+        loop {
+            if GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let t = if duration.is_zero() {
+                1.0
+            } else {
+                (started.elapsed().as_secs_f64() / duration.as_secs_f64()).min(1.0)
+            };
+            set_alpha(&window, from + (to - from) * t);
+            if t >= 1.0 {
+                break;
+            }
+            thread::sleep(FRAME);
+        }
+        if GENERATION.load(Ordering::SeqCst) == generation {
+            done(&window);
+        }
+    });
+}
+
+/// Makes the window transparent so it can be shown and then faded in.
+pub fn prepare(window: &WebviewWindow, settings: &FadeSettings) {
+    if !settings.instant {
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+        set_alpha(window, 0.0);
+    }
+}
+
+pub fn fade_in(window: &WebviewWindow, settings: &FadeSettings) {
+    animate(window, 1.0, settings, |_| {});
+}
+
+pub fn fade_out<F>(window: &WebviewWindow, settings: &FadeSettings, done: F)
+where
+    F: FnOnce(&WebviewWindow) + Send + 'static,
+{
+    animate(window, 0.0, settings, done);
+}
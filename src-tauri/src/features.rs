@@ -0,0 +1,254 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Manager, Window};
+
+use crate::bus::{self, AppEvent};
+use crate::ipc::{self, IpcError};
+use crate::retention;
+use crate::settings::{self, Settings};
+
+// --- Feature Flags ---
+//
+// Newer behaviors that can go wrong for someone ship behind a flag, so they
+// can be tried, or turned off again, without waiting for a release. The
+// defaults are compiled in per release channel (`RELEASE_CHANNEL` at build
+// time, stable unless set); what the user picked is kept by name in
+// `FeatureSettings` and wins over them. The code behind a flag asks
+// `enabled` each time before taking the new path, so a flip holds from the
+// next tick; subsystems with something already under way also watch for
+// `AppEvent::FeatureChanged`. `get_whats_new` has this version's release
+// notes from the bundled whats_new.json, once per version, with the flags
+// of the features they describe.
+
+const WHATS_NEW_RESOURCE: &str = "whats_new.json";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Flag {
+    /// Replaying the sound, raising the window and pushing for an alert
+    /// left unacknowledged.
+    Escalation,
+    /// Lead-time rules added for series joined late.
+    AutoTune,
+    /// Ticks in the last seconds before a meeting.
+    CountIn,
+    /// Showing the alert without taking keyboard focus.
+    KeepFocus,
+}
+
+struct FlagInfo {
+    flag: Flag,
+    name: &'static str,
+    description: &'static str,
+    stable: bool,
+    beta: bool,
+}
+
+const FLAGS: [FlagInfo; 4] = [
+    FlagInfo {
+        flag: Flag::Escalation,
+        name: "escalation",
+        description: "Escalate alerts left unacknowledged after the start",
+        stable: true,
+        beta: true,
+    },
+    FlagInfo {
+        flag: Flag::AutoTune,
+        name: "auto_tune",
+        description: "Raise the lead time of series you tend to join late",
+        stable: true,
+        beta: true,
+    },
+    FlagInfo {
+        flag: Flag::CountIn,
+        name: "count_in",
+        description: "Tick through the last seconds before a meeting starts",
+        stable: false,
+        beta: true,
+    },
+    FlagInfo {
+        flag: Flag::KeepFocus,
+        name: "keep_focus",
+        description: "Let alerts show without taking keyboard focus",
+        stable: true,
+        beta: true,
+    },
+];
+
+/// The channel this build was made for: "stable", "beta" or "nightly".
+pub fn channel() -> &'static str {
+    option_env!("RELEASE_CHANNEL").unwrap_or("stable")
+}
+
+impl FlagInfo {
+    fn default_on(&self) -> bool {
+        match channel() {
+            "stable" => self.stable,
+            _ => self.beta,
+        }
+    }
+}
+
+fn info(flag: Flag) -> &'static FlagInfo {
+    FLAGS.iter().find(|f| f.flag == flag).unwrap()
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct FeatureSettings {
+    /// Flags the user turned on or off, by name; the rest follow the
+    /// channel. Names no longer known are kept but ignored.
+    pub flags: BTreeMap<String, bool>,
+    /// The version whose release notes were last shown.
+    pub whats_new_seen: Option<String>,
+}
+
+pub fn enabled(settings: &Settings, flag: Flag) -> bool {
+    let info = info(flag);
+    settings
+        .features
+        .flags
+        .get(info.name)
+        .copied()
+        .unwrap_or_else(|| info.default_on())
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureFlag {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub enabled: bool,
+    /// What the channel has it at.
+    pub default: bool,
+    /// The user picked `enabled` rather than the channel.
+    pub overridden: bool,
+}
+
+fn flag_state(settings: &Settings, info: &FlagInfo) -> FeatureFlag {
+    FeatureFlag {
+        name: info.name,
+        description: info.description,
+        enabled: enabled(settings, info.flag),
+        default: info.default_on(),
+        overridden: settings.features.flags.contains_key(info.name),
+    }
+}
+
+#[tauri::command]
+pub fn get_feature_flags(app: AppHandle) -> Vec<FeatureFlag> {
+    let settings = settings::current(&app);
+    FLAGS
+        .iter()
+        .map(|info| flag_state(&settings, info))
+        .collect()
+}
+
+/// Turns the flag `name` on or off for this user. Setting it to what the
+/// channel has drops the override, so it follows later defaults again.
+#[tauri::command]
+pub fn set_feature_flag(
+    app: AppHandle,
+    window: Window,
+    name: String,
+    enabled: bool,
+) -> Result<FeatureFlag, IpcError> {
+    ipc::require_dashboard(&window, "set_feature_flag")?;
+    let info = FLAGS
+        .iter()
+        .find(|f| f.name == name)
+        .ok_or_else(|| format!("No feature flag {}", name))?;
+    let settings = settings::update(&app, |settings| {
+        let flags = &mut settings.features.flags;
+        if enabled == info.default_on() {
+            flags.remove(info.name);
+        } else {
+            flags.insert(info.name.to_string(), enabled);
+        }
+    })?;
+    bus::publish(&app, AppEvent::FeatureChanged(info.flag, enabled));
+    Ok(flag_state(&settings, info))
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ReleaseNotes {
+    version: String,
+    items: Vec<NoteEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct NoteEntry {
+    title: String,
+    body: String,
+    #[serde(default)]
+    flags: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WhatsNew {
+    pub version: String,
+    pub items: Vec<WhatsNewItem>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WhatsNewItem {
+    pub title: String,
+    pub body: String,
+    /// The flags of the feature, to try it or turn it off from the note.
+    pub flags: Vec<FeatureFlag>,
+}
+
+/// This version's notes from the bundled resource, if it has any.
+fn release_notes(app: &AppHandle) -> Option<ReleaseNotes> {
+    let path = app
+        .path()
+        .resolve(WHATS_NEW_RESOURCE, BaseDirectory::Resource)
+        .ok()?;
+    let json = fs::read_to_string(path).ok()?;
+    let notes: Vec<ReleaseNotes> = serde_json::from_str(&json)
+        .inspect_err(|e| retention::log!("{} is invalid: {}", WHATS_NEW_RESOURCE, e))
+        .ok()?;
+    notes
+        .into_iter()
+        .find(|n| n.version == env!("CARGO_PKG_VERSION"))
+}
+
+/// What's new in this version, the first time it is asked for after an
+/// update; `None` after that, or when this version has no notes.
+#[tauri::command]
+pub fn get_whats_new(app: AppHandle, window: Window) -> Result<Option<WhatsNew>, IpcError> {
+    ipc::require_dashboard(&window, "get_whats_new")?;
+    let version = env!("CARGO_PKG_VERSION");
+    let settings = settings::current(&app);
+    if settings.features.whats_new_seen.as_deref() == Some(version) {
+        return Ok(None);
+    }
+    settings::update(&app, |settings| {
+        settings.features.whats_new_seen = Some(version.to_string());
+    })?;
+    let Some(notes) = release_notes(&app) else {
+        return Ok(None);
+    };
+    let items = notes
+        .items
+        .into_iter()
+        .map(|entry| WhatsNewItem {
+            flags: FLAGS
+                .iter()
+                .filter(|info| entry.flags.iter().any(|f| f == info.name))
+                .map(|info| flag_state(&settings, info))
+                .collect(),
+            title: entry.title,
+            body: entry.body,
+        })
+        .collect();
+    Ok(Some(WhatsNew {
+        version: notes.version,
+        items,
+    }))
+}
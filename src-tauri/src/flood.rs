@@ -0,0 +1,104 @@
+use chrono::{DateTime, Duration, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// --- Alert Floods ---
+//
+// Importing a conference schedule can bring dozens of meetings alerting
+// within minutes. Once more than `max_alerts` fullscreen alerts come due
+// within `window_minutes`, the rest are collapsed into the alert that is
+// showing, which lists them with a mute button each; acknowledging it covers
+// all of them. A few overlapping meetings still queue or merge per
+// `concurrent_alerts`.
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct FloodSettings {
+    pub enabled: bool,
+    /// More alerts than this within the window make a flood.
+    pub max_alerts: usize,
+    pub window_minutes: i64,
+}
+
+impl Default for FloodSettings {
+    fn default() -> Self {
+        FloodSettings {
+            enabled: true,
+            max_alerts: 3,
+            window_minutes: 10,
+        }
+    }
+}
+
+/// When the recent fullscreen alerts came due, oldest first.
+#[derive(Default)]
+pub struct Burst {
+    due: VecDeque<DateTime<Local>>,
+}
+
+impl Burst {
+    /// Notes an alert coming due; true while it is part of a flood.
+    pub fn note(&mut self, settings: &FloodSettings, now: DateTime<Local>) -> bool {
+        let cutoff = now - Duration::minutes(settings.window_minutes);
+        while self.due.front().is_some_and(|due| *due <= cutoff) {
+            self.due.pop_front();
+        }
+        self.due.push_back(now);
+        settings.enabled && self.due.len() > settings.max_alerts
+    }
+
+    /// Alerts that came due within the window as of the last `note`.
+    pub fn count(&self) -> usize {
+        self.due.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::at;
+
+    #[test]
+    fn more_than_the_limit_within_the_window_is_a_flood() {
+        let settings = FloodSettings::default();
+        let mut burst = Burst::default();
+        let now = at("2026-03-02 09:00");
+        let flooded: Vec<bool> = (0..50)
+            .map(|i| burst.note(&settings, now + Duration::seconds(i)))
+            .collect();
+        assert_eq!(flooded[..3], [false; 3]);
+        assert!(flooded[3..].iter().all(|&f| f));
+        assert_eq!(burst.count(), 50);
+    }
+
+    #[test]
+    fn the_window_rolls() {
+        let settings = FloodSettings::default();
+        let mut burst = Burst::default();
+        // One alert every four minutes never floods.
+        for i in 0..20 {
+            assert!(!burst.note(&settings, at("2026-03-02 09:00") + Duration::minutes(4 * i)));
+        }
+        assert_eq!(burst.count(), 3);
+
+        // Three, then a fourth exactly a window after the first.
+        let mut burst = Burst::default();
+        for time in ["2026-03-02 10:00", "2026-03-02 10:01", "2026-03-02 10:02"] {
+            assert!(!burst.note(&settings, at(time)));
+        }
+        assert!(!burst.note(&settings, at("2026-03-02 10:10")));
+        assert!(burst.note(&settings, at("2026-03-02 10:10")));
+    }
+
+    #[test]
+    fn nothing_floods_while_it_is_off() {
+        let settings = FloodSettings {
+            enabled: false,
+            ..FloodSettings::default()
+        };
+        let mut burst = Burst::default();
+        let now = at("2026-03-02 09:00");
+        assert!((0..50).all(|_| !burst.note(&settings, now)));
+    }
+}
@@ -0,0 +1,65 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::features::{self, Flag};
+use crate::settings;
+
+// --- Alert Focus ---
+//
+// An alert can appear mid-sentence. It either leaves keyboard focus where it
+// was, or takes it but ignores dismiss/snooze/join for a short grace period,
+// so keystrokes meant for another app can't act on it. A click on the alert
+// ends the grace at once.
+
+/// Returned by commands ignored during the grace period; the alert stays up.
+pub const GRACE_ERROR: &str = "focus_grace";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusMode {
+    /// Activate the app and focus the alert.
+    #[default]
+    Take,
+    /// Show the alert above everything without taking keyboard focus.
+    Keep,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct FocusSettings {
+    pub mode: FocusMode,
+    /// How long after the alert takes focus its actions are ignored.
+    pub grace_ms: u64,
+}
+
+/// When the current grace period ends.
+static GRACE_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Whether showing the alert should take keyboard focus; starts the grace
+/// period if so.
+pub fn take_focus(app: &AppHandle) -> bool {
+    let settings = settings::current(app);
+    let focus = &settings.alert_focus;
+    if focus.mode == FocusMode::Keep && features::enabled(&settings, Flag::KeepFocus) {
+        return false;
+    }
+    *GRACE_UNTIL.lock().unwrap() = Some(Instant::now() + Duration::from_millis(focus.grace_ms));
+    true
+}
+
+/// `Err(GRACE_ERROR)` while the alert's actions are still being ignored.
+pub fn check_grace() -> Result<(), String> {
+    match *GRACE_UNTIL.lock().unwrap() {
+        Some(until) if Instant::now() < until => Err(GRACE_ERROR.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Sent by the alert on any click, before the click's own command.
+#[tauri::command]
+pub fn end_focus_grace() {
+    *GRACE_UNTIL.lock().unwrap() = None;
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::env;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+use crate::calendar_watch::{self, WatcherStatus};
+use crate::changes::{self, ChangeLogEntry};
+use crate::inbound::{self, InboundStatus};
+use crate::mqtt::{self, MqttStatus};
+use crate::renderer::{self, CrashStats};
+use crate::scheduler::{PlannedAlert, SchedulerHealth, SchedulerState};
+use crate::selftest::{self, SelfTestReport};
+use crate::{calendar, settings, simulate, sources, tray, webhooks};
+
+// --- Health Report ---
+//
+// One JSON snapshot of every subsystem, meant to be pasted into bug reports.
+// Webhook URLs keep only their host, emails only their domain and paths drop
+// the home folder, so the report carries no tokens, addresses or user names.
+
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    pub app_version: String,
+    /// EventKit authorization, e.g. "full_access" or "denied".
+    pub calendar_permission: String,
+    pub providers: Vec<String>,
+    /// The helper that refetches when the calendar changes.
+    pub calendar_watch: WatcherStatus,
+    pub scheduler: SchedulerHealth,
+    pub tray_alive: bool,
+    /// When the tray icon was last rebuilt after vanishing.
+    pub tray_rebuilt_at: Option<DateTime<Local>>,
+    /// Webview content processes that died, by window.
+    pub renderer_crashes: CrashStats,
+    pub webhooks: Vec<WebhookHealth>,
+    pub inbound: InboundStatus,
+    pub mqtt: MqttStatus,
+    /// What the latest fetches changed, newest first.
+    pub calendar_changes: Vec<ChangeLogEntry>,
+    pub settings_path: Option<String>,
+    pub settings_writable: bool,
+    /// The latest self-test, startup or on demand.
+    pub self_test: Option<SelfTestReport>,
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookHealth {
+    pub url: String,
+    pub enabled: bool,
+    pub consecutive_failures: u32,
+    pub last_success_at: Option<DateTime<Local>>,
+    pub last_error: Option<String>,
+}
+
+/// "https://hooks.example.com/…": the path and query often hold tokens.
+fn mask_url(url: &str) -> String {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("", url));
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = host.rsplit('@').next().unwrap_or_default();
+    let prefix = if scheme.is_empty() {
+        String::new()
+    } else {
+        format!("{}://", scheme)
+    };
+    if host.len() == rest.len() {
+        format!("{}{}", prefix, host)
+    } else {
+        format!("{}{}/…", prefix, host)
+    }
+}
+
+/// Replaces anything that looks like an email address with "…@domain".
+fn mask_emails(text: &str) -> String {
+    text.split(' ')
+        .map(|word| match word.split_once('@') {
+            Some((local, domain)) if !local.is_empty() && domain.contains('.') => {
+                format!("…@{}", domain)
+            }
+            _ => word.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// "~/Library/…" instead of the user's home folder.
+fn mask_home(path: &Path) -> String {
+    let home = env::var("HOME").unwrap_or_default();
+    match path.strip_prefix(&home) {
+        Ok(rest) if !home.is_empty() => format!("~/{}", rest.display()),
+        _ => path.display().to_string(),
+    }
+}
+
+#[tauri::command]
+pub async fn get_health(app: AppHandle) -> Result<HealthReport, String> {
+    let calendar_permission = tauri::async_runtime::spawn_blocking(calendar::permission_state)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let settings = settings::current(&app);
+    let scheduler = {
+        let state = app.state::<SchedulerState>();
+        let scheduler = state.0.lock().unwrap();
+        scheduler.health(simulate::now(), &settings)
+    };
+    let statuses = webhooks::statuses(&app);
+    let webhooks = settings
+        .webhooks
+        .iter()
+        .map(|hook| {
+            let status = statuses.get(&hook.url);
+            WebhookHealth {
+                url: mask_url(&hook.url),
+                enabled: hook.enabled,
+                consecutive_failures: status.map_or(0, |s| s.consecutive_failures),
+                last_success_at: status.and_then(|s| s.last_success_at),
+                last_error: status
+                    .and_then(|s| s.last_error.as_deref())
+                    .map(mask_emails),
+            }
+        })
+        .collect();
+
+    Ok(HealthReport {
+        app_version: app.package_info().version.to_string(),
+        calendar_permission,
+        providers: sources::enabled_names(&settings),
+        calendar_watch: calendar_watch::status(),
+        scheduler: SchedulerHealth {
+            active_alert: scheduler.active_alert.as_deref().map(mask_emails),
+            next_alert: scheduler.next_alert.map(|planned| PlannedAlert {
+                title: mask_emails(&planned.title),
+                ..planned
+            }),
+            ..scheduler
+        },
+        tray_alive: tray::is_alive(&app),
+        tray_rebuilt_at: tray::rebuilt_at(),
+        renderer_crashes: renderer::stats(),
+        webhooks,
+        inbound: inbound::status(),
+        mqtt: mqtt::status(),
+        calendar_changes: changes::recent()
+            .into_iter()
+            .map(|entry| ChangeLogEntry {
+                lines: entry.lines.iter().map(|line| mask_emails(line)).collect(),
+                ..entry
+            })
+            .collect(),
+        settings_path: settings::settings_path(&app).map(|p| mask_home(&p)),
+        settings_writable: settings::is_writable(&app),
+        self_test: selftest::report(&app),
+    })
+}
@@ -0,0 +1,268 @@
+use serde::de::DeserializeOwned;
+use serde_json::{Deserializer, Value};
+
+use crate::calendar::{CalendarEvent, CalendarInfo};
+use crate::retention;
+use crate::untrusted;
+
+// --- Swift Helper Output ---
+//
+// What a helper prints isn't trusted as is: Swift can print runtime
+// warnings, a permission prompt can add noise, and a helper killed halfway
+// leaves half a line. The helpers print their JSON between two sentinel
+// lines, and the payload is taken from between the last end line and the
+// first start line before it; only whole lines count as sentinels, so an
+// event titled like one can't move them. Output from an older helper
+// without them falls back to the last line that is a whole JSON value.
+// Whatever went to stderr is logged as diagnostics. Events are then
+// checked one by one, so a malformed one is skipped (and counted in the
+// log) instead of failing the whole fetch. A truncated array isn't
+// salvaged: a fetch missing its tail would read as cancelled meetings.
+
+/// Printed by the helpers around their JSON; see `emit` in the prelude.
+const BEGIN: &str = "<<<IN-YOUR-FACE-JSON";
+const END: &str = "IN-YOUR-FACE-JSON>>>";
+
+/// Far past any real calendar; a helper printing more than this is broken.
+const MAX_OUTPUT_BYTES: usize = 16 * 1024 * 1024;
+const MAX_EVENTS: usize = 20_000;
+const MAX_TITLE_CHARS: usize = 1_000;
+/// Locations, organizers, URLs and attendee names and addresses.
+const MAX_FIELD_CHARS: usize = 4_000;
+const MAX_DESCRIPTION_CHARS: usize = 64_000;
+const MAX_ATTENDEES: usize = 1_000;
+
+/// Logs the helper's stderr, a line at a time.
+pub fn log_stderr(file_name: &str, stderr: &[u8]) {
+    for line in String::from_utf8_lossy(stderr).lines() {
+        let line = line.trim();
+        if !line.is_empty() {
+            retention::log!("{}: {}", file_name, line);
+        }
+    }
+}
+
+/// The helper's stdout as text, refused if it is implausibly large.
+pub fn stdout_text(stdout: &[u8]) -> Result<String, String> {
+    if stdout.len() > MAX_OUTPUT_BYTES {
+        return Err(format!("Helper output too large ({} bytes)", stdout.len()));
+    }
+    let text = String::from_utf8_lossy(stdout);
+    Ok(text.trim_start_matches('\u{feff}').trim().to_string())
+}
+
+/// The last line of plain-text output, e.g. "added"; anything printed
+/// before it is noise.
+pub fn last_line(output: &str) -> &str {
+    output
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .unwrap_or("")
+}
+
+fn is_whole_json(text: &str) -> bool {
+    let mut values = Deserializer::from_str(text).into_iter::<Value>();
+    matches!(values.next(), Some(Ok(_))) && values.next().is_none()
+}
+
+fn is_sentinel(line: &str, sentinel: &str) -> bool {
+    line.trim().trim_start_matches('\u{feff}') == sentinel
+}
+
+/// The JSON in the output: between the last end line and the first start
+/// line before it, else the last line that is a whole JSON value.
+pub fn payload(output: &str) -> Result<&str, String> {
+    let output = output.trim_start_matches('\u{feff}');
+    // Each line with the offsets of its start and of the next line.
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in output.split_inclusive('\n') {
+        lines.push((offset, offset + line.len(), line));
+        offset += line.len();
+    }
+    if let Some(end) = lines.iter().rposition(|(_, _, l)| is_sentinel(l, END)) {
+        let (_, from, _) = lines[..end]
+            .iter()
+            .find(|(_, _, l)| is_sentinel(l, BEGIN))
+            .ok_or("Helper output has an end marker but no start")?;
+        return Ok(output[*from..lines[end].0]
+            .trim()
+            .trim_start_matches('\u{feff}'));
+    }
+    output
+        .lines()
+        .map(|line| line.trim().trim_start_matches('\u{feff}'))
+        .filter(|line| line.starts_with('[') || line.starts_with('{'))
+        .rfind(|line| is_whole_json(line))
+        .ok_or("No complete JSON in the helper's output".to_string())
+}
+
+/// The payload as one value, e.g. an event's details.
+pub fn parse<T: DeserializeOwned>(output: &str) -> Result<T, String> {
+    serde_json::from_str(payload(output)?).map_err(|e| e.to_string())
+}
+
+/// The payload as an array, each item parsed on its own; the ones that
+/// don't parse are left out, with how many and the first reason in the log.
+pub fn parse_items<T: DeserializeOwned>(
+    file_name: &str,
+    output: &str,
+    check: impl Fn(T) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    let items: Vec<Value> =
+        serde_json::from_str(payload(output)?).map_err(|e| format!("Not a JSON array: {}", e))?;
+    if items.len() > MAX_EVENTS {
+        return Err(format!("Implausibly many items ({})", items.len()));
+    }
+    let count = items.len();
+    let mut first_error = None;
+    let parsed: Vec<T> = items
+        .into_iter()
+        .filter_map(|item| {
+            let result = serde_json::from_value(item)
+                .map_err(|e| e.to_string())
+                .and_then(&check);
+            result
+                .map_err(|e| {
+                    first_error.get_or_insert(e);
+                })
+                .ok()
+        })
+        .collect();
+    if let Some(error) = first_error {
+        retention::log!(
+            "{}: skipped {} of {} malformed items, first: {}",
+            file_name,
+            count - parsed.len(),
+            count,
+            error
+        );
+    }
+    Ok(parsed)
+}
+
+/// An event from a helper, with dates that parse and its text cleaned as in
+/// `untrusted`.
+pub fn check_event(mut event: CalendarEvent) -> Result<CalendarEvent, String> {
+    let (Some(start), Some(end)) = (event.start_time(), event.end_time()) else {
+        return Err(format!(
+            "unreadable dates \"{}\" to \"{}\"",
+            event.start, event.end
+        ));
+    };
+    if end < start {
+        return Err("ends before it starts".to_string());
+    }
+    // An id can't be shortened without becoming another event's.
+    if event.id.chars().count() > MAX_FIELD_CHARS {
+        return Err("implausibly long id".to_string());
+    }
+    untrusted::clean_line(&mut event.title, MAX_TITLE_CHARS);
+    untrusted::clean_line(&mut event.calendar, MAX_FIELD_CHARS);
+    untrusted::clean_line_option(&mut event.location, MAX_FIELD_CHARS);
+    untrusted::clean_line_option(&mut event.url, MAX_FIELD_CHARS);
+    untrusted::clean_line_option(&mut event.organizer, MAX_FIELD_CHARS);
+    untrusted::clean_text_option(&mut event.description, MAX_DESCRIPTION_CHARS);
+    untrusted::clean_line_option(&mut event.time_zone, MAX_FIELD_CHARS);
+    event.attendees.truncate(MAX_ATTENDEES);
+    for attendee in &mut event.attendees {
+        untrusted::clean_line_option(&mut attendee.name, MAX_FIELD_CHARS);
+        untrusted::clean_line_option(&mut attendee.email, MAX_FIELD_CHARS);
+    }
+    Ok(event)
+}
+
+/// A calendar from a helper, its names cleaned like an event's, so that
+/// they still match the calendar names of its events.
+pub fn check_calendar(mut calendar: CalendarInfo) -> Result<CalendarInfo, String> {
+    untrusted::clean_line(&mut calendar.title, MAX_FIELD_CHARS);
+    untrusted::clean_line(&mut calendar.source, MAX_FIELD_CHARS);
+    Ok(calendar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event_json(title: &str) -> String {
+        serde_json::json!({
+            "title": title,
+            "start": "2026-03-02T09:00:00+01:00",
+            "end": "2026-03-02T09:30:00+01:00",
+            "location": null,
+            "description": null,
+            "url": null,
+            "isAllDay": false,
+        })
+        .to_string()
+    }
+
+    fn wrapped(json: &str) -> String {
+        format!("{}\n{}\n{}\n", BEGIN, json, END)
+    }
+
+    fn titles(output: &str) -> Vec<String> {
+        parse_items::<CalendarEvent>("test", output, check_event)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.title)
+            .collect()
+    }
+
+    #[test]
+    fn payload_skips_interleaved_warnings() {
+        let output = format!(
+            "[\"not this\"]\nwarning: CoreData: something\n{}2026-03-02 EventKit noise\n[]\n",
+            wrapped(&format!("[{}]", event_json("Standup")))
+        );
+        assert_eq!(titles(&output), ["Standup"]);
+    }
+
+    #[test]
+    fn payload_strips_a_bom() {
+        let output = format!(
+            "\u{feff}{}\n\u{feff}[{}]\n{}\n",
+            BEGIN,
+            event_json("Review"),
+            END
+        );
+        assert_eq!(titles(&output), ["Review"]);
+    }
+
+    #[test]
+    fn truncated_array_is_an_error() {
+        let full = format!("[{}]", event_json("Standup"));
+        let output = wrapped(&full[..full.len() / 2]);
+        assert!(parse_items::<CalendarEvent>("test", &output, check_event).is_err());
+        // Cut off before the end marker, nothing complete is left.
+        let output = format!("{}\n{}", BEGIN, &full[..full.len() / 2]);
+        assert!(payload(&output).is_err());
+    }
+
+    #[test]
+    fn no_markers_falls_back_to_the_last_json_line() {
+        let output = format!(
+            "[]\nsome log line\n[{}]\ntrailing noise\n",
+            event_json("Old helper")
+        );
+        assert_eq!(titles(&output), ["Old helper"]);
+        assert!(payload("no json here\n").is_err());
+    }
+
+    #[test]
+    fn markers_inside_a_title_are_text() {
+        let tricky = format!("{} {} [\"x\"]", END, BEGIN);
+        let output = wrapped(&format!(
+            "[{}, {}]",
+            event_json(&tricky),
+            event_json("Next")
+        ));
+        assert_eq!(titles(&output), [tricky, "Next".to_string()]);
+    }
+
+    #[test]
+    fn end_without_begin_is_an_error() {
+        assert!(payload(&format!("[]\n{}\n", END)).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::actions::StepResult;
+use crate::calendar::CalendarEvent;
+use crate::rules::TraceStep;
+use crate::{lateness, settings};
+
+// --- Alert History (persisted as JSON in the app data dir) ---
+
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryKind {
+    Fired,
+    Dismissed,
+    Snoozed,
+    Joined,
+    Escalated,
+    /// Auto-dismissed without being acknowledged.
+    Expired,
+    /// Found already past the catch-up grace, so it never alerted.
+    Missed,
+    /// Disappeared from the calendar shortly before it started.
+    Cancelled,
+    /// Came due, but the rules made it silent.
+    Suppressed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HistoryEntry {
+    pub event_id: String,
+    pub title: String,
+    pub start: String,
+    pub kind: HistoryKind,
+    pub at: DateTime<Local>,
+    /// For dismissals and joins: seconds after the start (negative if
+    /// early). Only recorded while punctuality tracking is on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub late_seconds: Option<i64>,
+    /// For missed and suppressed alerts: how the alert was decided.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trace: Vec<TraceStep>,
+    /// For dismissals and joins: how the rule's action chain went.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<StepResult>,
+}
+
+/// How late meetings get acknowledged, and when that's worth a hint.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PunctualitySettings {
+    pub enabled: bool,
+    /// Series joined or dismissed a median of more than this late get a
+    /// suggestion.
+    pub late_threshold_minutes: i64,
+    /// Occurrences needed before a series is judged.
+    pub min_samples: usize,
+}
+
+impl Default for PunctualitySettings {
+    fn default() -> Self {
+        PunctualitySettings {
+            enabled: true,
+            late_threshold_minutes: 2,
+            min_samples: 3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PunctualityInsight {
+    pub event_id: String,
+    pub title: String,
+    pub samples: usize,
+    pub median_late_minutes: i64,
+    /// e.g. "You join 'Weekly 1:1' a median 4 minutes late".
+    pub message: String,
+    /// Extra lead time that would have covered the median.
+    pub suggested_extra_lead_minutes: i64,
+}
+
+pub struct HistoryState(pub Mutex<Vec<HistoryEntry>>);
+
+fn history_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("history.json"))
+}
+
+pub fn load(app: &AppHandle) -> Vec<HistoryEntry> {
+    history_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn persist(app: &AppHandle, entries: &[HistoryEntry]) {
+    let Some(path) = history_path(app) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(entries) {
+        let _ = fs::write(path, json);
+    }
+}
+
+pub fn record(app: &AppHandle, event: &CalendarEvent, kind: HistoryKind) {
+    record_traced(app, event, kind, Vec::new());
+}
+
+/// `record`, keeping the trace of how the alert was decided.
+pub fn record_traced(
+    app: &AppHandle,
+    event: &CalendarEvent,
+    kind: HistoryKind,
+    trace: Vec<TraceStep>,
+) {
+    let now = Local::now();
+    let tracked = matches!(kind, HistoryKind::Dismissed | HistoryKind::Joined)
+        && settings::current(app).punctuality.enabled;
+    let late_seconds = event
+        .start_time()
+        .filter(|_| tracked)
+        .map(|start| (now - start).num_seconds());
+
+    let state = app.state::<HistoryState>();
+    let mut entries = state.0.lock().unwrap();
+    entries.push(HistoryEntry {
+        event_id: event.id.clone(),
+        title: event.title.clone(),
+        start: event.start.clone(),
+        kind,
+        at: now,
+        late_seconds,
+        trace,
+        actions: Vec::new(),
+    });
+    if entries.len() > MAX_ENTRIES {
+        let overflow = entries.len() - MAX_ENTRIES;
+        entries.drain(..overflow);
+    }
+    persist(app, &entries);
+    drop(entries);
+    if let Some(late) = late_seconds.filter(|_| kind == HistoryKind::Joined) {
+        lateness::joined(app, event, late);
+    }
+}
+
+/// Writes the entries again, once any write in progress is done; for
+/// shutdown.
+pub fn flush(app: &AppHandle) {
+    let state = app.state::<HistoryState>();
+    let entries = state.0.lock().unwrap();
+    persist(app, &entries);
+}
+
+/// Drops the entries from before `before` and writes the rest again,
+/// returning how many were dropped.
+pub fn prune(app: &AppHandle, before: DateTime<Local>) -> usize {
+    let state = app.state::<HistoryState>();
+    let mut entries = state.0.lock().unwrap();
+    let count = entries.len();
+    entries.retain(|e| e.at >= before);
+    persist(app, &entries);
+    count - entries.len()
+}
+
+/// Adds the results of an action chain to the latest `kind` entry of the
+/// occurrence, once the chain has run.
+pub fn attach_actions(
+    app: &AppHandle,
+    event: &CalendarEvent,
+    kind: HistoryKind,
+    results: Vec<StepResult>,
+) {
+    let state = app.state::<HistoryState>();
+    let mut entries = state.0.lock().unwrap();
+    let Some(entry) = entries
+        .iter_mut()
+        .rev()
+        .find(|e| e.event_id == event.id && e.start == event.start && e.kind == kind)
+    else {
+        return;
+    };
+    entry.actions = results;
+    persist(app, &entries);
+}
+
+/// Moves the entries of one event id to another, e.g. when a recurring
+/// series was recreated under a new id.
+pub fn rename_event(app: &AppHandle, from: &str, to: &str) {
+    let state = app.state::<HistoryState>();
+    let mut entries = state.0.lock().unwrap();
+    for entry in entries.iter_mut().filter(|e| e.event_id == from) {
+        entry.event_id = to.to_string();
+    }
+    persist(app, &entries);
+}
+
+/// Whether this occurrence has alerted before, e.g. in an earlier run.
+pub fn was_alerted(app: &AppHandle, event: &CalendarEvent) -> bool {
+    let state = app.state::<HistoryState>();
+    let entries = state.0.lock().unwrap();
+    entries.iter().any(|e| {
+        e.event_id == event.id
+            && e.start == event.start
+            && !matches!(e.kind, HistoryKind::Missed | HistoryKind::Cancelled)
+    })
+}
+
+fn median(values: &mut [i64]) -> i64 {
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2
+    } else {
+        values[mid]
+    }
+}
+
+/// Series (by event id, which recurring occurrences share) whose median
+/// lateness is above the threshold, latest first.
+pub fn punctuality_insights(
+    entries: &[HistoryEntry],
+    punctuality: &PunctualitySettings,
+) -> Vec<PunctualityInsight> {
+    let mut series: Vec<(&HistoryEntry, Vec<i64>)> = Vec::new();
+    for entry in entries.iter().rev() {
+        let Some(late) = entry.late_seconds else {
+            continue;
+        };
+        match series
+            .iter_mut()
+            .find(|(e, _)| e.event_id == entry.event_id)
+        {
+            Some((_, samples)) => samples.push(late),
+            None => series.push((entry, vec![late])),
+        }
+    }
+
+    series
+        .into_iter()
+        .filter(|(_, samples)| samples.len() >= punctuality.min_samples.max(1))
+        .filter_map(|(latest, mut samples)| {
+            let minutes = median(&mut samples) / 60;
+            if minutes <= punctuality.late_threshold_minutes {
+                return None;
+            }
+            let verb = if latest.kind == HistoryKind::Joined {
+                "join"
+            } else {
+                "get to"
+            };
+            Some(PunctualityInsight {
+                event_id: latest.event_id.clone(),
+                title: latest.title.clone(),
+                samples: samples.len(),
+                median_late_minutes: minutes,
+                message: format!(
+                    "You {} '{}' a median {} minutes late",
+                    verb, latest.title, minutes
+                ),
+                suggested_extra_lead_minutes: minutes,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_punctuality_insights(app: AppHandle) -> Vec<PunctualityInsight> {
+    let punctuality = settings::current(&app).punctuality;
+    if !punctuality.enabled {
+        return Vec::new();
+    }
+    let state = app.state::<HistoryState>();
+    let entries = state.0.lock().unwrap();
+    punctuality_insights(&entries, &punctuality)
+}
+
+#[tauri::command]
+pub fn get_alert_history(state: State<HistoryState>) -> Vec<HistoryEntry> {
+    state.0.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::at;
+
+    /// Occurrences of one series on consecutive days from Mar 2, each
+    /// acknowledged `late` minutes after its start.
+    fn series(id: &str, title: &str, kind: HistoryKind, late: &[i64]) -> Vec<HistoryEntry> {
+        late.iter()
+            .enumerate()
+            .map(|(day, minutes)| {
+                let start = at(&format!("2026-03-{:02} 10:00", day + 2));
+                HistoryEntry {
+                    event_id: id.to_string(),
+                    title: title.to_string(),
+                    start: start.to_rfc3339(),
+                    kind,
+                    at: start + chrono::Duration::minutes(*minutes),
+                    late_seconds: Some(minutes * 60),
+                    trace: Vec::new(),
+                    actions: Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    fn by_time(mut entries: Vec<HistoryEntry>) -> Vec<HistoryEntry> {
+        entries.sort_by_key(|e| e.at);
+        entries
+    }
+
+    #[test]
+    fn suggests_more_lead_time_for_series_joined_late() {
+        let entries = by_time(
+            [
+                series(
+                    "one-on-one",
+                    "Weekly 1:1",
+                    HistoryKind::Joined,
+                    &[4, 6, 3, 5],
+                ),
+                series("standup", "Standup", HistoryKind::Joined, &[1, 2, 1, 9]),
+                series("review", "Review", HistoryKind::Dismissed, &[3, 9, 3]),
+            ]
+            .concat(),
+        );
+        let insights = punctuality_insights(&entries, &PunctualitySettings::default());
+        let found: Vec<(&str, usize, i64)> = insights
+            .iter()
+            .map(|i| (i.event_id.as_str(), i.samples, i.median_late_minutes))
+            .collect();
+        // The latest occurrence first: the 1:1 ran a day longer.
+        assert_eq!(found, [("one-on-one", 4, 4), ("review", 3, 3)]);
+        assert_eq!(
+            insights[0].message,
+            "You join 'Weekly 1:1' a median 4 minutes late"
+        );
+        assert_eq!(insights[0].suggested_extra_lead_minutes, 4);
+        assert_eq!(
+            insights[1].message,
+            "You get to 'Review' a median 3 minutes late"
+        );
+    }
+
+    #[test]
+    fn judges_a_series_only_on_enough_samples_above_the_threshold() {
+        let settings = PunctualitySettings::default();
+        // Two samples aren't enough.
+        let board = series("board", "Board", HistoryKind::Joined, &[10, 12]);
+        assert!(punctuality_insights(&board, &settings).is_empty());
+        // A median at the threshold isn't above it.
+        let sync = series("sync", "Sync", HistoryKind::Joined, &[2, 2, 8]);
+        assert!(punctuality_insights(&sync, &settings).is_empty());
+        // Entries without a lateness don't count.
+        let mut fired = series("board", "Board", HistoryKind::Fired, &[0, 0, 0]);
+        for entry in &mut fired {
+            entry.late_seconds = None;
+        }
+        let entries = by_time([board, fired].concat());
+        assert!(punctuality_insights(&entries, &settings).is_empty());
+
+        let stricter = PunctualitySettings {
+            min_samples: 2,
+            late_threshold_minutes: 1,
+            ..settings
+        };
+        let insights = punctuality_insights(&entries, &stricter);
+        assert_eq!(insights.len(), 1);
+        assert_eq!(insights[0].median_late_minutes, 11);
+    }
+
+    #[test]
+    fn early_acknowledgements_pull_the_median_down() {
+        let entries = series("retro", "Retro", HistoryKind::Joined, &[-5, -3, 4, 20]);
+        assert!(punctuality_insights(&entries, &PunctualitySettings::default()).is_empty());
+        assert_eq!(median(&mut [-300, -180, 240, 1200]), 30);
+    }
+}
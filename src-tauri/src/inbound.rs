@@ -0,0 +1,354 @@
+use chrono::{DateTime, Duration, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration as StdDuration;
+use tauri::{AppHandle, Manager};
+
+use crate::calendar::CalendarEvent;
+use crate::scheduler::SchedulerState;
+use crate::{settings, shutdown, simulate, untrusted};
+
+// --- Inbound Reminders (opt-in listen mode) ---
+//
+// For reminders sent from elsewhere, e.g. a phone posting to an ntfy.sh
+// topic: the app keeps a streaming GET open to the configured URL and turns
+// each message, `{title, body, fire_at?}` as a JSON line or an SSE `data:`
+// line, into a reminder that alerts through the normal pipeline at
+// `fire_at`, or right away. ntfy's own JSON stream works as is; a message
+// whose text is such an object is read as one. The bearer token comes from
+// the Keychain, never from the settings file. Dropped connections are retried
+// with backoff; messages are deduplicated by id, size-checked and cleaned
+// like event text (see `untrusted`).
+
+/// Longer lines are skipped.
+const MAX_MESSAGE_BYTES: usize = 4096;
+const MAX_TITLE_CHARS: usize = 200;
+const MAX_BODY_CHARS: usize = 1000;
+/// Reminders further out than this are refused.
+const MAX_AHEAD_DAYS: i64 = 7;
+/// How long an inbound reminder's event lasts, for the alert's countdown.
+const REMINDER_MINUTES: i64 = 15;
+const MIN_BACKOFF_SECONDS: u64 = 2;
+const MAX_BACKOFF_SECONDS: u64 = 300;
+/// With no keepalive for this long the connection counts as dead; ntfy
+/// sends one every 45 seconds.
+const READ_TIMEOUT_SECONDS: u64 = 90;
+/// How often a disabled channel checks the settings again.
+const IDLE_SECONDS: u64 = 10;
+/// Message ids remembered for deduplication.
+const SEEN_IDS: usize = 200;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct InboundSettings {
+    pub enabled: bool,
+    /// A streaming endpoint, e.g. "https://ntfy.sh/<topic>/json".
+    pub url: Option<String>,
+    /// Service name of the Keychain item (a generic password) holding the
+    /// bearer token; `None` connects without one.
+    pub keychain_service: Option<String>,
+}
+
+/// For the integrations status and the health report; leaves out the URL,
+/// which for ntfy is as good as a password.
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InboundStatus {
+    pub enabled: bool,
+    pub connected: bool,
+    pub connected_since: Option<DateTime<Local>>,
+    pub last_message_at: Option<DateTime<Local>>,
+    pub last_error: Option<String>,
+    pub next_retry_at: Option<DateTime<Local>>,
+    pub received: u64,
+    /// Duplicates, oversized or malformed messages.
+    pub rejected: u64,
+}
+
+static STATUS: Mutex<InboundStatus> = Mutex::new(InboundStatus {
+    enabled: false,
+    connected: false,
+    connected_since: None,
+    last_message_at: None,
+    last_error: None,
+    next_retry_at: None,
+    received: 0,
+    rejected: 0,
+});
+static SEEN: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+#[derive(Debug, Deserialize)]
+struct InboundMessage {
+    #[serde(default)]
+    id: Option<String>,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    fire_at: Option<DateTime<Local>>,
+}
+
+pub fn status() -> InboundStatus {
+    STATUS.lock().unwrap().clone()
+}
+
+fn update_status(change: impl FnOnce(&mut InboundStatus)) {
+    change(&mut STATUS.lock().unwrap());
+}
+
+fn keychain_token(service: &str) -> Result<String, String> {
+    let output = Command::new("/usr/bin/security")
+        .args(["find-generic-password", "-s", service, "-w"])
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!("No token in the Keychain under \"{}\"", service));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The message in a stream line; `None` for keepalives and other events.
+fn parse(line: &str) -> Option<Result<InboundMessage, String>> {
+    let line = line.trim();
+    let data = line.strip_prefix("data:").unwrap_or(line).trim();
+    if data.is_empty() || line.starts_with(':') || line.starts_with("event:") {
+        return None;
+    }
+    let value: Value = match serde_json::from_str(data) {
+        Ok(value) => value,
+        Err(e) => return Some(Err(format!("Not JSON: {}", e))),
+    };
+    // ntfy: open/keepalive events, and the message's text in "message".
+    if value
+        .get("event")
+        .and_then(Value::as_str)
+        .is_some_and(|event| event != "message")
+    {
+        return None;
+    }
+    let outer_id = value.get("id").and_then(Value::as_str).map(str::to_string);
+    let text = value.get("message").and_then(Value::as_str);
+    if let Some(inner) = text.and_then(|t| serde_json::from_str::<Value>(t).ok()) {
+        if inner.is_object() {
+            return Some(
+                serde_json::from_value::<InboundMessage>(inner)
+                    .map(|message| InboundMessage {
+                        id: message.id.or(outer_id),
+                        ..message
+                    })
+                    .map_err(|e| e.to_string()),
+            );
+        }
+    }
+    if value.get("title").is_none() {
+        if let Some(text) = text {
+            // A plain ntfy message: its text is the title.
+            return Some(Ok(InboundMessage {
+                id: outer_id,
+                title: text.to_string(),
+                body: None,
+                fire_at: None,
+            }));
+        }
+    }
+    let body = text.map(str::to_string);
+    Some(
+        serde_json::from_value::<InboundMessage>(value)
+            .map(|message| InboundMessage {
+                body: message.body.or(body),
+                ..message
+            })
+            .map_err(|e| e.to_string()),
+    )
+}
+
+/// True the first time an id is seen; messages without one always pass.
+fn first_sighting(id: Option<&str>) -> bool {
+    let Some(id) = id else {
+        return true;
+    };
+    let mut seen = SEEN.lock().unwrap();
+    if seen.iter().any(|s| s == id) {
+        return false;
+    }
+    seen.push_back(id.to_string());
+    if seen.len() > SEEN_IDS {
+        seen.pop_front();
+    }
+    true
+}
+
+/// Validates the message and hands it to the scheduler.
+fn accept(app: &AppHandle, message: InboundMessage) -> Result<(), String> {
+    let title = untrusted::line(&message.title, MAX_TITLE_CHARS);
+    if title.is_empty() {
+        return Err("Message has no title".to_string());
+    }
+    if !first_sighting(message.id.as_deref()) {
+        return Err("Already received".to_string());
+    }
+    let now = simulate::now();
+    let at = message.fire_at.unwrap_or(now).max(now);
+    if at - now > Duration::days(MAX_AHEAD_DAYS) {
+        return Err(format!("More than {} days ahead", MAX_AHEAD_DAYS));
+    }
+    let id = message
+        .id
+        .unwrap_or_else(|| format!("{}", now.timestamp_millis()));
+    let event: CalendarEvent = serde_json::from_value(json!({
+        "id": format!("inbound-{}", id),
+        "calendar": "Inbound",
+        "title": title,
+        "description": message.body.map(|b| untrusted::text(b.trim(), MAX_BODY_CHARS)),
+        "start": at.to_rfc3339(),
+        "end": (at + Duration::minutes(REMINDER_MINUTES)).to_rfc3339(),
+        "isAllDay": false,
+    }))
+    .map_err(|e| e.to_string())?;
+    app.state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .add_reminder(event, at);
+    Ok(())
+}
+
+enum Line {
+    Text(String),
+    /// Over `MAX_MESSAGE_BYTES`; skipped to its end.
+    Oversized,
+}
+
+/// The error's kind only; a body error's message has the URL in it.
+fn lost(error: std::io::Error) -> String {
+    format!("Connection lost: {}", error.kind())
+}
+
+/// The next line, `None` at the end of the stream.
+fn read_line(reader: &mut impl BufRead) -> Result<Option<Line>, String> {
+    let mut line = Vec::new();
+    let read = reader
+        .by_ref()
+        .take(MAX_MESSAGE_BYTES as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .map_err(lost)?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if line.len() <= MAX_MESSAGE_BYTES || line.ends_with(b"\n") {
+        return Ok(Some(Line::Text(
+            String::from_utf8_lossy(&line).into_owned(),
+        )));
+    }
+    // Read the rest in bounded chunks, never holding more than one.
+    loop {
+        line.clear();
+        let read = reader
+            .by_ref()
+            .take(MAX_MESSAGE_BYTES as u64)
+            .read_until(b'\n', &mut line)
+            .map_err(lost)?;
+        if read == 0 || line.ends_with(b"\n") {
+            return Ok(Some(Line::Oversized));
+        }
+    }
+}
+
+/// Holds one connection open until it drops, or returns `Ok` once the
+/// settings changed.
+fn listen(app: &AppHandle, settings: &InboundSettings, url: &str) -> Result<(), String> {
+    let client = reqwest::blocking::Client::builder()
+        .connect_timeout(StdDuration::from_secs(10))
+        .timeout(StdDuration::from_secs(READ_TIMEOUT_SECONDS))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client.get(url);
+    if let Some(service) = settings.keychain_service.as_deref() {
+        request = request.bearer_auth(keychain_token(service)?);
+    }
+    // Without the URL, which ends up in the health report otherwise.
+    let response = request.send().map_err(|e| e.without_url().to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status().as_u16()));
+    }
+    update_status(|status| {
+        status.connected = true;
+        status.connected_since = Some(Local::now());
+        status.last_error = None;
+        status.next_retry_at = None;
+    });
+
+    let mut reader = BufReader::new(response);
+    while let Some(line) = read_line(&mut reader)? {
+        let current = settings::current(app).inbound;
+        if shutdown::in_progress()
+            || !current.enabled
+            || current.url.as_deref() != Some(url)
+            || current.keychain_service != settings.keychain_service
+        {
+            return Ok(());
+        }
+        let message = match line {
+            Line::Text(text) => parse(&text),
+            Line::Oversized => Some(Err(format!("Message over {} bytes", MAX_MESSAGE_BYTES))),
+        };
+        let Some(message) = message else {
+            continue;
+        };
+        let result = message.and_then(|message| accept(app, message));
+        update_status(|status| match result {
+            Ok(()) => {
+                status.received += 1;
+                status.last_message_at = Some(Local::now());
+            }
+            Err(e) => {
+                status.rejected += 1;
+                status.last_error = Some(e);
+            }
+        });
+    }
+    Err("Connection closed".to_string())
+}
+
+/// Keeps the channel connected while it is enabled, reconnecting with
+/// backoff; a connection that got through resets the backoff.
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || {
+        let mut backoff = MIN_BACKOFF_SECONDS;
+        while !shutdown::in_progress() {
+            let settings = settings::current(&app).inbound;
+            let url = settings.url.clone().filter(|_| settings.enabled);
+            update_status(|status| status.enabled = url.is_some());
+            let Some(url) = url else {
+                thread::sleep(StdDuration::from_secs(IDLE_SECONDS));
+                continue;
+            };
+            let result = listen(&app, &settings, &url);
+            let was_connected = {
+                let mut status = STATUS.lock().unwrap();
+                status.connected_since = None;
+                std::mem::take(&mut status.connected)
+            };
+            if was_connected {
+                backoff = MIN_BACKOFF_SECONDS;
+            }
+            let Err(e) = result else {
+                continue;
+            };
+            let retry = Local::now() + Duration::seconds(backoff as i64);
+            update_status(|status| {
+                status.next_retry_at = Some(retry);
+                status.last_error = Some(e);
+            });
+            thread::sleep(StdDuration::from_secs(backoff));
+            backoff = (backoff * 2).min(MAX_BACKOFF_SECONDS);
+        }
+    });
+}
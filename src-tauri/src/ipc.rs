@@ -0,0 +1,282 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use tauri::{Runtime, Window};
+
+use crate::retention;
+
+// --- IPC Callers ---
+//
+// Which window may call which command is set by the capability files: the
+// dashboard gets every command, the alert only what the alert and its corner
+// card use, the toast only dismiss, snooze, join and closing, the overlays
+// only their opacity (see `capabilities/` and `permissions/`). Commands that
+// change settings, read secrets or reach outside the app also check the
+// calling window themselves, so a capability granted too widely by mistake
+// still doesn't reach them; a test checks every command that writes the
+// settings does.
+
+/// The dashboard's window label.
+pub const DASHBOARD: &str = "main";
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum IpcError {
+    /// Called from a window that may not call it.
+    UnexpectedCaller {
+        command: &'static str,
+        window: String,
+    },
+    /// Called before startup finished; try again once `app-ready` arrives.
+    Starting {
+        command: String,
+    },
+    /// The frontend speaks an API version this backend doesn't.
+    IncompatibleClient {
+        client: String,
+        backend: u32,
+        min_client: u32,
+    },
+    Failed(String),
+}
+
+impl From<String> for IpcError {
+    fn from(message: String) -> Self {
+        IpcError::Failed(message)
+    }
+}
+
+/// Refuses `command` unless the dashboard asked for it.
+pub fn require_dashboard<R: Runtime>(
+    window: &Window<R>,
+    command: &'static str,
+) -> Result<(), IpcError> {
+    if window.label() == DASHBOARD {
+        return Ok(());
+    }
+    retention::log!("{} refused for window {}", command, window.label());
+    Err(IpcError::UnexpectedCaller {
+        command,
+        window: window.label().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{self, Settings, SettingsState};
+    use crate::testing::invoke;
+    use serde_json::json;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Mutex;
+    use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime};
+    use tauri::{App, Manager, WebviewWindowBuilder};
+
+    const ROOT: &str = env!("CARGO_MANIFEST_DIR");
+
+    const FN_PREFIXES: [&str; 5] = [
+        "fn ",
+        "pub fn ",
+        "pub(crate) fn ",
+        "async fn ",
+        "pub async fn ",
+    ];
+
+    /// A function at the top level of one of the source files.
+    struct Function {
+        module: String,
+        name: String,
+        command: bool,
+        body: String,
+    }
+
+    /// Every top-level function in `src/`, with whether it is a command.
+    fn functions() -> Vec<Function> {
+        let mut functions = Vec::new();
+        for entry in fs::read_dir(Path::new(ROOT).join("src")).unwrap() {
+            let path = entry.unwrap().path();
+            let module = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let source = fs::read_to_string(&path).unwrap();
+            let lines: Vec<&str> = source.lines().collect();
+            let mut attributes = Vec::new();
+            let mut i = 0;
+            while i < lines.len() {
+                let line = lines[i];
+                let signature = FN_PREFIXES
+                    .iter()
+                    .find_map(|prefix| line.strip_prefix(prefix));
+                match signature {
+                    Some(rest) => {
+                        let end = if line.ends_with('}') {
+                            i
+                        } else {
+                            i + lines[i..].iter().position(|l| *l == "}").unwrap()
+                        };
+                        let name = rest
+                            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                            .next()
+                            .unwrap();
+                        functions.push(Function {
+                            module: module.clone(),
+                            name: name.to_string(),
+                            command: attributes.contains(&"#[tauri::command]"),
+                            body: lines[i..=end].join("\n"),
+                        });
+                        attributes.clear();
+                        i = end;
+                    }
+                    None if line.starts_with("#[") || line.starts_with("///") => {
+                        attributes.push(line)
+                    }
+                    None => attributes.clear(),
+                }
+                i += 1;
+            }
+        }
+        functions
+    }
+
+    /// Whether `caller` calls `callee`: by its path from another module, or
+    /// by its bare name from the same one.
+    fn calls(caller: &Function, callee: &Function) -> bool {
+        let bare = format!("{}(", callee.name);
+        caller
+            .body
+            .contains(&format!("{}::{}", callee.module, bare))
+            || (caller.module == callee.module
+                && caller.body.match_indices(&bare).any(|(i, _)| {
+                    !caller.body[..i].ends_with(|c: char| c.is_alphanumeric() || "_.:".contains(c))
+                }))
+    }
+
+    #[test]
+    fn every_command_that_writes_the_settings_checks_its_caller() {
+        let functions = functions();
+        let mut writers: Vec<&Function> = functions
+            .iter()
+            .filter(|f| f.module == "settings" && f.name == "update")
+            .collect();
+        assert_eq!(writers.len(), 1);
+        loop {
+            let more: Vec<&Function> = functions
+                .iter()
+                .filter(|f| !writers.iter().any(|w| std::ptr::eq(*w, *f)))
+                .filter(|f| writers.iter().any(|w| calls(f, w)))
+                .collect();
+            if more.is_empty() {
+                break;
+            }
+            writers.extend(more);
+        }
+
+        let commands: Vec<&str> = writers
+            .iter()
+            .filter(|f| f.command)
+            .map(|f| f.name.as_str())
+            .collect();
+        for name in [
+            "save_settings",
+            "save_alert_rule",
+            "switch_profile",
+            "import_backup",
+        ] {
+            assert!(commands.contains(&name), "{} not found", name);
+        }
+        let unchecked: Vec<&str> = writers
+            .iter()
+            .filter(|f| f.command)
+            .filter(|f| {
+                let check = format!("require_dashboard(&window, \"{}\")", f.name);
+                !f.body.contains(&check)
+            })
+            .map(|f| f.name.as_str())
+            .collect();
+        assert!(unchecked.is_empty(), "unchecked: {:?}", unchecked);
+    }
+
+    /// A mock app with the real capabilities and a window for each label.
+    fn app_with_capabilities(labels: &[&str]) -> App<MockRuntime> {
+        let app = mock_builder()
+            .manage(SettingsState(Mutex::new(Settings::default())))
+            .invoke_handler(tauri::generate_handler![settings::get_settings])
+            .build(tauri::generate_context!(test = true))
+            .unwrap();
+        for label in labels {
+            if app.get_webview_window(label).is_none() {
+                WebviewWindowBuilder::new(&app, *label, Default::default())
+                    .build()
+                    .unwrap();
+            }
+        }
+        app
+    }
+
+    #[test]
+    fn only_the_dashboard_is_granted_a_privileged_command() {
+        let labels = [
+            DASHBOARD,
+            "alert",
+            "toast",
+            "popover",
+            "board",
+            "overlay-0_0",
+        ];
+        let app = app_with_capabilities(&labels);
+        let window = |label: &str| app.get_webview_window(label).unwrap();
+
+        assert!(invoke(&window(DASHBOARD), "get_settings", json!({})).is_ok());
+        for label in &labels[1..] {
+            let refused = invoke(&window(label), "get_settings", json!({})).unwrap_err();
+            assert!(
+                refused.as_str().is_some_and(|m| m.contains("not allowed")),
+                "{}",
+                refused
+            );
+        }
+    }
+
+    #[test]
+    fn the_toast_is_granted_only_what_acknowledges_the_alert() {
+        let app = app_with_capabilities(&["toast"]);
+        let toast = app.get_webview_window("toast").unwrap();
+        let allowed = |command: &str| {
+            let error = invoke(&toast, command, json!({})).unwrap_err();
+            // Past the ACL, only a handler this app lacks.
+            !error.to_string().contains("not allowed")
+        };
+        for command in [
+            "dismiss_alert",
+            "snooze_alert",
+            "join_meeting",
+            "exit_alert_mode",
+        ] {
+            assert!(allowed(command), "{}", command);
+        }
+        for command in [
+            "minimize_alert_to_corner",
+            "request_dismiss",
+            "get_active_alert",
+        ] {
+            assert!(!allowed(command), "{}", command);
+        }
+    }
+
+    #[test]
+    fn a_privileged_command_checks_its_caller_itself() {
+        // No capabilities: as if one granted the command too widely.
+        let app = mock_builder()
+            .manage(SettingsState(Mutex::new(Settings::default())))
+            .invoke_handler(tauri::generate_handler![settings::get_settings])
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let toast = WebviewWindowBuilder::new(&app, "toast", Default::default())
+            .build()
+            .unwrap();
+        let refused = invoke(&toast, "get_settings", json!({})).unwrap_err();
+        let expected = IpcError::UnexpectedCaller {
+            command: "get_settings",
+            window: "toast".to_string(),
+        };
+        assert_eq!(refused, serde_json::to_value(expected).unwrap());
+    }
+}
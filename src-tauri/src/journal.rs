@@ -0,0 +1,280 @@
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::calendar::CalendarEvent;
+use crate::scheduler::SchedulerState;
+
+// --- Side-Effect Journal (persisted as JSON in the app data dir) ---
+//
+// Records state the app has put in place that outlives a clean shutdown's
+// cleanup: an alert on screen, reminders paused, alerts snoozed or queued.
+// It is rewritten whenever that state changes (the scheduler's pending work
+// at most once a tick); the next launch reads what was left and puts things
+// right before the scheduler starts. The first fetch then drops snoozes of
+// events that are gone, and anything that came due in between fires as a
+// catch-up.
+
+/// Bumped when the format changes; a journal from a newer version is ignored.
+const JOURNAL_VERSION: u32 = 2;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Journal {
+    pub version: u32,
+    /// The fullscreen alert showing, until it is acknowledged.
+    pub alert: Option<JournaledAlert>,
+    #[serde(flatten)]
+    pub pending: PendingWork,
+}
+
+/// The scheduler's work still to do, as `get_scheduler_state` reports it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PendingWork {
+    pub paused_until: Option<DateTime<Local>>,
+    /// The pause runs to a fixed time rather than the next free slot.
+    pub pause_is_timed: bool,
+    /// Sorted by alert key.
+    pub snoozed: Vec<PendingSnooze>,
+    /// Fullscreen alerts waiting behind the one showing, in order.
+    pub queued: Vec<JournaledAlert>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PendingSnooze {
+    pub alert_key: String,
+    pub event: CalendarEvent,
+    pub until: DateTime<Local>,
+}
+
+/// The same snooze of the same occurrence; other edits to the event wait
+/// for the next change worth writing.
+impl PartialEq for PendingSnooze {
+    fn eq(&self, other: &Self) -> bool {
+        self.alert_key == other.alert_key
+            && self.until == other.until
+            && self.event.occurrence_key() == other.event.occurrence_key()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct JournaledAlert {
+    pub alert_key: String,
+    pub title: String,
+    pub end: Option<DateTime<Local>>,
+}
+
+/// What the last session left that still needs doing.
+#[derive(Debug, Default, PartialEq)]
+pub struct Recovery {
+    /// Alerts to bring back, the one showing first; their meetings haven't
+    /// ended.
+    pub alert_keys: Vec<String>,
+    /// Pause to restore; it hasn't expired.
+    pub paused_until: Option<DateTime<Local>>,
+    pub pause_is_timed: bool,
+    /// Snoozes of meetings that haven't ended, due or not.
+    pub snoozed: Vec<PendingSnooze>,
+}
+
+pub struct JournalState(pub Mutex<Journal>);
+
+fn journal_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("journal.json"))
+}
+
+fn load(app: &AppHandle) -> Journal {
+    journal_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str::<Journal>(&json).ok())
+        .filter(|journal| journal.version <= JOURNAL_VERSION)
+        .unwrap_or_default()
+}
+
+fn persist(app: &AppHandle, journal: &Journal) {
+    let Some(path) = journal_path(app) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(journal) {
+        let _ = fs::write(path, json);
+    }
+}
+
+fn update(app: &AppHandle, change: impl FnOnce(&mut Journal)) {
+    let state = app.state::<JournalState>();
+    let mut journal = state.0.lock().unwrap();
+    change(&mut journal);
+    persist(app, &journal);
+}
+
+pub fn alert_shown(app: &AppHandle, event: &CalendarEvent) {
+    let alert = JournaledAlert {
+        alert_key: event.alert_key(),
+        title: event.title.clone(),
+        end: event.end_time(),
+    };
+    update(app, |journal| journal.alert = Some(alert));
+}
+
+pub fn alert_closed(app: &AppHandle) {
+    update(app, |journal| journal.alert = None);
+}
+
+pub fn paused(app: &AppHandle, until: Option<DateTime<Local>>) {
+    update(app, |journal| journal.pending.paused_until = until);
+}
+
+/// Rewrites the journal when the scheduler's pending work changed; called
+/// once a tick.
+pub fn save_pending(app: &AppHandle, pending: PendingWork) {
+    let state = app.state::<JournalState>();
+    let mut journal = state.0.lock().unwrap();
+    if journal.pending != pending {
+        journal.pending = pending;
+        persist(app, &journal);
+    }
+}
+
+/// Sorts a leftover journal into what to restore; anything that has since
+/// expired is dropped.
+pub fn plan_recovery(journal: &Journal, now: DateTime<Local>) -> Recovery {
+    let running = |end: Option<DateTime<Local>>| end.is_some_and(|end| now < end);
+    let paused_until = journal.pending.paused_until.filter(|until| now < *until);
+    Recovery {
+        alert_keys: journal
+            .alert
+            .iter()
+            .chain(&journal.pending.queued)
+            .filter(|alert| running(alert.end))
+            .map(|alert| alert.alert_key.clone())
+            .collect(),
+        paused_until,
+        pause_is_timed: paused_until.is_some() && journal.pending.pause_is_timed,
+        snoozed: journal
+            .pending
+            .snoozed
+            .iter()
+            .filter(|snooze| running(snooze.event.end_time()))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Reads what the last session left, hands the scheduler what is still
+/// current, and starts a clean journal. Call after the scheduler state is
+/// managed and before it starts ticking.
+pub fn recover(app: &AppHandle) {
+    let leftover = load(app);
+    let recovery = plan_recovery(&leftover, Local::now());
+    {
+        let state = app.state::<SchedulerState>();
+        state.0.lock().unwrap().recover(&recovery);
+    }
+
+    let journal = Journal {
+        version: JOURNAL_VERSION,
+        alert: None,
+        pending: PendingWork {
+            paused_until: recovery.paused_until,
+            pause_is_timed: recovery.pause_is_timed,
+            snoozed: recovery.snoozed.clone(),
+            queued: Vec::new(),
+        },
+    };
+    persist(app, &journal);
+    app.manage(JournalState(Mutex::new(journal)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{at, event};
+    use serde_json::json;
+
+    /// What a session that crashed at 09:05 left: the alert for "Standup"
+    /// on screen with "Review" queued behind it, reminders paused to 09:10,
+    /// and two snoozes.
+    fn crashed_session() -> Journal {
+        let standup = event("Standup", at("2026-03-02 09:02"), 30);
+        let review = event("Review", at("2026-03-02 09:00"), 15);
+        let planning = event("Planning", at("2026-03-02 09:00"), 60);
+        serde_json::from_value(json!({
+            "version": JOURNAL_VERSION,
+            "alert": {
+                "alert_key": standup.alert_key(),
+                "title": "Standup",
+                "end": standup.end,
+            },
+            "queued": [{
+                "alert_key": review.alert_key(),
+                "title": "Review",
+                "end": review.end,
+            }],
+            "paused_until": at("2026-03-02 09:10"),
+            "pause_is_timed": true,
+            "snoozed": [
+                { "alert_key": review.alert_key(), "event": review, "until": at("2026-03-02 09:08") },
+                { "alert_key": planning.alert_key(), "event": planning, "until": at("2026-03-02 09:30") },
+            ],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn restores_what_is_still_current() {
+        let journal = crashed_session();
+        let recovery = plan_recovery(&journal, at("2026-03-02 09:06"));
+        assert_eq!(
+            recovery.alert_keys,
+            vec![
+                journal.alert.as_ref().unwrap().alert_key.clone(),
+                journal.pending.queued[0].alert_key.clone(),
+            ]
+        );
+        assert_eq!(recovery.paused_until, Some(at("2026-03-02 09:10")));
+        assert!(recovery.pause_is_timed);
+        // A snooze already due still comes back, to fire as a catch-up.
+        assert_eq!(recovery.snoozed.len(), 2);
+    }
+
+    #[test]
+    fn drops_what_expired_while_the_app_was_down() {
+        let recovery = plan_recovery(&crashed_session(), at("2026-03-02 09:20"));
+        // "Review" ended at 09:15; "Standup" runs to 09:32.
+        assert_eq!(recovery.alert_keys.len(), 1);
+        assert_eq!(recovery.paused_until, None);
+        assert!(!recovery.pause_is_timed);
+        let titles: Vec<&str> = recovery
+            .snoozed
+            .iter()
+            .map(|s| s.event.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Planning"]);
+
+        let recovery = plan_recovery(&crashed_session(), at("2026-03-02 11:00"));
+        assert_eq!(recovery, Recovery::default());
+    }
+
+    #[test]
+    fn a_clean_journal_leaves_nothing_to_do() {
+        let clean = Journal {
+            version: JOURNAL_VERSION,
+            ..Journal::default()
+        };
+        assert_eq!(
+            plan_recovery(&clean, at("2026-03-02 09:06")),
+            Recovery::default()
+        );
+    }
+}
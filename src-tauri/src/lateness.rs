@@ -0,0 +1,145 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use tauri::{AppHandle, Manager, Window};
+
+use crate::calendar::{self, CalendarEvent};
+use crate::ipc::{self, IpcError};
+use crate::{privacy, retention, settings};
+
+// --- Lateness Annotation (opt-in) ---
+//
+// For finding the meetings that always start late: a join more than
+// `threshold_minutes` after the start gets a line ("Joined 7 min late") or a
+// tag appended to the notes of that one occurrence, in the user's own
+// calendar; never on events the user organizes for others, whose notes are
+// shared. Events on read-only calendars, and everything with `history_only`,
+// only have the late join in the local history, which punctuality tracking
+// keeps anyway. Annotated occurrences are remembered so none gets a second
+// line; a failed write is logged and otherwise ignored.
+
+const MAX_ANNOTATED: usize = 500;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LatenessMode {
+    /// "Joined 7 min late".
+    #[default]
+    Note,
+    /// The configured tag, e.g. "#late".
+    Tag,
+    /// Nothing is written to the calendar.
+    HistoryOnly,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LatenessSettings {
+    pub enabled: bool,
+    pub threshold_minutes: i64,
+    pub mode: LatenessMode,
+    pub tag: String,
+}
+
+impl Default for LatenessSettings {
+    fn default() -> Self {
+        LatenessSettings {
+            enabled: false,
+            threshold_minutes: 5,
+            mode: LatenessMode::Note,
+            tag: "#late".to_string(),
+        }
+    }
+}
+
+/// Occurrence keys already annotated (or tried), oldest first.
+static ANNOTATED: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+fn annotated_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("lateness-annotated.json"))
+}
+
+/// True the first time an occurrence is claimed, across restarts.
+fn claim(app: &AppHandle, key: String) -> bool {
+    let mut annotated = ANNOTATED.lock().unwrap();
+    let keys = annotated.get_or_insert_with(|| {
+        annotated_path(app)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    });
+    if keys.contains(&key) {
+        return false;
+    }
+    keys.push(key);
+    if keys.len() > MAX_ANNOTATED {
+        let overflow = keys.len() - MAX_ANNOTATED;
+        keys.drain(..overflow);
+    }
+    if let (Some(path), Ok(json)) = (annotated_path(app), serde_json::to_string(keys)) {
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = fs::write(path, json);
+    }
+    true
+}
+
+/// Called with every join the history records; `late_seconds` after the
+/// start.
+pub fn joined(app: &AppHandle, event: &CalendarEvent, late_seconds: i64) {
+    let current = settings::current(app);
+    let settings = &current.lateness;
+    let minutes = late_seconds / 60;
+    if !settings.enabled
+        || settings.mode == LatenessMode::HistoryOnly
+        || minutes <= settings.threshold_minutes
+        || event.read_only
+    {
+        return;
+    }
+    let line = match settings.mode {
+        LatenessMode::Tag => settings.tag.trim().to_string(),
+        _ => format!("Joined {} min late", minutes),
+    };
+    if line.is_empty() || !claim(app, event.occurrence_key()) {
+        return;
+    }
+    let title = privacy::redact_title(current.privacy_mode, &event.title);
+    let (id, start) = (event.id.clone(), event.start.clone());
+    // The helper takes seconds; the join shouldn't wait for it.
+    thread::spawn(move || {
+        if let Err(e) = calendar::append_to_notes(&id, &start, &line) {
+            retention::log!("lateness note not added to {}: {}", title, e);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn set_lateness_annotation(
+    app: AppHandle,
+    window: Window,
+    enabled: bool,
+    threshold_minutes: i64,
+    mode: LatenessMode,
+) -> Result<(), IpcError> {
+    // Turns on writing to the calendar.
+    ipc::require_dashboard(&window, "set_lateness_annotation")?;
+    if threshold_minutes < 0 {
+        return Err(IpcError::Failed(
+            "The threshold can't be negative".to_string(),
+        ));
+    }
+    settings::update(&app, |settings| {
+        settings.lateness.enabled = enabled;
+        settings.lateness.threshold_minutes = threshold_minutes;
+        settings.lateness.mode = mode;
+    })?;
+    Ok(())
+}
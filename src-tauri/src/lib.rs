@@ -6,4 +6,4 @@ pub fn run() {
     tauri::Builder::default()
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
-}
\ No newline at end of file
+}
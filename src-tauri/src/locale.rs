@@ -0,0 +1,302 @@
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Weekday};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+// --- Time & Date Formatting ---
+//
+// Every time or date the backend puts in front of the user (notifications,
+// the digest, VoiceOver, templates) goes through `format_time` and
+// `format_date`, and relative times through `relative_start`. The clock and
+// the week start follow System Settings unless overridden.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockStyle {
+    #[default]
+    System,
+    #[serde(rename = "12h")]
+    TwelveHour,
+    #[serde(rename = "24h")]
+    TwentyFourHour,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DateStyle {
+    /// "14 Oct"
+    Short,
+    /// "Tue 14 Oct"
+    #[default]
+    Medium,
+    /// "Tuesday, 14 October 2025"
+    Long,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct FormatSettings {
+    pub clock: ClockStyle,
+    /// First day of the week; `None` follows the system.
+    pub week_start: Option<Weekday>,
+    pub date_style: DateStyle,
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use std::ffi::{CStr, CString};
+
+    /// Whether the user's locale (or the 24-hour toggle) uses a 12-hour clock.
+    pub fn uses_12_hour_clock() -> Option<bool> {
+        let template = CString::new("j").ok()?;
+        // SAFETY: autoreleased Foundation objects used within this call; the
+        // UTF-8 buffer is copied before they go away.
+        unsafe {
+            let template: *mut AnyObject =
+                msg_send![class!(NSString), stringWithUTF8String: template.as_ptr()];
+            let locale: *mut AnyObject = msg_send![class!(NSLocale), currentLocale];
+            if template.is_null() || locale.is_null() {
+                return None;
+            }
+            let format: *mut AnyObject = msg_send![
+                class!(NSDateFormatter),
+                dateFormatFromTemplate: template,
+                options: 0usize,
+                locale: locale
+            ];
+            let format = format.as_ref()?;
+            let utf8: *const std::ffi::c_char = msg_send![format, UTF8String];
+            if utf8.is_null() {
+                return None;
+            }
+            Some(CStr::from_ptr(utf8).to_string_lossy().contains('a'))
+        }
+    }
+
+    /// 1 is Sunday, as NSCalendar counts.
+    pub fn first_weekday() -> Option<usize> {
+        // SAFETY: plain getter on the shared current calendar.
+        unsafe {
+            let calendar: *mut AnyObject = msg_send![class!(NSCalendar), currentCalendar];
+            let calendar = calendar.as_ref()?;
+            let day: usize = msg_send![calendar, firstWeekday];
+            Some(day)
+        }
+    }
+}
+
+fn system_12_hour() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        macos::uses_12_hour_clock().unwrap_or(false)
+    }
+    #[cfg(not(target_os = "macos"))]
+    false
+}
+
+fn system_week_start() -> Weekday {
+    #[cfg(target_os = "macos")]
+    {
+        match macos::first_weekday() {
+            Some(1) => Weekday::Sun,
+            Some(7) => Weekday::Sat,
+            _ => Weekday::Mon,
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    Weekday::Mon
+}
+
+fn twelve_hour(format: &FormatSettings) -> bool {
+    match format.clock {
+        ClockStyle::System => system_12_hour(),
+        ClockStyle::TwelveHour => true,
+        ClockStyle::TwentyFourHour => false,
+    }
+}
+
+/// "14:30" or "2:30 PM", in the zone `time` is in.
+pub fn format_time<Tz: TimeZone>(format: &FormatSettings, time: DateTime<Tz>) -> String
+where
+    Tz::Offset: Display,
+{
+    if twelve_hour(format) {
+        time.format("%-I:%M %p").to_string()
+    } else {
+        time.format("%H:%M").to_string()
+    }
+}
+
+pub fn format_date(format: &FormatSettings, date: NaiveDate) -> String {
+    let pattern = match format.date_style {
+        DateStyle::Short => "%-d %b",
+        DateStyle::Medium => "%a %-d %b",
+        DateStyle::Long => "%A, %-d %B %Y",
+    };
+    date.format(pattern).to_string()
+}
+
+/// "in 12 min", "started 3 min ago", "tomorrow 09:00": one wording for every
+/// surface. Minutes round down, so 59 seconds out is still "in under 1 min".
+pub fn relative_start(
+    format: &FormatSettings,
+    start: DateTime<Local>,
+    now: DateTime<Local>,
+) -> String {
+    let seconds = (start - now).num_seconds();
+    match seconds {
+        0..60 => return "in under 1 min".to_string(),
+        60..3600 => return format!("in {} min", seconds / 60),
+        -59..0 => return "just started".to_string(),
+        -3599..=-60 => return format!("started {} min ago", -seconds / 60),
+        _ => {}
+    }
+    let time = format_time(format, start);
+    let days = (start.date_naive() - now.date_naive()).num_days();
+    let day = match days {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        2..=6 => start.format("%a").to_string(),
+        _ => format_date(format, start.date_naive()),
+    };
+    if seconds < 0 {
+        format!("started {} {}", day, time)
+    } else {
+        format!("{} {}", day, time)
+    }
+}
+
+pub fn week_start(format: &FormatSettings) -> Weekday {
+    format.week_start.unwrap_or_else(system_week_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::at;
+
+    fn clock(clock: ClockStyle) -> FormatSettings {
+        FormatSettings {
+            clock,
+            ..FormatSettings::default()
+        }
+    }
+
+    #[test]
+    fn formats_times_on_either_clock() {
+        let twelve = clock(ClockStyle::TwelveHour);
+        let twenty_four = clock(ClockStyle::TwentyFourHour);
+        for (time, on_12, on_24) in [
+            ("2026-03-02 00:05", "12:05 AM", "00:05"),
+            ("2026-03-02 09:00", "9:00 AM", "09:00"),
+            ("2026-03-02 12:00", "12:00 PM", "12:00"),
+            ("2026-03-02 14:30", "2:30 PM", "14:30"),
+            ("2026-03-02 23:59", "11:59 PM", "23:59"),
+        ] {
+            assert_eq!(format_time(&twelve, at(time)), on_12);
+            assert_eq!(format_time(&twenty_four, at(time)), on_24);
+        }
+    }
+
+    #[test]
+    fn formats_dates_in_each_style() {
+        let date = NaiveDate::from_ymd_opt(2025, 10, 14).unwrap();
+        for (date_style, expected) in [
+            (DateStyle::Short, "14 Oct"),
+            (DateStyle::Medium, "Tue 14 Oct"),
+            (DateStyle::Long, "Tuesday, 14 October 2025"),
+        ] {
+            let format = FormatSettings {
+                date_style,
+                ..FormatSettings::default()
+            };
+            assert_eq!(format_date(&format, date), expected);
+        }
+    }
+
+    #[test]
+    fn an_overridden_week_start_wins_over_the_system() {
+        for day in [Weekday::Sun, Weekday::Sat, Weekday::Mon] {
+            let format = FormatSettings {
+                week_start: Some(day),
+                ..FormatSettings::default()
+            };
+            assert_eq!(week_start(&format), day);
+        }
+    }
+
+    #[test]
+    fn relative_starts_round_down_to_the_minute() {
+        let format = clock(ClockStyle::TwentyFourHour);
+        let now = at("2026-03-02 09:00");
+        let seconds = |s: i64| relative_start(&format, now + chrono::Duration::seconds(s), now);
+        assert_eq!(seconds(0), "in under 1 min");
+        assert_eq!(seconds(59), "in under 1 min");
+        assert_eq!(seconds(60), "in 1 min");
+        assert_eq!(seconds(119), "in 1 min");
+        assert_eq!(seconds(3599), "in 59 min");
+        assert_eq!(seconds(-1), "just started");
+        assert_eq!(seconds(-59), "just started");
+        assert_eq!(seconds(-60), "started 1 min ago");
+        assert_eq!(seconds(-3599), "started 59 min ago");
+    }
+
+    #[test]
+    fn relative_days_turn_over_at_midnight() {
+        let format = clock(ClockStyle::TwentyFourHour);
+        let before = at("2026-03-02 23:30");
+        let after = at("2026-03-03 00:30");
+        let morning = at("2026-03-03 09:00");
+        assert_eq!(relative_start(&format, morning, before), "tomorrow 09:00");
+        assert_eq!(relative_start(&format, morning, after), "today 09:00");
+        let evening = at("2026-03-02 21:00");
+        assert_eq!(
+            relative_start(&format, evening, before),
+            "started today 21:00"
+        );
+        assert_eq!(
+            relative_start(&format, evening, after),
+            "started yesterday 21:00"
+        );
+        // Under the hour, minutes win over the day.
+        assert_eq!(relative_start(&format, after, before), "tomorrow 00:30");
+        assert_eq!(
+            relative_start(&format, at("2026-03-03 00:10"), before),
+            "in 40 min"
+        );
+    }
+
+    #[test]
+    fn relative_starts_name_the_weekday_within_a_week_and_the_date_beyond() {
+        let format = FormatSettings {
+            clock: ClockStyle::TwelveHour,
+            date_style: DateStyle::Short,
+            ..FormatSettings::default()
+        };
+        // A Monday.
+        let now = at("2026-03-02 09:00");
+        assert_eq!(
+            relative_start(&format, at("2026-03-04 14:00"), now),
+            "Wed 2:00 PM"
+        );
+        assert_eq!(
+            relative_start(&format, at("2026-03-08 14:00"), now),
+            "Sun 2:00 PM"
+        );
+        assert_eq!(
+            relative_start(&format, at("2026-03-09 14:00"), now),
+            "9 Mar 2:00 PM"
+        );
+        assert_eq!(
+            relative_start(&format, at("2026-04-20 14:00"), now),
+            "20 Apr 2:00 PM"
+        );
+        assert_eq!(
+            relative_start(&format, at("2026-02-20 14:00"), now),
+            "started 20 Feb 2:00 PM"
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::calendar::CalendarEvent;
+use crate::{scheduler, settings};
+
+// --- Location Classification ---
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LocationKind {
+    #[default]
+    None,
+    Url,
+    Room,
+    Address,
+    Other,
+}
+
+/// Extra lead time for in-person meetings, so the alert means "time to leave".
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct TravelSettings {
+    pub enabled: bool,
+    /// Lead time for physical locations that match no pattern.
+    pub default_lead_minutes: i64,
+    pub buffers: Vec<TravelBuffer>,
+}
+
+/// A fixed buffer for locations containing `pattern` (case-insensitive).
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TravelBuffer {
+    pub pattern: String,
+    pub lead_minutes: i64,
+}
+
+impl Default for TravelSettings {
+    fn default() -> Self {
+        TravelSettings {
+            enabled: false,
+            default_lead_minutes: 45,
+            buffers: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct LocationInfo {
+    pub kind: LocationKind,
+    /// Travel lead time the scheduler will use, if the location is physical.
+    pub lead_minutes: Option<i64>,
+    pub display: Option<String>,
+    pub map_url: Option<String>,
+}
+
+const URL_HINTS: [&str; 6] = [
+    "http://",
+    "https://",
+    "zoom.us",
+    "meet.google",
+    "teams.microsoft",
+    "webex.com",
+];
+const ROOM_HINTS: [&str; 5] = ["room", "conference", "boardroom", "meeting space", "huddle"];
+const STREET_HINTS: [&str; 16] = [
+    "street",
+    "st.",
+    " st ",
+    "avenue",
+    "ave",
+    "road",
+    " rd",
+    "boulevard",
+    "blvd",
+    "lane",
+    "drive",
+    "plaza",
+    "square",
+    "jl.",
+    "jalan",
+    "straße",
+];
+
+fn looks_like_room(location: &str) -> bool {
+    let lower = location.to_lowercase();
+    // Resource calendars append capacity and AV tags: "NYC-05-Boardroom (8) [VC]".
+    ROOM_HINTS.iter().any(|hint| lower.contains(hint))
+        || (location.contains('[') && location.contains(']'))
+}
+
+fn looks_like_address(location: &str) -> bool {
+    let lower = format!(" {} ", location.to_lowercase());
+    let has_street = STREET_HINTS.iter().any(|hint| lower.contains(hint));
+    let has_number = location.chars().any(|c| c.is_ascii_digit());
+    // "221B Baker Street, London" or "Alexanderplatz 1, 10178 Berlin".
+    has_number && (has_street || location.contains(','))
+}
+
+pub fn classify(event: &CalendarEvent) -> LocationKind {
+    let Some(location) = event
+        .location
+        .as_deref()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+    else {
+        return LocationKind::None;
+    };
+    let lower = location.to_lowercase();
+    if URL_HINTS.iter().any(|hint| lower.contains(hint)) {
+        LocationKind::Url
+    } else if event.has_coordinates {
+        LocationKind::Address
+    } else if looks_like_room(location) {
+        // Before the address check: "Room 5, HQ" has a number and a comma.
+        LocationKind::Room
+    } else if looks_like_address(location) {
+        LocationKind::Address
+    } else {
+        LocationKind::Other
+    }
+}
+
+/// Strips trailing capacity and AV tags: "NYC-05-Boardroom (8) [VC]" becomes
+/// "NYC-05-Boardroom".
+pub fn room_display_name(location: &str) -> String {
+    let mut name = location.trim();
+    loop {
+        let open = match name.chars().last() {
+            Some(')') => '(',
+            Some(']') => '[',
+            _ => break,
+        };
+        match name.rfind(open) {
+            Some(start) if start > 0 => name = name[..start].trim_end(),
+            _ => break,
+        }
+    }
+    name.to_string()
+}
+
+fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Apple Maps link for address locations.
+pub fn map_url(location: &str) -> String {
+    format!("maps://?q={}", encode_query(location.trim()))
+}
+
+/// Fills in the location fields of a freshly fetched event.
+pub fn annotate(event: &mut CalendarEvent) {
+    event.location_kind = classify(event);
+    let location = event.location.as_deref().map(str::trim).unwrap_or_default();
+    event.location_display = match event.location_kind {
+        LocationKind::None => None,
+        LocationKind::Room => Some(room_display_name(location)),
+        _ => Some(location.to_string()),
+    };
+    event.map_url = (event.location_kind == LocationKind::Address).then(|| map_url(location));
+}
+
+fn find_event(app: &AppHandle, event_id: &str) -> Result<CalendarEvent, String> {
+    scheduler::cached_events(app)
+        .into_iter()
+        .find(|e| e.id == event_id)
+        .ok_or(format!("Unknown event: {}", event_id))
+}
+
+/// Travel lead time for physical meetings when travel alerts are enabled.
+pub fn travel_lead_minutes(travel: &TravelSettings, event: &CalendarEvent) -> Option<i64> {
+    if !travel.enabled || classify(event) != LocationKind::Address {
+        return None;
+    }
+    let location = event.location.as_deref().unwrap_or_default().to_lowercase();
+    let buffer = travel
+        .buffers
+        .iter()
+        .find(|b| !b.pattern.is_empty() && location.contains(&b.pattern.to_lowercase()));
+    Some(buffer.map_or(travel.default_lead_minutes, |b| b.lead_minutes))
+}
+
+#[tauri::command]
+pub fn classify_location(app: AppHandle, event_id: String) -> Result<LocationInfo, String> {
+    let event = find_event(&app, &event_id)?;
+    let travel = settings::current(&app).travel;
+    Ok(LocationInfo {
+        kind: event.location_kind,
+        lead_minutes: travel_lead_minutes(&travel, &event),
+        display: event.location_display,
+        map_url: event.map_url,
+    })
+}
+
+#[tauri::command]
+pub fn open_map(app: AppHandle, event_id: String) -> Result<(), String> {
+    let event = find_event(&app, &event_id)?;
+    let url = event
+        .map_url
+        .ok_or(format!("Event {} has no address", event_id))?;
+    open::that(url).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{at, event};
+
+    fn at_location(location: Option<&str>) -> CalendarEvent {
+        let mut meeting = event("Meeting", at("2026-03-02 10:00"), 30);
+        meeting.location = location.map(str::to_string);
+        meeting
+    }
+
+    #[test]
+    fn classifies_locations() {
+        let cases = [
+            (None, LocationKind::None),
+            (Some("   "), LocationKind::None),
+            (Some("https://us02web.zoom.us/j/123"), LocationKind::Url),
+            (
+                Some("Microsoft Teams: teams.microsoft.com/l/x"),
+                LocationKind::Url,
+            ),
+            (Some("NYC-05-Boardroom (8) [VC]"), LocationKind::Room),
+            (Some("Room 4.02"), LocationKind::Room),
+            (Some("BER-3-Kiez [Display]"), LocationKind::Room),
+            // A number and a comma, but a room first.
+            (Some("Room 5, HQ"), LocationKind::Room),
+            (Some("221B Baker Street, London"), LocationKind::Address),
+            (
+                Some("Alexanderplatz 1, 10178 Berlin"),
+                LocationKind::Address,
+            ),
+            (Some("Jl. Sudirman No. 5"), LocationKind::Address),
+            (Some("Building 4"), LocationKind::Other),
+            (Some("Main Ave"), LocationKind::Other),
+            (Some("The café downstairs"), LocationKind::Other),
+        ];
+        for (location, kind) in cases {
+            assert_eq!(classify(&at_location(location)), kind, "{:?}", location);
+        }
+
+        // Picked from Maps in Calendar, whatever the text says.
+        let mut pinned = at_location(Some("Blue Bottle"));
+        pinned.has_coordinates = true;
+        assert_eq!(classify(&pinned), LocationKind::Address);
+    }
+
+    #[test]
+    fn room_names_drop_capacity_and_av_tags() {
+        assert_eq!(
+            room_display_name("NYC-05-Boardroom (8) [VC]"),
+            "NYC-05-Boardroom"
+        );
+        assert_eq!(room_display_name(" Room 4.02 (8) "), "Room 4.02");
+        assert_eq!(room_display_name("Room (East) wing"), "Room (East) wing");
+        // Nothing would be left.
+        assert_eq!(room_display_name("(8)"), "(8)");
+    }
+
+    #[test]
+    fn annotates_rooms_and_addresses() {
+        let mut room = at_location(Some("NYC-05-Boardroom (8) [VC]"));
+        annotate(&mut room);
+        assert_eq!(room.location_kind, LocationKind::Room);
+        assert_eq!(room.location_display.as_deref(), Some("NYC-05-Boardroom"));
+        assert_eq!(room.location.as_deref(), Some("NYC-05-Boardroom (8) [VC]"));
+        assert_eq!(room.map_url, None);
+
+        let mut address = at_location(Some(" 221B Baker Street, London "));
+        annotate(&mut address);
+        assert_eq!(address.location_kind, LocationKind::Address);
+        assert_eq!(
+            address.map_url.as_deref(),
+            Some("maps://?q=221B%20Baker%20Street%2C%20London")
+        );
+    }
+
+    #[test]
+    fn only_addresses_get_a_travel_buffer() {
+        let travel = TravelSettings {
+            enabled: true,
+            default_lead_minutes: 30,
+            buffers: vec![TravelBuffer {
+                pattern: "LONDON".to_string(),
+                lead_minutes: 60,
+            }],
+        };
+        let london = at_location(Some("221B Baker Street, London"));
+        let berlin = at_location(Some("Alexanderplatz 1, 10178 Berlin"));
+        let room = at_location(Some("Room 4.02"));
+        assert_eq!(travel_lead_minutes(&travel, &london), Some(60));
+        assert_eq!(travel_lead_minutes(&travel, &berlin), Some(30));
+        assert_eq!(travel_lead_minutes(&travel, &room), None);
+        let off = TravelSettings {
+            enabled: false,
+            ..travel
+        };
+        assert_eq!(travel_lead_minutes(&off, &london), None);
+    }
+}
@@ -0,0 +1,67 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::calendar::CalendarEvent;
+use crate::rules::AlertStyle;
+
+// --- Long Events ---
+//
+// Offsites, conference days and all-morning focus blocks are timed events,
+// but not meetings: past the threshold an event is a "long block". It still
+// shows on the dashboard, but alerts in a quieter style and doesn't count as
+// being in a meeting (lifecycle webhooks, the status file, pause until
+// free).
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LongEventSettings {
+    /// Events at least this long are long blocks; `None` turns it off.
+    pub threshold_minutes: Option<i64>,
+    /// How a long block alerts instead of taking over the screen.
+    pub style: AlertStyle,
+    /// Per-calendar thresholds, e.g. `None` for a calendar whose offsite
+    /// days should alert like meetings.
+    pub calendars: Vec<LongEventCalendar>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LongEventCalendar {
+    pub calendar: String,
+    pub threshold_minutes: Option<i64>,
+}
+
+impl Default for LongEventSettings {
+    fn default() -> Self {
+        LongEventSettings {
+            threshold_minutes: Some(240),
+            style: AlertStyle::Notification,
+            calendars: Vec::new(),
+        }
+    }
+}
+
+impl LongEventSettings {
+    fn threshold(&self, calendar: &str) -> Option<i64> {
+        self.calendars
+            .iter()
+            .find(|c| c.calendar == calendar)
+            .map_or(self.threshold_minutes, |c| c.threshold_minutes)
+    }
+
+    pub fn is_long_block(&self, event: &CalendarEvent) -> bool {
+        let (Some(start), Some(end)) = (event.start_time(), event.end_time()) else {
+            return false;
+        };
+        !event.is_all_day
+            && self
+                .threshold(&event.calendar)
+                .is_some_and(|minutes| (end - start).num_minutes() >= minutes)
+    }
+
+    /// Sets `is_long_block` on each event.
+    pub fn mark(&self, events: &mut [CalendarEvent]) {
+        for event in events {
+            event.is_long_block = self.is_long_block(event);
+        }
+    }
+}
@@ -1,184 +1,347 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::process::Command;
-use std::fs;
-use std::env;
-use tauri::{
-    menu::{Menu, MenuItem},
-    tray::{MouseButton, TrayIconBuilder, TrayIconEvent},
-    Manager, WebviewWindow,
-};
+mod accessibility;
+mod actions;
+mod agenda;
+mod api;
+mod appearance;
+mod attendance;
+mod audio;
+mod autotune;
+mod backup;
+mod board;
+mod budget;
+mod bulk;
+mod bus;
+mod cadence;
+mod calendar;
+mod calendar_watch;
+mod cancellations;
+mod changes;
+mod children;
+mod confidential;
+mod counterparts;
+mod countin;
+mod days_off;
+mod dismissal;
+mod displays;
+mod dock;
+mod explain;
+mod fade;
+mod features;
+mod flood;
+mod focus;
+mod health;
+mod helper;
+mod history;
+mod inbound;
+mod ipc;
+mod journal;
+mod lateness;
+mod locale;
+mod location;
+mod long_events;
+mod mqtt;
+mod notes;
+mod notify;
+mod overrides;
+mod popover;
+mod power;
+mod presentation;
+mod privacy;
+mod profiles;
+mod push;
+mod recent;
+mod renderer;
+mod retention;
+mod rules;
+mod scheduler;
+mod selftest;
+mod series;
+mod session;
+mod settings;
+mod shutdown;
+mod simulate;
+mod sound;
+mod sources;
+mod startup;
+mod statusfile;
+mod templates;
+#[cfg(test)]
+mod testing;
+mod theme;
+mod timezone;
+mod tray;
+mod untrusted;
+mod webhooks;
+mod week;
+mod window;
+mod working_hours;
 
-// --- Calendar Logic (Powered by Swift) ---
+use tauri::{AppHandle, Manager, RunEvent, WindowEvent};
 
-#[tauri::command]
-fn get_calendar_events() -> String {
-    // We use Swift's EventKit because JXA (AppleScript) fails to fetch 
-    // recurring events (like Daily Standups) properly.
-    let swift_script = r#"
-import EventKit
-import Foundation
+use scheduler::SchedulerState;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 200;
 
-let store = EKEventStore()
-let semaphore = DispatchSemaphore(value: 0)
+/// Events sorted by start, one page at a time. Notes and attendees are left
+/// out unless `include_details` is set; `get_event_details` has them. With
+/// `include_filtered`, excluded events are kept and carry the reason. A page
+/// over `calendar::MAX_PAGE_BYTES` is refused.
+///
+/// The helper runs for as long as EventKit takes, so off the main thread.
+#[tauri::command]
+async fn get_calendar_events(
+    app: AppHandle,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    include_details: Option<bool>,
+    include_filtered: Option<bool>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        calendar_events_page(&app, limit, offset, include_details, include_filtered)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-// Request Access
-if #available(macOS 14.0, *) {
-    store.requestFullAccessToEvents { (granted, error) in
-        if !granted {
-            print("[]")
-            semaphore.signal()
-            return
+fn calendar_events_page(
+    app: &AppHandle,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    include_details: Option<bool>,
+    include_filtered: Option<bool>,
+) -> Result<String, String> {
+    let settings = settings::current(app);
+    let filter = settings.calendar_filter.clone();
+    let include_filtered = include_filtered.unwrap_or(false);
+    let now = simulate::now();
+    let to = now + chrono::Duration::hours(24);
+    // A failed read shows the last good events, marked stale, rather than an
+    // empty day.
+    let (fetched, stale, last_success_at) = match calendar::try_fetch_events(now, to) {
+        Ok(events) => (events, false, Some(simulate::now())),
+        Err(_) => {
+            let cached = scheduler::cached_events(app)
+                .into_iter()
+                .filter(|e| e.start_time().is_some_and(|start| start < to))
+                .filter(|e| e.end_time().is_some_and(|end| end > now))
+                .collect();
+            let freshness = sources::freshness(app, &settings);
+            (cached, true, freshness.last_success_at)
         }
-        fetchEvents()
-    }
-} else {
-    store.requestAccess(to: .event) { (granted, error) in
-        if !granted {
-            print("[]")
-            semaphore.signal()
-            return
+    };
+    let mut events: Vec<_> = fetched
+        .into_iter()
+        .filter_map(|mut event| match filter.reason(&event) {
+            None => Some(event),
+            Some(reason) if include_filtered => {
+                event.filtered_reason = Some(reason);
+                Some(event)
+            }
+            Some(_) => None,
+        })
+        .collect();
+    events.sort_by_key(|e| e.start_time());
+    settings.long_events.mark(&mut events);
+    {
+        let state = app.state::<SchedulerState>();
+        let scheduler = state.0.lock().unwrap();
+        let now = simulate::now();
+        for event in events.iter_mut() {
+            event.muted = scheduler.is_muted(event);
+            *event = confidential::shown(&settings, event);
+            event.stamp(now, &settings.format);
         }
-        fetchEvents()
     }
-}
 
-func fetchEvents() {
-    let now = Date()
-    // Look ahead 24 hours
-    let end = Date().addingTimeInterval(24 * 3600)
-    
-    // Fetch all calendars
-    let predicate = store.predicateForEvents(withStart: now, end: end, calendars: nil)
-    let events = store.events(matching: predicate)
-    
-    struct JsonEvent: Codable {
-        let title: String
-        let start: String
-        let end: String
-        let location: String?
-        let description: String?
-        let url: String?
-        let isAllDay: Bool
-    }
-    
-    let formatter = ISO8601DateFormatter()
-    // Ensure we use local time for string output to match user expectation
-    formatter.timeZone = TimeZone.current 
-    
-    let jsonEvents = events.map { event in
-        return JsonEvent(
-            title: event.title,
-            start: formatter.string(from: event.startDate),
-            end: formatter.string(from: event.endDate),
-            location: event.location,
-            description: event.notes,
-            url: event.url?.absoluteString,
-            isAllDay: event.isAllDay
-        )
-    }
-    
-    let encoder = JSONEncoder()
-    if let data = try? encoder.encode(jsonEvents),
-       let string = String(data: data, encoding: .utf8) {
-        print(string)
-    } else {
-        print("[]")
+    let total = events.len();
+    let offset = offset.unwrap_or(0).min(total);
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let mut page: Vec<_> = events.into_iter().skip(offset).take(limit).collect();
+    if !include_details.unwrap_or(false) {
+        page.iter_mut().for_each(calendar::CalendarEvent::prune);
     }
-    semaphore.signal()
+    let next = offset + page.len();
+    let page = calendar::EventPage {
+        events: page,
+        total,
+        next_offset: (next < total).then_some(next),
+        stale,
+        last_success_at,
+    };
+    page.to_json()
 }
 
-semaphore.wait()
-    "#;
-
-    // 1. Write Swift script to a temporary file
-    let temp_dir = env::temp_dir();
-    let script_path = temp_dir.join("fetch_calendar.swift");
-    
-    if let Err(_) = fs::write(&script_path, swift_script) {
-        return "[]".to_string();
-    }
-
-    // 2. Run the Swift script
-    let output = Command::new("/usr/bin/swift")
-        .arg(&script_path)
-        .output();
-
-    // 3. Cleanup (Optional, but good practice)
-    let _ = fs::remove_file(script_path);
-
-    match output {
-        Ok(o) => {
-            let result = String::from_utf8(o.stdout).unwrap_or("[]".to_string());
-            result.trim().to_string()
-        },
-        Err(_) => "[]".to_string(),
-    }
+/// The full event, notes and attendees included.
+#[tauri::command]
+async fn get_event_details(
+    app: AppHandle,
+    event_id: String,
+    occurrence_start: Option<String>,
+) -> Result<calendar::CalendarEventDetails, calendar::DetailsError> {
+    let mut details = tauri::async_runtime::spawn_blocking(move || {
+        calendar::fetch_event_details(&event_id, occurrence_start.as_deref())
+    })
+    .await
+    .map_err(|e| calendar::DetailsError::Failed(e.to_string()))??;
+    let settings = settings::current(&app);
+    details.event = confidential::shown(&settings, &details.event);
+    details.counterpart_local_times = counterparts::for_event(&settings, &details.event);
+    details.event.stamp(simulate::now(), &settings.format);
+    Ok(details)
 }
 
-// --- Window Logic ---
-
 #[tauri::command]
-fn enter_alert_mode(window: WebviewWindow) {
-    window.show().unwrap();
-    window.set_fullscreen(true).unwrap();
-    window.set_always_on_top(true).unwrap();
-    window.set_focus().unwrap();
+fn get_calendars(app: AppHandle) -> Vec<calendar::CalendarInfo> {
+    let filter = settings::current(&app).calendar_filter;
+    calendar::fetch_calendars()
+        .into_iter()
+        .map(|mut info| {
+            info.auto_excluded = filter.is_auto_excluded(&info.title, &info.kind);
+            info.excluded = filter.is_excluded(&info.title, &info.kind);
+            info
+        })
+        .collect()
 }
 
 #[tauri::command]
-fn exit_alert_mode(window: WebviewWindow) {
-    window.set_always_on_top(false).unwrap();
-    window.set_fullscreen(false).unwrap();
+fn open_link(url: String) -> Result<(), String> {
+    let url = untrusted::openable_link(&url)?;
+    open::that(url).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn open_link(url: String) {
-    let _ = open::that(url);
+fn open_in_calendar(event_id: String) {
+    let _ = open::that(calendar::calendar_app_url(&event_id));
 }
 
 fn main() {
-    tauri::Builder::default()
+    let builder = tauri::Builder::default()
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_dialog::init());
+    // A white window whose content process died is rebuilt.
+    #[cfg(target_os = "macos")]
+    let builder = builder.on_web_content_process_terminate(renderer::on_terminated);
+    builder
         .setup(|app| {
-            let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let show_i = MenuItem::with_id(app, "show", "Show Dashboard", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_i, &quit_i])?;
-
-            let _tray = TrayIconBuilder::with_id("tray")
-                .icon(app.default_window_icon().unwrap().clone())
-                .menu(&menu)
-                .show_menu_on_left_click(false)
-                .on_menu_event(|app, event| match event.id().as_ref() {
-                    "quit" => app.exit(0),
-                    "show" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                    _ => {}
-                })
-                .on_tray_icon_event(|tray, event| match event {
-                    TrayIconEvent::Click { button: MouseButton::Left, .. } => {
-                        let app = tray.app_handle();
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                    _ => {}
-                })
-                .build(app)?;
-
+            // Only what has to exist before the event loop runs; the rest
+            // is loaded off the main thread.
+            startup::setup(app.handle(), startup::Live)?;
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            get_calendar_events, 
-            enter_alert_mode, 
-            exit_alert_mode, 
-            open_link
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
\ No newline at end of file
+        .on_window_event(|window, event| {
+            if window.label() == window::ALERT_WINDOW
+                && matches!(event, WindowEvent::Destroyed)
+                && startup::is_ready()
+            {
+                window::on_alert_destroyed(window.app_handle());
+            }
+            if window.label() == popover::POPOVER_WINDOW
+                && matches!(event, WindowEvent::Focused(false))
+            {
+                popover::on_blur(window);
+            }
+        })
+        .invoke_handler(api::checked(tauri::generate_handler![
+            api::get_api_version,
+            api::get_api_manifest,
+            get_calendar_events,
+            get_calendars,
+            get_event_details,
+            window::enter_alert_mode,
+            window::exit_alert_mode,
+            window::set_pill_mode,
+            open_link,
+            open_in_calendar,
+            settings::get_settings,
+            settings::save_settings,
+            templates::preview_template,
+            history::get_alert_history,
+            history::get_punctuality_insights,
+            recent::get_recent_meetings,
+            scheduler::dismiss_alert,
+            dismissal::request_dismiss,
+            dismissal::confirm_dismiss,
+            dismissal::begin_escape,
+            dismissal::end_escape,
+            scheduler::snooze_alert,
+            scheduler::join_meeting,
+            scheduler::get_active_alert,
+            scheduler::get_scheduler_state,
+            webhooks::test_webhook,
+            webhooks::get_integrations_status,
+            health::get_health,
+            selftest::run_self_test,
+            appearance::get_system_appearance,
+            displays::get_overlay_opacity,
+            focus::end_focus_grace,
+            simulate::load_simulation,
+            window::minimize_alert_to_corner,
+            window::restore_alert_fullscreen,
+            window::get_window_pool_stats,
+            sources::get_sources,
+            sources::refresh_source,
+            sources::set_source_enabled,
+            series::adopt_series_settings,
+            rules::get_alert_rules,
+            rules::save_alert_rule,
+            rules::simulate_rule_actions,
+            rules::delete_alert_rule,
+            autotune::reset_auto_tuned_rules,
+            actions::dispatch_action,
+            actions::list_actions,
+            agenda::get_free_gaps,
+            agenda::get_digest,
+            budget::get_meeting_budget_status,
+            agenda::trigger_tomorrow_preview,
+            profiles::list_profiles,
+            profiles::switch_profile,
+            profiles::save_current_as_profile,
+            backup::export_backup,
+            backup::import_backup,
+            backup::rollback_import,
+            location::classify_location,
+            location::open_map,
+            power::get_power_state,
+            presentation::get_presentation_status,
+            lateness::set_lateness_annotation,
+            accessibility::get_accessibility_prefs,
+            overrides::mute_event,
+            overrides::unmute_event,
+            overrides::force_alert,
+            scheduler::pause_until_free,
+            scheduler::resume_reminders,
+            week::get_week_agenda,
+            explain::explain_event,
+            popover::open_dashboard,
+            attendance::export_attendance,
+            retention::get_storage_usage,
+            retention::compact_storage,
+            notes::set_event_note,
+            notes::get_event_note,
+            board::enable_board_mode,
+            board::disable_board_mode,
+            confidential::reveal_event,
+            tray::rebuild_tray,
+            startup::get_startup_timings,
+            features::get_feature_flags,
+            features::set_feature_flag,
+            features::get_whats_new,
+            audio::get_audio_outputs,
+            counterparts::set_contact_timezone,
+            bulk::apply_rules_bulk,
+            bulk::mute_series_bulk,
+            bulk::add_ics_subscriptions_bulk,
+            bulk::copy_from_profile,
+        ]))
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::ExitRequested { .. } = event {
+                shutdown::run(app);
+            }
+        });
+}
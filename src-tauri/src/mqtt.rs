@@ -0,0 +1,298 @@
+use chrono::{DateTime, Local};
+use rumqttc::{Client, ConnectionError, Event, LastWill, MqttOptions, Outgoing, Packet, QoS};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::bus::{self, AppEvent};
+use crate::calendar::CalendarEvent;
+use crate::settings::{self, Settings};
+use crate::simulate;
+use crate::webhooks::{self, LifecycleEvent};
+
+// --- MQTT ---
+//
+// For home automation (a busy light, an "on air" sign): lifecycle events are
+// published to `<topic_prefix>/<event>`, e.g. "in-your-face/alert_fired",
+// with the same body a webhook gets, redacted for the privacy mode the same
+// way. One connection is kept to the broker and rebuilt when its settings
+// change; rumqttc reconnects it when the broker goes away.
+//
+// `<topic_prefix>/availability` says whether the app is there, retained so
+// a busy light that subscribes later still knows: "online" on each connect,
+// "offline" when the app quits or MQTT is turned off, and "offline" from the
+// broker, as the connection's last will, if the app dies or drops off.
+
+const DEFAULT_PORT: u16 = 1883;
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+/// Between attempts while the broker can't be reached.
+const RETRY_WAIT: Duration = Duration::from_secs(5);
+const QUEUE_CAPACITY: usize = 32;
+/// How long quitting waits for "offline" to leave.
+const OFFLINE_WAIT: Duration = Duration::from_secs(1);
+const ONLINE: &str = "online";
+const OFFLINE: &str = "offline";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct MqttSettings {
+    pub enabled: bool,
+    /// Broker host, over plain TCP as brokers on a home network are.
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub topic_prefix: String,
+    /// Lifecycle events to publish; empty means all of them.
+    pub events: Vec<LifecycleEvent>,
+}
+
+impl Default for MqttSettings {
+    fn default() -> Self {
+        MqttSettings {
+            enabled: false,
+            host: String::new(),
+            port: DEFAULT_PORT,
+            username: None,
+            password: None,
+            topic_prefix: "in-your-face".to_string(),
+            events: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct MqttStatus {
+    pub connected: bool,
+    pub last_publish_at: Option<DateTime<Local>>,
+    pub last_error: Option<String>,
+}
+
+static STATUS: Mutex<MqttStatus> = Mutex::new(MqttStatus {
+    connected: false,
+    last_publish_at: None,
+    last_error: None,
+});
+/// The client and the settings it was made with.
+static CLIENT: Mutex<Option<(MqttSettings, Client)>> = Mutex::new(None);
+
+pub fn start(app: &AppHandle) {
+    let events = bus::subscribe(app);
+    let handle = app.clone();
+    thread::spawn(move || {
+        for event in events {
+            match event {
+                AppEvent::Lifecycle(kind, event) => publish(&handle, kind, &event),
+                AppEvent::SettingsChanged => {
+                    let settings = settings::current(&handle).mqtt;
+                    if !settings.enabled {
+                        disconnect();
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+}
+
+pub fn status() -> MqttStatus {
+    STATUS.lock().unwrap().clone()
+}
+
+/// The topic and body for `kind`; the body is a webhook's.
+pub fn message(
+    settings: &Settings,
+    kind: LifecycleEvent,
+    event: &CalendarEvent,
+    now: DateTime<Local>,
+) -> Result<(String, String), String> {
+    let name = webhooks::event_name(kind);
+    let body = webhooks::body(settings, &name, event, now)?;
+    let prefix = settings.mqtt.topic_prefix.trim_end_matches('/');
+    Ok((format!("{}/{}", prefix, name), body))
+}
+
+/// Where the app's availability is published, retained.
+pub fn availability_topic(settings: &MqttSettings) -> String {
+    format!(
+        "{}/availability",
+        settings.topic_prefix.trim_end_matches('/')
+    )
+}
+
+/// Tells the broker the app is going, then waits a moment for that to be
+/// sent; quitting says goodbye this way, as the last will only covers a
+/// connection that drops.
+pub fn publish_offline() {
+    if !disconnect() {
+        return;
+    }
+    let deadline = Instant::now() + OFFLINE_WAIT;
+    while Instant::now() < deadline && STATUS.lock().unwrap().connected {
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+fn publish(app: &AppHandle, kind: LifecycleEvent, event: &CalendarEvent) {
+    let settings = settings::current(app);
+    let mqtt = &settings.mqtt;
+    if !mqtt.enabled
+        || mqtt.host.trim().is_empty()
+        || !(mqtt.events.is_empty() || mqtt.events.contains(&kind))
+    {
+        return;
+    }
+    let Ok((topic, body)) = message(&settings, kind, event, simulate::now()) else {
+        return;
+    };
+    let result = client_for(mqtt).try_publish(topic, QoS::AtLeastOnce, false, body);
+    let mut status = STATUS.lock().unwrap();
+    match result {
+        Ok(()) => status.last_publish_at = Some(Local::now()),
+        Err(e) => status.last_error = Some(e.to_string()),
+    }
+}
+
+/// The client for `settings`, connecting anew when they changed.
+fn client_for(settings: &MqttSettings) -> Client {
+    let mut current = CLIENT.lock().unwrap();
+    if let Some((config, client)) = current.as_ref() {
+        if config == settings {
+            return client.clone();
+        }
+        go_offline(config, client);
+    }
+    let id = format!("in-your-face-{}", std::process::id());
+    let mut options = MqttOptions::new(id, settings.host.trim(), settings.port);
+    options.set_keep_alive(KEEP_ALIVE);
+    let availability = availability_topic(settings);
+    options.set_last_will(LastWill::new(
+        availability.clone(),
+        OFFLINE,
+        QoS::AtLeastOnce,
+        true,
+    ));
+    if let Some(username) = settings.username.as_deref().filter(|u| !u.is_empty()) {
+        options.set_credentials(username, settings.password.clone().unwrap_or_default());
+    }
+    let (client, mut connection) = Client::new(options, QUEUE_CAPACITY);
+    // Ends once every handle to the client is dropped.
+    thread::spawn(move || {
+        for notification in connection.iter() {
+            let mut status = STATUS.lock().unwrap();
+            match notification {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    status.connected = true;
+                    status.last_error = None;
+                    drop(status);
+                    announce_online(&availability);
+                }
+                Ok(Event::Outgoing(Outgoing::Disconnect)) => status.connected = false,
+                Ok(_) => {}
+                Err(error) => {
+                    status.connected = false;
+                    status.last_error = Some(describe(&error));
+                    drop(status);
+                    thread::sleep(RETRY_WAIT);
+                }
+            }
+        }
+    });
+    *current = Some((settings.clone(), client.clone()));
+    client
+}
+
+fn describe(error: &ConnectionError) -> String {
+    match error {
+        ConnectionError::ConnectionRefused(code) => format!("Broker refused: {:?}", code),
+        other => other.to_string(),
+    }
+}
+
+/// Publishes "online" on the current connection, if it is still the one
+/// for `availability`; called on every connect, reconnects included.
+fn announce_online(availability: &str) {
+    if let Some((settings, client)) = CLIENT.lock().unwrap().as_ref() {
+        if availability_topic(settings) == availability {
+            let _ = client.try_publish(availability, QoS::AtLeastOnce, true, ONLINE);
+        }
+    }
+}
+
+/// Publishes "offline" and closes the connection; a disconnect the client
+/// asks for doesn't set off its last will.
+fn go_offline(settings: &MqttSettings, client: &Client) {
+    let _ = client.try_publish(
+        availability_topic(settings),
+        QoS::AtLeastOnce,
+        true,
+        OFFLINE,
+    );
+    let _ = client.try_disconnect();
+}
+
+/// Closes the connection, if there is one; false when there was none.
+fn disconnect() -> bool {
+    let Some((settings, client)) = CLIENT.lock().unwrap().take() else {
+        return false;
+    };
+    go_offline(&settings, &client);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::privacy::PrivacyMode;
+    use crate::testing::{at, event};
+
+    #[test]
+    fn publishes_under_the_prefix() {
+        let mut settings = Settings::default();
+        settings.mqtt.topic_prefix = "home/office/".to_string();
+        let standup = event("Standup", at("2026-03-02 10:00"), 15);
+        let (topic, body) = message(
+            &settings,
+            LifecycleEvent::AlertFired,
+            &standup,
+            at("2026-03-02 09:58"),
+        )
+        .unwrap();
+        assert_eq!(topic, "home/office/alert_fired");
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["event"], "alert_fired");
+        assert_eq!(body["data"]["title"], "Standup");
+    }
+
+    #[test]
+    fn redacts_like_a_webhook() {
+        let settings = Settings {
+            privacy_mode: PrivacyMode::RedactContent,
+            ..Settings::default()
+        };
+        let mut board = event("Board: layoffs Q3", at("2026-03-02 10:00"), 60);
+        board.description = Some("Draft list attached".to_string());
+        let (_, body) = message(
+            &settings,
+            LifecycleEvent::MeetingStarted,
+            &board,
+            at("2026-03-02 10:00"),
+        )
+        .unwrap();
+        assert!(!body.contains("layoffs"), "{}", body);
+        assert!(!body.contains("Draft list"), "{}", body);
+        assert!(body.contains("Busy #"));
+    }
+
+    #[test]
+    fn availability_sits_under_the_prefix() {
+        let settings = MqttSettings {
+            topic_prefix: "home/office/".to_string(),
+            ..MqttSettings::default()
+        };
+        assert_eq!(availability_topic(&settings), "home/office/availability");
+    }
+}
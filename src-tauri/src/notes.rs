@@ -0,0 +1,148 @@
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::calendar::CalendarEvent;
+use crate::overrides;
+
+// --- Event Notes (persisted as JSON in the app data dir) ---
+//
+// A scratchpad per occurrence ("ask about budget, share doc link"), shown on
+// the alert and with the recent meetings. Notes are local only: never
+// written back to the calendar, and left out of everything privacy mode
+// covers. A sticky note belongs to the whole series and shows on every
+// occurrence until cleared; a note for the occurrence itself wins over it.
+// Notes expire with the retention policy unless kept.
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EventNote {
+    pub event_id: String,
+    /// The occurrence's start; `None` for a sticky note.
+    pub occurrence_start: Option<DateTime<Local>>,
+    pub text: String,
+    /// Never expires.
+    #[serde(default)]
+    pub kept: bool,
+    pub updated_at: DateTime<Local>,
+}
+
+impl EventNote {
+    pub fn sticky(&self) -> bool {
+        self.occurrence_start.is_none()
+    }
+
+    /// When the note last mattered: its occurrence, or its last edit for a
+    /// sticky note.
+    fn last_relevant(&self) -> DateTime<Local> {
+        self.occurrence_start.unwrap_or(self.updated_at)
+    }
+}
+
+pub struct EventNotes(pub Mutex<Vec<EventNote>>);
+
+fn notes_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("notes.json"))
+}
+
+pub fn load(app: &AppHandle) -> EventNotes {
+    let notes = notes_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    EventNotes(Mutex::new(notes))
+}
+
+fn persist(app: &AppHandle, notes: &[EventNote]) {
+    let Some(path) = notes_path(app) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(notes) {
+        let _ = fs::write(path, json);
+    }
+}
+
+/// The note for the occurrence, else the series' sticky note.
+fn find(notes: &[EventNote], event_id: &str, start: DateTime<Local>) -> Option<EventNote> {
+    let of_event = || notes.iter().filter(|n| n.event_id == event_id);
+    of_event()
+        .find(|n| n.occurrence_start == Some(start))
+        .or_else(|| of_event().find(|n| n.sticky()))
+        .cloned()
+}
+
+/// The note's text for `event`, if it has one.
+pub fn note_for(app: &AppHandle, event: &CalendarEvent) -> Option<String> {
+    let start = event.start_time()?;
+    let state = app.state::<EventNotes>();
+    let notes = state.0.lock().unwrap();
+    find(&notes, &event.id, start).map(|n| n.text)
+}
+
+/// Drops the notes that weren't kept and last mattered before `before`,
+/// returning how many were dropped.
+pub fn prune(app: &AppHandle, before: DateTime<Local>) -> usize {
+    let state = app.state::<EventNotes>();
+    let mut notes = state.0.lock().unwrap();
+    let count = notes.len();
+    notes.retain(|n| n.kept || n.last_relevant() >= before);
+    persist(app, &notes);
+    count - notes.len()
+}
+
+/// Sets the note of the occurrence starting at `occurrence`, or with
+/// `sticky` the series' note for every occurrence, which also clears the
+/// occurrence's own note so the sticky one shows; empty text clears it.
+#[tauri::command]
+pub fn set_event_note(
+    app: AppHandle,
+    state: State<EventNotes>,
+    event_id: String,
+    occurrence: String,
+    text: String,
+    sticky: Option<bool>,
+    kept: Option<bool>,
+) -> Result<Option<EventNote>, String> {
+    let start = overrides::parse_start(&occurrence)?;
+    let sticky = sticky.unwrap_or(false);
+    let occurrence_start = (!sticky).then_some(start);
+    let mut notes = state.0.lock().unwrap();
+    notes.retain(|n| {
+        n.event_id != event_id
+            || !(n.occurrence_start == occurrence_start
+                || (sticky && n.occurrence_start == Some(start)))
+    });
+    let text = text.trim();
+    let note = (!text.is_empty()).then(|| EventNote {
+        event_id,
+        occurrence_start,
+        text: text.to_string(),
+        kept: kept.unwrap_or(false),
+        updated_at: Local::now(),
+    });
+    notes.extend(note.clone());
+    persist(&app, &notes);
+    Ok(note)
+}
+
+/// The note that shows for the occurrence starting at `occurrence`: its own
+/// or the series' sticky one.
+#[tauri::command]
+pub fn get_event_note(
+    state: State<EventNotes>,
+    event_id: String,
+    occurrence: String,
+) -> Result<Option<EventNote>, String> {
+    let start = overrides::parse_start(&occurrence)?;
+    Ok(find(&state.0.lock().unwrap(), &event_id, start))
+}
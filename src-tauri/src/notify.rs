@@ -0,0 +1,8 @@
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+// --- Native Notifications ---
+
+pub fn show(app: &AppHandle, title: &str, body: &str) {
+    let _ = app.notification().builder().title(title).body(body).show();
+}
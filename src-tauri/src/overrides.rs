@@ -0,0 +1,159 @@
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+use crate::calendar::CalendarEvent;
+use crate::scheduler::SchedulerState;
+
+// --- One-off Overrides (persisted as JSON in the app data dir) ---
+//
+// Mute or force the alert for a single occurrence without touching the
+// calendar or the rules. An override lapses an hour after its occurrence.
+
+const EXPIRE_AFTER_MINUTES: i64 = 60;
+/// A forced alert comes from the start up to a day before it.
+const FORCE_LEAD_MINUTES: RangeInclusive<i64> = 0..=1440;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OverrideKind {
+    Mute,
+    /// Alert even if filters or rules would keep it quiet.
+    Force {
+        lead_minutes: i64,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OccurrenceOverride {
+    pub event_id: String,
+    pub occurrence_start: DateTime<Local>,
+    #[serde(flatten)]
+    pub kind: OverrideKind,
+}
+
+impl OccurrenceOverride {
+    fn is_for(&self, event_id: &str, start: DateTime<Local>) -> bool {
+        self.event_id == event_id && self.occurrence_start == start
+    }
+
+    pub fn applies_to(&self, event: &CalendarEvent) -> bool {
+        event
+            .start_time()
+            .is_some_and(|start| self.is_for(&event.id, start))
+    }
+
+    pub fn is_expired(&self, now: DateTime<Local>) -> bool {
+        self.occurrence_start + Duration::minutes(EXPIRE_AFTER_MINUTES) < now
+    }
+
+    /// A forced lead time in range; the scheduler turns it into a `Duration`.
+    fn is_valid(&self) -> bool {
+        match self.kind {
+            OverrideKind::Mute => true,
+            OverrideKind::Force { lead_minutes } => FORCE_LEAD_MINUTES.contains(&lead_minutes),
+        }
+    }
+}
+
+fn overrides_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("overrides.json"))
+}
+
+pub fn load(app: &AppHandle) -> Vec<OccurrenceOverride> {
+    let overrides: Vec<OccurrenceOverride> = overrides_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    let now = Local::now();
+    overrides
+        .into_iter()
+        .filter(|o| !o.is_expired(now) && o.is_valid())
+        .collect()
+}
+
+fn persist(app: &AppHandle, overrides: &[OccurrenceOverride]) {
+    let Some(path) = overrides_path(app) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(overrides) {
+        let _ = fs::write(path, json);
+    }
+}
+
+pub fn parse_start(occurrence_start: &str) -> Result<DateTime<Local>, String> {
+    DateTime::parse_from_rfc3339(occurrence_start)
+        .map(|t| t.with_timezone(&Local))
+        .map_err(|e| format!("Invalid occurrence start: {}", e))
+}
+
+/// Replaces any override for the occurrence with `kind`, or removes it.
+fn set(
+    app: &AppHandle,
+    event_id: &str,
+    occurrence_start: &str,
+    kind: Option<OverrideKind>,
+) -> Result<(), String> {
+    let start = parse_start(occurrence_start)?;
+    let state = app.state::<SchedulerState>();
+    let mut scheduler = state.0.lock().unwrap();
+    let overrides = scheduler.overrides_mut();
+    overrides.retain(|o| !o.is_for(event_id, start));
+    if let Some(kind) = kind {
+        overrides.push(OccurrenceOverride {
+            event_id: event_id.to_string(),
+            occurrence_start: start,
+            kind,
+        });
+    }
+    persist(app, overrides);
+    scheduler.unqueue_muted();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn mute_event(
+    app: AppHandle,
+    event_id: String,
+    occurrence_start: String,
+) -> Result<(), String> {
+    set(&app, &event_id, &occurrence_start, Some(OverrideKind::Mute))
+}
+
+/// Removes the override for the occurrence, whether a mute or a forced alert.
+#[tauri::command]
+pub fn unmute_event(
+    app: AppHandle,
+    event_id: String,
+    occurrence_start: String,
+) -> Result<(), String> {
+    set(&app, &event_id, &occurrence_start, None)
+}
+
+#[tauri::command]
+pub fn force_alert(
+    app: AppHandle,
+    event_id: String,
+    occurrence_start: String,
+    lead_minutes: i64,
+) -> Result<(), String> {
+    if !FORCE_LEAD_MINUTES.contains(&lead_minutes) {
+        return Err(format!(
+            "Force the alert {} to {} minutes before, not {}",
+            FORCE_LEAD_MINUTES.start(),
+            FORCE_LEAD_MINUTES.end(),
+            lead_minutes
+        ));
+    }
+    let kind = OverrideKind::Force { lead_minutes };
+    set(&app, &event_id, &occurrence_start, Some(kind))
+}
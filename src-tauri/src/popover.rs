@@ -0,0 +1,158 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{
+    AppHandle, LogicalSize, Manager, Monitor, PhysicalPosition, Rect, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder, Window,
+};
+
+use crate::{retention, tray, window};
+
+// --- Agenda Popover ---
+//
+// A left click on the menu bar icon opens a small window under it with the
+// next few events and their join buttons, the usual menu bar app; another
+// click or clicking anywhere else closes it. The menu stays on right click.
+// The window is placed from the icon's own rect, on whichever display that
+// menu bar is on, and kept inside that display's work area.
+
+pub const POPOVER_WINDOW: &str = "popover";
+
+/// In points.
+const POPOVER_SIZE: (f64, f64) = (360.0, 420.0);
+/// Between the icon and the popover, in points.
+const GAP: f64 = 4.0;
+/// From the display's edges, in points.
+const MARGIN: f64 = 8.0;
+/// A click on the icon blurs the popover before the click arrives; a hide
+/// this recent was that click's, which then shouldn't reopen it.
+const REOPEN_GUARD: Duration = Duration::from_millis(300);
+
+static HIDDEN_AT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// What a left click on the menu bar icon opens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayClick {
+    #[default]
+    Popover,
+    Dashboard,
+}
+
+fn create(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    WebviewWindowBuilder::new(app, POPOVER_WINDOW, WebviewUrl::App("index.html".into()))
+        .title("Agenda")
+        .inner_size(POPOVER_SIZE.0, POPOVER_SIZE.1)
+        .visible(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .resizable(false)
+        .build()
+}
+
+/// The icon's rect in physical pixels: origin and size.
+fn icon_rect(rect: &Rect, scale: f64) -> ((f64, f64), (f64, f64)) {
+    let position = rect.position.to_physical::<f64>(scale);
+    let size = rect.size.to_physical::<f64>(scale);
+    ((position.x, position.y), (size.width, size.height))
+}
+
+/// Top-left corner of the popover, in physical pixels: centered under the
+/// icon, or above it when the bar is at the bottom of the display, and
+/// clamped to the work area so it never hangs off the side.
+fn anchor(monitor: &Monitor, rect: &Rect) -> PhysicalPosition<i32> {
+    let scale = monitor.scale_factor();
+    let ((x, y), (width, height)) = icon_rect(rect, scale);
+    let (popover_width, popover_height) = (POPOVER_SIZE.0 * scale, POPOVER_SIZE.1 * scale);
+    let (gap, margin) = (GAP * scale, MARGIN * scale);
+
+    let area = monitor.work_area();
+    let left = f64::from(area.position.x);
+    let top = f64::from(area.position.y);
+    let right = left + f64::from(area.size.width);
+    let bottom = top + f64::from(area.size.height);
+    let display_middle = f64::from(monitor.position().y) + f64::from(monitor.size().height) / 2.0;
+
+    let popover_x = (x + width / 2.0 - popover_width / 2.0)
+        .min(right - popover_width - margin)
+        .max(left + margin);
+    let popover_y = if y < display_middle {
+        (y + height + gap).max(top)
+    } else {
+        (y - popover_height - gap).min(bottom - popover_height)
+    };
+    PhysicalPosition::new(popover_x.round() as i32, popover_y.round() as i32)
+}
+
+/// The display the icon that was clicked is on, which needn't be the main
+/// one when every display has a menu bar.
+fn icon_display(app: &AppHandle, rect: &Rect) -> Option<Monitor> {
+    let scale = app
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map_or(1.0, |m| m.scale_factor());
+    let ((x, y), (width, height)) = icon_rect(rect, scale);
+    app.monitor_from_point(x + width / 2.0, y + height / 2.0)
+        .ok()
+        .flatten()
+        .or_else(|| app.primary_monitor().ok().flatten())
+}
+
+fn hide(window: &WebviewWindow) {
+    *HIDDEN_AT.lock().unwrap() = Some(Instant::now());
+    let _ = window.hide();
+}
+
+/// Opens the popover under the icon at `rect`, or closes it if it is open.
+pub fn toggle(app: &AppHandle, rect: Rect) {
+    if let Some(window) = app.get_webview_window(POPOVER_WINDOW) {
+        if window.is_visible().unwrap_or(false) {
+            hide(&window);
+            return;
+        }
+    }
+    if HIDDEN_AT
+        .lock()
+        .unwrap()
+        .is_some_and(|at| at.elapsed() < REOPEN_GUARD)
+    {
+        return;
+    }
+    let window = match app.get_webview_window(POPOVER_WINDOW) {
+        Some(window) => window,
+        None => match create(app) {
+            Ok(window) => window,
+            Err(e) => {
+                retention::log!("failed to create the popover: {}", e);
+                return;
+            }
+        },
+    };
+    if let Some(monitor) = icon_display(app, &rect) {
+        // Moved first, so the size is taken at that display's scale.
+        let _ = window.set_position(anchor(&monitor, &rect));
+        let _ = window.set_size(LogicalSize::new(POPOVER_SIZE.0, POPOVER_SIZE.1));
+    }
+    let _ = window.show();
+    window::activate_app(&window);
+    let _ = window.set_focus();
+}
+
+/// Closes the popover once it loses focus.
+pub fn on_blur(window: &Window) {
+    if let Some(window) = window.app_handle().get_webview_window(POPOVER_WINDOW) {
+        hide(&window);
+    }
+}
+
+/// The popover's "Open Dashboard" button.
+#[tauri::command]
+pub fn open_dashboard(app: AppHandle) {
+    if let Some(window) = app.get_webview_window(POPOVER_WINDOW) {
+        hide(&window);
+    }
+    tray::show_dashboard(&app);
+}
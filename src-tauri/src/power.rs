@@ -0,0 +1,129 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::settings::{self, Settings};
+
+// --- Power ---
+//
+// On battery the app switches to a low-power profile: the calendar is polled
+// less often and the dimming overlays are skipped.
+
+/// Poll interval used on battery, unless the configured one is longer.
+pub const LOW_POWER_POLL_SECONDS: u64 = 300;
+const RECHECK: Duration = Duration::from_secs(30);
+
+static CACHED: Mutex<Option<(Instant, PowerSource)>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerSource {
+    Ac,
+    Battery,
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerProfile {
+    HighFrequency,
+    LowPower,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PowerSettings {
+    /// Stay in high-frequency mode even on battery.
+    pub always_high_frequency: bool,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct PowerState {
+    pub source: PowerSource,
+    pub profile: PowerProfile,
+}
+
+#[cfg(target_os = "macos")]
+fn read_source() -> PowerSource {
+    use std::ffi::{c_char, c_void, CStr};
+
+    type CFTypeRef = *const c_void;
+    const UTF8: u32 = 0x0800_0100;
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOPSCopyPowerSourcesInfo() -> CFTypeRef;
+        fn IOPSGetProvidingPowerSourceType(snapshot: CFTypeRef) -> CFTypeRef;
+    }
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFRelease(cf: CFTypeRef);
+        fn CFStringGetCString(s: CFTypeRef, buf: *mut c_char, size: isize, encoding: u32) -> u8;
+    }
+
+    // SAFETY: the snapshot is released once read; the source type string
+    // belongs to the snapshot and is copied out before that.
+    unsafe {
+        let snapshot = IOPSCopyPowerSourcesInfo();
+        if snapshot.is_null() {
+            return PowerSource::Unknown;
+        }
+        let kind = IOPSGetProvidingPowerSourceType(snapshot);
+        let mut buf = [0 as c_char; 64];
+        let ok = !kind.is_null()
+            && CFStringGetCString(kind, buf.as_mut_ptr(), buf.len() as isize, UTF8) != 0;
+        CFRelease(snapshot);
+        if !ok {
+            return PowerSource::Unknown;
+        }
+        match CStr::from_ptr(buf.as_ptr()).to_str() {
+            Ok("AC Power") => PowerSource::Ac,
+            Ok("Battery Power") | Ok("UPS Power") => PowerSource::Battery,
+            _ => PowerSource::Unknown,
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn read_source() -> PowerSource {
+    PowerSource::Unknown
+}
+
+/// Current power source, re-read at most every 30 seconds.
+pub fn source() -> PowerSource {
+    let mut cached = CACHED.lock().unwrap();
+    if let Some((at, source)) = *cached {
+        if at.elapsed() < RECHECK {
+            return source;
+        }
+    }
+    let source = read_source();
+    *cached = Some((Instant::now(), source));
+    source
+}
+
+pub fn profile(settings: &Settings) -> PowerProfile {
+    if !settings.power.always_high_frequency && source() == PowerSource::Battery {
+        PowerProfile::LowPower
+    } else {
+        PowerProfile::HighFrequency
+    }
+}
+
+pub fn poll_interval_seconds(settings: &Settings) -> u64 {
+    match profile(settings) {
+        PowerProfile::HighFrequency => settings.poll_interval_seconds,
+        PowerProfile::LowPower => settings.poll_interval_seconds.max(LOW_POWER_POLL_SECONDS),
+    }
+}
+
+#[tauri::command]
+pub fn get_power_state(app: AppHandle) -> PowerState {
+    let settings = settings::current(&app);
+    PowerState {
+        source: source(),
+        profile: profile(&settings),
+    }
+}
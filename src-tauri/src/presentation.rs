@@ -0,0 +1,228 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Monitor};
+
+use crate::bus::{self, AppEvent};
+use crate::rules::{AlertDecision, AlertStyle};
+use crate::scheduler::SchedulerState;
+use crate::settings::{self, Settings};
+use crate::window;
+
+// --- Presentation ---
+//
+// Plugged into a projector or AirPlaying to a conference-room TV, a
+// fullscreen alert would land on whatever is being presented. While a
+// display is mirrored, or one is named like a TV or projector, alerts (per
+// `PresentationSettings`) show as a corner card on the built-in display
+// only, and never cover the other displays. Without a built-in display of
+// its own, or with the built-in one mirrored too, they fall back to a
+// notification. Polled every tick, so unplugging ends it right away.
+
+/// Words in a display name that mark it as presented to a room.
+const PRESENTATION_HINTS: [&str; 5] = ["airplay", "tv", "hdtv", "projector", "beamer"];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PresentationAction {
+    /// Alerts behave as usual.
+    Ignore,
+    /// A corner card on the built-in display.
+    #[default]
+    Toast,
+    /// A notification instead of the alert window.
+    Notification,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PresentationSettings {
+    /// What alerts do while presenting.
+    pub action: PresentationAction,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentationStatus {
+    pub presenting: bool,
+    /// e.g. "Screen mirroring" or "Presenting to Apple TV".
+    pub reason: Option<String>,
+    /// The built-in display a toast can go to; `None` while mirrored too.
+    pub toast_display: Option<String>,
+}
+
+static STATUS: Mutex<PresentationStatus> = Mutex::new(PresentationStatus {
+    presenting: false,
+    reason: None,
+    toast_display: None,
+});
+/// Top-left corner, in points, of the built-in display while it is usable
+/// for toasts.
+static BUILT_IN: Mutex<Option<(f64, f64)>> = Mutex::new(None);
+
+/// What CoreGraphics says about the connected displays.
+#[derive(Default)]
+struct DisplayScan {
+    mirrored: bool,
+    /// Top-left corner, in points, of a built-in display outside any mirror
+    /// set.
+    built_in: Option<(f64, f64)>,
+}
+
+#[cfg(target_os = "macos")]
+fn scan() -> DisplayScan {
+    #[repr(C)]
+    struct CGPoint {
+        x: f64,
+        y: f64,
+    }
+    #[repr(C)]
+    struct CGSize {
+        width: f64,
+        height: f64,
+    }
+    #[repr(C)]
+    struct CGRect {
+        origin: CGPoint,
+        size: CGSize,
+    }
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGGetOnlineDisplayList(max: u32, displays: *mut u32, count: *mut u32) -> i32;
+        fn CGDisplayIsInMirrorSet(display: u32) -> u32;
+        fn CGDisplayIsBuiltin(display: u32) -> u32;
+        fn CGDisplayBounds(display: u32) -> CGRect;
+    }
+
+    let mut displays = [0u32; 16];
+    let mut count = 0u32;
+    // SAFETY: the list has room for the 16 displays asked for, and the
+    // other calls take display ids CoreGraphics just returned.
+    unsafe {
+        if CGGetOnlineDisplayList(displays.len() as u32, displays.as_mut_ptr(), &mut count) != 0 {
+            return DisplayScan::default();
+        }
+        let online = &displays[..(count as usize).min(displays.len())];
+        let mirrored = online.iter().any(|&d| CGDisplayIsInMirrorSet(d) != 0);
+        let built_in = online
+            .iter()
+            .find(|&&d| CGDisplayIsBuiltin(d) != 0 && CGDisplayIsInMirrorSet(d) == 0)
+            .map(|&d| {
+                let bounds = CGDisplayBounds(d);
+                (bounds.origin.x, bounds.origin.y)
+            });
+        DisplayScan { mirrored, built_in }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn scan() -> DisplayScan {
+    DisplayScan::default()
+}
+
+fn is_presentation_display(name: &str) -> bool {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| PRESENTATION_HINTS.contains(&word))
+}
+
+fn at_origin(monitor: &Monitor, origin: (f64, f64)) -> bool {
+    let position = monitor.position().to_logical::<f64>(monitor.scale_factor());
+    (position.x - origin.0).abs() < 1.0 && (position.y - origin.1).abs() < 1.0
+}
+
+pub fn is_presenting() -> bool {
+    STATUS.lock().unwrap().presenting
+}
+
+/// The built-in display, while alerts should go there as a toast.
+pub fn toast_monitor(app: &AppHandle) -> Option<Monitor> {
+    if !is_presenting() || settings::current(app).presentation.action != PresentationAction::Toast {
+        return None;
+    }
+    let origin = (*BUILT_IN.lock().unwrap())?;
+    app.available_monitors()
+        .ok()?
+        .into_iter()
+        .find(|m| at_origin(m, origin))
+}
+
+/// Turns fullscreen alerts into notifications while presenting, when the
+/// settings ask for that or there is no built-in display for a toast.
+pub fn downgrade(settings: &Settings, decision: &mut AlertDecision) {
+    if decision.style != AlertStyle::Fullscreen {
+        return;
+    }
+    let status = STATUS.lock().unwrap();
+    let notify = match settings.presentation.action {
+        PresentationAction::Ignore => false,
+        PresentationAction::Toast => status.toast_display.is_none(),
+        PresentationAction::Notification => true,
+    };
+    if status.presenting && notify {
+        decision.style = AlertStyle::Notification;
+    }
+}
+
+/// Publishes `PresentationChanged` when presenting starts or stops.
+pub fn poll(app: &AppHandle) {
+    let monitors = app.available_monitors().unwrap_or_default();
+    let displays = scan();
+    let reason = if displays.mirrored {
+        Some("Screen mirroring".to_string())
+    } else {
+        monitors
+            .iter()
+            .filter_map(|m| m.name())
+            .find(|name| is_presentation_display(name))
+            .map(|name| format!("Presenting to {}", name))
+    };
+    let toast_display = displays.built_in.and_then(|origin| {
+        let monitor = monitors.iter().find(|m| at_origin(m, origin))?;
+        Some(
+            monitor
+                .name()
+                .cloned()
+                .unwrap_or_else(|| "Built-in display".to_string()),
+        )
+    });
+    let status = PresentationStatus {
+        presenting: reason.is_some(),
+        reason,
+        toast_display,
+    };
+    *BUILT_IN.lock().unwrap() = displays.built_in;
+    let was_presenting = {
+        let mut current = STATUS.lock().unwrap();
+        if *current == status {
+            return;
+        }
+        std::mem::replace(&mut *current, status.clone()).presenting
+    };
+    let presenting = status.presenting;
+    bus::publish(app, AppEvent::PresentationChanged(status));
+    if presenting == was_presenting {
+        return;
+    }
+    // An alert already up moves off the presented display, and back to
+    // fullscreen once presenting ends.
+    let alert_showing = app
+        .state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .has_active_alert();
+    if alert_showing && settings::current(app).presentation.action == PresentationAction::Toast {
+        if presenting {
+            window::raise_alert(app);
+        } else {
+            let _ = window::restore_alert_fullscreen(app.clone());
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_presentation_status() -> PresentationStatus {
+    STATUS.lock().unwrap().clone()
+}
@@ -0,0 +1,188 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::calendar::CalendarEvent;
+use crate::settings::Settings;
+
+// --- Privacy Mode ---
+//
+// What leaves the app about events: webhook bodies, push notifications, the
+// status file and log lines. Each of those boundaries goes through
+// `external`, which serializes the value and redacts the JSON by field name,
+// so a new field on `CalendarEvent` is covered without touching the
+// integrations. What shows on screen is up to the calendar's display policy
+// instead; see `confidential`.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyMode {
+    #[default]
+    Off,
+    /// Titles become "Busy #1a2b3c4d", the same for the same title, so
+    /// occurrences can still be told apart; notes, attendees, locations and
+    /// links are left out.
+    RedactContent,
+    /// Titles stay; everything else about the event is left out.
+    TitlesOnly,
+}
+
+/// Fields holding an event's title, in any of the serialized structs.
+const TITLE_FIELDS: [&str; 2] = ["title", "displayText"];
+/// Fields holding an event's content; left out entirely. `note` is the
+/// user's own note for the occurrence.
+const CONTENT_FIELDS: [&str; 10] = [
+    "description",
+    "note",
+    "location",
+    "locationDisplay",
+    "mapUrl",
+    "url",
+    "videoLink",
+    "organizer",
+    "attendees",
+    "hasCoordinates",
+];
+
+pub fn redact_title(mode: PrivacyMode, title: &str) -> String {
+    match mode {
+        PrivacyMode::RedactContent => {
+            let digest = Sha256::digest(title.trim().as_bytes());
+            format!("Busy #{}", &hex::encode(digest)[..8])
+        }
+        PrivacyMode::Off | PrivacyMode::TitlesOnly => title.to_string(),
+    }
+}
+
+fn redact(value: &mut Value, mode: PrivacyMode) {
+    match value {
+        Value::Object(map) => {
+            for field in CONTENT_FIELDS {
+                map.remove(field);
+            }
+            for field in TITLE_FIELDS {
+                if let Some(Value::String(title)) = map.get_mut(field) {
+                    *title = redact_title(mode, title);
+                }
+            }
+            map.values_mut().for_each(|v| redact(v, mode));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| redact(v, mode)),
+        _ => {}
+    }
+}
+
+/// `value` as JSON, redacted for the privacy mode; for anything written
+/// outside the app.
+pub fn external<T: Serialize>(settings: &Settings, value: &T) -> Result<Value, String> {
+    let mut json = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    if settings.privacy_mode != PrivacyMode::Off {
+        redact(&mut json, settings.privacy_mode);
+    }
+    Ok(json)
+}
+
+/// A redacted copy of the event, for text built from it (templates, push
+/// messages, logs). Goes through `external`, so both agree on what is left.
+pub fn event(settings: &Settings, event: &CalendarEvent) -> CalendarEvent {
+    if settings.privacy_mode == PrivacyMode::Off {
+        return event.clone();
+    }
+    external(settings, event)
+        .and_then(|json| serde_json::from_value(json).map_err(|e| e.to_string()))
+        .unwrap_or_else(|_| CalendarEvent {
+            title: redact_title(PrivacyMode::RedactContent, &event.title),
+            location: None,
+            description: None,
+            url: None,
+            organizer: None,
+            attendees: Vec::new(),
+            video_link: None,
+            location_display: None,
+            map_url: None,
+            ..event.clone()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::Attendee;
+    use crate::testing::{at, event};
+    use crate::webhooks::LifecycleEvent;
+    use crate::{mqtt, templates, webhooks};
+
+    const SECRETS: [&str; 4] = ["Layoffs", "Draft list", "Room 9", "ceo@example.com"];
+
+    fn sensitive() -> CalendarEvent {
+        let mut event = event("Layoffs Q3", at("2026-03-02 10:00"), 60);
+        event.description = Some("Draft list attached".to_string());
+        event.location = Some("Room 9".to_string());
+        event.organizer = Some("ceo@example.com".to_string());
+        event.attendees = vec![Attendee {
+            name: Some("CEO".to_string()),
+            email: Some("ceo@example.com".to_string()),
+            status: "accepted".to_string(),
+            is_current_user: false,
+        }];
+        event
+    }
+
+    fn assert_clean(surface: &str, artifact: &str) {
+        for secret in SECRETS {
+            assert!(
+                !artifact.contains(secret),
+                "{} has {}: {}",
+                surface,
+                secret,
+                artifact
+            );
+        }
+    }
+
+    #[test]
+    fn nothing_raw_leaves_in_redact_content() {
+        let settings = Settings {
+            privacy_mode: PrivacyMode::RedactContent,
+            ..Settings::default()
+        };
+        let event = sensitive();
+        let now = at("2026-03-02 09:55");
+
+        let webhook = webhooks::body(&settings, "alert_fired", &event, now).unwrap();
+        assert_clean("webhook", &webhook);
+        let (_, body) = mqtt::message(&settings, LifecycleEvent::AlertFired, &event, now).unwrap();
+        assert_clean("mqtt", &body);
+        let shown = super::event(&settings, &event);
+        assert_clean(
+            "status line",
+            &templates::status_line(&settings, &shown, now),
+        );
+        assert_clean(
+            "log line",
+            &redact_title(settings.privacy_mode, &event.title),
+        );
+    }
+
+    #[test]
+    fn titles_only_keeps_the_title_and_nothing_else() {
+        let settings = Settings {
+            privacy_mode: PrivacyMode::TitlesOnly,
+            ..Settings::default()
+        };
+        let json = external(&settings, &sensitive()).unwrap();
+        assert_eq!(json["title"], "Layoffs Q3");
+        for field in CONTENT_FIELDS {
+            assert!(json.get(field).is_none(), "{} left in", field);
+        }
+    }
+
+    #[test]
+    fn the_same_title_hashes_the_same() {
+        let a = redact_title(PrivacyMode::RedactContent, "Standup");
+        assert_eq!(a, redact_title(PrivacyMode::RedactContent, " Standup "));
+        assert_ne!(a, redact_title(PrivacyMode::RedactContent, "Retro"));
+        assert_eq!(redact_title(PrivacyMode::Off, "Standup"), "Standup");
+    }
+}
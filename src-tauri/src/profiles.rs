@@ -0,0 +1,117 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State, Window};
+
+use crate::bus::{self, AppEvent};
+use crate::calendar::CalendarFilter;
+use crate::ipc::{self, IpcError};
+use crate::rules::AlertRule;
+use crate::scheduler::SchedulerState;
+use crate::settings::{self, EscalationSettings, Settings, SettingsState, WebhookConfig};
+
+// --- Profiles ---
+//
+// A profile is a named snapshot of the parts of the settings that differ
+// between contexts (work / personal). Switching copies it over the settings.
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    pub calendar_filter: CalendarFilter,
+    pub rules: Vec<AlertRule>,
+    pub webhooks: Vec<WebhookConfig>,
+    pub escalation: EscalationSettings,
+}
+
+/// Profiles to switch to automatically at the start of each day.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ProfileSchedule {
+    pub enabled: bool,
+    /// On days with working hours.
+    pub weekday: Option<String>,
+    /// On days without any, per `working_hours`.
+    pub weekend: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct ProfileList {
+    pub active: Option<String>,
+    pub profiles: Vec<Profile>,
+}
+
+impl Profile {
+    fn capture(name: &str, settings: &Settings) -> Profile {
+        Profile {
+            name: name.to_string(),
+            calendar_filter: settings.calendar_filter.clone(),
+            rules: settings.rules.clone(),
+            webhooks: settings.webhooks.clone(),
+            escalation: settings.escalation.clone(),
+        }
+    }
+
+    fn apply_to(&self, settings: &mut Settings) {
+        settings.calendar_filter = self.calendar_filter.clone();
+        settings.rules = self.rules.clone();
+        settings.webhooks = self.webhooks.clone();
+        settings.escalation = self.escalation.clone();
+        settings.active_profile = Some(self.name.clone());
+    }
+}
+
+/// Applies the profile while holding the scheduler lock, so no alert is
+/// evaluated against a mix of old and new rules.
+pub fn switch(app: &AppHandle, name: &str) -> Result<(), String> {
+    let profile = settings::current(app)
+        .profiles
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or(format!("Unknown profile: {}", name))?;
+
+    let state = app.state::<SchedulerState>();
+    let mut scheduler = state.0.lock().unwrap();
+    let settings = settings::update(app, |settings| profile.apply_to(settings))?;
+    scheduler.reschedule(&settings);
+    drop(scheduler);
+
+    bus::publish(app, AppEvent::ProfileChanged(name.to_string()));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_profiles(state: State<SettingsState>) -> ProfileList {
+    let settings = state.0.lock().unwrap();
+    ProfileList {
+        active: settings.active_profile.clone(),
+        profiles: settings.profiles.clone(),
+    }
+}
+
+#[tauri::command]
+pub fn switch_profile(app: AppHandle, window: Window, name: String) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "switch_profile")?;
+    Ok(switch(&app, &name)?)
+}
+
+#[tauri::command]
+pub fn save_current_as_profile(
+    app: AppHandle,
+    window: Window,
+    name: String,
+) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "save_current_as_profile")?;
+    if name.trim().is_empty() {
+        return Err("Profile name is required".to_string().into());
+    }
+    settings::update(&app, |settings| {
+        let profile = Profile::capture(&name, settings);
+        match settings.profiles.iter_mut().find(|p| p.name == name) {
+            Some(existing) => *existing = profile,
+            None => settings.profiles.push(profile),
+        }
+        settings.active_profile = Some(name.clone());
+    })?;
+    Ok(())
+}
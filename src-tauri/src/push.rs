@@ -0,0 +1,16 @@
+use std::thread;
+
+// --- Push Notifications (ntfy.sh or any plain-text webhook) ---
+
+/// Fire-and-forget POST so a slow endpoint never delays the alert.
+pub fn send(url: String, title: String, message: String) {
+    thread::spawn(move || {
+        let client = reqwest::blocking::Client::new();
+        let _ = client
+            .post(&url)
+            .header("Title", title)
+            .header("Priority", "high")
+            .body(message)
+            .send();
+    });
+}
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Duration, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use tauri::{AppHandle, Manager, State};
+
+use crate::bus::{self, AppEvent};
+use crate::calendar::CalendarEvent;
+use crate::settings::{self, Settings};
+use crate::{confidential, notes, tray};
+
+// --- Recent Meetings (persisted as JSON in the app data dir) ---
+//
+// The last meetings that alerted, with everything known about them, for
+// looking up a link, an attendee or a doc after the meeting has left the
+// 24-hour window. Bounded in number and age. With `persist` off nothing about
+// them is written to disk; only this run's meetings are kept, in memory.
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RecentMeetingSettings {
+    pub limit: usize,
+    pub keep_days: i64,
+    /// Privacy: off keeps event content out of the app data dir.
+    pub persist: bool,
+}
+
+impl Default for RecentMeetingSettings {
+    fn default() -> Self {
+        RecentMeetingSettings {
+            limit: 20,
+            keep_days: 7,
+            persist: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentMeeting {
+    pub event: CalendarEvent,
+    pub alerted_at: DateTime<Local>,
+    /// The occurrence's note as it is now; looked up when listed, never
+    /// stored with the entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// The meeting as it may show: with its note, redacted to its display
+/// policy.
+fn shown(app: &AppHandle, settings: &Settings, mut meeting: RecentMeeting) -> RecentMeeting {
+    let policy = confidential::policy(settings, &meeting.event);
+    meeting.note = notes::note_for(app, &meeting.event).filter(|_| policy.shows_note());
+    meeting.event = confidential::redact(policy, &meeting.event);
+    meeting
+}
+
+/// Oldest first.
+pub struct RecentMeetings(pub Mutex<Vec<RecentMeeting>>);
+
+fn recent_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("recent-meetings.json"))
+}
+
+fn prune(entries: &mut Vec<RecentMeeting>, settings: &RecentMeetingSettings, now: DateTime<Local>) {
+    let cutoff = now - Duration::days(settings.keep_days);
+    entries.retain(|entry| entry.alerted_at > cutoff);
+    if entries.len() > settings.limit {
+        let overflow = entries.len() - settings.limit;
+        entries.drain(..overflow);
+    }
+}
+
+/// Writes the entries, or removes the file while persisting is off.
+fn persist(app: &AppHandle, settings: &Settings, entries: &[RecentMeeting]) {
+    let Some(path) = recent_path(app) else {
+        return;
+    };
+    if !settings.recent_meetings.persist {
+        let _ = fs::remove_file(path);
+        return;
+    }
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(entries) {
+        let _ = fs::write(path, json);
+    }
+}
+
+pub fn load(app: &AppHandle) -> RecentMeetings {
+    let settings = settings::current(app);
+    let mut entries: Vec<RecentMeeting> = recent_path(app)
+        .filter(|_| settings.recent_meetings.persist)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    prune(&mut entries, &settings.recent_meetings, Local::now());
+    persist(app, &settings, &entries);
+    RecentMeetings(Mutex::new(entries))
+}
+
+/// Applies a change of the settings right away, e.g. deletes the file when
+/// persisting is turned off.
+pub fn start(app: &AppHandle) {
+    let events = bus::subscribe(app);
+    let app = app.clone();
+    thread::spawn(move || {
+        for event in events {
+            if let AppEvent::SettingsChanged = event {
+                let settings = settings::current(&app);
+                let state = app.state::<RecentMeetings>();
+                let mut entries = state.0.lock().unwrap();
+                prune(&mut entries, &settings.recent_meetings, Local::now());
+                persist(&app, &settings, &entries);
+            }
+        }
+    });
+}
+
+/// Keeps a snapshot of an event that just alerted; an occurrence alerting
+/// again replaces its earlier entry.
+pub fn record(app: &AppHandle, event: &CalendarEvent) {
+    let settings = settings::current(app);
+    {
+        let state = app.state::<RecentMeetings>();
+        let mut entries = state.0.lock().unwrap();
+        let key = event.occurrence_key();
+        entries.retain(|entry| entry.event.occurrence_key() != key);
+        entries.push(RecentMeeting {
+            event: event.clone(),
+            alerted_at: Local::now(),
+            note: None,
+        });
+        prune(&mut entries, &settings.recent_meetings, Local::now());
+        persist(app, &settings, &entries);
+    }
+    tray::refresh(app);
+}
+
+/// Newest first, expired entries left out.
+pub fn list(app: &AppHandle, limit: usize) -> Vec<RecentMeeting> {
+    let settings = settings::current(app);
+    let cutoff = Local::now() - Duration::days(settings.recent_meetings.keep_days);
+    let state = app.state::<RecentMeetings>();
+    let entries: Vec<RecentMeeting> = state
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .rev()
+        .filter(|entry| entry.alerted_at > cutoff)
+        .take(limit)
+        .cloned()
+        .collect();
+    entries
+        .into_iter()
+        .map(|e| shown(app, &settings, e))
+        .collect()
+}
+
+pub fn find(app: &AppHandle, occurrence_key: &str) -> Option<RecentMeeting> {
+    let state = app.state::<RecentMeetings>();
+    let entry = state
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|entry| entry.event.occurrence_key() == occurrence_key)
+        .cloned();
+    let settings = settings::current(app);
+    entry.map(|e| shown(app, &settings, e))
+}
+
+#[tauri::command]
+pub fn get_recent_meetings(
+    app: AppHandle,
+    state: State<RecentMeetings>,
+    limit: Option<usize>,
+) -> Vec<RecentMeeting> {
+    let limit = limit.unwrap_or_else(|| state.0.lock().unwrap().len());
+    list(&app, limit)
+}
@@ -0,0 +1,164 @@
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Webview};
+
+use crate::scheduler::{self, Acknowledgement, SchedulerState};
+use crate::{confidential, notify, retention, settings, tray, window};
+
+// --- Renderer Crashes ---
+//
+// WebKit draws each webview in a content process of its own. When that
+// process dies, the window stays up but white, and nothing in it runs: the
+// dashboard is blank and an alert can't show. `on_terminated`, WebKit's
+// hook for it, destroys the window and builds it again through the
+// ensure-window helpers; an alert that was showing comes back in the new
+// one as after any destroyed alert window (`window::on_alert_destroyed`),
+// and the frontend takes it from `get_active_alert` once loaded. Other
+// webviews are reloaded in place. If the alert window crashes
+// `CRASH_LIMIT` times within `CRASH_PERIOD`, fullscreen alerts turn into
+// notifications until the app restarts (see `rules`): a banner is better
+// than an alert that never renders.
+
+const CRASH_LIMIT: usize = 3;
+const CRASH_PERIOD: Duration = Duration::from_secs(10 * 60);
+/// How long to wait for a destroyed window to be gone before rebuilding it.
+const DESTROY_TIMEOUT: Duration = Duration::from_secs(2);
+const DESTROY_POLL: Duration = Duration::from_millis(50);
+const WARNING_KEY: &str = "renderer";
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashStats {
+    pub dashboard: u32,
+    pub alert: u32,
+    /// Popover, board and overlay webviews.
+    pub other: u32,
+    pub last_crash_at: Option<DateTime<Local>>,
+    /// Fullscreen alerts are notifications until the next launch.
+    pub alerts_as_notifications: bool,
+}
+
+static STATS: Mutex<CrashStats> = Mutex::new(CrashStats {
+    dashboard: 0,
+    alert: 0,
+    other: 0,
+    last_crash_at: None,
+    alerts_as_notifications: false,
+});
+/// When the alert window crashed, within the last `CRASH_PERIOD`.
+static ALERT_CRASHES: Mutex<VecDeque<Instant>> = Mutex::new(VecDeque::new());
+
+pub fn stats() -> CrashStats {
+    STATS.lock().unwrap().clone()
+}
+
+pub fn alerts_as_notifications() -> bool {
+    STATS.lock().unwrap().alerts_as_notifications
+}
+
+/// Counts a crash of `label`; true if it is the one that gives up on the
+/// alert window.
+fn record(label: &str) -> bool {
+    let mut stats = STATS.lock().unwrap();
+    stats.last_crash_at = Some(Local::now());
+    match label {
+        window::MAIN_WINDOW => stats.dashboard += 1,
+        window::ALERT_WINDOW => stats.alert += 1,
+        _ => stats.other += 1,
+    }
+    if label != window::ALERT_WINDOW || stats.alerts_as_notifications {
+        return false;
+    }
+    let mut crashes = ALERT_CRASHES.lock().unwrap();
+    let now = Instant::now();
+    crashes.push_back(now);
+    while crashes
+        .front()
+        .is_some_and(|at| now.duration_since(*at) > CRASH_PERIOD)
+    {
+        crashes.pop_front();
+    }
+    stats.alerts_as_notifications = crashes.len() >= CRASH_LIMIT;
+    stats.alerts_as_notifications
+}
+
+/// Called by WebKit when a webview's content process ended.
+pub fn on_terminated(webview: &Webview) {
+    let app = webview.app_handle().clone();
+    let label = webview.label().to_string();
+    retention::log!("the {} webview's content process ended", label);
+    if record(&label) {
+        retention::log!(
+            "the alert window crashed {} times in {} min; alerts are notifications until restart",
+            CRASH_LIMIT,
+            CRASH_PERIOD.as_secs() / 60
+        );
+        tray::set_warning(
+            &app,
+            WARNING_KEY,
+            Some("Alerts keep crashing; showing notifications until restart"),
+        );
+    }
+    // Off WebKit's delegate callback, which this is called from.
+    thread::spawn(move || recover(&app, &label));
+}
+
+/// Destroys the window `label` and waits until it is gone.
+fn destroy(app: &AppHandle, label: &str) -> Option<bool> {
+    let window = app.get_webview_window(label)?;
+    let visible = window.is_visible().unwrap_or(true);
+    let _ = window.destroy();
+    let deadline = Instant::now() + DESTROY_TIMEOUT;
+    while app.get_webview_window(label).is_some() && Instant::now() < deadline {
+        thread::sleep(DESTROY_POLL);
+    }
+    Some(visible)
+}
+
+fn recover(app: &AppHandle, label: &str) {
+    match label {
+        window::MAIN_WINDOW => {
+            let Some(visible) = destroy(app, label) else {
+                return;
+            };
+            // Built from the config, which shows it.
+            let window = window::ensure_main_window(app);
+            if let Some(window) = window.filter(|_| !visible) {
+                let _ = window.hide();
+            }
+        }
+        window::ALERT_WINDOW => {
+            if destroy(app, label).is_some() {
+                window::ensure_alert_window(app);
+            }
+        }
+        _ => {
+            if let Some(window) = app.get_webview_window(label) {
+                let _ = window.reload();
+            }
+        }
+    }
+}
+
+/// Once alerts are notifications, the alert showing when the window went
+/// down is handed over as one instead of being brought back.
+pub fn hand_over_active_alert(app: &AppHandle) {
+    let event = app
+        .state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .active_event()
+        .cloned();
+    let Some(event) = event else {
+        return;
+    };
+    scheduler::acknowledge(app, Acknowledgement::Expired);
+    let shown = confidential::shown(&settings::current(app), &event);
+    notify::show(app, &shown.title, "The alert couldn't be shown");
+}
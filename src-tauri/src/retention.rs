@@ -0,0 +1,579 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, Timelike};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration as StdDuration;
+use tauri::{AppHandle, Manager, Window};
+
+use crate::ipc::{self, IpcError};
+use crate::scheduler::SchedulerState;
+use crate::{attendance, backup, history, notes, settings, shutdown};
+
+// --- Retention (what was pruned persisted as JSON in the app data dir) ---
+//
+// Keeps what the app writes from growing for as long as it runs: alert
+// history, attendance and notes older than their retention are dropped
+// (notes marked kept stay), the snapshots taken before imports are kept to
+// a count and a total size, and the rotated logs to a total size. Pruning
+// runs at startup and then once a day at the first check after
+// `QUIET_HOUR`, and never while an alert is showing. Each store remembers
+// where it was cut, so a ranged export reaching back past it says its
+// coverage is partial instead of looking like nothing happened. The status
+// file is rewritten in place, so it doesn't grow.
+//
+// What the app logs (`log!`) goes to stderr and to `logs/app.log` in the
+// app data dir. That file is rotated once it reaches `LOG_ROTATE_BYTES`,
+// and the rotated ones past the cap go, oldest first, as it is.
+
+/// Pruning is due from this hour of the day on.
+const QUIET_HOUR: u32 = 3;
+const CHECK_SECONDS: u64 = 600;
+const LOGS_DIR: &str = "logs";
+const LOG_FILE: &str = "app.log";
+const LOG_ROTATE_BYTES: u64 = 1024 * 1024;
+
+/// `eprintln!`, and into the log file once `start` has opened it.
+macro_rules! log {
+    ($($arg:tt)*) => {
+        $crate::retention::write_log(&format!($($arg)*))
+    };
+}
+pub(crate) use log;
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RetentionSettings {
+    pub history_days: i64,
+    pub attendance_days: i64,
+    /// After the occurrence, or after the last edit for a sticky note.
+    pub notes_days: i64,
+    /// Snapshots taken before an import; the newest is always kept.
+    pub snapshots_kept: usize,
+    pub snapshots_max_bytes: u64,
+    /// The rotated logs, all together.
+    pub logs_max_bytes: u64,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        RetentionSettings {
+            history_days: 180,
+            attendance_days: 365,
+            notes_days: 90,
+            snapshots_kept: 10,
+            snapshots_max_bytes: 20 * 1024 * 1024,
+            logs_max_bytes: 10 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Store {
+    History,
+    Attendance,
+}
+
+/// Where each store was last cut: everything before it was dropped.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Cuts {
+    history: Option<DateTime<Local>>,
+    attendance: Option<DateTime<Local>>,
+}
+
+impl Cuts {
+    fn get(&self, store: Store) -> Option<DateTime<Local>> {
+        match store {
+            Store::History => self.history,
+            Store::Attendance => self.attendance,
+        }
+    }
+
+    /// Moves the cut of each store that `report` dropped from up to where
+    /// it was cut this time; a cut never moves back.
+    fn advance(
+        &mut self,
+        report: &PruneReport,
+        history_cut: DateTime<Local>,
+        attendance_cut: DateTime<Local>,
+    ) {
+        if report.history_entries > 0 {
+            self.history = self.history.max(Some(history_cut));
+        }
+        if report.attendance_sessions > 0 {
+            self.attendance = self.attendance.max(Some(attendance_cut));
+        }
+    }
+
+    fn coverage(&self, store: Store, from: DateTime<Local>, to: DateTime<Local>) -> Coverage {
+        let cut = self.get(store).filter(|cut| from < *cut);
+        Coverage {
+            from,
+            to,
+            complete: cut.is_none(),
+            kept_from: cut,
+        }
+    }
+}
+
+/// How much of a range is still on record.
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Coverage {
+    pub from: DateTime<Local>,
+    pub to: DateTime<Local>,
+    /// False if part of the range was pruned.
+    pub complete: bool,
+    /// Where the records now start, when that is after `from`.
+    pub kept_from: Option<DateTime<Local>>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneReport {
+    pub history_entries: usize,
+    pub attendance_sessions: usize,
+    pub notes: usize,
+    pub snapshots: usize,
+    pub logs: usize,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactUsage {
+    /// File or directory name in the app data dir.
+    pub name: String,
+    pub bytes: u64,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageUsage {
+    /// Largest first.
+    pub artifacts: Vec<ArtifactUsage>,
+    pub total_bytes: u64,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactResult {
+    pub pruned: PruneReport,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+/// The day pruning last ran.
+static LAST_RUN: Mutex<Option<NaiveDate>> = Mutex::new(None);
+/// The logs directory and the cap on the rotated logs, once `start` ran.
+static LOGS: Mutex<Option<(PathBuf, u64)>> = Mutex::new(None);
+
+fn cuts_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("retention.json"))
+}
+
+fn load_cuts(app: &AppHandle) -> Cuts {
+    cuts_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn persist_cuts(app: &AppHandle, cuts: &Cuts) {
+    let Some(path) = cuts_path(app) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(cuts) {
+        let _ = fs::write(path, json);
+    }
+}
+
+/// How much of `from` to `to` the store still has.
+pub fn coverage(
+    app: &AppHandle,
+    store: Store,
+    from: DateTime<Local>,
+    to: DateTime<Local>,
+) -> Coverage {
+    load_cuts(app).coverage(store, from, to)
+}
+
+fn size_of(path: &Path) -> u64 {
+    let Ok(metadata) = fs::metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| size_of(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+fn usage(app: &AppHandle) -> StorageUsage {
+    let mut artifacts: Vec<ArtifactUsage> = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| ArtifactUsage {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    bytes: size_of(&entry.path()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    artifacts.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.name.cmp(&b.name)));
+    StorageUsage {
+        total_bytes: artifacts.iter().map(|a| a.bytes).sum(),
+        artifacts,
+    }
+}
+
+/// The snapshots past the count or the size cap, oldest first; the newest
+/// stays whatever its size.
+fn excess(mut snapshots: Vec<(PathBuf, u64)>, retention: &RetentionSettings) -> Vec<PathBuf> {
+    // Named by when they were taken, so newest first by name.
+    snapshots.sort_by(|a, b| b.0.cmp(&a.0));
+    let mut kept_bytes = 0;
+    let mut excess = Vec::new();
+    for (index, (path, bytes)) in snapshots.into_iter().enumerate() {
+        let keep = index == 0
+            || (index < retention.snapshots_kept
+                && kept_bytes + bytes <= retention.snapshots_max_bytes);
+        if keep {
+            kept_bytes += bytes;
+        } else {
+            excess.push(path);
+        }
+    }
+    excess
+}
+
+/// Deletes the snapshots past the count or the size cap.
+fn trim_snapshots(app: &AppHandle, retention: &RetentionSettings) -> usize {
+    let Ok(entries) =
+        backup::snapshots_dir(app).and_then(|dir| fs::read_dir(dir).map_err(|e| e.to_string()))
+    else {
+        return 0;
+    };
+    let snapshots: Vec<(PathBuf, u64)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .map(|path| {
+            let bytes = size_of(&path);
+            (path, bytes)
+        })
+        .collect();
+    excess(snapshots, retention)
+        .into_iter()
+        .filter(|path| fs::remove_file(path).is_ok())
+        .count()
+}
+
+/// The rotated logs past `max_bytes` altogether: all but the newest that
+/// fit under it.
+fn logs_over_cap(mut logs: Vec<(PathBuf, u64)>, max_bytes: u64) -> Vec<PathBuf> {
+    // Named by when they were rotated, so newest first by name.
+    logs.sort_by(|a, b| b.0.cmp(&a.0));
+    let mut kept_bytes = 0;
+    logs.into_iter()
+        .filter_map(|(path, bytes)| {
+            kept_bytes += bytes;
+            (kept_bytes > max_bytes).then_some(path)
+        })
+        .collect()
+}
+
+/// Deletes the rotated logs in `dir` past the cap.
+fn trim_logs(dir: &Path, max_bytes: u64) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    let logs: Vec<(PathBuf, u64)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.file_name() != Some(OsStr::new(LOG_FILE)))
+        .map(|path| {
+            let bytes = size_of(&path);
+            (path, bytes)
+        })
+        .collect();
+    logs_over_cap(logs, max_bytes)
+        .into_iter()
+        .filter(|path| fs::remove_file(path).is_ok())
+        .count()
+}
+
+/// Appends `line` to the log in `dir`, rotating it once it is big enough.
+fn append_log(dir: &Path, max_bytes: u64, line: &str, now: DateTime<Local>) {
+    let path = dir.join(LOG_FILE);
+    let Ok(mut file) = fs::OpenOptions::new().create(true).append(true).open(&path) else {
+        return;
+    };
+    let _ = writeln!(file, "{} {}", now.format("%Y-%m-%d %H:%M:%S"), line);
+    if size_of(&path) < LOG_ROTATE_BYTES {
+        return;
+    }
+    let rotated = dir.join(format!("app-{}.log", now.format("%Y%m%d-%H%M%S%.3f")));
+    if fs::rename(&path, rotated).is_ok() {
+        trim_logs(dir, max_bytes);
+    }
+}
+
+/// What `log!` runs.
+pub fn write_log(line: &str) {
+    eprintln!("{}", line);
+    // Held while writing, so lines don't interleave or race a rotation.
+    let logs = LOGS.lock().unwrap();
+    if let Some((dir, max_bytes)) = logs.as_ref() {
+        append_log(dir, *max_bytes, line, Local::now());
+    }
+}
+
+/// Starts logging to the logs directory, under the current cap.
+fn open_logs(app: &AppHandle, retention: &RetentionSettings) -> Option<PathBuf> {
+    let dir = app.path().app_data_dir().ok()?.join(LOGS_DIR);
+    fs::create_dir_all(&dir).ok()?;
+    *LOGS.lock().unwrap() = Some((dir.clone(), retention.logs_max_bytes));
+    Some(dir)
+}
+
+/// Prunes every store to its retention as of `now`; refused while an alert
+/// is showing, so nothing it is about to record gets in the way.
+fn prune(app: &AppHandle, now: DateTime<Local>) -> Result<PruneReport, String> {
+    if app
+        .state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .has_active_alert()
+    {
+        return Err("An alert is showing".to_string());
+    }
+    let retention = settings::current(app).retention;
+    let history_cut = now - Duration::days(retention.history_days.max(1));
+    let attendance_cut = now - Duration::days(retention.attendance_days.max(1));
+    let report = PruneReport {
+        history_entries: history::prune(app, history_cut),
+        attendance_sessions: attendance::prune(app, attendance_cut),
+        notes: notes::prune(app, now - Duration::days(retention.notes_days.max(1))),
+        snapshots: trim_snapshots(app, &retention),
+        logs: open_logs(app, &retention).map_or(0, |dir| trim_logs(&dir, retention.logs_max_bytes)),
+    };
+
+    let mut cuts = load_cuts(app);
+    cuts.advance(&report, history_cut, attendance_cut);
+    persist_cuts(app, &cuts);
+    *LAST_RUN.lock().unwrap() = Some(now.date_naive());
+    Ok(report)
+}
+
+fn due(now: DateTime<Local>) -> bool {
+    now.hour() >= QUIET_HOUR && *LAST_RUN.lock().unwrap() != Some(now.date_naive())
+}
+
+/// Prunes now, then once a day; a run refused for an alert is retried at
+/// the next check.
+pub fn start(app: &AppHandle) {
+    open_logs(app, &settings::current(app).retention);
+    let app = app.clone();
+    thread::spawn(move || {
+        if let Err(e) = prune(&app, Local::now()) {
+            log!("retention skipped at startup: {}", e);
+        }
+        while !shutdown::in_progress() {
+            thread::sleep(StdDuration::from_secs(CHECK_SECONDS));
+            let now = Local::now();
+            if due(now) {
+                let _ = prune(&app, now);
+            }
+        }
+    });
+}
+
+/// Bytes on disk per file or directory in the app data dir.
+#[tauri::command]
+pub fn get_storage_usage(app: AppHandle, window: Window) -> Result<StorageUsage, IpcError> {
+    ipc::require_dashboard(&window, "get_storage_usage")?;
+    Ok(usage(&app))
+}
+
+/// Prunes now and rewrites the stores it pruned.
+#[tauri::command]
+pub fn compact_storage(app: AppHandle, window: Window) -> Result<CompactResult, IpcError> {
+    ipc::require_dashboard(&window, "compact_storage")?;
+    let bytes_before = usage(&app).total_bytes;
+    let pruned = prune(&app, Local::now())?;
+    Ok(CompactResult {
+        pruned,
+        bytes_before,
+        bytes_after: usage(&app).total_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::at;
+    use proptest::prelude::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    /// Log `n` of a day, named the way rotation names them.
+    fn rotated(n: usize, bytes: u64) -> (PathBuf, u64) {
+        let name = format!("app-20260302-0900{:02}.000.log", n);
+        (PathBuf::from(LOGS_DIR).join(name), bytes)
+    }
+
+    /// An empty directory of the test's own.
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("retention-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Snapshot `n` of a day, named the way imports name them.
+    fn snapshot(n: usize, bytes: u64) -> (PathBuf, u64) {
+        let name = format!("pre-import-20260302-{:06}.json", n);
+        (PathBuf::from("backups").join(name), bytes)
+    }
+
+    proptest! {
+        #[test]
+        fn what_is_left_fits_the_count_and_the_cap(
+            sizes in prop::collection::vec(0..60 * MB, 1..200),
+            kept in 0..20_usize,
+            max_mb in 0..100_u64,
+        ) {
+            let retention = RetentionSettings {
+                snapshots_kept: kept,
+                snapshots_max_bytes: max_mb * MB,
+                ..RetentionSettings::default()
+            };
+            let snapshots: Vec<(PathBuf, u64)> =
+                sizes.iter().enumerate().map(|(n, bytes)| snapshot(n, *bytes)).collect();
+            let newest = snapshots.last().unwrap().0.clone();
+            let excess = excess(snapshots.clone(), &retention);
+
+            let left: Vec<&(PathBuf, u64)> =
+                snapshots.iter().filter(|(path, _)| !excess.contains(path)).collect();
+            prop_assert!(!excess.contains(&newest));
+            prop_assert!(left.len() <= kept.max(1));
+            // Over the cap only when the newest is all that's left.
+            let total: u64 = left.iter().map(|(_, bytes)| bytes).sum();
+            prop_assert!(left.len() == 1 || total <= retention.snapshots_max_bytes);
+            prop_assert_eq!(left.len() + excess.len(), snapshots.len());
+        }
+    }
+
+    #[test]
+    fn the_newest_stays_however_big() {
+        let retention = RetentionSettings::default();
+        let snapshots = vec![snapshot(0, MB), snapshot(1, 500 * MB)];
+        assert_eq!(excess(snapshots, &retention), vec![snapshot(0, MB).0]);
+    }
+
+    #[test]
+    fn an_older_snapshot_over_the_cap_goes_and_smaller_ones_stay() {
+        let retention = RetentionSettings {
+            snapshots_max_bytes: 10 * MB,
+            ..RetentionSettings::default()
+        };
+        let snapshots = vec![snapshot(0, MB), snapshot(1, 30 * MB), snapshot(2, MB)];
+        assert_eq!(excess(snapshots, &retention), vec![snapshot(1, 0).0]);
+    }
+
+    #[test]
+    fn the_oldest_rotated_logs_go_first() {
+        let logs = vec![rotated(1, 4 * MB), rotated(2, 4 * MB), rotated(3, MB)];
+        assert_eq!(logs_over_cap(logs, 6 * MB), vec![rotated(1, 0).0]);
+        // Even the newest, if it alone is over.
+        assert_eq!(logs_over_cap(vec![rotated(1, 2 * MB)], MB).len(), 1);
+    }
+
+    #[test]
+    fn a_log_written_past_the_cap_is_rotated_and_trimmed_under_it() {
+        let dir = scratch("logs");
+        let line = "x".repeat(64 * 1024);
+        let mut now = at("2026-03-02 09:00");
+        // About 13 MB: a dozen rotations.
+        for _ in 0..200 {
+            append_log(&dir, 3 * MB, &line, now);
+            now += Duration::seconds(1);
+        }
+        let rotated: Vec<u64> = fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .filter(|entry| entry.file_name() != LOG_FILE)
+            .map(|entry| size_of(&entry.path()))
+            .collect();
+        assert_eq!(rotated.len(), 2);
+        assert!(rotated.iter().sum::<u64>() <= 3 * MB);
+        assert!(size_of(&dir.join(LOG_FILE)) < LOG_ROTATE_BYTES);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn a_cut_only_moves_forward() {
+        let dropped = PruneReport {
+            history_entries: 3,
+            ..PruneReport::default()
+        };
+        let mut cuts = Cuts::default();
+        cuts.advance(&dropped, at("2026-03-01 09:00"), at("2025-03-01 09:00"));
+        cuts.advance(&dropped, at("2026-02-01 09:00"), at("2025-03-01 09:00"));
+        assert_eq!(cuts.history, Some(at("2026-03-01 09:00")));
+        // Nothing dropped, nothing cut.
+        assert_eq!(cuts.attendance, None);
+        cuts.advance(
+            &PruneReport::default(),
+            at("2026-04-01 09:00"),
+            at("2025-04-01 09:00"),
+        );
+        assert_eq!(cuts.history, Some(at("2026-03-01 09:00")));
+    }
+
+    #[test]
+    fn a_range_reaching_past_the_cut_is_partial() {
+        let cuts = Cuts {
+            history: Some(at("2026-03-01 09:00")),
+            attendance: None,
+        };
+        let before = cuts.coverage(
+            Store::History,
+            at("2026-02-01 00:00"),
+            at("2026-03-31 00:00"),
+        );
+        assert!(!before.complete);
+        assert_eq!(before.kept_from, Some(at("2026-03-01 09:00")));
+        let after = cuts.coverage(
+            Store::History,
+            at("2026-03-02 00:00"),
+            at("2026-03-31 00:00"),
+        );
+        assert!(after.complete && after.kept_from.is_none());
+        let attendance = cuts.coverage(
+            Store::Attendance,
+            at("2020-01-01 00:00"),
+            at("2026-03-31 00:00"),
+        );
+        assert!(attendance.complete);
+    }
+}
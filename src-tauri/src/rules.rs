@@ -0,0 +1,902 @@
+use chrono::Local;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State, Window};
+
+use crate::actions::{self, ActionError, ActionStep};
+use crate::audio::AudioRoute;
+use crate::calendar::CalendarEvent;
+use crate::confidential::DisplayPolicy;
+use crate::dismissal::DismissChallenge;
+use crate::features::{self, Flag};
+use crate::ipc::{self, IpcError};
+use crate::settings::{self, Settings, SettingsState};
+use crate::{days_off, location, presentation, renderer};
+
+// --- Alert Rules ---
+//
+// Every matching rule applies, least specific first, so the most specific
+// match wins for each field it sets. Specificity, from lowest to highest:
+// calendar, title, attendee domain, organizer domain, exact organizer, series.
+// Rules of equal specificity apply in the order they were saved.
+// The action chains (`on_acknowledge`, `on_join`) are whole lists, taken from
+// the most specific matching rule that sets one.
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStyle {
+    Fullscreen,
+    Notification,
+    Silent,
+}
+
+/// Where an event's alert times come from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LeadTimeSource {
+    /// The lead time from the settings and rules.
+    #[default]
+    Fixed,
+    /// The event's own alarms; the fixed lead time if it has none.
+    EventAlarms,
+    /// The fixed lead time and the event's alarms, earliest first.
+    EarliestOfBoth,
+}
+
+/// One step of a sound escalation: from `offset_seconds` after the start
+/// (negative before it), `sound` plays at `volume` (0 to 1), over and over
+/// if `looping`, until the next step or an acknowledgement.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SoundStage {
+    pub offset_seconds: i64,
+    pub sound: String,
+    #[serde(default = "full_volume")]
+    pub volume: f32,
+    #[serde(default)]
+    pub looping: bool,
+}
+
+fn full_volume() -> f32 {
+    1.0
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct AlertRule {
+    pub id: String,
+    /// Calendar title to match, or a pattern as in `calendar_matches`;
+    /// `None` matches every calendar.
+    pub calendar: Option<String>,
+    /// Case-insensitive substring of the event title.
+    pub title_contains: Option<String>,
+    /// Organizer email ("boss@corp.com") or domain ("@bigclient.com").
+    pub organizer: Option<String>,
+    /// Matches if any attendee has an address at this domain.
+    pub attendee_domain: Option<String>,
+    /// Event id of one recurring series (its occurrences share it).
+    pub series_id: Option<String>,
+    pub style: Option<AlertStyle>,
+    pub lead_time_seconds: Option<i64>,
+    /// System sound to play instead of the default one.
+    pub sound: Option<String>,
+    /// Replaces the single sound with steps that get more insistent.
+    pub sound_profile: Option<Vec<SoundStage>>,
+    /// Where the sounds play, instead of the route in the settings.
+    pub sound_route: Option<AudioRoute>,
+    /// Count in the last seconds before the start, or don't.
+    pub count_in: Option<bool>,
+    /// Alert normally even outside working hours and on days off (e.g. a
+    /// Saturday one-off).
+    pub ignore_working_hours: bool,
+    /// After joining, shrink the alert to a corner pill with the time left.
+    pub pill_after_join: Option<bool>,
+    /// Dismiss only after answering this challenge.
+    pub hard_dismiss: Option<DismissChallenge>,
+    /// Actions to run, in order, after the alert is dismissed.
+    pub on_acknowledge: Option<Vec<ActionStep>>,
+    /// Actions to run, in order, after joining from the alert.
+    pub on_join: Option<Vec<ActionStep>>,
+    /// How much of the event shows, instead of its calendar's policy.
+    pub display_policy: Option<DisplayPolicy>,
+    /// Created by lead-time auto-tuning; deleting it reverts the change.
+    pub auto_tuned: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlertDecision {
+    pub style: AlertStyle,
+    /// The first alert; `later_leads` are the event's other alarms.
+    pub lead_time_seconds: i64,
+    /// Shorter lead times that alert again, longest first.
+    pub later_leads: Vec<i64>,
+    pub sound: Option<String>,
+    /// In offset order; when set, it plays instead of `sound`.
+    pub sound_profile: Vec<SoundStage>,
+    /// Looked up again each time a sound starts; see `audio`.
+    pub sound_route: AudioRoute,
+    pub count_in: bool,
+    /// The lead time is a travel buffer for an in-person meeting.
+    pub time_to_leave: bool,
+    pub pill_after_join: bool,
+    pub hard_dismiss: Option<DismissChallenge>,
+    pub on_acknowledge: Vec<ActionStep>,
+    pub on_join: Vec<ActionStep>,
+    pub display_policy: DisplayPolicy,
+}
+
+pub fn domain_of(email: &str) -> Option<String> {
+    email
+        .rsplit_once('@')
+        .map(|(_, domain)| domain.to_lowercase())
+}
+
+pub fn normalize_domain(domain: &str) -> String {
+    domain.trim().trim_start_matches('@').to_lowercase()
+}
+
+/// Whether `pattern` names the calendar `title`: the title itself in any
+/// case, or with `*` standing for any run of characters ("Team *"). Shared
+/// by rules and the selectors of bulk changes (see `bulk`).
+pub fn calendar_matches(pattern: &str, title: &str) -> bool {
+    let (pattern, title) = (pattern.trim().to_lowercase(), title.to_lowercase());
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = title.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        let Some(at) = rest.find(part) else {
+            return false;
+        };
+        rest = &rest[at + part.len()..];
+    }
+    rest.ends_with(last)
+}
+
+/// An organizer condition without a local part is a domain match.
+fn is_domain_pattern(pattern: &str) -> bool {
+    let pattern = pattern.trim();
+    pattern.starts_with('@') || !pattern.contains('@')
+}
+
+impl AlertRule {
+    fn matches(&self, event: &CalendarEvent) -> bool {
+        let calendar_matches = self
+            .calendar
+            .as_ref()
+            .is_none_or(|c| calendar_matches(c, &event.calendar));
+        let title_matches = self
+            .title_contains
+            .as_ref()
+            .is_none_or(|needle| event.title.to_lowercase().contains(&needle.to_lowercase()));
+        let organizer_matches = self.organizer.as_ref().is_none_or(|pattern| {
+            let Some(organizer) = event.organizer.as_deref() else {
+                return false;
+            };
+            if is_domain_pattern(pattern) {
+                domain_of(organizer) == Some(normalize_domain(pattern))
+            } else {
+                organizer.eq_ignore_ascii_case(pattern.trim())
+            }
+        });
+        let attendee_matches = self.attendee_domain.as_ref().is_none_or(|domain| {
+            let domain = normalize_domain(domain);
+            event
+                .attendees
+                .iter()
+                .filter_map(|a| a.email.as_deref().and_then(domain_of))
+                .any(|d| d == domain)
+        });
+        let series_matches = self.series_id.as_ref().is_none_or(|id| *id == event.id);
+        calendar_matches && title_matches && organizer_matches && attendee_matches && series_matches
+    }
+
+    /// (highest condition rank, number of conditions); higher wins.
+    fn specificity(&self) -> (u8, usize) {
+        let organizer_rank =
+            self.organizer
+                .as_deref()
+                .map(|p| if is_domain_pattern(p) { 4 } else { 5 });
+        let ranks = [
+            self.calendar.as_ref().map(|_| 1),
+            self.title_contains.as_ref().map(|_| 2),
+            self.attendee_domain.as_ref().map(|_| 3),
+            organizer_rank,
+            self.series_id.as_ref().map(|_| 6),
+        ];
+        let set: Vec<u8> = ranks.into_iter().flatten().collect();
+        (set.iter().copied().max().unwrap_or(0), set.len())
+    }
+}
+
+/// The longest lead time any event could get from the settings: the default,
+/// a rule's, or a travel buffer.
+pub fn max_lead_time_seconds(settings: &Settings) -> i64 {
+    let rules = settings.rules.iter().filter_map(|r| r.lead_time_seconds);
+    let travel = &settings.travel;
+    let buffers = travel
+        .buffers
+        .iter()
+        .map(|b| b.lead_minutes)
+        .chain([travel.default_lead_minutes])
+        .filter(|_| travel.enabled)
+        .map(|minutes| minutes * 60);
+    rules
+        .chain(buffers)
+        .fold(settings.lead_time_seconds, i64::max)
+}
+
+/// Alarms going off in the same minute alert once, at the earliest.
+fn apply_alarms(decision: &mut AlertDecision, source: LeadTimeSource, alarms: &[i64]) {
+    let mut leads: Vec<i64> = alarms.iter().copied().filter(|s| *s >= 0).collect();
+    match source {
+        LeadTimeSource::Fixed => return,
+        LeadTimeSource::EventAlarms if leads.is_empty() => return,
+        LeadTimeSource::EventAlarms => {}
+        LeadTimeSource::EarliestOfBoth => leads.push(decision.lead_time_seconds),
+    }
+    leads.sort_unstable_by(|a, b| b.cmp(a));
+    leads.dedup_by_key(|s| (*s + 59) / 60);
+    decision.lead_time_seconds = leads.remove(0);
+    decision.later_leads = leads;
+}
+
+/// How a step of the decision bore on an event's alert.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StepOutcome {
+    /// Checked; nothing changed.
+    Passed,
+    /// Changed the style, lead time or sound.
+    Changed,
+    /// The occurrence won't alert (or won't alert yet) because of it.
+    Suppressed,
+}
+
+/// One decision, e.g. a matching rule or the working hours.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceStep {
+    /// The step ("working_hours"), or "rule:<id>" for a matching rule.
+    pub rule: String,
+    pub outcome: StepOutcome,
+    pub detail: String,
+}
+
+/// Collects the steps while explaining; `evaluate` uses one that doesn't,
+/// so the details are only formatted when asked for.
+#[derive(Default)]
+pub struct Trace(Option<Vec<TraceStep>>);
+
+impl Trace {
+    pub fn recording() -> Self {
+        Trace(Some(Vec::new()))
+    }
+
+    pub fn note(&mut self, rule: &str, outcome: StepOutcome, detail: impl FnOnce() -> String) {
+        if let Some(steps) = self.0.as_mut() {
+            steps.push(TraceStep {
+                rule: rule.to_string(),
+                outcome,
+                detail: detail(),
+            });
+        }
+    }
+
+    pub fn steps(self) -> Vec<TraceStep> {
+        self.0.unwrap_or_default()
+    }
+}
+
+/// The decision so far, handed from step to step.
+struct Draft {
+    decision: AlertDecision,
+    ignore_working_hours: bool,
+}
+
+type Step = fn(&Settings, &CalendarEvent, &mut Draft, &mut Trace);
+
+/// In order; each step sees what the ones before it decided.
+const STEPS: [Step; 8] = [
+    apply_rules,
+    apply_event_alarms,
+    apply_travel,
+    apply_long_block,
+    apply_working_hours,
+    apply_days_off,
+    apply_presentation,
+    apply_renderer,
+];
+
+fn style_name(style: AlertStyle) -> &'static str {
+    match style {
+        AlertStyle::Fullscreen => "fullscreen",
+        AlertStyle::Notification => "a notification",
+        AlertStyle::Silent => "silent",
+    }
+}
+
+fn apply_rules(settings: &Settings, event: &CalendarEvent, draft: &mut Draft, trace: &mut Trace) {
+    // Auto-tuned rules stay while the flag is off, but don't count.
+    let auto_tune = features::enabled(settings, Flag::AutoTune);
+    let mut matching: Vec<&AlertRule> = settings
+        .rules
+        .iter()
+        .filter(|r| (auto_tune || !r.auto_tuned) && r.matches(event))
+        .collect();
+    matching.sort_by_key(|r| r.specificity());
+    if matching.is_empty() {
+        trace.note("rules", StepOutcome::Passed, || {
+            "No alert rule matches".to_string()
+        });
+    }
+
+    let decision = &mut draft.decision;
+    for rule in matching {
+        if let Some(style) = rule.style {
+            decision.style = style;
+        }
+        if let Some(lead) = rule.lead_time_seconds {
+            decision.lead_time_seconds = lead;
+        }
+        if rule.sound.is_some() {
+            decision.sound = rule.sound.clone();
+        }
+        if let Some(profile) = &rule.sound_profile {
+            decision.sound_profile = profile.clone();
+            decision.sound_profile.sort_by_key(|s| s.offset_seconds);
+        }
+        if let Some(route) = rule.sound_route {
+            decision.sound_route = route;
+        }
+        if let Some(count_in) = rule.count_in {
+            decision.count_in = count_in;
+        }
+        draft.ignore_working_hours |= rule.ignore_working_hours;
+        if let Some(pill) = rule.pill_after_join {
+            decision.pill_after_join = pill;
+        }
+        if rule.hard_dismiss.is_some() {
+            decision.hard_dismiss = rule.hard_dismiss;
+        }
+        if let Some(steps) = &rule.on_acknowledge {
+            decision.on_acknowledge = steps.clone();
+        }
+        if let Some(steps) = &rule.on_join {
+            decision.on_join = steps.clone();
+        }
+        if let Some(policy) = rule.display_policy {
+            decision.display_policy = policy;
+        }
+        let outcome = match rule.style {
+            Some(AlertStyle::Silent) => StepOutcome::Suppressed,
+            _ => StepOutcome::Changed,
+        };
+        trace.note(&format!("rule:{}", rule.id), outcome, || {
+            let mut set = Vec::new();
+            if let Some(style) = rule.style {
+                set.push(format!("style {}", style_name(style)));
+            }
+            if let Some(lead) = rule.lead_time_seconds {
+                set.push(format!("lead time {}s", lead));
+            }
+            if rule.ignore_working_hours {
+                set.push("ignores working hours".to_string());
+            }
+            if set.is_empty() {
+                "Matches; sets no timing or style".to_string()
+            } else {
+                format!("Matches; {}", set.join(", "))
+            }
+        });
+    }
+}
+
+fn apply_event_alarms(
+    settings: &Settings,
+    event: &CalendarEvent,
+    draft: &mut Draft,
+    trace: &mut Trace,
+) {
+    let before = draft.decision.lead_time_seconds;
+    apply_alarms(
+        &mut draft.decision,
+        settings.lead_time_source,
+        &event.alarm_offsets,
+    );
+    let lead = draft.decision.lead_time_seconds;
+    if lead == before && draft.decision.later_leads.is_empty() {
+        trace.note("event_alarms", StepOutcome::Passed, || {
+            format!("Lead time {}s", lead)
+        });
+    } else {
+        trace.note("event_alarms", StepOutcome::Changed, || {
+            format!(
+                "The event's alarms set the lead time to {}s, then {:?}",
+                lead, draft.decision.later_leads
+            )
+        });
+    }
+}
+
+fn apply_travel(settings: &Settings, event: &CalendarEvent, draft: &mut Draft, trace: &mut Trace) {
+    let Some(minutes) = location::travel_lead_minutes(&settings.travel, event) else {
+        return;
+    };
+    let decision = &mut draft.decision;
+    decision.lead_time_seconds = minutes * 60;
+    decision.later_leads.clear();
+    decision.time_to_leave = true;
+    trace.note("travel", StepOutcome::Changed, || {
+        format!("In person; leave {} min before", minutes)
+    });
+}
+
+fn apply_long_block(
+    settings: &Settings,
+    event: &CalendarEvent,
+    draft: &mut Draft,
+    trace: &mut Trace,
+) {
+    let decision = &mut draft.decision;
+    if decision.style == AlertStyle::Fullscreen && settings.long_events.is_long_block(event) {
+        decision.style = settings.long_events.style;
+        let outcome = match decision.style {
+            AlertStyle::Silent => StepOutcome::Suppressed,
+            _ => StepOutcome::Changed,
+        };
+        trace.note("long_block", outcome, || {
+            format!("A long block; {}", style_name(settings.long_events.style))
+        });
+    }
+}
+
+fn apply_working_hours(
+    settings: &Settings,
+    event: &CalendarEvent,
+    draft: &mut Draft,
+    trace: &mut Trace,
+) {
+    let hours = &settings.working_hours;
+    if draft.decision.style != AlertStyle::Fullscreen || !hours.notify_outside_hours {
+        return;
+    }
+    if draft.ignore_working_hours {
+        trace.note("working_hours", StepOutcome::Passed, || {
+            "A rule ignores working hours".to_string()
+        });
+        return;
+    }
+    if let (Some(start), Some(end)) = (event.start_time(), event.end_time()) {
+        if hours.overlaps(start, end) {
+            trace.note("working_hours", StepOutcome::Passed, || {
+                "Within working hours".to_string()
+            });
+        } else {
+            draft.decision.style = AlertStyle::Notification;
+            trace.note("working_hours", StepOutcome::Changed, || {
+                "Outside working hours; a notification".to_string()
+            });
+        }
+    }
+}
+
+/// On a day off only meetings with other people alert, and events a rule
+/// lets alert outside working hours.
+fn apply_days_off(
+    settings: &Settings,
+    event: &CalendarEvent,
+    draft: &mut Draft,
+    trace: &mut Trace,
+) {
+    let Some(start) = event.start_time() else {
+        return;
+    };
+    if draft.decision.style == AlertStyle::Silent
+        || !days_off::is_day_off(settings, start.date_naive())
+    {
+        return;
+    }
+    if draft.ignore_working_hours {
+        trace.note("days_off", StepOutcome::Passed, || {
+            "A day off, but a rule ignores working hours".to_string()
+        });
+    } else if event.attendees.iter().any(|a| !a.is_current_user) {
+        trace.note("days_off", StepOutcome::Passed, || {
+            "A day off, but a meeting with others".to_string()
+        });
+    } else {
+        draft.decision.style = AlertStyle::Silent;
+        trace.note("days_off", StepOutcome::Suppressed, || {
+            "A day off; only meetings with others alert".to_string()
+        });
+    }
+}
+
+fn apply_presentation(
+    settings: &Settings,
+    _event: &CalendarEvent,
+    draft: &mut Draft,
+    trace: &mut Trace,
+) {
+    let before = draft.decision.style;
+    presentation::downgrade(settings, &mut draft.decision);
+    if draft.decision.style != before {
+        trace.note("presentation", StepOutcome::Changed, || {
+            "Presenting; a notification".to_string()
+        });
+    }
+}
+
+/// After the alert window kept crashing, a notification; see `renderer`.
+fn apply_renderer(
+    _settings: &Settings,
+    _event: &CalendarEvent,
+    draft: &mut Draft,
+    trace: &mut Trace,
+) {
+    if draft.decision.style == AlertStyle::Fullscreen && renderer::alerts_as_notifications() {
+        draft.decision.style = AlertStyle::Notification;
+        trace.note("renderer", StepOutcome::Changed, || {
+            "The alert window kept crashing; a notification".to_string()
+        });
+    }
+}
+
+pub fn evaluate(settings: &Settings, event: &CalendarEvent) -> AlertDecision {
+    evaluate_traced(settings, event, &mut Trace::default())
+}
+
+/// `evaluate`, noting each step in `trace`.
+pub fn evaluate_traced(
+    settings: &Settings,
+    event: &CalendarEvent,
+    trace: &mut Trace,
+) -> AlertDecision {
+    let mut draft = Draft {
+        decision: AlertDecision {
+            style: AlertStyle::Fullscreen,
+            lead_time_seconds: settings.lead_time_seconds,
+            later_leads: Vec::new(),
+            sound: settings.alert_sound.clone(),
+            sound_profile: Vec::new(),
+            sound_route: settings.sound_route,
+            count_in: settings.count_in.enabled,
+            time_to_leave: false,
+            pill_after_join: false,
+            hard_dismiss: None,
+            on_acknowledge: Vec::new(),
+            on_join: Vec::new(),
+            display_policy: settings.confidential.calendar_policy(&event.calendar),
+        },
+        ignore_working_hours: false,
+    };
+    for step in STEPS {
+        step(settings, event, &mut draft, trace);
+    }
+    draft.decision
+}
+
+#[tauri::command]
+pub fn get_alert_rules(state: State<SettingsState>) -> Vec<AlertRule> {
+    state.0.lock().unwrap().rules.clone()
+}
+
+/// Which of a rule's chains a step is in.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainTrigger {
+    OnAcknowledge,
+    OnJoin,
+}
+
+impl ChainTrigger {
+    fn name(self) -> &'static str {
+        match self {
+            ChainTrigger::OnAcknowledge => "on_acknowledge",
+            ChainTrigger::OnJoin => "on_join",
+        }
+    }
+}
+
+/// A step of a rule's chain as it would run, without running it.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedStep {
+    pub trigger: ChainTrigger,
+    pub action: String,
+    pub description: Option<&'static str>,
+    /// Why the step would fail before running, if it would.
+    pub error: Option<ActionError>,
+}
+
+fn chains(rule: &AlertRule) -> [(ChainTrigger, &[ActionStep]); 2] {
+    [
+        (
+            ChainTrigger::OnAcknowledge,
+            rule.on_acknowledge.as_deref().unwrap_or_default(),
+        ),
+        (
+            ChainTrigger::OnJoin,
+            rule.on_join.as_deref().unwrap_or_default(),
+        ),
+    ]
+}
+
+/// Rejects a rule with a step that couldn't run, naming the first one.
+pub fn check_chains(rule: &AlertRule) -> Result<(), String> {
+    for (trigger, steps) in chains(rule) {
+        for (index, step) in steps.iter().enumerate() {
+            if let Err(error) = actions::check_step(step) {
+                return Err(format!(
+                    "{} step {}: {}",
+                    trigger.name(),
+                    index + 1,
+                    error.message()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `check_chains` for every rule in `settings`, profiles' included, naming
+/// the rule; for settings that come in whole, saved or imported.
+pub fn check_all_chains(settings: &Settings) -> Result<(), String> {
+    let profile_rules = settings
+        .profiles
+        .iter()
+        .flat_map(|p| p.rules.iter().map(move |r| (Some(p.name.as_str()), r)));
+    for (profile, rule) in settings
+        .rules
+        .iter()
+        .map(|r| (None, r))
+        .chain(profile_rules)
+    {
+        check_chains(rule).map_err(|error| match profile {
+            Some(profile) => format!("Rule {} in profile {}: {}", rule.id, profile, error),
+            None => format!("Rule {}: {}", rule.id, error),
+        })?;
+    }
+    Ok(())
+}
+
+/// Inserts the rule, or replaces the existing one with the same id.
+#[tauri::command]
+pub fn save_alert_rule(
+    app: AppHandle,
+    window: Window,
+    mut rule: AlertRule,
+) -> Result<AlertRule, IpcError> {
+    ipc::require_dashboard(&window, "save_alert_rule")?;
+    check_chains(&rule)?;
+    if rule.id.is_empty() {
+        rule.id = format!("rule-{}", Local::now().timestamp_millis());
+    }
+    let saved = rule.clone();
+    settings::update(&app, |settings| {
+        match settings.rules.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => *existing = rule,
+            None => settings.rules.push(rule),
+        }
+    })?;
+    Ok(saved)
+}
+
+/// The steps the rule would run on a dismiss and on a join, each checked
+/// but none run.
+#[tauri::command]
+pub fn simulate_rule_actions(
+    state: State<SettingsState>,
+    rule_id: String,
+) -> Result<Vec<SimulatedStep>, String> {
+    let settings = state.0.lock().unwrap();
+    let rule = settings
+        .rules
+        .iter()
+        .find(|r| r.id == rule_id)
+        .ok_or(format!("No rule with id {}", rule_id))?;
+    Ok(chains(rule)
+        .into_iter()
+        .flat_map(|(trigger, steps)| {
+            steps.iter().map(move |step| {
+                let checked = actions::check_step(step);
+                SimulatedStep {
+                    trigger,
+                    action: step.action.clone(),
+                    description: checked.as_ref().ok().map(|a| a.description),
+                    error: checked.err(),
+                }
+            })
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn delete_alert_rule(app: AppHandle, window: Window, id: String) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "delete_alert_rule")?;
+    settings::update(&app, |settings| settings.rules.retain(|r| r.id != id))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::Attendee;
+    use crate::profiles::Profile;
+    use crate::testing::{at, event};
+
+    fn rule_with_step(id: &str, action: &str) -> AlertRule {
+        AlertRule {
+            id: id.to_string(),
+            on_join: Some(vec![ActionStep {
+                action: action.to_string(),
+                args: None,
+            }]),
+            ..AlertRule::default()
+        }
+    }
+
+    #[test]
+    fn check_all_chains_covers_profiles() {
+        let mut settings = Settings::default();
+        assert!(check_all_chains(&settings).is_ok());
+        settings.profiles.push(Profile {
+            name: "Work".to_string(),
+            rules: vec![rule_with_step("rule-1", "no_such_action")],
+            ..Profile::default()
+        });
+        let error = check_all_chains(&settings).unwrap_err();
+        assert!(
+            error.starts_with("Rule rule-1 in profile Work: "),
+            "{}",
+            error
+        );
+        settings.profiles.clear();
+        settings
+            .rules
+            .push(rule_with_step("rule-2", "no_such_action"));
+        assert!(check_all_chains(&settings)
+            .unwrap_err()
+            .starts_with("Rule rule-2: "));
+    }
+
+    fn lead_rule(id: &str, lead_minutes: i64, rule: AlertRule) -> AlertRule {
+        AlertRule {
+            id: id.to_string(),
+            lead_time_seconds: Some(lead_minutes * 60),
+            ..rule
+        }
+    }
+
+    fn attendee(email: &str) -> Attendee {
+        Attendee {
+            name: None,
+            email: Some(email.to_string()),
+            status: "accepted".to_string(),
+            is_current_user: false,
+        }
+    }
+
+    #[test]
+    fn the_most_specific_matching_rule_wins() {
+        let settings = Settings {
+            lead_time_seconds: 60,
+            rules: vec![
+                lead_rule(
+                    "work",
+                    2,
+                    AlertRule {
+                        calendar: Some("work".to_string()),
+                        ..AlertRule::default()
+                    },
+                ),
+                lead_rule(
+                    "syncs",
+                    4,
+                    AlertRule {
+                        title_contains: Some("SYNC".to_string()),
+                        ..AlertRule::default()
+                    },
+                ),
+                lead_rule(
+                    "work-syncs",
+                    3,
+                    AlertRule {
+                        calendar: Some("Work".to_string()),
+                        title_contains: Some("sync".to_string()),
+                        ..AlertRule::default()
+                    },
+                ),
+                lead_rule(
+                    "client-invites",
+                    5,
+                    AlertRule {
+                        attendee_domain: Some("@BigClient.com".to_string()),
+                        ..AlertRule::default()
+                    },
+                ),
+                lead_rule(
+                    "client",
+                    10,
+                    AlertRule {
+                        organizer: Some("@bigclient.com".to_string()),
+                        ..AlertRule::default()
+                    },
+                ),
+                lead_rule(
+                    "manager",
+                    15,
+                    AlertRule {
+                        organizer: Some("Boss@Corp.com".to_string()),
+                        ..AlertRule::default()
+                    },
+                ),
+            ],
+            ..Settings::default()
+        };
+        // Title, calendar, organizer, attendees, lead minutes; 1 is the
+        // default.
+        type Case = (
+            &'static str,
+            &'static str,
+            Option<&'static str>,
+            &'static [&'static str],
+            i64,
+        );
+        let table: [Case; 9] = [
+            ("Planning", "Personal", None, &[], 1),
+            ("Planning", "Work", None, &[], 2),
+            ("Design sync", "Personal", None, &[], 4),
+            ("Design sync", "Work", None, &[], 3),
+            (
+                "Review",
+                "Work",
+                Some("pat@corp.com"),
+                &["ann@bigclient.com"],
+                5,
+            ),
+            ("Review", "Work", Some("ann@BIGCLIENT.com"), &[], 10),
+            (
+                "Design sync",
+                "Work",
+                Some("boss@corp.com"),
+                &["ann@bigclient.com"],
+                15,
+            ),
+            // Neither the domain nor the address of the manager.
+            ("Review", "Work", Some("boss@corp.com.example"), &[], 2),
+            ("Review", "Work", None, &["ann@notbigclient.com"], 2),
+        ];
+        for (title, calendar, organizer, attendees, lead_minutes) in table {
+            let mut meeting = event(title, at("2026-03-02 10:00"), 30);
+            meeting.calendar = calendar.to_string();
+            meeting.organizer = organizer.map(str::to_string);
+            meeting.attendees = attendees.iter().map(|a| attendee(a)).collect();
+            assert_eq!(
+                evaluate(&settings, &meeting).lead_time_seconds,
+                lead_minutes * 60,
+                "{} in {} by {:?} with {:?}",
+                title,
+                calendar,
+                organizer,
+                attendees
+            );
+        }
+    }
+
+    #[test]
+    fn calendar_patterns_match_whole_titles() {
+        assert!(calendar_matches("Work", "work"));
+        assert!(calendar_matches("Team *", "Team Berlin"));
+        assert!(calendar_matches("*@corp.com", "pat@corp.com"));
+        assert!(calendar_matches("a*b*c", "aXbYc"));
+        assert!(!calendar_matches("Work", "Workshop"));
+        assert!(!calendar_matches("Team *", "The Team Berlin"));
+        assert!(!calendar_matches("a*b*c", "aXcYb"));
+    }
+}
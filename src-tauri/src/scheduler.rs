@@ -0,0 +1,3159 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::RangeInclusive;
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration as StdDuration, Instant};
+use tauri::{AppHandle, Manager, Runtime, State, Wry};
+
+use crate::actions::ActionStep;
+use crate::audio::AudioRoute;
+use crate::bus::{self, AppEvent};
+use crate::cadence::{self, Cadence};
+use crate::calendar::CalendarEvent;
+use crate::cancellations::{self, MeetingChange};
+use crate::changes::{self, CalendarDiff};
+use crate::counterparts::{self, CounterpartTime};
+use crate::explain::Explanation;
+use crate::features::{self, Flag};
+use crate::flood::Burst;
+use crate::history::{self, HistoryKind};
+use crate::journal::{self, JournaledAlert, PendingSnooze, PendingWork, Recovery};
+use crate::location::LocationKind;
+use crate::overrides::{OccurrenceOverride, OverrideKind};
+use crate::power::PowerProfile;
+use crate::rules::{self, AlertDecision, AlertStyle, SoundStage, StepOutcome, Trace, TraceStep};
+use crate::settings::{self, Settings, MIN_NAG_INTERVAL_SECONDS};
+use crate::sources::Freshness;
+use crate::theme::{self, AlertTheme};
+use crate::webhooks::LifecycleEvent;
+use crate::{
+    accessibility, actions, agenda, appearance, autotune, budget, confidential, countin, days_off,
+    dismissal, displays, dock, focus, locale, notes, notify, power, presentation, privacy,
+    profiles, push, recent, retention, selftest, series, session, shutdown, simulate, sound,
+    sources, startup, templates, timezone, untrusted, window,
+};
+
+// --- Alert Scheduler ---
+//
+// Runs on its own thread: refetches the calendar as often as `cadence`
+// says, fires the alert at the lead time decided by the alert rules,
+// escalates alerts that are still unacknowledged a while after the event
+// started, and sends the morning digest and the evening preview of tomorrow.
+
+const TICK: StdDuration = StdDuration::from_secs(1);
+// Start/end transitions older than this (e.g. at launch) are not reported.
+const TRANSITION_WINDOW_SECONDS: i64 = 60;
+// How far ahead the calendar is read, before lead times and the poll
+// interval are added (see `fetch_horizon`).
+const LOOKAHEAD_HOURS: i64 = 24;
+// A longer gap between ticks means the Mac was asleep.
+const WAKE_GAP_SECONDS: i64 = 30;
+// The digest and preview are skipped if the app wasn't running around their
+// scheduled time.
+const DIGEST_WINDOW_MINUTES: i64 = 60;
+// A snooze is from a minute up to a day; anything else is refused before
+// it reaches the scheduler's arithmetic.
+const SNOOZE_MINUTES: RangeInclusive<i64> = 1..=1440;
+const DEFAULT_SNOOZE_MINUTES: i64 = 5;
+
+/// What the alert window receives: the event plus how the alert was timed.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertPayload {
+    #[serde(flatten)]
+    pub event: CalendarEvent,
+    pub lead_time_seconds: i64,
+    pub time_to_leave: bool,
+    /// Set for catch-up alerts of meetings that already started.
+    pub started_minutes_ago: Option<i64>,
+    /// The heading, rendered from the alert template.
+    pub display_text: String,
+    pub theme: AlertTheme,
+    /// The user's own note for the occurrence.
+    pub note: Option<String>,
+    /// The start for attendees in other zones; see `counterparts`.
+    pub counterpart_local_times: Vec<CounterpartTime>,
+}
+
+/// What happens to a fullscreen alert that comes due while one is showing.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConcurrentAlerts {
+    /// Show it after the current one is acknowledged.
+    Queue,
+    /// Add it to the current alert; acknowledging covers all of them.
+    Merge,
+}
+
+/// Sent to the alert window as `alert-queued` whenever the queue changes.
+#[derive(Clone, Debug, Serialize)]
+pub struct QueuedAlerts {
+    pub count: usize,
+    pub events: Vec<CalendarEvent>,
+    pub merged: bool,
+    /// Collapsed from a flood of alerts; each can be muted from the list.
+    pub flood: bool,
+}
+
+struct FiredAlert {
+    start: DateTime<Local>,
+    at: DateTime<Local>,
+    /// Lead time of the alarm that fired; later alarms have shorter ones.
+    lead: Duration,
+}
+
+struct ActiveAlert {
+    event: CalendarEvent,
+    escalated: bool,
+    shown_at: DateTime<Local>,
+    /// When the alert was last shown or nagged.
+    raised_at: DateTime<Local>,
+    /// Last theme sent to the alert window.
+    theme: Option<AlertTheme>,
+    /// Moved aside into a corner: still pending, but not nagged.
+    cornered: bool,
+    /// Index of the next sound-profile stage to play.
+    sound_stage: usize,
+    /// The count-in was started.
+    counted_in: bool,
+}
+
+#[derive(Default)]
+pub struct Scheduler {
+    events: Vec<CalendarEvent>,
+    last_fetch: Option<Instant>,
+    /// Keyed by `alert_key`, so moving an event doesn't make it a new alert.
+    fired: HashMap<String, FiredAlert>,
+    snoozed: HashMap<String, (CalendarEvent, DateTime<Local>)>,
+    active: Option<ActiveAlert>,
+    started: HashSet<String>,
+    /// Alerts that were showing when a previous session crashed; they come
+    /// back while their meeting runs, even though the history has them.
+    recovered: HashSet<String>,
+    /// Occurrences already checked for a missing join link.
+    link_checked: HashSet<String>,
+    ended: HashSet<String>,
+    last_digest: Option<NaiveDate>,
+    last_preview: Option<NaiveDate>,
+    /// The day last warned about for going over the meeting budget.
+    budget_warned: Option<NaiveDate>,
+    /// Another user's session is in front; alerts wait until ours is back.
+    session_away: bool,
+    /// The pause runs to a fixed time rather than the next free slot.
+    pause_is_timed: bool,
+    profile_day: Option<NaiveDate>,
+    badge: Option<String>,
+    last_tick: Option<DateTime<Local>>,
+    /// Fullscreen alerts waiting for (or merged into) the active one.
+    queue: VecDeque<(CalendarEvent, AlertDecision)>,
+    queue_changed: bool,
+    burst: Burst,
+    /// The queue is part of a flood, merged into the active alert until it
+    /// is acknowledged.
+    flooding: bool,
+    overrides: Vec<OccurrenceOverride>,
+    /// Non-meeting reminders (the digest) wait until this time. Set by
+    /// "pause until free" and recomputed when the calendar changes.
+    paused_until_free: Option<DateTime<Local>>,
+    /// Reminders received on the inbound channel and when they are due.
+    reminders: Vec<(CalendarEvent, DateTime<Local>)>,
+    /// The self-test's alert and when it is due; it never shows.
+    dry_run: Option<(CalendarEvent, DateTime<Local>)>,
+    /// The joined meeting the alert window shows as a pill, until it ends.
+    pill: Option<CalendarEvent>,
+    /// Sources disabled as of the last fetch; events that vanish because a
+    /// source was turned off weren't cancelled.
+    disabled_sources: Vec<String>,
+    /// Whether the events are from a failed fetch's sources' last good sync;
+    /// alerts keep firing from them either way.
+    freshness: Freshness,
+}
+
+pub struct SchedulerState(pub Mutex<Scheduler>);
+
+/// The scheduler's side of the health report.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulerHealth {
+    /// Ticked within the last few seconds.
+    pub running: bool,
+    pub last_tick: Option<DateTime<Local>>,
+    pub last_fetch_at: Option<DateTime<Local>>,
+    #[serde(flatten)]
+    pub freshness: Freshness,
+    pub events_cached: usize,
+    pub active_alert: Option<String>,
+    pub queued_alerts: usize,
+    pub paused_until: Option<DateTime<Local>>,
+    pub next_alert: Option<PlannedAlert>,
+    pub poll_cadence: Cadence,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedAlert {
+    pub title: String,
+    pub at: DateTime<Local>,
+}
+
+/// What `apply_fetch` found, for `tick` to report.
+struct Fetched {
+    diff: CalendarDiff,
+    /// The first fetch, which adds everything.
+    first: bool,
+    changes: Vec<MeetingChange>,
+    /// The active alert's meeting was cancelled.
+    close: bool,
+    /// The pause until free moved (or ended) with the calendar.
+    moved_pause: Option<Option<DateTime<Local>>>,
+}
+
+pub enum Acknowledgement {
+    Dismissed,
+    Snoozed(i64),
+    Joined,
+    /// Auto-dismissed by the scheduler.
+    Expired,
+}
+
+impl Scheduler {
+    pub fn with_overrides(overrides: Vec<OccurrenceOverride>) -> Self {
+        Scheduler {
+            overrides,
+            ..Default::default()
+        }
+    }
+
+    pub fn overrides_mut(&mut self) -> &mut Vec<OccurrenceOverride> {
+        &mut self.overrides
+    }
+
+    fn override_for(&self, event: &CalendarEvent) -> Option<&OverrideKind> {
+        self.overrides
+            .iter()
+            .find(|o| o.applies_to(event))
+            .map(|o| &o.kind)
+    }
+
+    pub fn is_muted(&self, event: &CalendarEvent) -> bool {
+        self.override_for(event) == Some(&OverrideKind::Mute)
+    }
+
+    /// Takes muted occurrences out of the queue and the snoozes, e.g. one
+    /// muted from a flood's list.
+    pub fn unqueue_muted(&mut self) {
+        let queued = self.queue.len();
+        let queue = std::mem::take(&mut self.queue);
+        self.queue = queue
+            .into_iter()
+            .filter(|(e, _)| !self.is_muted(e))
+            .collect();
+        self.queue_changed |= self.queue.len() != queued;
+        let snoozed = std::mem::take(&mut self.snoozed);
+        self.snoozed = snoozed
+            .into_iter()
+            .filter(|(_, (e, _))| !self.is_muted(e))
+            .collect();
+    }
+
+    /// Whether acknowledging the active alert covers the queue too.
+    fn merges_queue(&self, settings: &Settings) -> bool {
+        settings.concurrent_alerts == ConcurrentAlerts::Merge || self.flooding
+    }
+
+    /// The rules' decision, adjusted by a one-off override. `None` means the
+    /// occurrence doesn't alert at all.
+    fn decide(&self, settings: &Settings, event: &CalendarEvent) -> Option<AlertDecision> {
+        self.decide_traced(settings, event, &mut Trace::default())
+    }
+
+    /// `decide`, noting the override and the filters ahead of the rules.
+    fn decide_traced(
+        &self,
+        settings: &Settings,
+        event: &CalendarEvent,
+        trace: &mut Trace,
+    ) -> Option<AlertDecision> {
+        match self.override_for(event) {
+            Some(OverrideKind::Mute) => {
+                trace.note("override", StepOutcome::Suppressed, || {
+                    "Muted for this occurrence".to_string()
+                });
+                None
+            }
+            Some(OverrideKind::Force { lead_minutes }) => {
+                trace.note("override", StepOutcome::Changed, || {
+                    format!("Forced to alert {} min before", lead_minutes)
+                });
+                let decision = rules::evaluate_traced(settings, event, trace);
+                Some(AlertDecision {
+                    style: match decision.style {
+                        AlertStyle::Silent => AlertStyle::Fullscreen,
+                        style => style,
+                    },
+                    lead_time_seconds: lead_minutes * 60,
+                    later_leads: Vec::new(),
+                    ..decision
+                })
+            }
+            None if event.is_all_day => {
+                trace.note("all_day", StepOutcome::Suppressed, || {
+                    "All-day events don't alert".to_string()
+                });
+                None
+            }
+            None => match settings.calendar_filter.reason(event) {
+                Some(reason) => {
+                    trace.note("calendar_filter", StepOutcome::Suppressed, || {
+                        format!("Filtered out: {}", reason)
+                    });
+                    None
+                }
+                None => {
+                    trace.note("calendar_filter", StepOutcome::Passed, || {
+                        format!("Calendar \"{}\" is included", event.calendar)
+                    });
+                    Some(rules::evaluate_traced(settings, event, trace))
+                }
+            },
+        }
+    }
+
+    /// How the occurrence's alert is decided, for the history of one that
+    /// won't alert.
+    pub fn decision_trace(&self, settings: &Settings, event: &CalendarEvent) -> Vec<TraceStep> {
+        let mut trace = Trace::recording();
+        self.decide_traced(settings, event, &mut trace);
+        trace.steps()
+    }
+
+    /// Dry run of everything that decides whether and when the occurrence
+    /// alerts; `alerted` is whether the history has it.
+    pub fn explain(
+        &self,
+        settings: &Settings,
+        event: &CalendarEvent,
+        now: DateTime<Local>,
+        alerted: bool,
+    ) -> Explanation {
+        let mut trace = Trace::recording();
+        trace.note("declined", StepOutcome::Passed, || {
+            if event.is_declined() {
+                "Declined, which doesn't stop the alert".to_string()
+            } else {
+                "Not declined".to_string()
+            }
+        });
+        let decision = self.decide_traced(settings, event, &mut trace);
+        let key = event.alert_key();
+        let start = event.start_time();
+        let lead = decision
+            .as_ref()
+            .map(|d| Duration::seconds(d.lead_time_seconds));
+        let alert_at = start
+            .zip(lead)
+            .map(|(start, lead)| start - lead)
+            .filter(|_| {
+                decision
+                    .as_ref()
+                    .is_some_and(|d| d.style != AlertStyle::Silent)
+            });
+
+        if let Some(until) = self.paused_until_free.filter(|until| now < *until) {
+            trace.note("pause", StepOutcome::Passed, || {
+                format!(
+                    "Paused until {}; meeting alerts still fire",
+                    locale::format_time(&settings.format, until)
+                )
+            });
+        }
+        if self.session_away {
+            trace.note("session", StepOutcome::Suppressed, || {
+                "Another user's session is in front; alerts wait for ours".to_string()
+            });
+        }
+        if self
+            .active
+            .as_ref()
+            .is_some_and(|a| a.event.alert_key() == key)
+        {
+            trace.note("state", StepOutcome::Passed, || {
+                "The alert is showing".to_string()
+            });
+        } else if let Some((_, until)) = self.snoozed.get(&key) {
+            trace.note("state", StepOutcome::Suppressed, || {
+                format!(
+                    "Snoozed until {}",
+                    locale::format_time(&settings.format, *until)
+                )
+            });
+        } else if self.queue.iter().any(|(e, _)| e.alert_key() == key) {
+            trace.note("state", StepOutcome::Suppressed, || {
+                "Queued behind the alert that is showing".to_string()
+            });
+        } else if let Some(fired) = self.fired.get(&key) {
+            // Missed occurrences are marked fired too, but aren't alerts in
+            // the history.
+            if alerted {
+                trace.note("state", StepOutcome::Passed, || {
+                    format!(
+                        "Already alerted at {}",
+                        locale::format_time(&settings.format, fired.at)
+                    )
+                });
+            } else {
+                trace.note("catch_up", StepOutcome::Suppressed, || {
+                    format!(
+                        "Found past the {}-minute catch-up grace; logged as missed",
+                        settings.catch_up_minutes
+                    )
+                });
+            }
+        } else if let Some(start) = start.filter(|start| *start <= now) {
+            let grace = Duration::minutes(settings.catch_up_minutes);
+            if alerted {
+                trace.note("state", StepOutcome::Passed, || {
+                    "Alerted in an earlier session".to_string()
+                });
+            } else if now - start > grace {
+                trace.note("catch_up", StepOutcome::Suppressed, || {
+                    format!(
+                        "Started more than {} min ago without alerting; logged as missed",
+                        settings.catch_up_minutes
+                    )
+                });
+            }
+        }
+        Explanation::new(trace.steps(), decision.map(|d| d.style), alert_at)
+    }
+
+    /// Meetings about to alert that have attendees but no join link and no
+    /// physical location, once each.
+    fn take_missing_links(
+        &mut self,
+        now: DateTime<Local>,
+        settings: &Settings,
+    ) -> Vec<CalendarEvent> {
+        if !settings.link_check.enabled {
+            return Vec::new();
+        }
+        let early = Duration::minutes(settings.link_check.minutes_before_alert);
+        let mut missing = Vec::new();
+        for event in &self.events {
+            if event.attendees.is_empty()
+                || matches!(
+                    event.location_kind,
+                    LocationKind::Room | LocationKind::Address
+                )
+                || self.link_checked.contains(&event.occurrence_key())
+            {
+                continue;
+            }
+            let (Some(start), Some(decision)) = (event.start_time(), self.decide(settings, event))
+            else {
+                continue;
+            };
+            if matches!(decision.style, AlertStyle::Silent) {
+                continue;
+            }
+            let alert_at = start - Duration::seconds(decision.lead_time_seconds);
+            if now < alert_at - early || now >= alert_at {
+                continue;
+            }
+            if event.find_video_link().is_none() {
+                missing.push(event.clone());
+            }
+        }
+        self.link_checked
+            .extend(missing.iter().map(CalendarEvent::occurrence_key));
+        missing
+    }
+
+    /// The active alert's theme when it differs from the one last sent, e.g.
+    /// once the meeting has started.
+    fn take_theme_change(
+        &mut self,
+        now: DateTime<Local>,
+        settings: &Settings,
+    ) -> Option<AlertTheme> {
+        let active = self.active.as_mut()?;
+        let theme = theme::derive(&settings.theme, &active.event, now, appearance::current());
+        if active.theme.as_ref() == Some(&theme) {
+            return None;
+        }
+        active.theme = Some(theme.clone());
+        Some(theme)
+    }
+
+    /// The lookahead plus the longest lead time and one poll interval, so an
+    /// event just past the lookahead is cached before its alert is due even
+    /// if the next poll is late. With a meeting budget, at least through the
+    /// end of tomorrow.
+    fn fetch_horizon(&self, settings: &Settings) -> Duration {
+        let forced = self.overrides.iter().filter_map(|o| match o.kind {
+            OverrideKind::Force { lead_minutes } => Some(lead_minutes * 60),
+            _ => None,
+        });
+        let lead = forced.fold(rules::max_lead_time_seconds(settings), i64::max);
+        let interval = cadence::longest_seconds(settings) as i64;
+        let horizon = Duration::hours(LOOKAHEAD_HOURS) + Duration::seconds(lead.max(0) + interval);
+        budget::horizon(settings, simulate::now()).map_or(horizon, |until| horizon.max(until))
+    }
+
+    /// Alerts due within one poll interval of the horizon's edge; a later
+    /// poll may not come in time to keep them.
+    fn near_horizon(
+        &self,
+        settings: &Settings,
+        now: DateTime<Local>,
+        horizon: Duration,
+    ) -> Vec<String> {
+        let interval = Duration::seconds(cadence::longest_seconds(settings) as i64);
+        let edge = now + horizon - interval;
+        self.events
+            .iter()
+            .filter_map(|event| {
+                let start = event.start_time()?;
+                let decision = self.decide(settings, event)?;
+                let alert_at = start - Duration::seconds(decision.lead_time_seconds);
+                (alert_at >= edge).then(|| {
+                    let title = privacy::redact_title(settings.privacy_mode, &event.title);
+                    format!("{} at {}", title, alert_at)
+                })
+            })
+            .collect()
+    }
+
+    /// Drops cancelled meetings from the queue; true if the active alert was
+    /// one of them and should close.
+    fn drop_cancelled(&mut self, changes: &[MeetingChange]) -> bool {
+        let cancelled: Vec<String> = changes
+            .iter()
+            .filter(|c| c.is_cancelled())
+            .map(|c| c.event.occurrence_key())
+            .collect();
+        let queued = self.queue.len();
+        self.queue
+            .retain(|(event, _)| !cancelled.contains(&event.occurrence_key()));
+        if self.queue.len() != queued {
+            self.queue_changed = true;
+        }
+        let active = self
+            .active
+            .as_ref()
+            .is_some_and(|a| cancelled.contains(&a.event.occurrence_key()));
+        if active {
+            self.active = None;
+        }
+        active
+    }
+
+    /// How often to poll at `now`, tightening before the alerts and event
+    /// starts and ends in the cache.
+    pub fn poll_cadence(&self, settings: &Settings, now: DateTime<Local>) -> Cadence {
+        let boundaries: Vec<DateTime<Local>> = self
+            .events
+            .iter()
+            .filter(|event| !event.is_all_day && !settings.calendar_filter.excludes(event))
+            .flat_map(|event| {
+                let alert_at = self
+                    .decide(settings, event)
+                    .filter(|decision| decision.style != AlertStyle::Silent)
+                    .zip(event.start_time())
+                    .map(|(decision, start)| start - Duration::seconds(decision.lead_time_seconds));
+                [alert_at, event.start_time(), event.end_time()]
+            })
+            .flatten()
+            .collect();
+        let low_power = power::profile(settings) == PowerProfile::LowPower;
+        cadence::interval(settings, &boundaries, now, low_power)
+    }
+
+    fn needs_fetch(&self, settings: &Settings, now: DateTime<Local>) -> bool {
+        let interval = StdDuration::from_secs(self.poll_cadence(settings, now).seconds);
+        self.last_fetch.is_none_or(|t| t.elapsed() >= interval)
+            || self
+                .freshness
+                .next_retry_at
+                .is_some_and(|at| Local::now() >= at)
+    }
+
+    /// Forces a refetch after sleep; `Instant` doesn't advance while asleep,
+    /// so the poll interval alone would leave stale events in place.
+    fn note_tick(&mut self, now: DateTime<Local>) {
+        if self
+            .last_tick
+            .is_some_and(|last| now - last > Duration::seconds(WAKE_GAP_SECONDS))
+        {
+            self.last_fetch = None;
+        }
+        self.last_tick = Some(now);
+    }
+
+    fn set_events(&mut self, events: Vec<CalendarEvent>, now: DateTime<Local>) {
+        let known = |key: &String| events.iter().any(|e| e.occurrence_key() == *key);
+        let by_alert_key = |key: &str| events.iter().find(|e| e.alert_key() == key);
+
+        // Fired alerts outlive the event briefly, so one that is cancelled and
+        // recreated doesn't alert twice.
+        let cutoff = now - Duration::days(1);
+        self.fired.retain(|_, fired| fired.start > cutoff);
+        self.overrides.retain(|o| !o.is_expired(now));
+        // A moved event replaces its pending snooze rather than adding one,
+        // and a cancelled one drops it.
+        self.snoozed
+            .retain(|key, (event, _)| match by_alert_key(key) {
+                Some(current) => {
+                    *event = current.clone();
+                    true
+                }
+                None => false,
+            });
+        if let Some(active) = self.active.as_mut() {
+            if let Some(current) = by_alert_key(&active.event.alert_key()) {
+                active.event = current.clone();
+            }
+        }
+        self.started.retain(known);
+        self.link_checked.retain(known);
+        self.ended.retain(known);
+        self.events = events;
+        self.last_fetch = Some(Instant::now());
+    }
+
+    /// Takes in a fetch made at `now`: what changed, the meetings cancelled
+    /// or moved, and what the scheduler did about them.
+    fn apply_fetch(
+        &mut self,
+        events: Vec<CalendarEvent>,
+        freshness: Freshness,
+        settings: &Settings,
+        now: DateTime<Local>,
+        horizon: Duration,
+    ) -> Fetched {
+        let diff = changes::diff(&self.events, &events, now);
+        let first = self.events.is_empty();
+        // Turning a source off or on isn't cancelling its meetings.
+        let changes = if self.disabled_sources == settings.sources.disabled {
+            cancellations::detect(&diff, now, settings)
+        } else {
+            Vec::new()
+        };
+        self.disabled_sources = settings.sources.disabled.clone();
+        self.set_events(events, now);
+        self.freshness = freshness;
+        let close = self.drop_cancelled(&changes);
+        for alert in self.near_horizon(settings, now, horizon) {
+            retention::log!("alert close to the fetch horizon: {}", alert);
+        }
+        let moved_pause = self.refresh_pause(now, settings);
+        Fetched {
+            diff,
+            first,
+            changes,
+            close,
+            moved_pause,
+        }
+    }
+
+    /// Picks the next alert to show (queued, then snoozed ones first).
+    /// Fullscreen alerts become the active alert, or join the queue while
+    /// another one is showing.
+    /// Meetings that started within the catch-up grace and never alerted
+    /// (per `alerted`, which checks the history) alert immediately.
+    fn take_due(
+        &mut self,
+        now: DateTime<Local>,
+        settings: &Settings,
+        alerted: impl Fn(&CalendarEvent) -> bool,
+    ) -> Option<(CalendarEvent, AlertDecision)> {
+        if self.session_away {
+            return None;
+        }
+        if self.active.is_none() {
+            if let Some((event, decision)) = self.queue.pop_front() {
+                self.queue_changed = true;
+                return Some((self.activate(event, now), decision));
+            }
+            let due: Vec<String> = self
+                .snoozed
+                .iter()
+                .filter(|(_, (_, until))| *until <= now)
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in due {
+                let (event, _) = self.snoozed.remove(&key).unwrap();
+                // Muted or filtered out since it was snoozed.
+                let Some(decision) = self.decide(settings, &event) else {
+                    continue;
+                };
+                let decision = AlertDecision {
+                    style: AlertStyle::Fullscreen,
+                    ..decision
+                };
+                return Some((self.activate(event, now), decision));
+            }
+            if let Some(index) = self.reminders.iter().position(|(_, at)| *at <= now) {
+                let (event, _) = self.reminders.remove(index);
+                let decision = rules::evaluate(settings, &event);
+                if decision.style == AlertStyle::Fullscreen {
+                    return Some((self.activate(event, now), decision));
+                }
+                return Some((event, decision));
+            }
+        }
+
+        let cooldown = Duration::minutes(settings.alert_cooldown_minutes);
+        let (event, mut decision, start, lead) = self.events.iter().find_map(|event| {
+            let decision = self.decide(settings, event)?;
+            let start = event.start_time()?;
+            let lead = Duration::seconds(decision.lead_time_seconds);
+            let key = event.alert_key();
+            let upcoming = start - lead <= now && now < start;
+            let recovered =
+                self.recovered.contains(&key) && event.end_time().is_some_and(|end| now < end);
+            let catch_up = start <= now
+                && event.end_time().is_some_and(|end| end > now)
+                && !self.fired.contains_key(&key)
+                && (recovered
+                    || (now - start <= Duration::minutes(settings.catch_up_minutes)
+                        && !alerted(event)));
+            let due = catch_up || (upcoming && self.may_fire(&key, start, now, lead, cooldown));
+            if due {
+                return Some((event.clone(), decision, start, lead));
+            }
+            let later = self.later_alarm(&key, start, now, &decision)?;
+            Some((event.clone(), decision, start, later))
+        })?;
+        let key = event.alert_key();
+        self.recovered.remove(&key);
+        self.fired.insert(
+            key.clone(),
+            FiredAlert {
+                start,
+                at: now,
+                lead,
+            },
+        );
+        decision.lead_time_seconds = lead.num_seconds();
+        // A later alarm of the meeting already on screen has nothing to add.
+        if self
+            .active
+            .as_ref()
+            .is_some_and(|a| a.event.alert_key() == key)
+        {
+            return None;
+        }
+        if decision.style == AlertStyle::Fullscreen {
+            if self.burst.note(&settings.flood, now) && !self.flooding {
+                self.flooding = true;
+                self.queue_changed = true;
+                retention::log!(
+                    "alert flood: {} alerts within {} min, collapsed into one",
+                    self.burst.count(),
+                    settings.flood.window_minutes
+                );
+            }
+            if self.active.is_some() {
+                self.queue.push_back((event, decision));
+                self.queue_changed = true;
+                return None;
+            }
+            self.activate(event.clone(), now);
+        }
+        Some((event, decision))
+    }
+
+    /// The new Dock badge text when it changed since the last call.
+    fn take_badge(&mut self, now: DateTime<Local>, settings: &Settings) -> Option<Option<String>> {
+        if self.session_away {
+            return None;
+        }
+        let dock = &settings.dock;
+        let badge = self
+            .events
+            .iter()
+            .filter(|e| !e.is_all_day && !settings.calendar_filter.excludes(e))
+            .filter_map(|e| e.start_time())
+            .filter(|start| *start > now)
+            .min()
+            .map(|start| ((start - now).num_seconds() + 59) / 60)
+            .filter(|minutes| dock.badge && *minutes <= dock.badge_within_minutes)
+            .map(|minutes| minutes.to_string());
+        if badge == self.badge {
+            return None;
+        }
+        self.badge = badge.clone();
+        Some(badge)
+    }
+
+    /// An alert fires once per occurrence. If the event moved since, it fires
+    /// again right away when it moved by more than the lead time, otherwise
+    /// only after the cooldown.
+    fn may_fire(
+        &self,
+        key: &str,
+        start: DateTime<Local>,
+        now: DateTime<Local>,
+        lead: Duration,
+        cooldown: Duration,
+    ) -> bool {
+        let Some(fired) = self.fired.get(key) else {
+            return true;
+        };
+        let moved = (start - fired.start).abs();
+        !moved.is_zero() && (moved > lead || now - fired.at >= cooldown)
+    }
+
+    /// The shortest of the event's later alarms that is due since the last
+    /// one fired; alarms passed together alert once.
+    fn later_alarm(
+        &self,
+        key: &str,
+        start: DateTime<Local>,
+        now: DateTime<Local>,
+        decision: &AlertDecision,
+    ) -> Option<Duration> {
+        let fired = self.fired.get(key).filter(|f| f.start == start)?;
+        if now >= start {
+            return None;
+        }
+        decision
+            .later_leads
+            .iter()
+            .map(|seconds| Duration::seconds(*seconds))
+            .filter(|lead| *lead < fired.lead && start - *lead <= now)
+            .min()
+    }
+
+    /// Ongoing meetings past the catch-up grace that never alerted; each is
+    /// returned once, with how its alert was decided, so it can be logged as
+    /// missed.
+    fn take_missed(
+        &mut self,
+        now: DateTime<Local>,
+        settings: &Settings,
+        alerted: impl Fn(&CalendarEvent) -> bool,
+    ) -> Vec<(CalendarEvent, Vec<TraceStep>)> {
+        let grace = Duration::minutes(settings.catch_up_minutes);
+        let missed: Vec<(CalendarEvent, DateTime<Local>)> = self
+            .events
+            .iter()
+            .filter(|e| !self.fired.contains_key(&e.alert_key()))
+            .filter(|e| {
+                self.decide(settings, e)
+                    .is_some_and(|d| d.style != AlertStyle::Silent)
+            })
+            .filter_map(|e| Some((e.clone(), e.start_time()?)))
+            .filter(|(_, start)| now - *start > grace)
+            .filter(|(e, _)| !alerted(e))
+            .collect();
+        for (event, start) in &missed {
+            self.fired.insert(
+                event.alert_key(),
+                FiredAlert {
+                    start: *start,
+                    at: now,
+                    lead: Duration::zero(),
+                },
+            );
+        }
+        missed
+            .into_iter()
+            .map(|(event, start)| {
+                let mut trace = self.decision_trace(settings, &event);
+                trace.push(TraceStep {
+                    rule: "catch_up".to_string(),
+                    outcome: StepOutcome::Suppressed,
+                    detail: format!(
+                        "Found {} min after the start, past the {}-minute catch-up grace",
+                        (now - start).num_minutes(),
+                        settings.catch_up_minutes
+                    ),
+                });
+                (event, trace)
+            })
+            .collect()
+    }
+
+    pub fn has_active_alert(&self) -> bool {
+        self.active.is_some()
+    }
+
+    pub fn active_event(&self) -> Option<&CalendarEvent> {
+        self.active.as_ref().map(|active| &active.event)
+    }
+
+    /// Moves the active alert into or out of the corner; false if no alert
+    /// is showing.
+    pub fn set_cornered(&mut self, cornered: bool) -> bool {
+        let Some(active) = self.active.as_mut() else {
+            return false;
+        };
+        active.cornered = cornered;
+        true
+    }
+
+    fn take_queue_change(&mut self, settings: &Settings) -> Option<QueuedAlerts> {
+        if !std::mem::take(&mut self.queue_changed) {
+            return None;
+        }
+        Some(QueuedAlerts {
+            count: self.queue.len(),
+            events: self
+                .queue
+                .iter()
+                .map(|(e, _)| confidential::shown(settings, e))
+                .collect(),
+            merged: self.merges_queue(settings),
+            flood: self.flooding,
+        })
+    }
+
+    fn activate(&mut self, event: CalendarEvent, now: DateTime<Local>) -> CalendarEvent {
+        self.pill = None;
+        self.active = Some(ActiveAlert {
+            event: event.clone(),
+            escalated: false,
+            shown_at: now,
+            raised_at: now,
+            theme: None,
+            cornered: false,
+            sound_stage: 0,
+            counted_in: false,
+        });
+        event
+    }
+
+    /// Meetings that started or ended since the last tick.
+    fn take_transitions(&mut self, now: DateTime<Local>) -> Vec<(LifecycleEvent, CalendarEvent)> {
+        let window = Duration::seconds(TRANSITION_WINDOW_SECONDS);
+        let mut transitions = Vec::new();
+        for event in self
+            .events
+            .iter()
+            .filter(|e| !e.is_all_day && !e.is_long_block)
+        {
+            let key = event.occurrence_key();
+            if let Some(start) = event.start_time() {
+                if start <= now && now < start + window && self.started.insert(key.clone()) {
+                    transitions.push((LifecycleEvent::MeetingStarted, event.clone()));
+                }
+            }
+            if let Some(end) = event.end_time() {
+                if end <= now && now < end + window && self.ended.insert(key) {
+                    transitions.push((LifecycleEvent::MeetingEnded, event.clone()));
+                }
+            }
+        }
+        transitions
+    }
+
+    /// Closes the active alert for `ack`, snoozing what it covered if that
+    /// was the answer. The events it covered (the merged queue too) and its
+    /// decision; `None` if no alert was showing.
+    fn acknowledge(
+        &mut self,
+        ack: &Acknowledgement,
+        settings: &Settings,
+        now: DateTime<Local>,
+    ) -> Option<(Vec<CalendarEvent>, AlertDecision)> {
+        let active = self.active.take()?;
+        let decision = rules::evaluate(settings, &active.event);
+        let pill = matches!(ack, Acknowledgement::Joined)
+            && decision.pill_after_join
+            && active.event.end_time().is_some_and(|end| now < end);
+        if pill {
+            self.pill = Some(active.event.clone());
+        }
+        let mut events = vec![active.event];
+        if self.merges_queue(settings) {
+            events.extend(self.queue.drain(..).map(|(event, _)| event));
+            self.queue_changed = true;
+        }
+        self.flooding = false;
+        if let Acknowledgement::Snoozed(minutes) = ack {
+            let until = now + Duration::minutes(*minutes);
+            for event in &events {
+                self.snoozed
+                    .insert(event.alert_key(), (event.clone(), until));
+            }
+        }
+        Some((events, decision))
+    }
+
+    /// A meeting joined before it alerted doesn't alert any more, snoozed or
+    /// not.
+    pub fn mark_joined(&mut self, event: &CalendarEvent, now: DateTime<Local>) {
+        let key = event.alert_key();
+        self.snoozed.remove(&key);
+        if let Some(start) = event.start_time() {
+            self.fired.entry(key).or_insert(FiredAlert {
+                start,
+                at: now,
+                lead: Duration::zero(),
+            });
+        }
+    }
+
+    pub fn pill(&self) -> Option<&CalendarEvent> {
+        self.pill.as_ref()
+    }
+
+    /// Shows `event` as the pill, or clears it.
+    pub fn set_pill(&mut self, event: Option<CalendarEvent>) {
+        self.pill = event;
+    }
+
+    /// Clears the pill once its meeting is over.
+    fn take_pill_ended(&mut self, now: DateTime<Local>) -> bool {
+        let ended = self
+            .pill
+            .as_ref()
+            .is_some_and(|event| event.end_time().is_none_or(|end| now >= end));
+        if ended {
+            self.pill = None;
+        }
+        ended
+    }
+
+    /// A reminder from the inbound channel; it alerts once `at` comes, like
+    /// a snooze running out, and isn't tied to the calendar.
+    pub fn add_reminder(&mut self, event: CalendarEvent, at: DateTime<Local>) {
+        self.reminders.push((event, at));
+    }
+
+    pub fn schedule_dry_run(&mut self, event: CalendarEvent, due: DateTime<Local>) {
+        self.dry_run = Some((event, due));
+    }
+
+    fn take_dry_run(&mut self, now: DateTime<Local>) -> Option<(CalendarEvent, DateTime<Local>)> {
+        if !self.session_away && self.dry_run.as_ref().is_some_and(|(_, due)| now >= *due) {
+            return self.dry_run.take();
+        }
+        None
+    }
+
+    /// Refetches on the next tick, e.g. after the event source changes.
+    pub fn invalidate(&mut self) {
+        self.last_fetch = None;
+    }
+
+    /// Drops pending snoozes the current settings and overrides would no
+    /// longer alert for.
+    pub fn reschedule(&mut self, settings: &Settings) {
+        let snoozed = std::mem::take(&mut self.snoozed);
+        self.snoozed = snoozed
+            .into_iter()
+            .filter(|(_, (event, _))| {
+                self.decide(settings, event)
+                    .is_some_and(|d| d.style != AlertStyle::Silent)
+            })
+            .collect();
+    }
+
+    /// The scheduled profile for today, once per day and only if it differs.
+    fn take_scheduled_profile(
+        &mut self,
+        now: DateTime<Local>,
+        settings: &Settings,
+    ) -> Option<String> {
+        let schedule = &settings.profile_schedule;
+        let today = now.date_naive();
+        if !schedule.enabled || self.profile_day == Some(today) {
+            return None;
+        }
+        self.profile_day = Some(today);
+        // A day with no working hours, like a Friday off in a four-day week.
+        let day_off = settings.working_hours.hours_on(today).is_none();
+        let wanted = if day_off {
+            schedule.weekend.clone()
+        } else {
+            schedule.weekday.clone()
+        }?;
+        (settings.active_profile.as_ref() != Some(&wanted)).then_some(wanted)
+    }
+
+    /// Pauses non-meeting reminders until the current block of meetings
+    /// ends. Returns the resume time, or `None` if there is nothing to wait
+    /// for.
+    fn pause_until_free(
+        &mut self,
+        now: DateTime<Local>,
+        settings: &Settings,
+    ) -> Option<DateTime<Local>> {
+        let meetings: Vec<CalendarEvent> = self
+            .events
+            .iter()
+            .filter(|e| {
+                !settings.calendar_filter.excludes(e) && !self.is_muted(e) && !e.is_long_block
+            })
+            .cloned()
+            .collect();
+        self.paused_until_free = agenda::busy_until(&meetings, now);
+        self.pause_is_timed = false;
+        self.paused_until_free
+    }
+
+    /// Pauses for a fixed time, which later fetches leave alone.
+    pub fn pause_for(&mut self, now: DateTime<Local>, duration: Duration) -> DateTime<Local> {
+        let until = now + duration;
+        self.paused_until_free = Some(until);
+        self.pause_is_timed = true;
+        until
+    }
+
+    /// Follows the free slot if the calendar moved it. Returns the new resume
+    /// time if it changed.
+    fn refresh_pause(
+        &mut self,
+        now: DateTime<Local>,
+        settings: &Settings,
+    ) -> Option<Option<DateTime<Local>>> {
+        let before = self.paused_until_free.filter(|_| !self.pause_is_timed)?;
+        let after = self.pause_until_free(now, settings);
+        (after != Some(before)).then_some(after)
+    }
+
+    pub fn health(&self, now: DateTime<Local>, settings: &Settings) -> SchedulerHealth {
+        let next_alert = self
+            .events
+            .iter()
+            .filter_map(|event| {
+                let decision = self.decide(settings, event)?;
+                if decision.style == AlertStyle::Silent {
+                    return None;
+                }
+                let at = event.start_time()? - Duration::seconds(decision.lead_time_seconds);
+                (at > now).then(|| PlannedAlert {
+                    title: event.title.clone(),
+                    at,
+                })
+            })
+            .min_by_key(|planned| planned.at);
+        let last_fetch_at = self
+            .last_fetch
+            .and_then(|t| Duration::from_std(t.elapsed()).ok())
+            .map(|elapsed| now - elapsed);
+        SchedulerHealth {
+            running: self
+                .last_tick
+                .is_some_and(|t| now - t <= Duration::seconds(WAKE_GAP_SECONDS)),
+            last_tick: self.last_tick,
+            last_fetch_at,
+            freshness: self.freshness.clone(),
+            events_cached: self.events.len(),
+            active_alert: self.active.as_ref().map(|a| a.event.title.clone()),
+            queued_alerts: self.queue.len(),
+            paused_until: self.paused_until_free,
+            next_alert,
+            poll_cadence: self.poll_cadence(settings, now),
+        }
+    }
+
+    /// Picks up what a crashed session left behind; see `journal`.
+    pub fn recover(&mut self, recovery: &Recovery) {
+        self.recovered.extend(recovery.alert_keys.iter().cloned());
+        self.paused_until_free = recovery.paused_until;
+        self.pause_is_timed = recovery.pause_is_timed;
+        for snooze in &recovery.snoozed {
+            self.snoozed.insert(
+                snooze.alert_key.clone(),
+                (snooze.event.clone(), snooze.until),
+            );
+        }
+    }
+
+    /// What a restart would have to pick up again.
+    pub fn pending(&self) -> PendingWork {
+        let mut snoozed: Vec<PendingSnooze> = self
+            .snoozed
+            .iter()
+            .map(|(key, (event, until))| PendingSnooze {
+                alert_key: key.clone(),
+                event: event.clone(),
+                until: *until,
+            })
+            .collect();
+        snoozed.sort_by(|a, b| a.alert_key.cmp(&b.alert_key));
+        PendingWork {
+            paused_until: self.paused_until_free,
+            pause_is_timed: self.pause_is_timed,
+            snoozed,
+            queued: self
+                .queue
+                .iter()
+                .map(|(event, _)| JournaledAlert {
+                    alert_key: event.alert_key(),
+                    title: event.title.clone(),
+                    end: event.end_time(),
+                })
+                .collect(),
+        }
+    }
+
+    /// When non-meeting reminders resume, if paused until free.
+    pub fn paused_until(&self) -> Option<DateTime<Local>> {
+        self.paused_until_free
+    }
+
+    fn is_paused(&mut self, now: DateTime<Local>) -> bool {
+        if self.paused_until_free.is_some_and(|until| now >= until) {
+            self.paused_until_free = None;
+        }
+        self.paused_until_free.is_some()
+    }
+
+    /// Digests and other notices wait out a pause or a backgrounded session.
+    fn is_held(&mut self, now: DateTime<Local>) -> bool {
+        self.session_away || self.is_paused(now)
+    }
+
+    pub fn set_session_active(&mut self, active: bool) {
+        self.session_away = !active;
+    }
+
+    fn take_digest_due(&mut self, now: DateTime<Local>, settings: &Settings) -> bool {
+        // While paused the digest is held back, not skipped, so it still goes
+        // out if the pause ends within the digest window.
+        if !settings.digest.enabled || self.is_held(now) {
+            return false;
+        }
+        // Checked each time, so "I'm Working Today" within the window still
+        // sends it.
+        if days_off::is_day_off(settings, now.date_naive()) {
+            return false;
+        }
+        take_daily_due(&mut self.last_digest, now, settings.digest.time)
+    }
+
+    /// "Tomorrow: 5.5h of meetings, over your 4h budget", the first time
+    /// tomorrow goes over; once per day warned about.
+    fn take_budget_warning(&mut self, now: DateTime<Local>, settings: &Settings) -> Option<String> {
+        let tomorrow = now.date_naive().succ_opt()?;
+        if self.budget_warned == Some(tomorrow) || self.is_held(now) {
+            return None;
+        }
+        let usage = budget::day_usage(settings, &self.events, tomorrow)?;
+        if !usage.over_budget {
+            return None;
+        }
+        self.budget_warned = Some(tomorrow);
+        Some(format!("Tomorrow: {}", usage.summary))
+    }
+
+    fn take_preview_due(&mut self, now: DateTime<Local>, settings: &Settings) -> bool {
+        let preview = &settings.tomorrow_preview;
+        if !preview.enabled || !preview.days.contains(&now.weekday()) || self.is_held(now) {
+            return false;
+        }
+        take_daily_due(&mut self.last_preview, now, preview.time)
+    }
+
+    /// Returns the active alert once it has gone unacknowledged for too long.
+    fn take_escalation(
+        &mut self,
+        now: DateTime<Local>,
+        settings: &Settings,
+    ) -> Option<CalendarEvent> {
+        if !settings.escalation.enabled
+            || !features::enabled(settings, Flag::Escalation)
+            || self.session_away
+        {
+            return None;
+        }
+        let active = self
+            .active
+            .as_mut()
+            .filter(|a| !a.escalated && !a.cornered)?;
+        let start = active.event.start_time()?;
+        if now < start + Duration::minutes(settings.escalation.after_minutes) {
+            return None;
+        }
+        active.escalated = true;
+        Some(active.event.clone())
+    }
+
+    /// True once the active alert has been up longer than the auto-dismiss
+    /// delay.
+    fn is_expired(&self, now: DateTime<Local>, settings: &Settings) -> bool {
+        let (Some(active), Some(after)) = (&self.active, settings.nag.auto_dismiss_after_seconds)
+        else {
+            return false;
+        };
+        now - active.shown_at >= Duration::seconds(after as i64)
+    }
+
+    /// The active alert's latest sound stage that is due and hasn't played,
+    /// with the alert's route; stages passed together play only the last.
+    /// Queued alerts wait their turn, so only one profile is ever heard.
+    fn take_sound_stage(
+        &mut self,
+        now: DateTime<Local>,
+        settings: &Settings,
+    ) -> Option<(SoundStage, AudioRoute)> {
+        if self.session_away {
+            return None;
+        }
+        let active = self.active.as_mut().filter(|a| !a.cornered)?;
+        let start = active.event.start_time()?;
+        let decision = rules::evaluate(settings, &active.event);
+        let (index, stage) = decision
+            .sound_profile
+            .into_iter()
+            .enumerate()
+            .skip(active.sound_stage)
+            .rfind(|(_, stage)| start + Duration::seconds(stage.offset_seconds) <= now)?;
+        active.sound_stage = index + 1;
+        Some((stage, decision.sound_route))
+    }
+
+    /// The active alert's occurrence, start and route once its count-in is
+    /// due: in the last seconds before the start, with sound, in full view
+    /// and not counted in yet.
+    fn take_count_in(
+        &mut self,
+        now: DateTime<Local>,
+        settings: &Settings,
+    ) -> Option<(String, DateTime<Local>, AudioRoute)> {
+        if self.session_away || presentation::is_presenting() {
+            return None;
+        }
+        let active = self
+            .active
+            .as_mut()
+            .filter(|a| !a.cornered && !a.counted_in)?;
+        let start = active.event.start_time()?;
+        let decision = rules::evaluate(settings, &active.event);
+        let silent = decision.sound.is_none() && decision.sound_profile.is_empty();
+        if !decision.count_in || silent || !features::enabled(settings, Flag::CountIn) {
+            return None;
+        }
+        // A tick early, so the first second isn't already gone.
+        let window = Duration::seconds(i64::from(settings.count_in.seconds) + 1);
+        let left = start - now;
+        if left <= Duration::zero() || left > window {
+            return None;
+        }
+        active.counted_in = true;
+        Some((active.event.occurrence_key(), start, decision.sound_route))
+    }
+
+    /// Whether the count-in for the occurrence `key` may go on: its alert is
+    /// still the one showing, in full view.
+    pub fn counts_in(&self, key: &str) -> bool {
+        !self.session_away
+            && self
+                .active
+                .as_ref()
+                .is_some_and(|a| !a.cornered && a.event.occurrence_key() == key)
+    }
+
+    /// Returns the active alert when it is due to be raised again. Nagging
+    /// stops a grace period after the event ended; a snooze clears the active
+    /// alert and so pauses it until the snooze fires.
+    fn take_nag(&mut self, now: DateTime<Local>, settings: &Settings) -> Option<CalendarEvent> {
+        if self.session_away {
+            return None;
+        }
+        let seconds = settings.nag.interval_seconds?.max(MIN_NAG_INTERVAL_SECONDS);
+        let interval = Duration::seconds(seconds as i64);
+        let active = self.active.as_mut().filter(|a| !a.cornered)?;
+        let end = active.event.end_time()?;
+        if now > end + Duration::minutes(settings.nag.grace_minutes)
+            || now - active.raised_at < interval
+        {
+            return None;
+        }
+        active.raised_at = now;
+        Some(active.event.clone())
+    }
+}
+
+/// True once a day, within the window after `time`; `last` records the day.
+fn take_daily_due(last: &mut Option<NaiveDate>, now: DateTime<Local>, time: NaiveTime) -> bool {
+    let today = now.date_naive();
+    if *last == Some(today) {
+        return false;
+    }
+    let Some(at) = today.and_time(time).and_local_timezone(Local).earliest() else {
+        return false;
+    };
+    if now < at || now >= at + Duration::minutes(DIGEST_WINDOW_MINUTES) {
+        return false;
+    }
+    *last = Some(today);
+    true
+}
+
+// --- Host ---
+//
+// `tick`, `fire` and `acknowledge` reach the rest of the app only through
+// `Host`. The app implements it by calling the subsystems; the test harness
+// (the `test-harness` feature) implements it over a mock calendar and clock
+// and records what the alert window was asked to do.
+
+/// What the scheduler's tick and the alerts it fires ask of the app.
+pub(crate) trait Host {
+    /// The scheduler's clock.
+    fn now(&self) -> DateTime<Local>;
+    fn settings(&self) -> Settings;
+    fn scheduler(&self) -> MutexGuard<'_, Scheduler>;
+    fn publish(&self, event: AppEvent);
+    /// Polls the system state the scheduler follows: appearance, time zone,
+    /// session, presentation and days off.
+    fn poll(&self);
+    /// The events up to `horizon` from now, and how fresh they are.
+    fn fetch(&self, settings: &Settings, horizon: Duration) -> (Vec<CalendarEvent>, Freshness);
+    /// Shows a fetch to what watches the calendar for recreated series and
+    /// days off.
+    fn observe(&self, settings: &Settings, events: &[CalendarEvent]);
+    fn fetched(&self);
+    fn announce_change(&self, settings: &Settings, change: MeetingChange);
+    fn switch_profile(&self, name: &str);
+    fn was_alerted(&self, event: &CalendarEvent) -> bool;
+    fn record(&self, event: &CalendarEvent, kind: HistoryKind, trace: Vec<TraceStep>);
+    fn record_recent(&self, event: &CalendarEvent);
+    /// Learns from the lateness of an acknowledged alert (auto-tuning).
+    fn review(&self, event: &CalendarEvent);
+    /// Runs an alert rule's chain off the scheduler's thread.
+    fn run_chain(&self, chain: Vec<ActionStep>, event: CalendarEvent, kind: HistoryKind);
+    fn journal_shown(&self, event: &CalendarEvent);
+    fn journal_closed(&self);
+    fn journal_pause(&self, until: Option<DateTime<Local>>);
+    fn journal_pending(&self, pending: PendingWork);
+    fn note_for(&self, event: &CalendarEvent) -> Option<String>;
+    fn notify(&self, title: &str, body: &str);
+    fn request_attention(&self);
+    fn set_badge(&self, badge: Option<String>);
+    fn play_sound(&self, name: &str, route: AudioRoute);
+    fn play_stage(&self, stage: SoundStage, route: AudioRoute);
+    fn count_in(&self, key: String, start: DateTime<Local>, route: AudioRoute, settings: &Settings);
+    fn announce_alert(&self, text: String);
+    fn ensure_alert_window(&self);
+    fn raise_alert(&self);
+    fn close_alert(&self);
+    /// Opens a meeting's link, already checked.
+    fn open_link(&self, url: &str);
+    /// Shrinks the alert window to the pill.
+    fn dock_pill(&self);
+    fn sync_displays(&self);
+    fn dry_run(&self, event: &CalendarEvent, due: DateTime<Local>);
+    fn send_digest(&self);
+    fn send_tomorrow_preview(&self);
+}
+
+impl Host for AppHandle {
+    fn now(&self) -> DateTime<Local> {
+        simulate::now()
+    }
+
+    fn settings(&self) -> Settings {
+        settings::current(self)
+    }
+
+    fn scheduler(&self) -> MutexGuard<'_, Scheduler> {
+        self.state::<SchedulerState>().inner().0.lock().unwrap()
+    }
+
+    fn publish(&self, event: AppEvent) {
+        bus::publish(self, event);
+    }
+
+    fn poll(&self) {
+        appearance::poll(self);
+        timezone::poll(self);
+        session::poll(self);
+        presentation::poll(self);
+        days_off::poll(self);
+    }
+
+    fn fetch(&self, settings: &Settings, horizon: Duration) -> (Vec<CalendarEvent>, Freshness) {
+        let events = sources::fetch(self, settings, horizon);
+        (events, sources::freshness(self, settings))
+    }
+
+    fn observe(&self, settings: &Settings, events: &[CalendarEvent]) {
+        series::observe(self, settings, events);
+        days_off::observe(self, settings, events);
+    }
+
+    fn fetched(&self) {
+        startup::fetched(self);
+    }
+
+    fn announce_change(&self, settings: &Settings, change: MeetingChange) {
+        cancellations::announce(self, settings, change);
+    }
+
+    fn switch_profile(&self, name: &str) {
+        let _ = profiles::switch(self, name);
+    }
+
+    fn was_alerted(&self, event: &CalendarEvent) -> bool {
+        history::was_alerted(self, event)
+    }
+
+    fn record(&self, event: &CalendarEvent, kind: HistoryKind, trace: Vec<TraceStep>) {
+        history::record_traced(self, event, kind, trace);
+    }
+
+    fn record_recent(&self, event: &CalendarEvent) {
+        recent::record(self, event);
+    }
+
+    fn review(&self, event: &CalendarEvent) {
+        autotune::review(self, event);
+    }
+
+    fn run_chain(&self, chain: Vec<ActionStep>, event: CalendarEvent, kind: HistoryKind) {
+        let app = self.clone();
+        thread::spawn(move || {
+            let results = actions::run_chain(&app, &chain);
+            history::attach_actions(&app, &event, kind, results);
+        });
+    }
+
+    fn journal_shown(&self, event: &CalendarEvent) {
+        journal::alert_shown(self, event);
+    }
+
+    fn journal_closed(&self) {
+        journal::alert_closed(self);
+    }
+
+    fn journal_pause(&self, until: Option<DateTime<Local>>) {
+        journal::paused(self, until);
+    }
+
+    fn journal_pending(&self, pending: PendingWork) {
+        journal::save_pending(self, pending);
+    }
+
+    fn note_for(&self, event: &CalendarEvent) -> Option<String> {
+        notes::note_for(self, event)
+    }
+
+    fn notify(&self, title: &str, body: &str) {
+        notify::show(self, title, body);
+    }
+
+    fn request_attention(&self) {
+        dock::request_attention(self);
+    }
+
+    fn set_badge(&self, badge: Option<String>) {
+        dock::set_badge(self, badge);
+    }
+
+    fn play_sound(&self, name: &str, route: AudioRoute) {
+        sound::play(self, name, route);
+    }
+
+    fn play_stage(&self, stage: SoundStage, route: AudioRoute) {
+        sound::play_stage(self, &stage.sound, stage.volume, stage.looping, route);
+    }
+
+    fn count_in(
+        &self,
+        key: String,
+        start: DateTime<Local>,
+        route: AudioRoute,
+        settings: &Settings,
+    ) {
+        countin::start(self, key, start, route, &settings.count_in);
+    }
+
+    fn announce_alert(&self, text: String) {
+        accessibility::announce_alert(self, text);
+    }
+
+    fn ensure_alert_window(&self) {
+        window::ensure_alert_window(self);
+    }
+
+    fn raise_alert(&self) {
+        window::raise_alert(self);
+    }
+
+    fn close_alert(&self) {
+        window::close_alert(self);
+    }
+
+    fn open_link(&self, url: &str) {
+        let _ = open::that(url);
+    }
+
+    fn dock_pill(&self) {
+        window::dock_pill(self);
+    }
+
+    fn sync_displays(&self) {
+        displays::sync(self);
+    }
+
+    fn dry_run(&self, event: &CalendarEvent, due: DateTime<Local>) {
+        selftest::fire(self, event, due);
+    }
+
+    fn send_digest(&self) {
+        agenda::send_digest(self);
+    }
+
+    fn send_tomorrow_preview(&self) {
+        agenda::send_tomorrow_preview(self);
+    }
+}
+
+/// The runtime the alert commands run on, and the `Host` they acknowledge
+/// through: the app itself, or the harness's mock app's `Harness`.
+pub(crate) trait AlertRuntime: Runtime {
+    type Host: Host;
+
+    fn with_host<T, F: FnOnce(&Self::Host) -> T>(app: &AppHandle<Self>, f: F) -> T;
+}
+
+impl AlertRuntime for Wry {
+    type Host = AppHandle;
+
+    fn with_host<T, F: FnOnce(&AppHandle) -> T>(app: &AppHandle, f: F) -> T {
+        f(app)
+    }
+}
+
+pub fn start(app: AppHandle) {
+    thread::spawn(move || loop {
+        tick(&app);
+        thread::sleep(TICK);
+    });
+}
+
+fn tick(host: &impl Host) {
+    // Nothing new fires while the shutdown hooks run.
+    if shutdown::in_progress() {
+        return;
+    }
+    let settings = host.settings();
+    host.scheduler().note_tick(Local::now());
+    host.publish(AppEvent::Tick(host.now()));
+    host.poll();
+
+    if host.scheduler().needs_fetch(&settings, host.now()) {
+        let horizon = host.scheduler().fetch_horizon(&settings);
+        // Fetch without holding the lock; the Swift helper can take seconds.
+        let (events, freshness) = host.fetch(&settings, horizon);
+        host.observe(&settings, &events);
+        let count = events.len();
+        let now = host.now();
+        let fetched = host
+            .scheduler()
+            .apply_fetch(events, freshness, &settings, now, horizon);
+        // The first fetch adds everything; that isn't a change worth logging.
+        if !fetched.first {
+            changes::log(&settings, &fetched.diff, now);
+        }
+        host.fetched();
+        host.publish(AppEvent::CalendarUpdated {
+            events: count,
+            diff: Box::new(confidential::shown_diff(&settings, &fetched.diff)),
+        });
+        if let Some(until) = fetched.moved_pause {
+            host.journal_pause(until);
+        }
+        if fetched.close {
+            sound::stop();
+            host.journal_closed();
+            host.close_alert();
+            host.publish(AppEvent::AlertCleared);
+        }
+        for change in fetched.changes {
+            host.announce_change(&settings, change);
+        }
+    }
+
+    let now = host.now();
+    let profile = host.scheduler().take_scheduled_profile(now, &settings);
+    if let Some(name) = profile {
+        host.switch_profile(&name);
+    }
+
+    // Read the settings under the scheduler lock so a profile switch is
+    // either fully applied or not at all for this decision.
+    let (due, settings) = {
+        let mut scheduler = host.scheduler();
+        let settings = host.settings();
+        let alerted = |event: &CalendarEvent| host.was_alerted(event);
+        (scheduler.take_due(now, &settings, alerted), settings)
+    };
+    if let Some((event, decision)) = due {
+        fire(host, &event, &decision);
+    }
+
+    if host.scheduler().take_pill_ended(now) {
+        host.close_alert();
+    }
+
+    let dry_run = host.scheduler().take_dry_run(now);
+    if let Some((event, due)) = dry_run {
+        host.dry_run(&event, due);
+    }
+
+    let missing = host.scheduler().take_missing_links(now, &settings);
+    for event in missing {
+        warn_missing_link(host, &confidential::shown(&settings, &event));
+    }
+
+    let queued = host.scheduler().take_queue_change(&settings);
+    if let Some(queued) = queued {
+        host.publish(AppEvent::AlertQueued(queued));
+    }
+
+    let alerted = |event: &CalendarEvent| host.was_alerted(event);
+    let missed = host.scheduler().take_missed(now, &settings, alerted);
+    for (event, trace) in missed {
+        host.record(&event, HistoryKind::Missed, trace);
+    }
+
+    let transitions = host.scheduler().take_transitions(now);
+    for (kind, event) in transitions {
+        host.publish(AppEvent::Lifecycle(kind, event));
+    }
+
+    let expired = host.scheduler().is_expired(now, &settings);
+    if expired {
+        answer(host, Acknowledgement::Expired);
+        host.close_alert();
+        host.publish(AppEvent::AlertExpired);
+    }
+
+    let nag = host.scheduler().take_nag(now, &settings);
+    if let Some(event) = nag {
+        if settings.nag.replay_sound && !has_sound_profile(&settings, &event) {
+            if let Some(name) = &settings.alert_sound {
+                host.play_sound(name, rules::evaluate(&settings, &event).sound_route);
+            }
+        }
+        host.raise_alert();
+    }
+
+    let stage = host.scheduler().take_sound_stage(now, &settings);
+    if let Some((stage, route)) = stage {
+        host.play_stage(stage, route);
+    }
+
+    let count_in = host.scheduler().take_count_in(now, &settings);
+    if let Some((key, start, route)) = count_in {
+        host.count_in(key, start, route, &settings);
+    }
+
+    let theme = host.scheduler().take_theme_change(now, &settings);
+    if let Some(theme) = theme {
+        host.publish(AppEvent::AlertTheme(theme));
+    }
+
+    let escalation = host.scheduler().take_escalation(now, &settings);
+    if let Some(event) = escalation {
+        escalate(host, &event, &settings);
+    }
+
+    if host.scheduler().take_digest_due(now, &settings) {
+        host.send_digest();
+    }
+
+    if host.scheduler().take_preview_due(now, &settings) {
+        host.send_tomorrow_preview();
+    }
+
+    let over_budget = host.scheduler().take_budget_warning(now, &settings);
+    if let Some(message) = over_budget {
+        host.notify("Meeting budget", &message);
+    }
+
+    let badge = host.scheduler().take_badge(now, &settings);
+    if let Some(badge) = badge {
+        host.set_badge(badge);
+    }
+
+    host.sync_displays();
+
+    let pending = host.scheduler().pending();
+    host.journal_pending(pending);
+}
+
+fn started_minutes_ago(event: &CalendarEvent, now: DateTime<Local>) -> Option<i64> {
+    event
+        .start_time()
+        .filter(|start| *start <= now)
+        .map(|start| (now - start).num_minutes())
+}
+
+fn fire(host: &impl Host, event: &CalendarEvent, decision: &AlertDecision) {
+    let now = host.now();
+    let started_ago = started_minutes_ago(event, now);
+    // What the alert, the notification and speech may show of the event.
+    let shown = confidential::redact(decision.display_policy, event);
+    match decision.style {
+        AlertStyle::Silent => {
+            let settings = host.settings();
+            let trace = host.scheduler().decision_trace(&settings, event);
+            host.record(event, HistoryKind::Suppressed, trace);
+            return;
+        }
+        AlertStyle::Notification => {
+            let settings = host.settings();
+            let verb = if decision.time_to_leave {
+                "Time to leave, starts at"
+            } else {
+                "Starts at"
+            };
+            let starts = match started_ago {
+                Some(0) => "Started just now".to_string(),
+                Some(minutes) => format!("Started {} min ago", minutes),
+                None => event
+                    .start_time()
+                    .map(|t| format!("{} {}", verb, locale::format_time(&settings.format, t)))
+                    .unwrap_or_default(),
+            };
+            let body = match &settings.templates.notification {
+                Some(template) => templates::render(template, &shown, now, &settings.format),
+                None => starts,
+            };
+            host.notify(&shown.title, &body);
+            if settings.dock.bounce_on_notification {
+                host.request_attention();
+            }
+        }
+        AlertStyle::Fullscreen => {
+            // A sound profile starts on the next tick instead.
+            if let Some(name) = decision
+                .sound
+                .as_ref()
+                .filter(|_| decision.sound_profile.is_empty())
+            {
+                host.play_sound(name, decision.sound_route);
+            }
+            let settings = host.settings();
+            let mut stamped = shown.clone();
+            stamped.stamp(now, &settings.format);
+            let payload = AlertPayload {
+                event: stamped,
+                lead_time_seconds: decision.lead_time_seconds,
+                time_to_leave: decision.time_to_leave,
+                started_minutes_ago: started_ago,
+                display_text: templates::display_text(&settings, &shown),
+                theme: theme::derive(&settings.theme, &shown, now, appearance::current()),
+                note: host
+                    .note_for(event)
+                    .filter(|_| decision.display_policy.shows_note()),
+                counterpart_local_times: counterparts::for_event(&settings, &shown),
+            };
+            host.journal_shown(event);
+            host.ensure_alert_window();
+            host.announce_alert(templates::speech(&settings, &shown));
+            host.publish(AppEvent::AlertFired(Box::new(payload)));
+            host.raise_alert();
+        }
+    }
+    host.record(event, HistoryKind::Fired, Vec::new());
+    host.record_recent(event);
+    host.publish(AppEvent::Lifecycle(
+        LifecycleEvent::AlertFired,
+        event.clone(),
+    ));
+}
+
+/// A notification, plus a banner on the dashboard with a button that opens
+/// the invite in Calendar.app.
+fn warn_missing_link(host: &impl Host, event: &CalendarEvent) {
+    let body = format!(
+        "No join link found for '{}' — check the invite",
+        event.title
+    );
+    host.notify("No join link", &body);
+    host.publish(AppEvent::LinkMissing(event.clone()));
+}
+
+fn has_sound_profile(settings: &Settings, event: &CalendarEvent) -> bool {
+    !rules::evaluate(settings, event).sound_profile.is_empty()
+}
+
+fn escalate(host: &impl Host, event: &CalendarEvent, settings: &Settings) {
+    let escalation = &settings.escalation;
+    host.record(event, HistoryKind::Escalated, Vec::new());
+
+    if escalation.replay_sound && !has_sound_profile(settings, event) {
+        if let Some(name) = &settings.alert_sound {
+            host.play_sound(name, rules::evaluate(settings, event).sound_route);
+        }
+    }
+    if escalation.raise_window {
+        host.raise_alert();
+    }
+    if let Some(url) = &escalation.push_url {
+        let started = event
+            .start_time()
+            .map(|t| locale::format_time(&settings.format, t))
+            .unwrap_or_default();
+        push::send(
+            url.clone(),
+            format!(
+                "Missed: {}",
+                privacy::redact_title(settings.privacy_mode, &event.title)
+            ),
+            format!(
+                "Started at {} and the alert is still unacknowledged.",
+                started
+            ),
+        );
+    }
+    let shown = confidential::shown(settings, event);
+    host.publish(AppEvent::AlertEscalated(shown));
+}
+
+/// The events from the most recent fetch.
+pub fn cached_events(app: &AppHandle) -> Vec<CalendarEvent> {
+    app.state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .events
+        .clone()
+}
+
+/// Any of dismiss, snooze, or join counts as acknowledging the active alert,
+/// and with merged alerts every event merged into it. Queued alerts follow
+/// on the next tick.
+pub fn acknowledge(app: &AppHandle, ack: Acknowledgement) {
+    answer(app, ack);
+}
+
+/// Shows the joined meeting `event` as the pill, redacted as its alert was.
+pub fn show_pill(app: &AppHandle, event: &CalendarEvent) {
+    pill(app, event);
+}
+
+/// `show_pill`, through `host`.
+fn pill(host: &impl Host, event: &CalendarEvent) {
+    host.ensure_alert_window();
+    let shown = confidential::shown(&host.settings(), event);
+    host.publish(AppEvent::PillMode(Some(shown)));
+    host.dock_pill();
+}
+
+/// `acknowledge`, through `host`.
+fn answer(host: &impl Host, ack: Acknowledgement) {
+    sound::stop();
+    dismissal::clear();
+    let settings = host.settings();
+    let acknowledged = host.scheduler().acknowledge(&ack, &settings, host.now());
+    let Some((mut events, decision)) = acknowledged else {
+        return;
+    };
+
+    let (kind, lifecycle) = match ack {
+        Acknowledgement::Dismissed => {
+            (HistoryKind::Dismissed, Some(LifecycleEvent::AlertDismissed))
+        }
+        Acknowledgement::Joined => (HistoryKind::Joined, None),
+        Acknowledgement::Expired => (HistoryKind::Expired, None),
+        Acknowledgement::Snoozed(_) => (HistoryKind::Snoozed, Some(LifecycleEvent::AlertSnoozed)),
+    };
+    host.journal_closed();
+    let chain = match kind {
+        HistoryKind::Dismissed => decision.on_acknowledge,
+        HistoryKind::Joined => decision.on_join,
+        _ => Vec::new(),
+    };
+    for event in &events {
+        host.record(event, kind, Vec::new());
+        if matches!(kind, HistoryKind::Dismissed | HistoryKind::Joined) {
+            host.review(event);
+        }
+        if let Some(lifecycle) = lifecycle {
+            host.publish(AppEvent::Lifecycle(lifecycle, event.clone()));
+        }
+    }
+    host.publish(AppEvent::AlertCleared);
+    // The alert's own rule chain, once for a merged alert; off this thread,
+    // so a slow step never holds up the dismiss or the join.
+    if !chain.is_empty() {
+        host.run_chain(chain, events.swap_remove(0), kind);
+    }
+}
+
+#[tauri::command]
+pub fn dismiss_alert<R: AlertRuntime>(app: AppHandle<R>) -> Result<(), String> {
+    focus::check_grace()?;
+    R::with_host(&app, |host| {
+        let settings = host.settings();
+        if dismissal::challenge_for(&settings, &host.scheduler()).is_some() {
+            return Err("This alert needs request_dismiss and an answer".to_string());
+        }
+        answer(host, Acknowledgement::Dismissed);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn snooze_alert<R: AlertRuntime>(
+    app: AppHandle<R>,
+    minutes: Option<i64>,
+) -> Result<(), String> {
+    let minutes = minutes.unwrap_or(DEFAULT_SNOOZE_MINUTES);
+    if !SNOOZE_MINUTES.contains(&minutes) {
+        return Err(format!(
+            "Snooze for {} to {} minutes, not {}",
+            SNOOZE_MINUTES.start(),
+            SNOOZE_MINUTES.end(),
+            minutes
+        ));
+    }
+    focus::check_grace()?;
+    R::with_host(&app, |host| answer(host, Acknowledgement::Snoozed(minutes)));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn join_meeting<R: AlertRuntime>(app: AppHandle<R>, url: String) -> Result<(), String> {
+    focus::check_grace()?;
+    let url = untrusted::openable_link(&url)?;
+    R::with_host(&app, |host| {
+        host.open_link(url);
+        answer(host, Acknowledgement::Joined);
+    });
+    Ok(())
+}
+
+/// Pauses everything but meeting alerts until the current run of meetings
+/// is over, returning when they resume.
+#[tauri::command]
+pub fn pause_until_free(app: AppHandle) -> Result<DateTime<Local>, String> {
+    let settings = settings::current(&app);
+    let state = app.state::<SchedulerState>();
+    let until = state
+        .0
+        .lock()
+        .unwrap()
+        .pause_until_free(simulate::now(), &settings);
+    journal::paused(&app, until);
+    until.ok_or("You have no meetings right now".to_string())
+}
+
+#[tauri::command]
+pub fn resume_reminders(app: AppHandle, state: State<SchedulerState>) {
+    state.0.lock().unwrap().paused_until_free = None;
+    journal::paused(&app, None);
+}
+
+/// The snoozes, queued alerts and pause a restart would pick up again.
+#[tauri::command]
+pub fn get_scheduler_state(state: State<SchedulerState>) -> PendingWork {
+    state.0.lock().unwrap().pending()
+}
+
+#[tauri::command]
+pub fn get_active_alert(app: AppHandle, state: State<SchedulerState>) -> Option<CalendarEvent> {
+    let event = state.0.lock().unwrap().active.as_ref()?.event.clone();
+    let settings = settings::current(&app);
+    let mut event = confidential::shown(&settings, &event);
+    event.stamp(simulate::now(), &settings.format);
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{at, event};
+
+    fn nagging(interval_seconds: u64) -> Settings {
+        let mut settings = Settings::default();
+        settings.nag.interval_seconds = Some(interval_seconds);
+        settings
+    }
+
+    /// A scheduler showing `title`, raised at `raised`.
+    fn showing(title: &str, raised: DateTime<Local>) -> Scheduler {
+        let mut scheduler = Scheduler::default();
+        scheduler.activate(event(title, at("2026-03-02 10:00"), 30), raised);
+        scheduler
+    }
+
+    #[test]
+    fn the_scheduled_profile_follows_the_working_days() {
+        let mut settings = Settings::default();
+        settings.working_hours.enabled = true;
+        settings
+            .working_hours
+            .days
+            .retain(|day| day.weekday != chrono::Weekday::Fri);
+        settings.profile_schedule.enabled = true;
+        settings.profile_schedule.weekday = Some("Work".to_string());
+        settings.profile_schedule.weekend = Some("Off".to_string());
+        let mut scheduler = Scheduler::default();
+
+        let thursday = scheduler.take_scheduled_profile(at("2026-03-05 07:00"), &settings);
+        assert_eq!(thursday.as_deref(), Some("Work"));
+        // Once a day.
+        assert!(scheduler
+            .take_scheduled_profile(at("2026-03-05 12:00"), &settings)
+            .is_none());
+        let friday = scheduler.take_scheduled_profile(at("2026-03-06 07:00"), &settings);
+        assert_eq!(friday.as_deref(), Some("Off"));
+        settings.active_profile = Some("Off".to_string());
+        assert!(scheduler
+            .take_scheduled_profile(at("2026-03-07 07:00"), &settings)
+            .is_none());
+    }
+
+    #[test]
+    fn nags_on_the_interval() {
+        let settings = nagging(120);
+        let mut scheduler = showing("Standup", at("2026-03-02 09:55"));
+        assert!(scheduler
+            .take_nag(at("2026-03-02 09:56"), &settings)
+            .is_none());
+        let nag = scheduler.take_nag(at("2026-03-02 09:57"), &settings);
+        assert_eq!(nag.unwrap().title, "Standup");
+        // The next one counts from this one.
+        assert!(scheduler
+            .take_nag(at("2026-03-02 09:58"), &settings)
+            .is_none());
+        assert!(scheduler
+            .take_nag(at("2026-03-02 09:59"), &settings)
+            .is_some());
+    }
+
+    #[test]
+    fn stops_nagging_after_the_grace() {
+        let settings = nagging(120);
+        let mut scheduler = showing("Standup", at("2026-03-02 10:30"));
+        // Grace is 5 minutes after the 10:30 end.
+        assert!(scheduler
+            .take_nag(at("2026-03-02 10:35"), &settings)
+            .is_some());
+        assert!(scheduler
+            .take_nag(at("2026-03-02 10:40"), &settings)
+            .is_none());
+    }
+
+    #[test]
+    fn a_corner_or_no_interval_stops_nagging() {
+        let mut scheduler = showing("Standup", at("2026-03-02 09:55"));
+        assert!(scheduler
+            .take_nag(at("2026-03-02 09:59"), &Settings::default())
+            .is_none());
+        scheduler.set_cornered(true);
+        assert!(scheduler
+            .take_nag(at("2026-03-02 09:59"), &nagging(60))
+            .is_none());
+    }
+
+    #[test]
+    fn an_interval_under_the_floor_nags_at_the_floor() {
+        let settings = nagging(1);
+        let mut scheduler = showing("Standup", at("2026-03-02 09:55"));
+        let raised = at("2026-03-02 09:55");
+        let early = raised + Duration::seconds(MIN_NAG_INTERVAL_SECONDS as i64 - 1);
+        assert!(scheduler.take_nag(early, &settings).is_none());
+        let due = raised + Duration::seconds(MIN_NAG_INTERVAL_SECONDS as i64);
+        assert!(scheduler.take_nag(due, &settings).is_some());
+    }
+
+    #[test]
+    fn the_floor_applies_when_saved_or_loaded() {
+        assert!(nagging(MIN_NAG_INTERVAL_SECONDS - 1).nag.check().is_err());
+        assert!(nagging(MIN_NAG_INTERVAL_SECONDS).nag.check().is_ok());
+        assert!(Settings::default().nag.check().is_ok());
+        let mut settings = nagging(0);
+        settings.nag.raise_to_floor();
+        assert_eq!(
+            settings.nag.interval_seconds,
+            Some(MIN_NAG_INTERVAL_SECONDS)
+        );
+    }
+
+    #[test]
+    fn a_snooze_pauses_nagging_until_it_fires() {
+        let settings = nagging(60);
+        let mut scheduler = showing("Standup", at("2026-03-02 09:55"));
+        let active = scheduler.active.take().unwrap();
+        scheduler.snoozed.insert(
+            active.event.alert_key(),
+            (active.event, at("2026-03-02 09:58")),
+        );
+        assert!(scheduler
+            .take_nag(at("2026-03-02 09:57"), &settings)
+            .is_none());
+        let (event, decision) = scheduler
+            .take_due(at("2026-03-02 09:58"), &settings, |_| false)
+            .unwrap();
+        assert_eq!(event.title, "Standup");
+        assert_eq!(decision.style, AlertStyle::Fullscreen);
+        assert!(scheduler
+            .take_nag(at("2026-03-02 09:58"), &settings)
+            .is_none());
+        assert!(scheduler
+            .take_nag(at("2026-03-02 09:59"), &settings)
+            .is_some());
+    }
+
+    #[test]
+    fn auto_dismiss_counts_from_when_it_was_shown() {
+        let mut settings = nagging(60);
+        settings.nag.auto_dismiss_after_seconds = Some(180);
+        let mut scheduler = showing("Standup", at("2026-03-02 09:55"));
+        // A nag doesn't restart it.
+        assert!(scheduler
+            .take_nag(at("2026-03-02 09:56"), &settings)
+            .is_some());
+        assert!(!scheduler.is_expired(at("2026-03-02 09:57"), &settings));
+        assert!(scheduler.is_expired(at("2026-03-02 09:58"), &settings));
+    }
+}
+
+// --- Test Harness ---
+//
+// Runs the real `tick`, `fire` and `acknowledge` second by second against a
+// mock calendar and a mock clock, through a `Host` that records what the
+// alert window was asked to do and what was published on the bus. Behind the
+// `test-harness` feature, which `cargo harness` turns on.
+// The alert's commands run for real in a `tauri::test` mock app that manages
+// the harness, through the IPC as the alert calls them.
+#[cfg(all(test, feature = "test-harness"))]
+pub(crate) mod harness {
+    use super::*;
+    use crate::journal::Journal;
+    use crate::testing::{at, event, invoke};
+    use crate::webhooks;
+    use crate::working_hours::WorkingHours;
+    use serde_json::json;
+    use std::cell::RefCell;
+    use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime};
+    use tauri::{App, WebviewWindow, WebviewWindowBuilder};
+
+    /// What the app emitted or asked of the alert window.
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        /// `alert-fired`, with the event's title.
+        AlertFired(String),
+        /// `raise_alert`: the alert takes over the screen.
+        EnterAlertMode,
+        CloseAlert,
+        Notification(String),
+        Suppressed(String),
+        /// Recorded as missed in the history.
+        Missed(String),
+        /// `join_meeting` opened the link.
+        Opened(String),
+    }
+
+    pub(crate) struct Harness {
+        scheduler: Mutex<Scheduler>,
+        settings: Settings,
+        /// The mock clock.
+        now: DateTime<Local>,
+        /// The mock provider: what a fetch returns.
+        calendar: Vec<CalendarEvent>,
+        /// Off, fetches fail and the sources serve their last good events.
+        online: bool,
+        last_fetched: RefCell<Vec<CalendarEvent>>,
+        /// What the history counts as alerted, by alert key.
+        alerted: RefCell<Vec<String>>,
+        /// The spy.
+        calls: RefCell<Vec<Call>>,
+        /// What went on the bus.
+        published: RefCell<Vec<AppEvent>>,
+    }
+
+    impl Host for Harness {
+        fn now(&self) -> DateTime<Local> {
+            self.now
+        }
+
+        fn settings(&self) -> Settings {
+            self.settings.clone()
+        }
+
+        fn scheduler(&self) -> MutexGuard<'_, Scheduler> {
+            self.scheduler.lock().unwrap()
+        }
+
+        fn publish(&self, event: AppEvent) {
+            if let AppEvent::AlertFired(payload) = &event {
+                let title = payload.event.title.clone();
+                self.calls.borrow_mut().push(Call::AlertFired(title));
+            }
+            self.published.borrow_mut().push(event);
+        }
+
+        fn poll(&self) {}
+
+        /// What the provider has up to the horizon; offline, the last good
+        /// fetch again.
+        fn fetch(&self, _: &Settings, horizon: Duration) -> (Vec<CalendarEvent>, Freshness) {
+            if !self.online {
+                return (self.last_fetched.borrow().clone(), Freshness::default());
+            }
+            let until = self.now + horizon;
+            let events: Vec<CalendarEvent> = self
+                .calendar
+                .iter()
+                .filter(|e| e.start_time().is_some_and(|start| start < until))
+                .cloned()
+                .collect();
+            *self.last_fetched.borrow_mut() = events.clone();
+            (events, Freshness::default())
+        }
+
+        fn observe(&self, _: &Settings, _: &[CalendarEvent]) {}
+
+        fn fetched(&self) {}
+
+        fn announce_change(&self, _: &Settings, _: MeetingChange) {}
+
+        fn switch_profile(&self, _: &str) {}
+
+        fn was_alerted(&self, event: &CalendarEvent) -> bool {
+            self.alerted.borrow().contains(&event.alert_key())
+        }
+
+        /// As `history::was_alerted` reads it back; suppressed and missed
+        /// alerts also go to the spy.
+        fn record(&self, event: &CalendarEvent, kind: HistoryKind, _: Vec<TraceStep>) {
+            match kind {
+                HistoryKind::Suppressed => {
+                    let call = Call::Suppressed(event.title.clone());
+                    self.calls.borrow_mut().push(call);
+                }
+                HistoryKind::Missed => {
+                    let call = Call::Missed(event.title.clone());
+                    self.calls.borrow_mut().push(call);
+                }
+                _ => {}
+            }
+            if !matches!(kind, HistoryKind::Missed | HistoryKind::Cancelled) {
+                self.alerted.borrow_mut().push(event.alert_key());
+            }
+        }
+
+        fn record_recent(&self, _: &CalendarEvent) {}
+
+        fn review(&self, _: &CalendarEvent) {}
+
+        fn run_chain(&self, _: Vec<ActionStep>, _: CalendarEvent, _: HistoryKind) {}
+
+        fn journal_shown(&self, _: &CalendarEvent) {}
+
+        fn journal_closed(&self) {}
+
+        fn journal_pause(&self, _: Option<DateTime<Local>>) {}
+
+        fn journal_pending(&self, _: PendingWork) {}
+
+        fn note_for(&self, _: &CalendarEvent) -> Option<String> {
+            None
+        }
+
+        fn notify(&self, title: &str, _: &str) {
+            let call = Call::Notification(title.to_string());
+            self.calls.borrow_mut().push(call);
+        }
+
+        fn request_attention(&self) {}
+
+        fn set_badge(&self, _: Option<String>) {}
+
+        fn play_sound(&self, _: &str, _: AudioRoute) {}
+
+        fn play_stage(&self, _: SoundStage, _: AudioRoute) {}
+
+        fn count_in(&self, _: String, _: DateTime<Local>, _: AudioRoute, _: &Settings) {}
+
+        fn announce_alert(&self, _: String) {}
+
+        fn ensure_alert_window(&self) {}
+
+        fn raise_alert(&self) {
+            self.calls.borrow_mut().push(Call::EnterAlertMode);
+        }
+
+        fn close_alert(&self) {
+            self.calls.borrow_mut().push(Call::CloseAlert);
+        }
+
+        fn open_link(&self, url: &str) {
+            self.calls.borrow_mut().push(Call::Opened(url.to_string()));
+        }
+
+        fn dock_pill(&self) {}
+
+        fn sync_displays(&self) {}
+
+        fn dry_run(&self, _: &CalendarEvent, _: DateTime<Local>) {}
+
+        fn send_digest(&self) {}
+
+        fn send_tomorrow_preview(&self) {}
+    }
+
+    /// The mock app acknowledges through the `Harness` it manages.
+    impl AlertRuntime for MockRuntime {
+        type Host = Harness;
+
+        fn with_host<T, F: FnOnce(&Harness) -> T>(app: &AppHandle<MockRuntime>, f: F) -> T {
+            f(&app.state::<Mutex<Harness>>().lock().unwrap())
+        }
+    }
+
+    impl Harness {
+        pub(crate) fn new(settings: Settings, now: &str) -> Self {
+            Harness {
+                scheduler: Mutex::new(Scheduler::default()),
+                settings,
+                now: at(now),
+                calendar: Vec::new(),
+                online: true,
+                last_fetched: RefCell::new(Vec::new()),
+                alerted: RefCell::new(Vec::new()),
+                calls: RefCell::new(Vec::new()),
+                published: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn with_meeting(self, title: &str, start: &str) -> Self {
+            self.with_event(event(title, at(start), 30))
+        }
+
+        pub(crate) fn with_event(mut self, event: CalendarEvent) -> Self {
+            self.calendar.push(event);
+            self
+        }
+
+        /// One `tick`, polling the provider whatever the poll interval.
+        fn tick(&mut self) {
+            self.online = true;
+            self.scheduler.get_mut().unwrap().invalidate();
+            tick(&*self);
+        }
+
+        /// Moves the clock on to `until`, a tick a second as the app does.
+        pub(crate) fn run_until(&mut self, until: &str) {
+            let until = at(until);
+            while self.now < until {
+                self.now += Duration::seconds(1);
+                self.tick();
+            }
+        }
+
+        /// Ticks on to `until` with every fetch failing, as when polls are
+        /// skipped on battery or the calendar can't be read.
+        fn run_offline_until(&mut self, until: &str) {
+            self.online = false;
+            let until = at(until);
+            while self.now < until {
+                self.now += Duration::seconds(1);
+                tick(&*self);
+            }
+        }
+
+        fn run_for(&mut self, seconds: i64) {
+            for _ in 0..seconds {
+                self.now += Duration::seconds(1);
+                self.tick();
+            }
+        }
+
+        pub(crate) fn answer(&mut self, ack: Acknowledgement) {
+            answer(&*self, ack);
+        }
+
+        fn cancel(&mut self, title: &str) {
+            self.calendar.retain(|event| event.title != title);
+        }
+
+        /// The organizer moves `title` to `start`; it keeps its id.
+        fn reschedule(&mut self, title: &str, start: &str) {
+            for meeting in self.calendar.iter_mut().filter(|e| e.title == title) {
+                *meeting = event(title, at(start), 30);
+            }
+        }
+
+        /// What `exit_alert_mode` does once a join has left a pill.
+        pub(crate) fn show_pill(&mut self) {
+            let joined = self.scheduler.get_mut().unwrap().pill().cloned();
+            if let Some(event) = joined {
+                pill(&*self, &event);
+            }
+        }
+
+        /// A mock app serving the alert's commands to an "alert" window,
+        /// acknowledging through this harness.
+        fn serve(self) -> (App<MockRuntime>, WebviewWindow<MockRuntime>) {
+            let app = mock_builder()
+                .manage(Mutex::new(self))
+                .invoke_handler(tauri::generate_handler![
+                    dismiss_alert,
+                    snooze_alert,
+                    join_meeting
+                ])
+                .build(mock_context(noop_assets()))
+                .unwrap();
+            let alert = WebviewWindowBuilder::new(&app, window::ALERT_WINDOW, Default::default())
+                .build()
+                .unwrap();
+            (app, alert)
+        }
+
+        fn take_calls(&mut self) -> Vec<Call> {
+            self.calls.take()
+        }
+
+        /// Everything that went on the bus.
+        pub(crate) fn take_sent(&mut self) -> Vec<AppEvent> {
+            self.published.take()
+        }
+
+        /// What went on the bus, leaving out the ticks and the fetches.
+        fn take_published(&mut self) -> Vec<AppEvent> {
+            self.take_sent()
+                .into_iter()
+                .filter(|e| !matches!(e, AppEvent::Tick(_) | AppEvent::CalendarUpdated { .. }))
+                .collect()
+        }
+
+        /// What `journal::recover` hands a fresh scheduler at launch.
+        fn recover(&mut self, leftover: &Journal) {
+            let recovery = journal::plan_recovery(leftover, self.now);
+            self.scheduler.get_mut().unwrap().recover(&recovery);
+        }
+    }
+
+    /// A minute's lead. Escalation, which raises the alert again a while
+    /// after the start, has a test of its own.
+    fn one_minute_lead() -> Settings {
+        let mut settings = Settings {
+            lead_time_seconds: 60,
+            ..Settings::default()
+        };
+        settings.escalation.enabled = false;
+        settings
+    }
+
+    fn fired(title: &str) -> Vec<Call> {
+        vec![Call::AlertFired(title.to_string()), Call::EnterAlertMode]
+    }
+
+    #[test]
+    fn alerts_once_at_the_lead_time() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        app.tick();
+        app.run_for(59);
+        assert!(app.take_calls().is_empty());
+        app.run_for(1);
+        assert_eq!(app.take_calls(), fired("Standup"));
+        // Still on screen through the start, and nothing more.
+        app.run_until("2026-03-02 09:10");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn a_snoozed_alert_fires_again_when_the_snooze_runs_out() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        app.run_until("2026-03-02 09:01");
+        assert_eq!(app.take_calls(), fired("Standup"));
+        app.answer(Acknowledgement::Snoozed(5));
+        app.run_until("2026-03-02 09:05");
+        assert!(app.take_calls().is_empty());
+        app.run_until("2026-03-02 09:06");
+        assert_eq!(app.take_calls(), fired("Standup"));
+        // Dismissed, it stays gone.
+        app.answer(Acknowledgement::Dismissed);
+        app.run_until("2026-03-02 09:20");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn outside_working_hours_it_is_only_a_notification() {
+        let mut settings = one_minute_lead();
+        settings.working_hours = WorkingHours {
+            enabled: true,
+            notify_outside_hours: true,
+            ..WorkingHours::default()
+        };
+        let mut app = Harness::new(settings, "2026-03-02 16:55")
+            .with_meeting("Wrap-up", "2026-03-02 16:57")
+            .with_meeting("Call with Sydney", "2026-03-02 19:00");
+        app.run_until("2026-03-02 16:56");
+        assert_eq!(app.take_calls(), fired("Wrap-up"));
+        app.answer(Acknowledgement::Dismissed);
+        app.now = at("2026-03-02 18:58");
+        app.run_until("2026-03-02 19:05");
+        assert_eq!(
+            app.take_calls(),
+            vec![Call::Notification("Call with Sydney".to_string())]
+        );
+    }
+
+    #[test]
+    fn cancelling_the_meeting_closes_its_alert() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        app.run_until("2026-03-02 09:01");
+        assert_eq!(app.take_calls(), fired("Standup"));
+        app.cancel("Standup");
+        app.run_until("2026-03-02 09:10");
+        assert_eq!(app.take_calls(), vec![Call::CloseAlert]);
+        assert!(!app.scheduler().has_active_alert());
+    }
+
+    #[test]
+    fn a_meeting_cancelled_before_its_alert_never_alerts() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        app.tick();
+        app.cancel("Standup");
+        app.run_until("2026-03-02 09:10");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn the_same_occurrence_alerts_once() {
+        // Reported twice, e.g. by two sources, and refetched every tick.
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        app.run_until("2026-03-02 09:01");
+        assert_eq!(app.take_calls(), fired("Standup"));
+        app.answer(Acknowledgement::Dismissed);
+        app.run_until("2026-03-02 09:10");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn a_meeting_moved_later_before_its_alert_alerts_once_at_the_new_time() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        app.tick();
+        app.reschedule("Standup", "2026-03-02 09:30");
+        app.run_until("2026-03-02 09:28");
+        assert!(app.take_calls().is_empty());
+        app.run_until("2026-03-02 09:29");
+        assert_eq!(app.take_calls(), fired("Standup"));
+        app.answer(Acknowledgement::Dismissed);
+        app.run_until("2026-03-02 09:45");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn a_meeting_moved_after_its_alert_alerts_again_only_if_it_moved_far() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        app.run_until("2026-03-02 09:01");
+        assert_eq!(app.take_calls(), fired("Standup"));
+        app.answer(Acknowledgement::Dismissed);
+
+        // Nudged by no more than the lead time: within the cooldown, nothing.
+        app.reschedule("Standup", "2026-03-02 09:03");
+        app.run_until("2026-03-02 09:03");
+        assert!(app.take_calls().is_empty());
+
+        // Dragged to later in the morning: it alerts there, once.
+        app.reschedule("Standup", "2026-03-02 10:00");
+        app.run_until("2026-03-02 09:59");
+        assert_eq!(app.take_calls(), fired("Standup"));
+        app.answer(Acknowledgement::Dismissed);
+        app.run_until("2026-03-02 10:10");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn a_meeting_moved_earlier_into_the_past_catches_up_once() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Review", "2026-03-02 09:30");
+        app.run_until("2026-03-02 09:05");
+        assert!(app.take_calls().is_empty());
+        app.reschedule("Review", "2026-03-02 09:03");
+        app.run_for(1);
+        assert_eq!(app.take_calls(), fired("Review"));
+        app.answer(Acknowledgement::Dismissed);
+        // Dragged around in the past again: still the one alert.
+        app.reschedule("Review", "2026-03-02 09:01");
+        app.run_until("2026-03-02 09:20");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn a_meeting_cancelled_then_recreated_alerts_once() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        app.run_until("2026-03-02 09:01");
+        assert_eq!(app.take_calls(), fired("Standup"));
+        app.cancel("Standup");
+        app.run_for(5);
+        assert_eq!(app.take_calls(), vec![Call::CloseAlert]);
+        app.calendar
+            .push(event("Standup", at("2026-03-02 09:02"), 30));
+        app.run_until("2026-03-02 09:20");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn the_scheduler_state_command_reports_the_snooze() {
+        let mut harness = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        harness.run_until("2026-03-02 09:01");
+        harness.answer(Acknowledgement::Snoozed(5));
+
+        let app = mock_builder()
+            .manage(SchedulerState(harness.scheduler))
+            .invoke_handler(tauri::generate_handler![get_scheduler_state])
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+            .build()
+            .unwrap();
+        let pending: PendingWork = invoke(&webview, "get_scheduler_state", json!({}))
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        assert_eq!(pending.snoozed.len(), 1);
+        assert_eq!(pending.snoozed[0].event.title, "Standup");
+        assert_eq!(pending.snoozed[0].until, at("2026-03-02 09:06"));
+    }
+
+    /// The harness back from the mock app `serve` made.
+    fn served(app: &App<MockRuntime>) -> MutexGuard<'_, Harness> {
+        app.state::<Mutex<Harness>>().inner().lock().unwrap()
+    }
+
+    /// Fired for Standup at 09:01, then served.
+    fn standup_served() -> (App<MockRuntime>, WebviewWindow<MockRuntime>) {
+        let mut harness = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        harness.run_until("2026-03-02 09:01");
+        assert_eq!(harness.take_calls(), fired("Standup"));
+        harness.take_published();
+        harness.serve()
+    }
+
+    #[test]
+    fn the_dismiss_command_closes_the_alert_for_good() {
+        let (app, alert) = standup_served();
+        invoke(&alert, "dismiss_alert", json!({})).unwrap();
+        let mut harness = served(&app);
+        assert_eq!(
+            summary(&harness.take_published()),
+            vec!["alert_dismissed Standup", "alert-cleared"]
+        );
+        harness.run_until("2026-03-02 09:20");
+        assert!(harness.take_calls().is_empty());
+    }
+
+    #[test]
+    fn the_snooze_command_checks_its_minutes_and_snoozes() {
+        let (app, alert) = standup_served();
+        let refused = invoke(&alert, "snooze_alert", json!({ "minutes": 0 })).unwrap_err();
+        assert_eq!(refused, "Snooze for 1 to 1440 minutes, not 0");
+        assert!(served(&app).take_published().is_empty());
+
+        invoke(&alert, "snooze_alert", json!({ "minutes": 5 })).unwrap();
+        let mut harness = served(&app);
+        assert_eq!(
+            summary(&harness.take_published()),
+            vec!["alert_snoozed Standup", "alert-cleared"]
+        );
+        harness.run_until("2026-03-02 09:05");
+        assert!(harness.take_calls().is_empty());
+        harness.run_until("2026-03-02 09:06");
+        assert_eq!(harness.take_calls(), fired("Standup"));
+    }
+
+    #[test]
+    fn the_join_command_opens_only_a_safe_link_and_joins() {
+        let (app, alert) = standup_served();
+        let args = json!({ "url": "javascript:alert(1)" });
+        assert!(invoke(&alert, "join_meeting", args).is_err());
+        assert!(served(&app).take_calls().is_empty());
+
+        let args = json!({ "url": "https://zoom.us/j/1" });
+        invoke(&alert, "join_meeting", args).unwrap();
+        let mut harness = served(&app);
+        assert_eq!(
+            harness.take_calls(),
+            vec![Call::Opened("https://zoom.us/j/1".to_string())]
+        );
+        assert_eq!(summary(&harness.take_published()), vec!["alert-cleared"]);
+        harness.run_until("2026-03-02 09:20");
+        assert!(harness.take_calls().is_empty());
+    }
+
+    #[test]
+    fn a_catch_up_alert_skips_what_the_history_has() {
+        // Launched after the start: the history already has the alert.
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:03")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        let key = app.calendar[0].alert_key();
+        app.alerted.get_mut().push(key);
+        app.run_until("2026-03-02 09:10");
+        assert!(app.take_calls().is_empty());
+
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:03")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        app.run_until("2026-03-02 09:10");
+        assert_eq!(app.take_calls(), fired("Standup"));
+    }
+
+    #[test]
+    fn launched_mid_meeting_it_alerts_within_the_grace_and_logs_a_miss_after() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 10:02")
+            .with_meeting("Standup", "2026-03-02 10:00")
+            .with_meeting("Planning", "2026-03-02 09:45");
+        app.tick();
+        assert_eq!(
+            app.take_calls(),
+            vec![
+                Call::AlertFired("Standup".to_string()),
+                Call::EnterAlertMode,
+                Call::Missed("Planning".to_string()),
+            ]
+        );
+        app.answer(Acknowledgement::Dismissed);
+        app.run_until("2026-03-02 10:20");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn woken_mid_meeting_it_catches_up_the_same_way() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 08:50")
+            .with_meeting("Standup", "2026-03-02 09:00")
+            .with_meeting("Review", "2026-03-02 09:10");
+        app.tick();
+        // Asleep from 08:50 to 09:14, without a tick.
+        app.now = at("2026-03-02 09:14");
+        app.tick();
+        assert_eq!(
+            app.take_calls(),
+            vec![
+                Call::AlertFired("Review".to_string()),
+                Call::EnterAlertMode,
+                Call::Missed("Standup".to_string()),
+            ]
+        );
+        app.answer(Acknowledgement::Dismissed);
+        app.run_until("2026-03-02 09:40");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn woken_after_a_short_meeting_ended_it_does_not_catch_it_up() {
+        let standup = event("Standup", at("2026-03-02 09:00"), 5);
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 08:50").with_event(standup);
+        app.tick();
+        // Asleep from 08:50 to 09:07: within the grace, but past the end.
+        app.now = at("2026-03-02 09:07");
+        app.tick();
+        assert!(app.take_calls().is_empty());
+    }
+
+    /// The bus events, briefly: the alert window's ones by name, the
+    /// integrations' ones by lifecycle event.
+    fn summary(events: &[AppEvent]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| match event {
+                AppEvent::AlertFired(payload) => format!("alert-fired {}", payload.event.title),
+                AppEvent::AlertQueued(queued) => format!("alert-queued {}", queued.count),
+                AppEvent::AlertExpired => "alert-expired".to_string(),
+                AppEvent::AlertCleared => "alert-cleared".to_string(),
+                AppEvent::AlertTheme(_) => "alert-theme".to_string(),
+                AppEvent::AlertEscalated(event) => format!("alert-escalated {}", event.title),
+                AppEvent::Lifecycle(kind, event) => {
+                    format!("{} {}", webhooks::event_name(*kind), event.title)
+                }
+                other => format!("{:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn an_alert_decision_publishes_its_events_in_order() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02")
+            .with_meeting("Review", "2026-03-02 09:02");
+        app.run_until("2026-03-02 09:01");
+        app.run_for(1);
+        assert_eq!(
+            summary(&app.take_published()),
+            vec![
+                "alert-fired Standup",
+                "alert_fired Standup",
+                "alert-theme",
+                "alert-queued 1",
+            ]
+        );
+        // The theme changes once the meeting has started.
+        app.run_until("2026-03-02 09:02");
+        assert_eq!(
+            summary(&app.take_published()),
+            vec![
+                "meeting_started Standup",
+                "meeting_started Review",
+                "alert-theme",
+            ]
+        );
+        app.answer(Acknowledgement::Dismissed);
+        app.tick();
+        assert_eq!(
+            summary(&app.take_published()),
+            vec![
+                "alert_dismissed Standup",
+                "alert-cleared",
+                "alert-fired Review",
+                "alert_fired Review",
+                "alert-queued 0",
+                "alert-theme",
+            ]
+        );
+    }
+
+    #[test]
+    fn an_unanswered_alert_publishes_its_expiry() {
+        let mut settings = one_minute_lead();
+        settings.nag.auto_dismiss_after_seconds = Some(300);
+        let mut app =
+            Harness::new(settings, "2026-03-02 09:00").with_meeting("Standup", "2026-03-02 09:02");
+        app.run_until("2026-03-02 09:01");
+        app.take_published();
+        app.run_until("2026-03-02 09:10");
+        assert_eq!(
+            summary(&app.take_published()),
+            vec![
+                "meeting_started Standup",
+                "alert-theme",
+                "alert-cleared",
+                "alert-expired"
+            ]
+        );
+    }
+
+    #[test]
+    fn an_unanswered_alert_escalates_once_after_the_start() {
+        let mut settings = one_minute_lead();
+        settings.escalation.enabled = true;
+        let mut app =
+            Harness::new(settings, "2026-03-02 09:00").with_meeting("Standup", "2026-03-02 09:02");
+        app.run_until("2026-03-02 09:06");
+        assert_eq!(app.take_calls(), fired("Standup"));
+        app.take_published();
+        app.run_until("2026-03-02 09:07");
+        assert_eq!(app.take_calls(), vec![Call::EnterAlertMode]);
+        assert_eq!(
+            summary(&app.take_published()),
+            vec!["alert-escalated Standup"]
+        );
+        app.run_until("2026-03-02 09:20");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn a_meeting_just_past_the_lookahead_is_cached_by_the_first_fetch() {
+        let settings = Settings {
+            lead_time_seconds: 10 * 60,
+            ..Settings::default()
+        };
+        // 24h and a minute out.
+        let mut app =
+            Harness::new(settings, "2026-03-02 09:00").with_meeting("Offsite", "2026-03-03 09:01");
+        assert!(app.scheduler().fetch_horizon(&app.settings) > Duration::hours(LOOKAHEAD_HOURS));
+        app.tick();
+        assert_eq!(app.scheduler().events.len(), 1);
+        // Not one more poll before its alert is due, and it still comes.
+        app.run_offline_until("2026-03-03 08:50");
+        assert!(app.take_calls().is_empty());
+        app.run_offline_until("2026-03-03 08:51");
+        assert_eq!(app.take_calls(), fired("Offsite"));
+    }
+
+    #[test]
+    fn alerts_within_a_poll_of_the_horizon_are_flagged() {
+        // The horizon is 24h, the minute's lead and half an hour, the
+        // longest poll interval.
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 10:00")
+            .with_meeting("Offsite", "2026-03-03 09:05");
+        app.tick();
+        assert_eq!(app.scheduler().events.len(), 2);
+        let horizon = app.scheduler().fetch_horizon(&app.settings);
+        let flagged = app
+            .scheduler()
+            .near_horizon(&app.settings, app.now, horizon);
+        assert_eq!(flagged.len(), 1);
+        assert!(flagged[0].starts_with("Offsite at "), "{:?}", flagged);
+    }
+
+    /// The queue as the alert window last heard of it.
+    fn last_queued(app: &Harness) -> Option<QueuedAlerts> {
+        app.published
+            .borrow()
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                AppEvent::AlertQueued(queued) => Some(queued.clone()),
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn a_burst_of_fifty_collapses_into_one_alert() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00");
+        for i in 1..=50 {
+            app = app.with_meeting(&format!("Talk {}", i), "2026-03-02 09:02");
+        }
+        app.run_until("2026-03-02 09:02");
+        assert_eq!(app.take_calls(), fired("Talk 1"));
+        let queued = last_queued(&app).unwrap();
+        assert!(queued.flood && queued.merged);
+        assert_eq!(queued.count, 49);
+        assert_eq!(queued.events.len(), 49);
+
+        // One answer covers all fifty.
+        app.answer(Acknowledgement::Dismissed);
+        app.run_until("2026-03-02 09:30");
+        assert!(app.take_calls().is_empty());
+        let queued = last_queued(&app).unwrap();
+        assert!(!queued.flood);
+        assert_eq!(queued.count, 0);
+    }
+
+    #[test]
+    fn three_real_overlaps_queue_as_usual() {
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02")
+            .with_meeting("Review", "2026-03-02 09:02")
+            .with_meeting("Planning", "2026-03-02 09:02");
+        app.run_until("2026-03-02 09:02");
+        assert_eq!(app.take_calls(), fired("Standup"));
+        let queued = last_queued(&app).unwrap();
+        assert!(!queued.flood && !queued.merged);
+        assert_eq!(queued.count, 2);
+
+        app.answer(Acknowledgement::Dismissed);
+        app.tick();
+        assert_eq!(app.take_calls(), fired("Review"));
+        app.answer(Acknowledgement::Dismissed);
+        app.tick();
+        assert_eq!(app.take_calls(), fired("Planning"));
+    }
+
+    #[test]
+    fn after_a_crash_the_alert_on_screen_comes_back_and_a_stale_pause_does_not() {
+        let standup = event("Standup", at("2026-03-02 09:02"), 30);
+        let leftover = Journal {
+            alert: Some(JournaledAlert {
+                alert_key: standup.alert_key(),
+                title: standup.title.clone(),
+                end: standup.end_time(),
+            }),
+            pending: PendingWork {
+                paused_until: Some(at("2026-03-02 09:10")),
+                pause_is_timed: true,
+                ..PendingWork::default()
+            },
+            ..Journal::default()
+        };
+        // Relaunched well past the catch-up window, with the alert in the
+        // history from before the crash.
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:20")
+            .with_meeting("Standup", "2026-03-02 09:02");
+        app.alerted.get_mut().push(standup.alert_key());
+        app.recover(&leftover);
+        assert_eq!(app.scheduler().paused_until(), None);
+        app.tick();
+        assert_eq!(app.take_calls(), fired("Standup"));
+        app.answer(Acknowledgement::Dismissed);
+        app.run_until("2026-03-02 09:40");
+        assert!(app.take_calls().is_empty());
+    }
+
+    #[test]
+    fn pending_work_survives_a_restart_past_its_deadlines() {
+        let mut before = Harness::new(one_minute_lead(), "2026-03-02 09:00")
+            .with_meeting("Standup", "2026-03-02 09:02")
+            .with_meeting("Review", "2026-03-02 09:02")
+            .with_meeting("Planning", "2026-03-02 09:02");
+        before.run_until("2026-03-02 09:01");
+        before.run_for(2);
+        before.answer(Acknowledgement::Snoozed(5));
+        before.tick();
+        let paused_until = before
+            .scheduler()
+            .pause_for(before.now, Duration::minutes(20));
+        assert_eq!(
+            before.take_calls(),
+            vec![
+                Call::AlertFired("Standup".to_string()),
+                Call::EnterAlertMode,
+                Call::AlertFired("Review".to_string()),
+                Call::EnterAlertMode,
+            ]
+        );
+
+        // What the journal holds when the app dies at 09:01:02.
+        let showing = before.scheduler().active_event().unwrap().clone();
+        let written = Journal {
+            alert: Some(JournaledAlert {
+                alert_key: showing.alert_key(),
+                title: showing.title.clone(),
+                end: showing.end_time(),
+            }),
+            pending: before.scheduler().pending(),
+            ..Journal::default()
+        };
+        assert_eq!(written.pending.paused_until, Some(paused_until));
+        assert_eq!(written.pending.snoozed.len(), 1);
+        assert_eq!(written.pending.queued.len(), 1);
+        let file = serde_json::to_string(&written).unwrap();
+
+        // Back at 09:25, past the snooze, the pause and the catch-up window;
+        // "Planning" was cancelled in between.
+        let mut after = Harness::new(one_minute_lead(), "2026-03-02 09:25")
+            .with_meeting("Standup", "2026-03-02 09:02")
+            .with_meeting("Review", "2026-03-02 09:02");
+        after.alerted = before.alerted.clone();
+        after.recover(&serde_json::from_str(&file).unwrap());
+        assert_eq!(after.scheduler().paused_until(), None);
+        after.tick();
+        assert_eq!(after.take_calls(), fired("Standup"));
+        after.answer(Acknowledgement::Dismissed);
+        after.tick();
+        assert_eq!(after.take_calls(), fired("Review"));
+        after.answer(Acknowledgement::Dismissed);
+        after.run_until("2026-03-02 09:40");
+        assert!(after.take_calls().is_empty());
+        assert_eq!(after.scheduler().pending(), PendingWork::default());
+    }
+
+    #[test]
+    fn after_a_crash_an_alert_for_a_meeting_that_ended_stays_gone() {
+        let review = event("Review", at("2026-03-02 09:00"), 30);
+        let leftover = Journal {
+            alert: Some(JournaledAlert {
+                alert_key: review.alert_key(),
+                title: review.title.clone(),
+                end: review.end_time(),
+            }),
+            ..Journal::default()
+        };
+        let mut app = Harness::new(one_minute_lead(), "2026-03-02 09:45")
+            .with_meeting("Review", "2026-03-02 09:00");
+        app.alerted.get_mut().push(review.alert_key());
+        app.recover(&leftover);
+        app.run_until("2026-03-02 10:00");
+        assert!(app.take_calls().is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Duration, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::bus::{self, AppEvent};
+use crate::calendar::CalendarEvent;
+use crate::scheduler::SchedulerState;
+use crate::{
+    appearance, retention, rules, settings, simulate, sound, templates, theme, tray, window,
+};
+
+// --- Self-Test ---
+//
+// A dry-run alert five seconds out goes through the scheduler like a real
+// one, but where the alert would show, each stage is checked and recorded
+// instead. The result is part of the health report; a failure puts the tray
+// in its warning state, so "it didn't alert" can be traced to the scheduler,
+// the alert window or the sound.
+
+const TRAY_WARNING: &str = "self-test";
+const LEAD_SECONDS: i64 = 5;
+/// A dry run that hasn't fired this long after it was due failed.
+const DEADLINE_SECONDS: i64 = 30;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SelfTestSettings {
+    /// Run the self-test every launch.
+    pub on_startup: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Stage {
+    Scheduled,
+    Fired,
+    WindowResolvable,
+    SoundDeviceAvailable,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StageResult {
+    pub stage: Stage,
+    pub ok: bool,
+    pub detail: Option<String>,
+    pub at: DateTime<Local>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestReport {
+    pub started_at: DateTime<Local>,
+    pub due_at: DateTime<Local>,
+    pub stages: Vec<StageResult>,
+    /// `None` while the dry run is pending.
+    pub passed: Option<bool>,
+}
+
+impl SelfTestReport {
+    fn record(&mut self, stage: Stage, result: Result<String, String>) {
+        let ok = result.is_ok();
+        self.stages.push(StageResult {
+            stage,
+            ok,
+            detail: Some(result.unwrap_or_else(|e| e)),
+            at: simulate::now(),
+        });
+        if !ok {
+            self.passed = Some(false);
+        }
+    }
+
+    fn failure(&self) -> Option<&StageResult> {
+        self.stages.iter().find(|s| !s.ok)
+    }
+}
+
+static REPORT: Mutex<Option<SelfTestReport>> = Mutex::new(None);
+
+fn dry_run_event(start: DateTime<Local>) -> Option<CalendarEvent> {
+    let end = start + Duration::minutes(30);
+    serde_json::from_value(json!({
+        "id": "self-test",
+        "calendar": "Self-Test",
+        "title": "Self-test alert",
+        "start": start.to_rfc3339(),
+        "end": end.to_rfc3339(),
+        "isAllDay": false,
+        "videoLink": "https://example.com/self-test",
+    }))
+    .ok()
+}
+
+/// The latest report; a dry run stuck past its deadline is marked failed.
+pub fn report(app: &AppHandle) -> Option<SelfTestReport> {
+    let mut report = REPORT.lock().unwrap();
+    let current = report.as_mut()?;
+    let overdue = simulate::now() > current.due_at + Duration::seconds(DEADLINE_SECONDS);
+    if current.passed.is_some() || !overdue {
+        return Some(current.clone());
+    }
+    current.record(
+        Stage::Fired,
+        Err("the scheduler never fired the dry run".to_string()),
+    );
+    let failed = current.clone();
+    drop(report);
+    finish(app, failed.clone());
+    Some(failed)
+}
+
+/// Hands the scheduler a dry-run alert due in a few seconds.
+pub fn start(app: &AppHandle) -> SelfTestReport {
+    let now = simulate::now();
+    let due_at = now + Duration::seconds(LEAD_SECONDS);
+    let mut report = SelfTestReport {
+        started_at: now,
+        due_at,
+        stages: Vec::new(),
+        passed: None,
+    };
+    match dry_run_event(due_at) {
+        Some(event) => {
+            let state = app.state::<SchedulerState>();
+            state.0.lock().unwrap().schedule_dry_run(event, due_at);
+            report.record(Stage::Scheduled, Ok(format!("due at {}", due_at)));
+        }
+        None => report.record(
+            Stage::Scheduled,
+            Err("couldn't build the dry-run event".to_string()),
+        ),
+    }
+    *REPORT.lock().unwrap() = Some(report.clone());
+    if report.passed.is_some() {
+        finish(app, report.clone());
+    }
+    report
+}
+
+/// Where `fire` would show the alert: checks each remaining stage.
+pub fn fire(app: &AppHandle, event: &CalendarEvent, due_at: DateTime<Local>) {
+    let settings = settings::current(app);
+    let now = simulate::now();
+    let Some(mut report) = REPORT.lock().unwrap().clone() else {
+        return;
+    };
+
+    let decision = rules::evaluate(&settings, event);
+    let _ = templates::display_text(&settings, event);
+    let _ = theme::derive(&settings.theme, event, now, appearance::current());
+    let late = (now - due_at).num_seconds();
+    report.record(
+        Stage::Fired,
+        Ok(format!("{}s after due, style {:?}", late, decision.style)),
+    );
+
+    let window = match window::ensure_alert_window(app) {
+        Some(_) => Ok("alert window ready".to_string()),
+        None => Err("the alert window couldn't be created".to_string()),
+    };
+    report.record(Stage::WindowResolvable, window);
+    report.record(
+        Stage::SoundDeviceAvailable,
+        sound::check(settings.alert_sound.as_deref()),
+    );
+
+    report.passed.get_or_insert(true);
+    *REPORT.lock().unwrap() = Some(report.clone());
+    finish(app, report);
+}
+
+fn finish(app: &AppHandle, report: SelfTestReport) {
+    match report.failure() {
+        Some(failed) => {
+            let detail = failed.detail.clone().unwrap_or_default();
+            retention::log!("self-test failed at {:?}: {}", failed.stage, detail);
+            tray::set_warning(
+                app,
+                TRAY_WARNING,
+                Some(&format!("Self-test failed: {}", detail)),
+            );
+        }
+        None => tray::set_warning(app, TRAY_WARNING, None),
+    }
+    bus::publish(app, AppEvent::SelfTestFinished(report));
+}
+
+/// Starts a self-test; the result arrives as `self-test-finished` and in the
+/// health report.
+#[tauri::command]
+pub fn run_self_test(app: AppHandle) -> SelfTestReport {
+    start(&app)
+}
@@ -0,0 +1,402 @@
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State, Window};
+
+use crate::bus::{self, AppEvent};
+use crate::calendar::CalendarEvent;
+use crate::ipc::{self, IpcError};
+use crate::settings::{self, Settings};
+use crate::{autotune, history, simulate};
+
+// --- Successor Series (persisted as JSON in the app data dir) ---
+//
+// Organizers sometimes recreate a recurring meeting under a new id, e.g. to
+// move it, which orphans the rules tied to the old series (auto-tuned lead
+// times included). Series with such rules are remembered by title and
+// attendees. When one stops showing up and a series with the same title and
+// the same attendees starts within a week of its last occurrence, the
+// dashboard is told with `series-changed`; nothing moves until the user
+// confirms with `adopt_series_settings`. One-off overrides belong to the old
+// occurrences and stay behind.
+
+const SUCCESSOR_WITHIN_DAYS: i64 = 7;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KnownSeries {
+    pub id: String,
+    pub title: String,
+    /// Lowercased attendee addresses, sorted.
+    pub attendees: Vec<String>,
+    /// The latest occurrence seen.
+    pub last_start: DateTime<Local>,
+}
+
+/// Sent to the dashboard as `series-changed`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesChange {
+    pub old_id: String,
+    pub new_id: String,
+    pub title: String,
+    /// Rules tied to the old series that would move.
+    pub rule_count: usize,
+    pub message: String,
+}
+
+#[derive(Default)]
+pub struct SeriesBook {
+    known: Vec<KnownSeries>,
+    /// Old and new id of every change offered this run; each is asked once.
+    offered: Vec<(String, String)>,
+}
+
+pub struct SeriesState(pub Mutex<SeriesBook>);
+
+fn series_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join("series.json"))
+}
+
+pub fn load(app: &AppHandle) -> SeriesState {
+    let known = series_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    SeriesState(Mutex::new(SeriesBook {
+        known,
+        offered: Vec::new(),
+    }))
+}
+
+fn persist(app: &AppHandle, known: &[KnownSeries]) {
+    let Some(path) = series_path(app) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string(known) {
+        let _ = fs::write(path, json);
+    }
+}
+
+fn normalize_title(title: &str) -> String {
+    title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn attendee_set(event: &CalendarEvent) -> Vec<String> {
+    let mut emails: Vec<String> = event
+        .attendees
+        .iter()
+        .filter_map(|a| a.email.as_deref())
+        .map(|email| email.trim().to_lowercase())
+        .collect();
+    emails.sort();
+    emails.dedup();
+    emails
+}
+
+fn is_successor(old: &KnownSeries, new: &CalendarEvent, new_start: DateTime<Local>) -> bool {
+    // Without attendees a title is all there is to go on; too weak a match.
+    !old.attendees.is_empty()
+        && normalize_title(&old.title) == normalize_title(&new.title)
+        && old.attendees == attendee_set(new)
+        && new_start > old.last_start
+        && new_start - old.last_start <= Duration::days(SUCCESSOR_WITHIN_DAYS)
+}
+
+/// Pairs a known series that no longer shows up with a series it hasn't seen
+/// before, by exact title and attendee set. Anything ambiguous, like two
+/// candidates for one series, is left alone.
+pub fn find_successors<'a>(
+    known: &'a [KnownSeries],
+    events: &'a [CalendarEvent],
+) -> Vec<(&'a KnownSeries, &'a CalendarEvent)> {
+    let present: HashSet<&str> = events.iter().map(|e| e.id.as_str()).collect();
+    let mut candidates: Vec<(DateTime<Local>, &CalendarEvent)> = Vec::new();
+    for event in events {
+        if event.recurrence.is_none() || event.is_all_day || known.iter().any(|k| k.id == event.id)
+        {
+            continue;
+        }
+        let Some(start) = event.start_time() else {
+            continue;
+        };
+        match candidates.iter_mut().find(|(_, e)| e.id == event.id) {
+            Some(first) if start < first.0 => *first = (start, event),
+            Some(_) => {}
+            None => candidates.push((start, event)),
+        }
+    }
+
+    let ended: Vec<&KnownSeries> = known
+        .iter()
+        .filter(|k| !present.contains(k.id.as_str()))
+        .collect();
+    let matches = |old: &KnownSeries| -> Vec<&'a CalendarEvent> {
+        candidates
+            .iter()
+            .filter(|(start, new)| is_successor(old, new, *start))
+            .map(|(_, new)| *new)
+            .collect()
+    };
+    ended
+        .iter()
+        .filter_map(|old| match matches(old).as_slice() {
+            [new] => Some((*old, *new)),
+            _ => None,
+        })
+        .filter(|(old, new)| {
+            ended
+                .iter()
+                .filter(|other| other.id != old.id)
+                .all(|other| !matches(other).iter().any(|e| e.id == new.id))
+        })
+        .collect()
+}
+
+fn rule_count(settings: &Settings, series_id: &str) -> usize {
+    settings
+        .rules
+        .iter()
+        .filter(|r| r.series_id.as_deref() == Some(series_id))
+        .count()
+}
+
+/// Remembers the series that have rules and offers any successor found.
+/// Called after every fetch.
+pub fn observe(app: &AppHandle, settings: &Settings, events: &[CalendarEvent]) {
+    if simulate::is_active() {
+        return;
+    }
+    let tracked: HashSet<&str> = settings
+        .rules
+        .iter()
+        .filter_map(|r| r.series_id.as_deref())
+        .collect();
+    let state = app.state::<SeriesState>();
+    let mut book = state.0.lock().unwrap();
+    let before = book.known.clone();
+    book.known.retain(|k| tracked.contains(k.id.as_str()));
+    for event in events.iter().filter(|e| e.recurrence.is_some()) {
+        let Some(start) = event.start_time() else {
+            continue;
+        };
+        if !tracked.contains(event.id.as_str()) {
+            continue;
+        }
+        let seen = KnownSeries {
+            id: event.id.clone(),
+            title: event.title.clone(),
+            attendees: attendee_set(event),
+            last_start: start,
+        };
+        match book.known.iter_mut().find(|k| k.id == event.id) {
+            Some(known) if known.last_start <= start => *known = seen,
+            Some(_) => {}
+            None => book.known.push(seen),
+        }
+    }
+    if book.known != before {
+        persist(app, &book.known);
+    }
+
+    let changes: Vec<SeriesChange> = find_successors(&book.known, events)
+        .into_iter()
+        .filter(|(old, new)| {
+            !book
+                .offered
+                .iter()
+                .any(|(o, n)| *o == old.id && *n == new.id)
+        })
+        .map(|(old, new)| SeriesChange {
+            old_id: old.id.clone(),
+            new_id: new.id.clone(),
+            title: new.title.clone(),
+            rule_count: rule_count(settings, &old.id),
+            message: format!(
+                "{} looks like it was recreated; keep its settings?",
+                new.title
+            ),
+        })
+        .collect();
+    for change in &changes {
+        book.offered
+            .push((change.old_id.clone(), change.new_id.clone()));
+    }
+    drop(book);
+    for change in changes {
+        bus::publish(app, AppEvent::SeriesChanged(change));
+    }
+}
+
+/// Moves the rules and history of series `old_id` over to `new_id`, after the
+/// user confirmed a `series-changed` offer. Returns how many rules moved.
+#[tauri::command]
+pub fn adopt_series_settings(
+    app: AppHandle,
+    window: Window,
+    state: State<SeriesState>,
+    old_id: String,
+    new_id: String,
+) -> Result<usize, IpcError> {
+    ipc::require_dashboard(&window, "adopt_series_settings")?;
+    if old_id == new_id {
+        return Err("The series is the same".to_string().into());
+    }
+    let count = rule_count(&settings::current(&app), &old_id);
+    if count == 0 {
+        return Err("No settings to adopt for this series".to_string().into());
+    }
+    let (old_rule, new_rule) = (
+        autotune::series_rule_id(&old_id),
+        autotune::series_rule_id(&new_id),
+    );
+    settings::update(&app, |settings| {
+        for rule in settings.rules.iter_mut() {
+            if rule.series_id.as_deref() != Some(old_id.as_str()) {
+                continue;
+            }
+            rule.series_id = Some(new_id.clone());
+            if rule.id == old_rule {
+                rule.id = new_rule.clone();
+            }
+        }
+    })?;
+    history::rename_event(&app, &old_id, &new_id);
+
+    let mut book = state.0.lock().unwrap();
+    book.known.retain(|k| k.id != old_id);
+    persist(&app, &book.known);
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::Attendee;
+    use crate::testing::{at, event};
+
+    fn attendee(email: &str) -> Attendee {
+        Attendee {
+            name: None,
+            email: Some(email.to_string()),
+            status: "accepted".to_string(),
+            is_current_user: false,
+        }
+    }
+
+    /// An occurrence of a weekly series; `id` stands for its series.
+    fn weekly(id: &str, title: &str, start: &str, emails: &[&str]) -> CalendarEvent {
+        let mut occurrence = event(title, at(start), 30);
+        occurrence.id = id.to_string();
+        occurrence.recurrence = Some("Weekly on Mon".to_string());
+        occurrence.attendees = emails.iter().map(|e| attendee(e)).collect();
+        occurrence
+    }
+
+    /// "Team sync" as last seen on a Monday, with Ana and Ben.
+    fn old_sync() -> KnownSeries {
+        KnownSeries {
+            id: "old".to_string(),
+            title: "Team sync".to_string(),
+            attendees: vec!["ana@example.com".to_string(), "ben@example.com".to_string()],
+            last_start: at("2026-03-02 10:00"),
+        }
+    }
+
+    fn successor_ids(known: &[KnownSeries], events: &[CalendarEvent]) -> Vec<(String, String)> {
+        find_successors(known, events)
+            .into_iter()
+            .map(|(old, new)| (old.id.clone(), new.id.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn pairs_a_recreated_series() {
+        // Moved to Tuesday; spacing and case in the title don't matter, nor
+        // does the order or case of the attendees.
+        let new = weekly(
+            "new",
+            " team  Sync",
+            "2026-03-03 11:00",
+            &["Ben@example.com", "ana@example.com"],
+        );
+        assert_eq!(
+            successor_ids(&[old_sync()], &[new]),
+            vec![("old".to_string(), "new".to_string())]
+        );
+    }
+
+    #[test]
+    fn leaves_near_misses_alone() {
+        let attendees = ["ana@example.com", "ben@example.com"];
+        let near_misses = [
+            // Renamed.
+            weekly("new", "Team sync (new)", "2026-03-03 11:00", &attendees),
+            weekly("new", "Team standup", "2026-03-03 11:00", &attendees),
+            // Someone joined, or left.
+            weekly(
+                "new",
+                "Team sync",
+                "2026-03-03 11:00",
+                &["ana@example.com", "ben@example.com", "cy@example.com"],
+            ),
+            weekly("new", "Team sync", "2026-03-03 11:00", &["ana@example.com"]),
+            // Started more than a week after the last occurrence.
+            weekly("new", "Team sync", "2026-03-09 10:01", &attendees),
+            // Or before it.
+            weekly("new", "Team sync", "2026-03-01 10:00", &attendees),
+        ];
+        for new in near_misses {
+            assert!(
+                successor_ids(&[old_sync()], std::slice::from_ref(&new)).is_empty(),
+                "{} at {}",
+                new.title,
+                new.start
+            );
+        }
+        // A one-off with everything else the same.
+        let mut one_off = weekly("new", "Team sync", "2026-03-03 11:00", &attendees);
+        one_off.recurrence = None;
+        assert!(successor_ids(&[old_sync()], &[one_off]).is_empty());
+    }
+
+    #[test]
+    fn needs_the_old_series_gone_and_one_clear_candidate() {
+        let attendees = ["ana@example.com", "ben@example.com"];
+        let new = weekly("new", "Team sync", "2026-03-03 11:00", &attendees);
+        let still_there = weekly("old", "Team sync", "2026-03-09 10:00", &attendees);
+        assert!(successor_ids(&[old_sync()], &[still_there, new.clone()]).is_empty());
+
+        let other = weekly("other", "Team sync", "2026-03-04 11:00", &attendees);
+        assert!(successor_ids(&[old_sync()], &[new.clone(), other]).is_empty());
+
+        // Two ended series both matching the same newcomer.
+        let twin = KnownSeries {
+            id: "twin".to_string(),
+            ..old_sync()
+        };
+        assert!(successor_ids(&[old_sync(), twin], &[new]).is_empty());
+    }
+
+    #[test]
+    fn a_title_alone_is_not_enough() {
+        let old = KnownSeries {
+            attendees: Vec::new(),
+            ..old_sync()
+        };
+        let new = weekly("new", "Team sync", "2026-03-03 11:00", &[]);
+        assert!(successor_ids(&[old], &[new]).is_empty());
+    }
+}
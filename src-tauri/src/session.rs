@@ -0,0 +1,95 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager};
+
+use crate::bus::{self, AppEvent};
+use crate::scheduler::SchedulerState;
+use crate::sound;
+
+// --- Login Session ---
+//
+// With fast user switching our session can sit in the background while
+// someone else uses the Mac. The scheduler then holds alerts and their
+// sounds, and the tray and dock stop updating; once the session is in front
+// again the usual catch-up rules decide what still alerts and what counts as
+// missed. The state is polled every tick from the session dictionary's
+// on-console flag.
+
+/// Whether our session was in front at the last `poll`.
+static ACTIVE: AtomicBool = AtomicBool::new(true);
+
+#[cfg(target_os = "macos")]
+fn on_console() -> Option<bool> {
+    use std::ffi::{c_char, c_void};
+
+    type CFTypeRef = *const c_void;
+    const UTF8: u32 = 0x0800_0100;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGSessionCopyCurrentDictionary() -> CFTypeRef;
+    }
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFRelease(cf: CFTypeRef);
+        fn CFStringCreateWithCString(
+            allocator: CFTypeRef,
+            s: *const c_char,
+            encoding: u32,
+        ) -> CFTypeRef;
+        fn CFDictionaryGetValue(dict: CFTypeRef, key: CFTypeRef) -> CFTypeRef;
+        fn CFBooleanGetValue(boolean: CFTypeRef) -> u8;
+    }
+
+    // SAFETY: the dictionary and key are released once read; the value
+    // belongs to the dictionary and is read before that.
+    unsafe {
+        let session = CGSessionCopyCurrentDictionary();
+        if session.is_null() {
+            return None;
+        }
+        let key =
+            CFStringCreateWithCString(std::ptr::null(), c"kCGSSessionOnConsoleKey".as_ptr(), UTF8);
+        let value = if key.is_null() {
+            std::ptr::null()
+        } else {
+            CFDictionaryGetValue(session, key)
+        };
+        let on_console = (!value.is_null()).then(|| CFBooleanGetValue(value) != 0);
+        if !key.is_null() {
+            CFRelease(key);
+        }
+        CFRelease(session);
+        on_console
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn on_console() -> Option<bool> {
+    None
+}
+
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Tells the scheduler when the session moves to the background or back,
+/// and publishes `SessionChanged`. Unknown counts as in front, so a failed
+/// read never silences alerts.
+pub fn poll(app: &AppHandle) {
+    let active = on_console().unwrap_or(true);
+    if ACTIVE.swap(active, Ordering::SeqCst) == active {
+        return;
+    }
+    if !active {
+        sound::stop();
+    }
+    {
+        let state = app.state::<SchedulerState>();
+        let mut scheduler = state.0.lock().unwrap();
+        scheduler.set_session_active(active);
+        if active {
+            scheduler.invalidate();
+        }
+    }
+    bus::publish(app, AppEvent::SessionChanged(active));
+}
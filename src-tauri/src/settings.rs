@@ -0,0 +1,458 @@
+use chrono::{NaiveTime, Weekday};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime, State, Window};
+
+use crate::attendance::AttendanceSettings;
+use crate::audio::AudioRoute;
+use crate::autotune::AutoTuneSettings;
+use crate::board::BoardSettings;
+use crate::budget::BudgetSettings;
+use crate::bus::{self, AppEvent};
+use crate::calendar::CalendarFilter;
+use crate::cancellations::CancellationSettings;
+use crate::confidential::ConfidentialSettings;
+use crate::counterparts::ContactZone;
+use crate::countin::CountInSettings;
+use crate::days_off::DaysOffSettings;
+use crate::displays::DisplaySettings;
+use crate::dock::DockSettings;
+use crate::fade::FadeSettings;
+use crate::features::FeatureSettings;
+use crate::flood::FloodSettings;
+use crate::focus::FocusSettings;
+use crate::history::PunctualitySettings;
+use crate::inbound::InboundSettings;
+use crate::ipc::{self, IpcError};
+use crate::lateness::LatenessSettings;
+use crate::locale::FormatSettings;
+use crate::location::TravelSettings;
+use crate::long_events::LongEventSettings;
+use crate::mqtt::MqttSettings;
+use crate::popover::TrayClick;
+use crate::power::PowerSettings;
+use crate::presentation::PresentationSettings;
+use crate::privacy::PrivacyMode;
+use crate::profiles::{Profile, ProfileSchedule};
+use crate::recent::RecentMeetingSettings;
+use crate::retention::RetentionSettings;
+use crate::rules::{self, AlertRule, LeadTimeSource};
+use crate::scheduler::ConcurrentAlerts;
+use crate::selftest::SelfTestSettings;
+use crate::sources::SourceSettings;
+use crate::statusfile::StatusFileSettings;
+use crate::templates::{self, TemplateSettings};
+use crate::theme::ThemeSettings;
+use crate::webhooks::LifecycleEvent;
+use crate::working_hours::WorkingHours;
+
+// --- Settings (persisted as JSON in the app config dir) ---
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct Settings {
+    /// How long before the event start the fullscreen alert fires.
+    pub lead_time_seconds: i64,
+    /// Whether the events' own alarms replace or add to the lead time.
+    pub lead_time_source: LeadTimeSource,
+    /// How often the scheduler refetches the calendar, unless
+    /// `adaptive_polling` picks the interval.
+    pub poll_interval_seconds: u64,
+    /// Poll often just before alerts and meetings, rarely at night; see
+    /// `cadence`.
+    pub adaptive_polling: bool,
+    /// A meeting that was moved alerts again only after this long, unless it
+    /// moved by more than its lead time.
+    pub alert_cooldown_minutes: i64,
+    /// Meetings found up to this long after their start (at launch or on
+    /// wake) still alert; older ones are logged as missed.
+    pub catch_up_minutes: i64,
+    pub concurrent_alerts: ConcurrentAlerts,
+    /// When many alerts at once collapse into one, e.g. after an import.
+    pub flood: FloodSettings,
+    /// Name of a macOS system sound (e.g. "Glass"), `None` for silence.
+    pub alert_sound: Option<String>,
+    /// Which outputs alert sounds play on, unless a rule says otherwise.
+    pub sound_route: AudioRoute,
+    /// Ticks in the last seconds before the start, then a chime on it.
+    pub count_in: CountInSettings,
+    pub escalation: EscalationSettings,
+    pub nag: NagSettings,
+    pub link_check: LinkCheckSettings,
+    pub webhooks: Vec<WebhookConfig>,
+    /// Reminders received from a relay such as an ntfy.sh topic.
+    pub inbound: InboundSettings,
+    /// Lifecycle events published to an MQTT broker.
+    pub mqtt: MqttSettings,
+    pub working_hours: WorkingHours,
+    /// Weekends and holidays: no digest, and alerts only for meetings.
+    pub days_off: DaysOffSettings,
+    pub rules: Vec<AlertRule>,
+    pub digest: DigestSettings,
+    pub tomorrow_preview: PreviewSettings,
+    /// Calendars whose events never alert or show on the dashboard.
+    pub calendar_filter: CalendarFilter,
+    pub profiles: Vec<Profile>,
+    pub active_profile: Option<String>,
+    pub profile_schedule: ProfileSchedule,
+    pub travel: TravelSettings,
+    /// Fade-in and fade-out of the fullscreen alert.
+    pub fade: FadeSettings,
+    /// Whether a new alert takes keyboard focus, and how soon it reacts.
+    pub alert_focus: FocusSettings,
+    pub self_test: SelfTestSettings,
+    /// Daily cap on meeting hours, and the warning when tomorrow exceeds it.
+    pub budget: BudgetSettings,
+    /// How many alerted meetings to keep for later, and whether on disk.
+    pub recent_meetings: RecentMeetingSettings,
+    /// How much about events goes to webhooks, push, the status file and logs.
+    pub privacy_mode: PrivacyMode,
+    /// How much of each calendar's events shows on screen.
+    pub confidential: ConfidentialSettings,
+    /// What alerts do while a display is mirrored or is a TV or projector.
+    pub presentation: PresentationSettings,
+    /// Opt-in notes on occurrences joined late.
+    pub lateness: LatenessSettings,
+    /// Offsites and all-morning blocks: when they stop counting as meetings.
+    pub long_events: LongEventSettings,
+    /// Next-meeting status for status bars and shell prompts.
+    pub status_file: StatusFileSettings,
+    /// Reporting meetings that are cancelled or moved shortly before they start.
+    pub cancellations: CancellationSettings,
+    /// Calendar sources the user turned off, and when a failing one warns.
+    pub sources: SourceSettings,
+    /// Which display shows the alert and what happens to the others.
+    pub display: DisplaySettings,
+    /// A spare display showing the agenda all the time.
+    pub board: BoardSettings,
+    pub power: PowerSettings,
+    pub dock: DockSettings,
+    /// What a left click on the menu bar icon opens.
+    pub tray_click: TrayClick,
+    /// Custom wording for the alert, VoiceOver and notifications.
+    pub templates: TemplateSettings,
+    /// Colors and icon of the fullscreen alert.
+    pub theme: ThemeSettings,
+    /// Tracks how late alerts are acknowledged, for lead-time hints.
+    pub punctuality: PunctualitySettings,
+    /// Records when meetings were actually attended, for timesheets.
+    pub attendance: AttendanceSettings,
+    /// How long the history and attendance are kept.
+    pub retention: RetentionSettings,
+    /// Raise the lead time of series that are consistently joined late.
+    pub auto_tune: AutoTuneSettings,
+    /// Clock, week start and date style for backend-made text.
+    pub format: FormatSettings,
+    /// People's time zones, for the start as it is for them.
+    pub contact_time_zones: Vec<ContactZone>,
+    /// Flags the user flipped, and which release notes were shown.
+    pub features: FeatureSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            lead_time_seconds: 5,
+            lead_time_source: LeadTimeSource::default(),
+            poll_interval_seconds: 60,
+            adaptive_polling: true,
+            alert_cooldown_minutes: 10,
+            catch_up_minutes: 10,
+            concurrent_alerts: ConcurrentAlerts::Queue,
+            flood: FloodSettings::default(),
+            alert_sound: Some("Glass".to_string()),
+            sound_route: AudioRoute::default(),
+            count_in: CountInSettings::default(),
+            escalation: EscalationSettings::default(),
+            nag: NagSettings::default(),
+            link_check: LinkCheckSettings::default(),
+            webhooks: Vec::new(),
+            inbound: InboundSettings::default(),
+            mqtt: MqttSettings::default(),
+            working_hours: WorkingHours::default(),
+            days_off: DaysOffSettings::default(),
+            rules: Vec::new(),
+            digest: DigestSettings::default(),
+            tomorrow_preview: PreviewSettings::default(),
+            calendar_filter: CalendarFilter::default(),
+            profiles: Vec::new(),
+            active_profile: None,
+            profile_schedule: ProfileSchedule::default(),
+            travel: TravelSettings::default(),
+            fade: FadeSettings::default(),
+            alert_focus: FocusSettings::default(),
+            self_test: SelfTestSettings::default(),
+            budget: BudgetSettings::default(),
+            recent_meetings: RecentMeetingSettings::default(),
+            privacy_mode: PrivacyMode::default(),
+            confidential: ConfidentialSettings::default(),
+            presentation: PresentationSettings::default(),
+            lateness: LatenessSettings::default(),
+            long_events: LongEventSettings::default(),
+            status_file: StatusFileSettings::default(),
+            cancellations: CancellationSettings::default(),
+            sources: SourceSettings::default(),
+            display: DisplaySettings::default(),
+            board: BoardSettings::default(),
+            power: PowerSettings::default(),
+            dock: DockSettings::default(),
+            tray_click: TrayClick::default(),
+            templates: TemplateSettings::default(),
+            theme: ThemeSettings::default(),
+            punctuality: PunctualitySettings::default(),
+            attendance: AttendanceSettings::default(),
+            retention: RetentionSettings::default(),
+            auto_tune: AutoTuneSettings::default(),
+            format: FormatSettings::default(),
+            contact_time_zones: Vec::new(),
+            features: FeatureSettings::default(),
+        }
+    }
+}
+
+/// How long a fullscreen alert stays up, and how often it comes back.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct NagSettings {
+    /// Hide an unacknowledged alert after this long, logged as expired.
+    pub auto_dismiss_after_seconds: Option<u64>,
+    /// Raise the alert again this often until it is acknowledged.
+    pub interval_seconds: Option<u64>,
+    pub replay_sound: bool,
+    /// Stop nagging this long after the event ended.
+    pub grace_minutes: i64,
+}
+
+/// The shortest nag interval; anything shorter would bring the alert back,
+/// sound and all, before it could be read.
+pub const MIN_NAG_INTERVAL_SECONDS: u64 = 30;
+
+impl NagSettings {
+    pub fn check(&self) -> Result<(), String> {
+        match self.interval_seconds {
+            Some(seconds) if seconds < MIN_NAG_INTERVAL_SECONDS => Err(format!(
+                "Nag at most every {} seconds, not {}",
+                MIN_NAG_INTERVAL_SECONDS, seconds
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Raises an interval under the floor to it, for a file written before
+    /// there was one or edited by hand.
+    pub fn raise_to_floor(&mut self) {
+        if let Some(seconds) = self.interval_seconds.as_mut() {
+            *seconds = (*seconds).max(MIN_NAG_INTERVAL_SECONDS);
+        }
+    }
+}
+
+impl Default for NagSettings {
+    fn default() -> Self {
+        NagSettings {
+            auto_dismiss_after_seconds: None,
+            interval_seconds: None,
+            replay_sound: true,
+            grace_minutes: 5,
+        }
+    }
+}
+
+/// Early warning for meetings with attendees but no join link.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LinkCheckSettings {
+    pub enabled: bool,
+    /// How long before the alert itself the warning goes out.
+    pub minutes_before_alert: i64,
+}
+
+impl Default for LinkCheckSettings {
+    fn default() -> Self {
+        LinkCheckSettings {
+            enabled: false,
+            minutes_before_alert: 5,
+        }
+    }
+}
+
+/// What to do when an alert is still unacknowledged after the event started.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct EscalationSettings {
+    pub enabled: bool,
+    /// Minutes after the event start before escalating.
+    pub after_minutes: i64,
+    pub replay_sound: bool,
+    pub raise_window: bool,
+    /// ntfy.sh topic or any webhook URL that accepts a plain-text POST.
+    pub push_url: Option<String>,
+}
+
+impl Default for EscalationSettings {
+    fn default() -> Self {
+        EscalationSettings {
+            enabled: true,
+            after_minutes: 5,
+            replay_sound: true,
+            raise_window: true,
+            push_url: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Used to sign each payload (`X-Signature-256: sha256=<hex hmac>`).
+    pub secret: Option<String>,
+    pub enabled: bool,
+    /// Lifecycle events to deliver; empty means all of them.
+    pub events: Vec<LifecycleEvent>,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        WebhookConfig {
+            url: String::new(),
+            secret: None,
+            enabled: true,
+            events: Vec::new(),
+        }
+    }
+}
+
+/// Morning summary of the day's meetings, sent as a native notification.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DigestSettings {
+    pub enabled: bool,
+    pub time: NaiveTime,
+}
+
+impl Default for DigestSettings {
+    fn default() -> Self {
+        DigestSettings {
+            enabled: false,
+            time: NaiveTime::from_hms_opt(8, 30, 0).unwrap(),
+        }
+    }
+}
+
+/// Evening summary of tomorrow's meetings, sent as a native notification.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PreviewSettings {
+    pub enabled: bool,
+    pub time: NaiveTime,
+    /// Days the preview is sent on.
+    pub days: Vec<Weekday>,
+    /// Meetings starting before this are flagged as early.
+    pub early_before: NaiveTime,
+    /// Days on which an empty tomorrow sends nothing.
+    pub skip_if_empty_on: Vec<Weekday>,
+}
+
+impl Default for PreviewSettings {
+    fn default() -> Self {
+        PreviewSettings {
+            enabled: false,
+            time: NaiveTime::from_hms_opt(17, 30, 0).unwrap(),
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            early_before: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            skip_if_empty_on: vec![Weekday::Fri],
+        }
+    }
+}
+
+pub struct SettingsState(pub Mutex<Settings>);
+
+pub fn settings_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_config_dir()
+        .ok()
+        .map(|dir| dir.join("settings.json"))
+}
+
+pub fn load(app: &AppHandle) -> Settings {
+    let mut settings: Settings = settings_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    settings.nag.raise_to_floor();
+    settings
+}
+
+fn persist(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let path = settings_path(app).ok_or("No config directory available")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Whether saving would succeed, checked without touching the file.
+pub fn is_writable(app: &AppHandle) -> bool {
+    let Some(path) = settings_path(app) else {
+        return false;
+    };
+    if path.exists() {
+        return fs::OpenOptions::new().append(true).open(&path).is_ok();
+    }
+    let Some(dir) = path.parent() else {
+        return false;
+    };
+    let probe = dir.join(".write-test");
+    let writable = fs::create_dir_all(dir).is_ok() && fs::write(&probe, b"").is_ok();
+    let _ = fs::remove_file(probe);
+    writable
+}
+
+/// Snapshot of the current settings for background tasks.
+pub fn current(app: &AppHandle) -> Settings {
+    app.state::<SettingsState>().0.lock().unwrap().clone()
+}
+
+/// Applies `change` to a copy of the settings, persists it, then swaps it in.
+pub fn update(app: &AppHandle, change: impl FnOnce(&mut Settings)) -> Result<Settings, String> {
+    let state = app.state::<SettingsState>();
+    let mut settings = state.0.lock().unwrap();
+    let mut next = settings.clone();
+    change(&mut next);
+    persist(app, &next)?;
+    *settings = next.clone();
+    drop(settings);
+    bus::publish(app, AppEvent::SettingsChanged);
+    Ok(next)
+}
+
+/// Dashboard only: the settings carry the webhook secrets.
+#[tauri::command]
+pub fn get_settings<R: Runtime>(
+    window: Window<R>,
+    state: State<'_, SettingsState>,
+) -> Result<Settings, IpcError> {
+    ipc::require_dashboard(&window, "get_settings")?;
+    Ok(state.0.lock().unwrap().clone())
+}
+
+#[tauri::command]
+pub fn save_settings(app: AppHandle, window: Window, settings: Settings) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "save_settings")?;
+    templates::validate(&settings.templates)?;
+    rules::check_all_chains(&settings)?;
+    settings.nag.check()?;
+    update(&app, |current| *current = settings)?;
+    Ok(())
+}
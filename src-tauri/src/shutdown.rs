@@ -0,0 +1,272 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::scheduler::SchedulerState;
+use crate::{
+    attendance, children, dock, history, journal, mqtt, retention, sound, startup, statusfile,
+    tray, window,
+};
+
+// --- Shutdown ---
+//
+// Quitting, from the tray, the Dock or a logout, runs the hooks below once,
+// in order, before the process exits, so nothing the app set outside itself
+// is left behind and nothing mid-write is lost. The window hooks run on the
+// event loop, which the exit is on; the rest run on a worker the exit waits
+// for at most `TIMEOUT`. From the first hook on, no new alert fires. An
+// alert that was showing stays in the journal and comes back next launch.
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// What the hooks act on: the app, or a recorder in the tests.
+trait Target: Clone + Send + 'static {
+    fn close_alert(&self);
+    fn clear_badges(&self);
+    fn clear_status_file(&self);
+    fn flush_attendance(&self);
+    fn flush_history(&self);
+    fn flush_scheduler(&self);
+    fn publish_offline(&self);
+    fn kill_helpers(&self);
+}
+
+type Hook<T> = (&'static str, fn(&T));
+
+/// Run in order on the event loop; they only talk to windows.
+fn window_hooks<T: Target>() -> [Hook<T>; 2] {
+    [("alert", T::close_alert), ("badges", T::clear_badges)]
+}
+
+/// Run in order on the worker.
+fn worker_hooks<T: Target>() -> [Hook<T>; 6] {
+    [
+        ("status file", T::clear_status_file),
+        ("attendance", T::flush_attendance),
+        ("history", T::flush_history),
+        ("scheduler state", T::flush_scheduler),
+        ("mqtt", T::publish_offline),
+        ("helpers", T::kill_helpers),
+    ]
+}
+
+pub fn in_progress() -> bool {
+    SHUTTING_DOWN.load(Ordering::SeqCst)
+}
+
+impl Target for AppHandle {
+    fn close_alert(&self) {
+        sound::stop();
+        window::close_alert(self);
+    }
+
+    fn clear_badges(&self) {
+        dock::set_badge(self, None);
+        tray::clear_title(self);
+    }
+
+    fn clear_status_file(&self) {
+        statusfile::clear(self);
+    }
+
+    fn flush_attendance(&self) {
+        attendance::flush(self);
+    }
+
+    fn flush_history(&self) {
+        history::flush(self);
+    }
+
+    fn flush_scheduler(&self) {
+        let pending = self.state::<SchedulerState>().0.lock().unwrap().pending();
+        journal::save_pending(self, pending);
+    }
+
+    fn publish_offline(&self) {
+        mqtt::publish_offline();
+    }
+
+    fn kill_helpers(&self) {
+        children::kill_all();
+    }
+}
+
+/// Runs the hooks; later calls return right away. Waits for the worker
+/// hooks at most `TIMEOUT`, then lets the exit go ahead regardless.
+pub fn run(app: &AppHandle) {
+    if SHUTTING_DOWN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    window::set_exiting();
+    // Quit while still starting, the stores never loaded and have nothing to
+    // save; only the helpers are left to stop.
+    let ready = startup::is_ready();
+    let worker: Vec<Hook<AppHandle>> = worker_hooks()
+        .into_iter()
+        .filter(|(name, _)| ready || *name == "helpers")
+        .collect();
+    if let Err(name) = run_hooks(app, &window_hooks(), worker, TIMEOUT) {
+        retention::log!("shutdown: gave up waiting for {}", name);
+    }
+}
+
+/// Runs `here` in order on this thread, then `worker` in order on a thread
+/// of its own, waiting for that at most `timeout`. The hook it gave up on,
+/// if any.
+fn run_hooks<T: Target>(
+    target: &T,
+    here: &[Hook<T>],
+    worker: Vec<Hook<T>>,
+    timeout: Duration,
+) -> Result<(), &'static str> {
+    for (_, hook) in here {
+        hook(target);
+    }
+
+    let names: Vec<&'static str> = worker.iter().map(|(name, _)| *name).collect();
+    let (done, finished) = mpsc::channel();
+    let target = target.clone();
+    thread::spawn(move || {
+        for (name, hook) in worker {
+            hook(&target);
+            let _ = done.send(name);
+        }
+    });
+    let deadline = Instant::now() + timeout;
+    for name in names {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if finished.recv_timeout(left).is_err() {
+            return Err(name);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::{Arc, Mutex};
+
+    /// Stands in for the app: what the hooks did, in order.
+    #[derive(Clone, Default)]
+    struct Calls(Arc<Mutex<Vec<&'static str>>>);
+
+    impl Calls {
+        fn push(&self, call: &'static str) {
+            self.0.lock().unwrap().push(call);
+        }
+
+        fn take(&self) -> Vec<&'static str> {
+            std::mem::take(&mut *self.0.lock().unwrap())
+        }
+    }
+
+    impl Target for Calls {
+        fn close_alert(&self) {
+            self.push("close alert");
+        }
+
+        fn clear_badges(&self) {
+            self.push("clear badges");
+        }
+
+        fn clear_status_file(&self) {
+            self.push("clear status file");
+        }
+
+        fn flush_attendance(&self) {
+            self.push("flush attendance");
+        }
+
+        fn flush_history(&self) {
+            self.push("flush history");
+        }
+
+        fn flush_scheduler(&self) {
+            self.push("flush scheduler");
+        }
+
+        fn publish_offline(&self) {
+            self.push("publish offline");
+        }
+
+        fn kill_helpers(&self) {
+            self.push("kill helpers");
+        }
+    }
+
+    fn names<T: Target>() -> Vec<&'static str> {
+        window_hooks::<T>()
+            .iter()
+            .chain(&worker_hooks::<T>())
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    #[test]
+    fn every_hook_runs_once_in_order() {
+        let calls = Calls::default();
+        let worker = worker_hooks::<Calls>().to_vec();
+        assert_eq!(run_hooks(&calls, &window_hooks(), worker, TIMEOUT), Ok(()));
+        assert_eq!(
+            calls.take(),
+            vec![
+                "close alert",
+                "clear badges",
+                "clear status file",
+                "flush attendance",
+                "flush history",
+                "flush scheduler",
+                "publish offline",
+                "kill helpers",
+            ]
+        );
+    }
+
+    #[test]
+    fn hook_names_are_unique() {
+        let all = names::<Calls>();
+        let unique: HashSet<&str> = all.iter().copied().collect();
+        assert_eq!(unique.len(), all.len());
+    }
+
+    #[test]
+    fn the_broker_hears_of_the_exit_before_the_helpers_stop() {
+        let names = names::<Calls>();
+        let mqtt = names.iter().position(|name| *name == "mqtt");
+        let helpers = names.iter().position(|name| *name == "helpers");
+        assert!(mqtt.unwrap() < helpers.unwrap());
+    }
+
+    #[test]
+    fn a_stuck_hook_doesnt_hold_up_the_exit() {
+        let calls = Calls::default();
+        let mut worker = worker_hooks::<Calls>().to_vec();
+        worker.insert(
+            2,
+            ("stuck", |calls: &Calls| {
+                calls.push("stuck");
+                thread::sleep(Duration::from_secs(2));
+            }),
+        );
+        let started = Instant::now();
+        let result = run_hooks(&calls, &window_hooks(), worker, Duration::from_millis(200));
+        assert_eq!(result, Err("stuck"));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(
+            calls.take(),
+            vec![
+                "close alert",
+                "clear badges",
+                "clear status file",
+                "flush attendance",
+                "stuck",
+            ]
+        );
+    }
+}
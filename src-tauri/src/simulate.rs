@@ -0,0 +1,217 @@
+use chrono::{DateTime, Duration, Local};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Manager, Window};
+
+use crate::calendar::{CalendarEvent, CalendarInfo};
+use crate::ipc::{self, IpcError};
+use crate::retention;
+use crate::scheduler::SchedulerState;
+
+// --- Simulated Calendar ---
+//
+// `--simulate <file.json>` (or `load_simulation`) replaces EventKit with
+// events from a fixture, so the whole pipeline can be exercised without real
+// meetings:
+//
+//     { "time_scale": 30,
+//       "events": [{ "title": "Standup", "start": "+5m", "end": "+20m" }] }
+//
+// Times are relative to when the fixture is loaded ("+90s", "-1h", "+2d") or
+// absolute RFC 3339. With a `time_scale` the scheduler's clock runs that many
+// times faster, so a five-minute lead time passes in ten seconds at 30x.
+
+const FLAG: &str = "--simulate";
+
+#[derive(Deserialize)]
+struct Fixture {
+    #[serde(default = "default_scale")]
+    time_scale: f64,
+    events: Vec<Value>,
+}
+
+fn default_scale() -> f64 {
+    1.0
+}
+
+struct Simulation {
+    events: Vec<CalendarEvent>,
+    /// Wall-clock time and instant the fixture was loaded at.
+    origin: DateTime<Local>,
+    started: Instant,
+    time_scale: f64,
+}
+
+static SIMULATION: Mutex<Option<Simulation>> = Mutex::new(None);
+
+/// "+5m" relative to `origin`, or an RFC 3339 time as is.
+fn resolve_time(value: &str, origin: DateTime<Local>) -> Result<String, String> {
+    let Some(sign) = value.chars().next().filter(|c| *c == '+' || *c == '-') else {
+        return DateTime::parse_from_rfc3339(value)
+            .map(|_| value.to_string())
+            .map_err(|_| format!("Invalid time '{}': use +5m or RFC 3339", value));
+    };
+    let body = &value[1..];
+    let split = body.len().saturating_sub(1);
+    let (amount, unit) = body.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("Invalid offset '{}'", value))?;
+    let offset = match unit {
+        "s" => Duration::seconds(amount),
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        _ => {
+            return Err(format!(
+                "Invalid offset unit in '{}': use s, m, h or d",
+                value
+            ))
+        }
+    };
+    let time = if sign == '+' {
+        origin + offset
+    } else {
+        origin - offset
+    };
+    Ok(time.to_rfc3339())
+}
+
+fn resolve_event(
+    index: usize,
+    mut raw: Value,
+    origin: DateTime<Local>,
+) -> Result<CalendarEvent, String> {
+    let object = raw
+        .as_object_mut()
+        .ok_or(format!("Event {} is not an object", index))?;
+    for key in ["start", "end"] {
+        let value = object
+            .get(key)
+            .and_then(Value::as_str)
+            .ok_or(format!("Event {} has no {}", index, key))?;
+        let resolved = resolve_time(value, origin)?;
+        object.insert(key.to_string(), Value::String(resolved));
+    }
+    object
+        .entry("id")
+        .or_insert_with(|| Value::String(format!("sim-{}", index)));
+    object
+        .entry("calendar")
+        .or_insert_with(|| Value::String("Simulated".to_string()));
+    object.entry("isAllDay").or_insert(Value::Bool(false));
+    let event: CalendarEvent =
+        serde_json::from_value(raw).map_err(|e| format!("Event {}: {}", index, e))?;
+    Ok(event.described())
+}
+
+fn load_file(path: &str) -> Result<usize, String> {
+    let json = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let fixture: Fixture =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid simulation: {}", e))?;
+    if !fixture.time_scale.is_finite() || fixture.time_scale <= 0.0 {
+        return Err("time_scale must be positive".to_string());
+    }
+    let origin = Local::now();
+    let events = fixture
+        .events
+        .into_iter()
+        .enumerate()
+        .map(|(i, raw)| resolve_event(i, raw, origin))
+        .collect::<Result<Vec<_>, _>>()?;
+    let count = events.len();
+    *SIMULATION.lock().unwrap() = Some(Simulation {
+        events,
+        origin,
+        started: Instant::now(),
+        time_scale: fixture.time_scale,
+    });
+    Ok(count)
+}
+
+/// Loads the fixture named by `--simulate`, if the app was launched with it.
+pub fn load_from_args() {
+    let args: Vec<String> = std::env::args().collect();
+    let Some(path) = args
+        .iter()
+        .position(|a| a == FLAG)
+        .and_then(|i| args.get(i + 1))
+    else {
+        return;
+    };
+    if let Err(e) = load_file(path) {
+        retention::log!("Simulation not loaded: {}", e);
+    }
+}
+
+pub fn is_active() -> bool {
+    SIMULATION.lock().unwrap().is_some()
+}
+
+/// The scheduler's clock: real time, or sped-up time while simulating.
+pub fn now() -> DateTime<Local> {
+    let simulation = SIMULATION.lock().unwrap();
+    let Some(sim) = simulation.as_ref() else {
+        return Local::now();
+    };
+    let elapsed = sim.started.elapsed().as_secs_f64() * sim.time_scale;
+    sim.origin + Duration::milliseconds((elapsed * 1000.0) as i64)
+}
+
+/// Simulated events overlapping `from..to`; `None` when not simulating.
+pub fn events_between(from: DateTime<Local>, to: DateTime<Local>) -> Option<Vec<CalendarEvent>> {
+    let simulation = SIMULATION.lock().unwrap();
+    let sim = simulation.as_ref()?;
+    let events = sim
+        .events
+        .iter()
+        .filter(|e| {
+            let (Some(start), Some(end)) = (e.start_time(), e.end_time()) else {
+                return false;
+            };
+            start < to && end > from
+        })
+        .cloned()
+        .collect();
+    Some(events)
+}
+
+pub fn find(event_id: &str) -> Option<Option<CalendarEvent>> {
+    let simulation = SIMULATION.lock().unwrap();
+    let sim = simulation.as_ref()?;
+    Some(sim.events.iter().find(|e| e.id == event_id).cloned())
+}
+
+pub fn calendars() -> Option<Vec<CalendarInfo>> {
+    let simulation = SIMULATION.lock().unwrap();
+    let sim = simulation.as_ref()?;
+    let mut calendars: Vec<CalendarInfo> = Vec::new();
+    for event in &sim.events {
+        if calendars.iter().all(|c| c.title != event.calendar) {
+            calendars.push(CalendarInfo {
+                id: event.calendar.clone(),
+                title: event.calendar.clone(),
+                kind: event.calendar_type.clone(),
+                source: "Simulation".to_string(),
+                source_type: "local".to_string(),
+                allows_modifications: true,
+                delegate: false,
+                excluded: false,
+                auto_excluded: false,
+            });
+        }
+    }
+    Some(calendars)
+}
+
+/// Swaps in a fixture at runtime; the scheduler refetches on its next tick.
+#[tauri::command]
+pub fn load_simulation(app: AppHandle, window: Window, path: String) -> Result<usize, IpcError> {
+    ipc::require_dashboard(&window, "load_simulation")?;
+    let count = load_file(&path)?;
+    app.state::<SchedulerState>().0.lock().unwrap().invalidate();
+    Ok(count)
+}
@@ -0,0 +1,333 @@
+use std::path::Path;
+use std::process::{Child, Command};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::audio::{self, AudioRoute};
+use crate::retention;
+
+// --- Alert Sounds (macOS system sounds via afplay) ---
+//
+// Escalation stages play one at a time: each replaces the previous one, and
+// `stop` ends whatever is playing. Volume is afplay's own gain, so the system
+// volume is left alone. Short samples that have to land on the second (the
+// count-in) play in-process through NSSound instead, which keeps a named
+// sound loaded after its first use, so a repeat starts without a process
+// being spawned.
+//
+// afplay can only play on the default output, so a copy that the route
+// (see `audio`) sends to another device is an NSSound set to that device.
+// It is watched like a looping stage: `stop` ends it, and if its device
+// goes away mid-sound it stops and the default output takes over, unless
+// the sound plays there already. An afplay that exits with an error is
+// started again on whatever the default is by then, so losing the output
+// doesn't lose the sound.
+
+const AFPLAY: &str = "/usr/bin/afplay";
+/// How often a looping stage checks whether to play again.
+const LOOP_POLL: Duration = Duration::from_millis(200);
+
+/// The stage playing now, if any.
+static STAGE: Mutex<Option<Child>> = Mutex::new(None);
+/// Bumped by `stop`, which ends a looping stage's thread.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Names the copies playing on a device other than the default.
+static NEXT_ROUTED: AtomicU64 = AtomicU64::new(0);
+
+/// Whether `play` can work: afplay is there and so is the sound, if one is
+/// configured.
+pub fn check(name: Option<&str>) -> Result<String, String> {
+    if !Path::new(AFPLAY).exists() {
+        return Err(format!("{} is missing", AFPLAY));
+    }
+    let Some(name) = name else {
+        return Ok("no alert sound configured".to_string());
+    };
+    if !Path::new(&sound_path(name)).exists() {
+        return Err(format!("sound '{}' not found", name));
+    }
+    Ok(format!("'{}' ready", name))
+}
+
+fn sound_path(name: &str) -> String {
+    format!("/System/Library/Sounds/{}.aiff", name)
+}
+
+/// Plays the sound once where `route` sends it.
+pub fn play(app: &AppHandle, name: &str, route: AudioRoute) {
+    let targets = audio::targets(route);
+    let fallback = !targets.contains(&None);
+    for target in targets {
+        match target {
+            None => play_default_once(name),
+            Some(uid) => play_routed(app, name, 1.0, false, uid, fallback),
+        }
+    }
+}
+
+/// Plays once on the default output, again if afplay fails partway.
+fn play_default_once(name: &str) {
+    let path = sound_path(name);
+    thread::spawn(move || {
+        for _ in 0..2 {
+            let Ok(mut child) = Command::new(AFPLAY).arg(&path).spawn() else {
+                return;
+            };
+            if child.wait().is_ok_and(|status| status.success()) {
+                return;
+            }
+        }
+    });
+}
+
+fn spawn_at(path: &str, volume: &str) -> Option<Child> {
+    Command::new(AFPLAY).args(["-v", volume, path]).spawn().ok()
+}
+
+/// Plays an escalation stage in place of the one playing.
+pub fn play_stage(app: &AppHandle, name: &str, volume: f32, looping: bool, route: AudioRoute) {
+    stop();
+    let targets = audio::targets(route);
+    let fallback = !targets.contains(&None);
+    for target in targets {
+        match target {
+            None => play_default_stage(name, volume, looping),
+            Some(uid) => play_routed(app, name, volume, looping, uid, fallback),
+        }
+    }
+}
+
+/// The stage on the default output, through afplay.
+fn play_default_stage(name: &str, volume: f32, looping: bool) {
+    let generation = GENERATION.load(Ordering::SeqCst);
+    let path = sound_path(name);
+    let volume = volume.clamp(0.0, 1.0).to_string();
+    *STAGE.lock().unwrap() = spawn_at(&path, &volume);
+    let mut retried = false;
+    thread::spawn(move || loop {
+        thread::sleep(LOOP_POLL);
+        let mut stage = STAGE.lock().unwrap();
+        if GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let status = match stage.as_mut().map(Child::try_wait) {
+            Some(Ok(None)) => continue,
+            Some(Ok(Some(status))) => Some(status),
+            _ => None,
+        };
+        let failed = status.is_none_or(|s| !s.success());
+        if looping || (failed && !retried) {
+            retried |= failed;
+            *stage = spawn_at(&path, &volume);
+        } else {
+            return;
+        }
+    });
+}
+
+/// Plays a copy on the output `uid`, watched until it ends; `fallback`
+/// replays it on the default output if the device goes away first.
+fn play_routed(
+    app: &AppHandle,
+    name: &str,
+    volume: f32,
+    looping: bool,
+    uid: String,
+    fallback: bool,
+) {
+    #[cfg(target_os = "macos")]
+    {
+        let generation = GENERATION.load(Ordering::SeqCst);
+        let id = NEXT_ROUTED.fetch_add(1, Ordering::SeqCst);
+        let volume = volume.clamp(0.0, 1.0);
+        {
+            let (name, uid) = (name.to_string(), uid.clone());
+            let _ = app
+                .run_on_main_thread(move || macos::play_routed(id, &name, &uid, volume, looping));
+        }
+        let app = app.clone();
+        let name = name.to_string();
+        thread::spawn(move || loop {
+            thread::sleep(LOOP_POLL);
+            if GENERATION.load(Ordering::SeqCst) != generation {
+                let _ = app.run_on_main_thread(move || macos::stop_routed(id));
+                return;
+            }
+            if !audio::is_connected(&uid) {
+                retention::log!("sound output {} went away; playing on the default", uid);
+                let _ = app.run_on_main_thread(move || macos::stop_routed(id));
+                if fallback {
+                    play_default_stage(&name, volume, looping);
+                }
+                return;
+            }
+            let (tx, rx) = mpsc::channel();
+            let _ = app.run_on_main_thread(move || {
+                let _ = tx.send(macos::is_routed_playing(id));
+            });
+            if !rx.recv_timeout(LOOP_POLL).unwrap_or(true) {
+                return;
+            }
+        });
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app, uid, fallback);
+        play_default_stage(name, volume, looping);
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyObject, Bool};
+    use objc2::{class, msg_send};
+    use std::cell::RefCell;
+    use std::ffi::CString;
+
+    thread_local! {
+        /// The copies playing on another output, by id; main thread only.
+        static ROUTED: RefCell<Vec<(u64, Retained<AnyObject>)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn string(value: &str) -> Option<Retained<AnyObject>> {
+        let value = CString::new(value).ok()?;
+        // SAFETY: stringWithUTF8String: copies the NUL-terminated bytes.
+        unsafe { msg_send![class!(NSString), stringWithUTF8String: value.as_ptr()] }
+    }
+
+    fn is_playing(sound: &AnyObject) -> bool {
+        // SAFETY: a plain NSSound getter.
+        let playing: bool = unsafe { msg_send![sound, isPlaying] };
+        playing
+    }
+
+    /// Must run on the main thread. A copy of the named sound, so the one
+    /// the count-in keeps loaded stays on the default output.
+    pub fn play_routed(id: u64, name: &str, uid: &str, volume: f32, looping: bool) {
+        let (Some(named), Some(uid)) = (named(name), string(uid)) else {
+            return;
+        };
+        // SAFETY: NSSound conforms to NSCopying; the copy is ours and is
+        // kept in ROUTED until it stops.
+        let sound: Option<Retained<AnyObject>> = unsafe { msg_send![named, copy] };
+        let Some(sound) = sound else {
+            return;
+        };
+        unsafe {
+            let _: Bool = msg_send![&*sound, setPlaybackDeviceIdentifier: &*uid];
+            let _: () = msg_send![&*sound, setVolume: volume];
+            let _: () = msg_send![&*sound, setLoops: Bool::new(looping)];
+            let _: bool = msg_send![&*sound, play];
+        }
+        ROUTED.with_borrow_mut(|routed| {
+            routed.retain(|(_, sound)| is_playing(sound));
+            routed.push((id, sound));
+        });
+    }
+
+    /// Must run on the main thread.
+    pub fn is_routed_playing(id: u64) -> bool {
+        ROUTED.with_borrow(|routed| {
+            routed
+                .iter()
+                .any(|(other, sound)| *other == id && is_playing(sound))
+        })
+    }
+
+    /// Must run on the main thread.
+    pub fn stop_routed(id: u64) {
+        ROUTED.with_borrow_mut(|routed| {
+            routed.retain(|(other, sound)| {
+                if *other == id {
+                    // SAFETY: a plain NSSound message.
+                    let _: bool = unsafe { msg_send![&**sound, stop] };
+                }
+                *other != id
+            })
+        });
+    }
+
+    fn named(name: &str) -> Option<&'static AnyObject> {
+        let name = CString::new(name).ok()?;
+        // SAFETY: NSSound keeps named sounds in a cache for the life of the
+        // process, so the returned sound outlives the autoreleased name.
+        unsafe {
+            let name: *mut AnyObject =
+                msg_send![class!(NSString), stringWithUTF8String: name.as_ptr()];
+            if name.is_null() {
+                return None;
+            }
+            let sound: *mut AnyObject = msg_send![class!(NSSound), soundNamed: name];
+            sound.as_ref()
+        }
+    }
+
+    /// Loads the sound so its first play isn't late.
+    pub fn preload(name: &str) {
+        let _ = named(name);
+    }
+
+    /// Must run on the main thread.
+    pub fn play_sample(name: &str, volume: f32) {
+        let Some(sound) = named(name) else {
+            return;
+        };
+        // SAFETY: plain NSSound messages on a cached sound; `stop` first so
+        // a sample still playing restarts instead of being skipped.
+        unsafe {
+            let _: bool = msg_send![sound, stop];
+            let _: () = msg_send![sound, setVolume: volume];
+            let _: bool = msg_send![sound, play];
+        }
+    }
+}
+
+/// Loads a short sample ahead of `play_sample`.
+pub fn preload(app: &AppHandle, name: &str) {
+    #[cfg(target_os = "macos")]
+    {
+        let name = name.to_string();
+        let _ = app.run_on_main_thread(move || macos::preload(&name));
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = (app, name);
+}
+
+/// Plays a short sample in-process, alongside any stage, where `route`
+/// sends it.
+pub fn play_sample(app: &AppHandle, name: &str, volume: f32, route: AudioRoute) {
+    let targets = audio::targets(route);
+    let fallback = !targets.contains(&None);
+    for target in targets {
+        let Some(uid) = target else {
+            #[cfg(target_os = "macos")]
+            {
+                let name = name.to_string();
+                let volume = volume.clamp(0.0, 1.0);
+                let _ = app.run_on_main_thread(move || macos::play_sample(&name, volume));
+            }
+            continue;
+        };
+        play_routed(app, name, volume, false, uid, fallback);
+    }
+}
+
+/// Changes with every `stop`; a sequence started before it ends when it
+/// no longer matches.
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+/// Silences the current stage, looping or not, and ends a count-in.
+pub fn stop() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    if let Some(mut child) = STAGE.lock().unwrap().take() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
@@ -0,0 +1,301 @@
+use chrono::{DateTime, Duration, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State, Window};
+
+use crate::calendar::{self, CalendarEvent};
+use crate::ipc::{self, IpcError};
+use crate::scheduler::SchedulerState;
+use crate::settings::{self, Settings};
+use crate::{power, simulate, tray};
+
+// --- Calendar Sources ---
+//
+// Every provider the scheduler reads events from, with its own bookkeeping:
+// when it last synced, what went wrong, and when to retry. A failing source
+// keeps its last good events and backs off; other sources are unaffected,
+// and one can be refetched on its own. EventKit is the only provider so far.
+//
+// A failed fetch is never read as an empty calendar: the scheduler keeps
+// alerting from the last good events, which are then reported as stale
+// (see `Freshness`), and retries sooner than the poll interval until the
+// source is back.
+
+const TRAY_WARNING: &str = "sources";
+const RETRY_BASE_SECONDS: i64 = 30;
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SourceSettings {
+    /// Ids of sources the user turned off.
+    pub disabled: Vec<String>,
+    /// The tray warns once an enabled source has failed this many times in
+    /// a row, or for `warn_after_minutes`, whichever comes first.
+    pub warn_after_failures: u32,
+    pub warn_after_minutes: i64,
+}
+
+impl Default for SourceSettings {
+    fn default() -> Self {
+        SourceSettings {
+            disabled: Vec::new(),
+            warn_after_failures: 3,
+            warn_after_minutes: 15,
+        }
+    }
+}
+
+/// Reads the events overlapping `from..to`.
+type FetchFn = fn(DateTime<Local>, DateTime<Local>) -> Result<Vec<CalendarEvent>, String>;
+
+struct Provider {
+    id: &'static str,
+    name: &'static str,
+    fetch: FetchFn,
+}
+
+pub const EVENTKIT: &str = "eventkit";
+
+const PROVIDERS: [Provider; 1] = [Provider {
+    id: EVENTKIT,
+    name: "EventKit",
+    fetch: calendar::try_fetch_events,
+}];
+
+#[derive(Default)]
+struct SourceState {
+    events: Vec<CalendarEvent>,
+    last_sync_at: Option<DateTime<Local>>,
+    last_error: Option<String>,
+    failing_since: Option<DateTime<Local>>,
+    failures: u32,
+    retry_at: Option<DateTime<Local>>,
+    refresh_requested: bool,
+}
+
+impl SourceState {
+    /// A failing source sits out fetches until its backoff has passed.
+    fn is_due(&self, now: DateTime<Local>) -> bool {
+        self.refresh_requested || self.retry_at.is_none_or(|at| now >= at)
+    }
+
+    /// Failures back off from `RETRY_BASE_SECONDS`, doubling up to
+    /// `max_backoff`, the poll interval.
+    fn record(
+        &mut self,
+        result: Result<Vec<CalendarEvent>, String>,
+        now: DateTime<Local>,
+        max_backoff: i64,
+    ) {
+        self.refresh_requested = false;
+        match result {
+            Ok(events) => {
+                self.events = events;
+                self.last_sync_at = Some(now);
+                self.last_error = None;
+                self.failing_since = None;
+                self.failures = 0;
+                self.retry_at = None;
+            }
+            Err(error) => {
+                self.failures += 1;
+                let backoff = RETRY_BASE_SECONDS
+                    .saturating_mul(1 << self.failures.min(10).saturating_sub(1))
+                    .min(max_backoff.max(1));
+                self.retry_at = Some(now + Duration::seconds(backoff));
+                self.failing_since.get_or_insert(now);
+                self.last_error = Some(error);
+            }
+        }
+    }
+}
+
+/// Whether the events are current, for the dashboard and the health report.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Freshness {
+    /// Some events are left over from an earlier sync, because a source's
+    /// last fetch failed.
+    pub stale: bool,
+    /// The oldest last good sync among the enabled sources.
+    pub last_success_at: Option<DateTime<Local>>,
+    /// When the next failing source retries.
+    pub next_retry_at: Option<DateTime<Local>>,
+}
+
+#[derive(Default)]
+pub struct SourceRegistry(Mutex<HashMap<&'static str, SourceState>>);
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceStatus {
+    pub id: String,
+    pub display_name: String,
+    pub enabled: bool,
+    pub last_sync_at: Option<DateTime<Local>>,
+    pub last_error: Option<String>,
+    pub failing_since: Option<DateTime<Local>>,
+    pub next_retry_at: Option<DateTime<Local>>,
+    /// Events in the current 24-hour window.
+    pub event_count: usize,
+}
+
+fn is_enabled(settings: &Settings, id: &str) -> bool {
+    !settings.sources.disabled.iter().any(|d| d == id)
+}
+
+/// Refetches the sources that are due, each for the next `horizon`, and
+/// returns the events of every enabled one. Runs without the scheduler lock;
+/// helpers can take seconds.
+pub fn fetch(app: &AppHandle, settings: &Settings, horizon: Duration) -> Vec<CalendarEvent> {
+    let registry = app.state::<SourceRegistry>();
+    let max_backoff = power::poll_interval_seconds(settings) as i64;
+    for provider in PROVIDERS.iter().filter(|p| is_enabled(settings, p.id)) {
+        let now = Local::now();
+        let due = registry
+            .0
+            .lock()
+            .unwrap()
+            .entry(provider.id)
+            .or_default()
+            .is_due(now);
+        if due {
+            let from = simulate::now();
+            let result = (provider.fetch)(from, from + horizon);
+            let mut sources = registry.0.lock().unwrap();
+            sources
+                .entry(provider.id)
+                .or_default()
+                .record(result, Local::now(), max_backoff);
+        }
+    }
+    update_warning(app, settings);
+
+    let sources = registry.0.lock().unwrap();
+    let mut events: Vec<CalendarEvent> = PROVIDERS
+        .iter()
+        .filter(|p| is_enabled(settings, p.id))
+        .filter_map(|p| sources.get(p.id))
+        .flat_map(|state| state.events.iter().cloned())
+        .collect();
+    settings.long_events.mark(&mut events);
+    events
+}
+
+pub fn freshness(app: &AppHandle, settings: &Settings) -> Freshness {
+    let registry = app.state::<SourceRegistry>();
+    let sources = registry.0.lock().unwrap();
+    let enabled: Vec<&SourceState> = PROVIDERS
+        .iter()
+        .filter(|p| is_enabled(settings, p.id))
+        .filter_map(|p| sources.get(p.id))
+        .collect();
+    let failing = || enabled.iter().filter(|s| s.last_error.is_some());
+    Freshness {
+        stale: failing().next().is_some(),
+        last_success_at: enabled.iter().filter_map(|s| s.last_sync_at).min(),
+        next_retry_at: failing().filter_map(|s| s.retry_at).min(),
+    }
+}
+
+/// Warns in the tray while an enabled source has been failing too long; a
+/// single failed fetch only makes the events stale.
+fn update_warning(app: &AppHandle, settings: &Settings) {
+    let now = Local::now();
+    let limit = Duration::minutes(settings.sources.warn_after_minutes);
+    let failures = settings.sources.warn_after_failures.max(1);
+    let sources = app.state::<SourceRegistry>();
+    let failing = {
+        let sources = sources.0.lock().unwrap();
+        PROVIDERS
+            .iter()
+            .filter(|p| is_enabled(settings, p.id))
+            .find(|p| {
+                sources.get(p.id).is_some_and(|s| {
+                    s.failures >= failures
+                        || s.failing_since.is_some_and(|since| now - since >= limit)
+                })
+            })
+            .map(|p| p.name)
+    };
+    let warning = failing.map(|name| format!("{} hasn't synced", name));
+    tray::set_warning(app, TRAY_WARNING, warning.as_deref());
+}
+
+/// Display names of the enabled sources, for the health report.
+pub fn enabled_names(settings: &Settings) -> Vec<String> {
+    if simulate::is_active() {
+        return vec!["Simulation".to_string()];
+    }
+    PROVIDERS
+        .iter()
+        .filter(|p| is_enabled(settings, p.id))
+        .map(|p| p.name.to_string())
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_sources(app: AppHandle, registry: State<SourceRegistry>) -> Vec<SourceStatus> {
+    let settings = settings::current(&app);
+    let sources = registry.0.lock().unwrap();
+    PROVIDERS
+        .iter()
+        .map(|provider| {
+            let state = sources.get(provider.id);
+            SourceStatus {
+                id: provider.id.to_string(),
+                display_name: provider.name.to_string(),
+                enabled: is_enabled(&settings, provider.id),
+                last_sync_at: state.and_then(|s| s.last_sync_at),
+                last_error: state.and_then(|s| s.last_error.clone()),
+                failing_since: state.and_then(|s| s.failing_since),
+                next_retry_at: state.and_then(|s| s.retry_at),
+                event_count: state.map_or(0, |s| s.events.len()),
+            }
+        })
+        .collect()
+}
+
+/// Refetches one source on the next tick, skipping its backoff, when asked
+/// to or when it reported a change; the others keep their schedule.
+pub fn refresh(app: &AppHandle, id: &str) -> Result<(), String> {
+    let provider = PROVIDERS
+        .iter()
+        .find(|p| p.id == id)
+        .ok_or(format!("Unknown source: {}", id))?;
+    {
+        let registry = app.state::<SourceRegistry>();
+        let mut sources = registry.0.lock().unwrap();
+        sources.entry(provider.id).or_default().refresh_requested = true;
+    }
+    app.state::<SchedulerState>().0.lock().unwrap().invalidate();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn refresh_source(app: AppHandle, id: String) -> Result<(), String> {
+    refresh(&app, &id)
+}
+
+#[tauri::command]
+pub fn set_source_enabled(
+    app: AppHandle,
+    window: Window,
+    id: String,
+    enabled: bool,
+) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "set_source_enabled")?;
+    if !PROVIDERS.iter().any(|p| p.id == id) {
+        return Err(format!("Unknown source: {}", id).into());
+    }
+    settings::update(&app, |settings| {
+        settings.sources.disabled.retain(|d| *d != id);
+        if !enabled {
+            settings.sources.disabled.push(id.clone());
+        }
+    })?;
+    app.state::<SchedulerState>().0.lock().unwrap().invalidate();
+    Ok(())
+}
@@ -0,0 +1,329 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Instant;
+use tauri::{AppHandle, Manager, Runtime, Wry};
+
+use crate::bus::{self, AppEvent};
+use crate::history::HistoryState;
+use crate::scheduler::{self, Scheduler, SchedulerState};
+use crate::settings::{self, SettingsState};
+use crate::{
+    attendance, board, calendar_watch, children, countin, history, inbound, journal, mqtt, notes,
+    overrides, recent, retention, selftest, series, simulate, sources, statusfile, tray, webhooks,
+    week, window,
+};
+
+// --- Startup ---
+//
+// `setup` only makes the windows and the tray, so the icon is up right away;
+// everything that reads a file, starts a process or connects somewhere runs
+// afterwards as timed phases on a worker. Until they are done the stores
+// aren't managed yet, so commands are refused as `starting` (see
+// `api::checked`) and the tray offers nothing but Quit; `AppEvent::Ready`
+// says when that ends, and the tray shows it is loading until the first
+// fetch lands. `get_startup_timings` has how long each phase took.
+//
+// What `setup` makes and what the phases load is behind `Launch`, so a test
+// can run `setup` in a mock app with loaders that panic when called.
+
+/// Commands that don't touch any store, served while starting.
+pub const SERVED_WHILE_STARTING: [&str; 3] =
+    ["get_api_version", "get_api_manifest", "get_startup_timings"];
+
+static LAUNCHED: OnceLock<Instant> = OnceLock::new();
+static READY: AtomicBool = AtomicBool::new(false);
+static TIMINGS: Mutex<StartupTimings> = Mutex::new(StartupTimings {
+    phases: Vec::new(),
+    tray_ms: None,
+    ready_ms: None,
+    first_fetch_ms: None,
+});
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTiming {
+    pub name: &'static str,
+    /// Since launch, in milliseconds.
+    pub started_ms: u64,
+    pub duration_ms: u64,
+}
+
+/// Milliseconds since launch for each milestone, once reached.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupTimings {
+    pub phases: Vec<PhaseTiming>,
+    pub tray_ms: Option<u64>,
+    pub ready_ms: Option<u64>,
+    pub first_fetch_ms: Option<u64>,
+}
+
+fn elapsed_ms() -> u64 {
+    LAUNCHED.get_or_init(Instant::now).elapsed().as_millis() as u64
+}
+
+/// Starts the clock; the first thing `setup` does.
+pub fn launched() {
+    LAUNCHED.get_or_init(Instant::now);
+}
+
+pub fn is_ready() -> bool {
+    READY.load(Ordering::SeqCst)
+}
+
+fn phase(name: &'static str, run: impl FnOnce()) {
+    let started_ms = elapsed_ms();
+    run();
+    TIMINGS.lock().unwrap().phases.push(PhaseTiming {
+        name,
+        started_ms,
+        duration_ms: elapsed_ms() - started_ms,
+    });
+}
+
+/// The tray is up; `setup` is done with its part.
+pub fn tray_shown() {
+    TIMINGS.lock().unwrap().tray_ms = Some(elapsed_ms());
+}
+
+/// The first fetch reached the scheduler; later calls do nothing.
+pub fn fetched(app: &AppHandle) {
+    let first = {
+        let mut timings = TIMINGS.lock().unwrap();
+        let first = timings.first_fetch_ms.is_none();
+        if first {
+            timings.first_fetch_ms = Some(elapsed_ms());
+        }
+        first
+    };
+    if first {
+        tray::loaded(app);
+    }
+}
+
+/// The windows and tray `setup` makes, and the stores and providers the
+/// phases load. The app's are `Live`.
+pub trait Launch<R: Runtime>: Send + 'static {
+    fn windows(&self, app: &AppHandle<R>) -> tauri::Result<()>;
+    fn tray(&self, app: &AppHandle<R>) -> tauri::Result<()>;
+    fn cleanup(&self);
+    fn stores(&self, app: &AppHandle<R>);
+    fn recover(&self, app: &AppHandle<R>);
+    fn integrations(&self, app: &AppHandle<R>);
+    fn scheduler(&self, app: &AppHandle<R>);
+    /// Once the app is ready: announce it and start what waits for that.
+    fn ready(&self, app: &AppHandle<R>);
+}
+
+pub struct Live;
+
+impl Launch<Wry> for Live {
+    fn windows(&self, app: &AppHandle) -> tauri::Result<()> {
+        window::create_alert_window(app)?;
+        window::create_toast_window(app).map(drop)
+    }
+
+    fn tray(&self, app: &AppHandle) -> tauri::Result<()> {
+        tray::create(app)
+    }
+
+    fn cleanup(&self) {
+        children::clean_stale();
+        simulate::load_from_args();
+    }
+
+    fn stores(&self, app: &AppHandle) {
+        app.manage(SettingsState(Mutex::new(settings::load(app))));
+        app.manage(HistoryState(Mutex::new(history::load(app))));
+        app.manage(attendance::load(app));
+        app.manage(recent::load(app));
+        app.manage(notes::load(app));
+        let overrides = overrides::load(app);
+        app.manage(SchedulerState(Mutex::new(Scheduler::with_overrides(
+            overrides,
+        ))));
+        app.manage(week::WeekCache::default());
+        app.manage(sources::SourceRegistry::default());
+        app.manage(series::load(app));
+    }
+
+    fn recover(&self, app: &AppHandle) {
+        journal::recover(app);
+    }
+
+    fn integrations(&self, app: &AppHandle) {
+        app.manage(webhooks::start(app));
+        recent::start(app);
+        statusfile::start(app);
+        inbound::start(app);
+        mqtt::start(app);
+        attendance::start(app);
+        retention::start(app);
+        countin::watch(app);
+        calendar_watch::start(app);
+    }
+
+    fn scheduler(&self, app: &AppHandle) {
+        scheduler::start(app.clone());
+    }
+
+    fn ready(&self, app: &AppHandle) {
+        bus::publish(app, AppEvent::Ready);
+        tray::refresh(app);
+        board::start(app);
+        if settings::current(app).self_test.on_startup {
+            selftest::start(app);
+        }
+    }
+}
+
+/// `setup`'s part: only what has to exist before the event loop runs. The
+/// rest goes to a worker.
+pub fn setup<R: Runtime>(app: &AppHandle<R>, launch: impl Launch<R>) -> tauri::Result<()> {
+    launched();
+    app.manage(bus::EventBus::default());
+    launch.windows(app)?;
+    launch.tray(app)?;
+    tray_shown();
+    run(app, launch);
+    Ok(())
+}
+
+/// Runs the rest of the startup on a worker.
+fn run<R: Runtime>(app: &AppHandle<R>, launch: impl Launch<R>) {
+    let app = app.clone();
+    thread::spawn(move || {
+        phase("cleanup", || launch.cleanup());
+        phase("stores", || launch.stores(&app));
+        phase("recovery", || launch.recover(&app));
+        phase("integrations", || launch.integrations(&app));
+        phase("scheduler", || launch.scheduler(&app));
+
+        READY.store(true, Ordering::SeqCst);
+        TIMINGS.lock().unwrap().ready_ms = Some(elapsed_ms());
+        launch.ready(&app);
+    });
+}
+
+#[tauri::command]
+pub fn get_startup_timings() -> StartupTimings {
+    TIMINGS.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::{self, Receiver};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime};
+
+    /// What was launched, in order.
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    /// Makes the real alert window. The tray icon needs the platform's
+    /// event loop, which the mock app hasn't got, so it is only noted.
+    /// Everything the phases load panics until `go` says the worker may
+    /// start, which it waits for first.
+    struct Stub {
+        log: Log,
+        go: Mutex<Receiver<()>>,
+        started: AtomicBool,
+    }
+
+    impl Stub {
+        fn load(&self, what: &'static str) {
+            assert!(
+                self.started.load(Ordering::SeqCst),
+                "{} loaded before the worker ran",
+                what
+            );
+            self.log.lock().unwrap().push(what);
+        }
+    }
+
+    impl Launch<MockRuntime> for Stub {
+        fn windows(&self, app: &AppHandle<MockRuntime>) -> tauri::Result<()> {
+            window::create_alert_window(app)?;
+            window::create_toast_window(app)?;
+            self.log.lock().unwrap().push("windows");
+            Ok(())
+        }
+
+        fn tray(&self, _: &AppHandle<MockRuntime>) -> tauri::Result<()> {
+            self.log.lock().unwrap().push("tray");
+            Ok(())
+        }
+
+        fn cleanup(&self) {
+            self.go.lock().unwrap().recv().unwrap();
+            self.started.store(true, Ordering::SeqCst);
+            self.load("cleanup");
+        }
+
+        fn stores(&self, _: &AppHandle<MockRuntime>) {
+            self.load("stores");
+        }
+
+        fn recover(&self, _: &AppHandle<MockRuntime>) {
+            self.load("recovery");
+        }
+
+        fn integrations(&self, _: &AppHandle<MockRuntime>) {
+            self.load("integrations");
+        }
+
+        fn scheduler(&self, _: &AppHandle<MockRuntime>) {
+            self.load("scheduler");
+        }
+
+        fn ready(&self, _: &AppHandle<MockRuntime>) {
+            self.load("ready");
+        }
+    }
+
+    #[test]
+    fn setup_makes_the_windows_and_tray_and_loads_nothing() {
+        let app = mock_builder().build(mock_context(noop_assets())).unwrap();
+        let log = Log::default();
+        let (go, waiting) = mpsc::channel();
+        let stub = Stub {
+            log: log.clone(),
+            go: Mutex::new(waiting),
+            started: AtomicBool::new(false),
+        };
+
+        setup(app.handle(), stub).unwrap();
+        assert!(app.get_webview_window(window::ALERT_WINDOW).is_some());
+        assert!(app.get_webview_window(window::TOAST_WINDOW).is_some());
+        assert!(TIMINGS.lock().unwrap().tray_ms.is_some());
+        thread::sleep(Duration::from_millis(100));
+        assert!(!is_ready());
+        assert_eq!(*log.lock().unwrap(), vec!["windows", "tray"]);
+
+        go.send(()).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !is_ready() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(is_ready());
+        // `ready` runs just after the flag is set.
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "windows",
+                "tray",
+                "cleanup",
+                "stores",
+                "recovery",
+                "integrations",
+                "scheduler",
+                "ready",
+            ]
+        );
+    }
+}
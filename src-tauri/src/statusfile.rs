@@ -0,0 +1,160 @@
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use tauri::{AppHandle, Manager};
+
+use crate::bus;
+use crate::calendar::CalendarEvent;
+use crate::scheduler::{self, SchedulerState};
+use crate::settings::{self, Settings};
+use crate::{privacy, retention, shutdown, simulate, templates};
+
+// --- Status File ---
+//
+// For status bars and shell prompts: the next meeting, whether one is on and
+// whether reminders are paused, as `status.json` plus a one-line
+// `status.txt` next to it. Both are rewritten only when their content
+// changes, by writing a temporary file and renaming it over the old one, so
+// a watcher never reads half a file.
+
+const DEFAULT_PATH: &str = "~/.local/state/in-your-face/status.json";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct StatusFileSettings {
+    pub enabled: bool,
+    /// Where `status.json` goes; `status.txt` is written next to it.
+    pub path: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NextMeeting {
+    title: String,
+    start: String,
+    minutes_until: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Status {
+    next: Option<NextMeeting>,
+    in_meeting: bool,
+    paused: bool,
+}
+
+fn json_path(settings: &StatusFileSettings) -> Option<PathBuf> {
+    let path = settings.path.as_deref().unwrap_or(DEFAULT_PATH);
+    match path.strip_prefix("~/") {
+        Some(rest) => std::env::var_os("HOME").map(|home| Path::new(&home).join(rest)),
+        None => Some(PathBuf::from(path)),
+    }
+}
+
+fn snapshot(app: &AppHandle, settings: &Settings, now: DateTime<Local>) -> (Status, String) {
+    let paused = app
+        .state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .paused_until()
+        .is_some_and(|until| now < until);
+    let events: Vec<CalendarEvent> = scheduler::cached_events(app)
+        .into_iter()
+        .filter(|e| !e.is_all_day && !settings.calendar_filter.excludes(e))
+        .collect();
+    let in_meeting =
+        events
+            .iter()
+            .filter(|e| !e.is_long_block)
+            .any(|e| match (e.start_time(), e.end_time()) {
+                (Some(start), Some(end)) => start <= now && now < end,
+                _ => false,
+            });
+    let next = events
+        .iter()
+        .filter_map(|e| Some((e.start_time()?, e)))
+        .filter(|(start, _)| *start > now)
+        .min_by_key(|(start, _)| *start)
+        // Redacted before anything is built from it, line and JSON alike.
+        .map(|(start, event)| (start, privacy::event(settings, event)));
+
+    let line = next
+        .as_ref()
+        .map(|(_, event)| templates::status_line(settings, event, now))
+        .unwrap_or_else(|| "No upcoming meetings".to_string());
+    let status = Status {
+        next: next.map(|(start, event)| NextMeeting {
+            title: event.title.clone(),
+            start: event.start.clone(),
+            // Rounded up, so "0" only while it is about to start.
+            minutes_until: ((start - now).num_seconds() + 59) / 60,
+        }),
+        in_meeting,
+        paused,
+    };
+    (status, line)
+}
+
+fn write_atomically(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    fs::write(&temp, contents).map_err(|e| e.to_string())?;
+    fs::rename(&temp, path).map_err(|e| e.to_string())
+}
+
+fn write(path: &Path, status: &Status, line: &str) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(status).map_err(|e| e.to_string())?;
+    write_atomically(path, &json)?;
+    write_atomically(&path.with_extension("txt"), &format!("{}\n", line))
+}
+
+/// Removes the files, so nothing reads a status from after the quit.
+pub fn clear(app: &AppHandle) {
+    let settings = settings::current(app);
+    if let Some(path) = json_path(&settings.status_file) {
+        let _ = fs::remove_file(path.with_extension("txt"));
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Refreshes the files after every bus event; the tick keeps the minutes
+/// current. Turning the feature off removes them again.
+pub fn start(app: &AppHandle) {
+    let events = bus::subscribe(app);
+    let app = app.clone();
+    thread::spawn(move || {
+        let mut written: Option<(PathBuf, Status, String)> = None;
+        for _ in events {
+            if shutdown::in_progress() {
+                break;
+            }
+            let settings = settings::current(&app);
+            let path = json_path(&settings.status_file).filter(|_| settings.status_file.enabled);
+            let Some(path) = path else {
+                if let Some((old, ..)) = written.take() {
+                    let _ = fs::remove_file(old.with_extension("txt"));
+                    let _ = fs::remove_file(old);
+                }
+                continue;
+            };
+            let (status, line) = snapshot(&app, &settings, simulate::now());
+            let current = (path, status, line);
+            if written.as_ref() == Some(&current) {
+                continue;
+            }
+            let (path, status, line) = &current;
+            // A failure is logged once per change, not every tick.
+            if let Err(e) = write(path, status, line) {
+                retention::log!("status file not written to {}: {}", path.display(), e);
+            }
+            written = Some(current);
+        }
+    });
+}
@@ -0,0 +1,230 @@
+use chrono::{DateTime, Duration, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::calendar::CalendarEvent;
+use crate::locale::{self, format_time, FormatSettings};
+use crate::settings::{self, Settings};
+use crate::simulate;
+
+// --- Alert Templates ---
+//
+// Power users can reword the alert heading, the VoiceOver sentence and the
+// notification body, e.g. "{title} — {start_time} ({minutes_until}m)".
+// `{{` and `}}` are literal braces. Templates are checked when the settings
+// are saved, so rendering never fails.
+
+const PLACEHOLDERS: [&str; 10] = [
+    "title",
+    "start_time",
+    "end_time",
+    "minutes_until",
+    "relative_start",
+    "duration_minutes",
+    "location",
+    "calendar",
+    "organizer",
+    "link",
+];
+
+/// `None` keeps the built-in wording.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct TemplateSettings {
+    /// Heading of the fullscreen alert, sent as `display_text`.
+    pub alert: Option<String>,
+    /// What VoiceOver announces when the alert shows.
+    pub speech: Option<String>,
+    /// Body of notification-style alerts.
+    pub notification: Option<String>,
+    /// The line in the status file's text variant.
+    pub status: Option<String>,
+}
+
+enum Part<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+fn parse(template: &str) -> Result<Vec<Part<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        parts.push(Part::Text(&rest[..i]));
+        let tail = &rest[i..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            parts.push(Part::Text(&tail[..1]));
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('}') {
+            return Err("Unmatched '}' in template (use '}}' for a literal brace)".to_string());
+        }
+        let end = tail
+            .find('}')
+            .ok_or("Unclosed '{' in template (use '{{' for a literal brace)".to_string())?;
+        let name = tail[1..end].trim();
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "Unknown placeholder {{{}}}; expected one of: {}",
+                name,
+                PLACEHOLDERS.join(", ")
+            ));
+        }
+        parts.push(Part::Placeholder(name));
+        rest = &tail[end + 1..];
+    }
+    parts.push(Part::Text(rest));
+    Ok(parts)
+}
+
+pub fn validate(templates: &TemplateSettings) -> Result<(), String> {
+    let named = [
+        ("Alert", &templates.alert),
+        ("Speech", &templates.speech),
+        ("Notification", &templates.notification),
+        ("Status", &templates.status),
+    ];
+    for (label, template) in named {
+        if let Some(template) = template {
+            parse(template).map_err(|e| format!("{} template: {}", label, e))?;
+        }
+    }
+    Ok(())
+}
+
+fn value(
+    name: &str,
+    event: &CalendarEvent,
+    now: DateTime<Local>,
+    format: &FormatSettings,
+) -> String {
+    let start = event.start_time();
+    let end = event.end_time();
+    let minutes = |d: Duration| d.num_minutes().max(0).to_string();
+    match name {
+        "title" => event.title.clone(),
+        "start_time" => start.map(|t| format_time(format, t)).unwrap_or_default(),
+        "end_time" => end.map(|t| format_time(format, t)).unwrap_or_default(),
+        "minutes_until" => start.map(|t| minutes(t - now)).unwrap_or_default(),
+        "relative_start" => start
+            .map(|t| locale::relative_start(format, t, now))
+            .unwrap_or_default(),
+        "duration_minutes" => match (start, end) {
+            (Some(start), Some(end)) => minutes(end - start),
+            _ => String::new(),
+        },
+        "location" => event
+            .location_display
+            .clone()
+            .or_else(|| event.location.clone())
+            .unwrap_or_default(),
+        "calendar" => event.calendar.clone(),
+        "organizer" => event.organizer.clone().unwrap_or_default(),
+        "link" => event
+            .video_link
+            .clone()
+            .or_else(|| event.find_video_link())
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Renders a template checked by `validate`; an invalid one renders as is.
+pub fn render(
+    template: &str,
+    event: &CalendarEvent,
+    now: DateTime<Local>,
+    format: &FormatSettings,
+) -> String {
+    let Ok(parts) = parse(template) else {
+        return template.to_string();
+    };
+    parts
+        .into_iter()
+        .map(|part| match part {
+            Part::Text(text) => text.to_string(),
+            Part::Placeholder(name) => value(name, event, now, format),
+        })
+        .collect()
+}
+
+/// The alert heading; the title unless a template is set.
+pub fn display_text(settings: &Settings, event: &CalendarEvent) -> String {
+    settings
+        .templates
+        .alert
+        .as_deref()
+        .map(|t| render(t, event, simulate::now(), &settings.format))
+        .unwrap_or_else(|| event.title.clone())
+}
+
+/// The status file's line, e.g. "Standup in 5 min".
+pub fn status_line(settings: &Settings, event: &CalendarEvent, now: DateTime<Local>) -> String {
+    let template = settings
+        .templates
+        .status
+        .as_deref()
+        .unwrap_or("{title} {relative_start}");
+    render(template, event, now, &settings.format)
+}
+
+/// The VoiceOver sentence, e.g. "Meeting alert: Standup at 10:00".
+pub fn speech(settings: &Settings, event: &CalendarEvent) -> String {
+    if let Some(template) = &settings.templates.speech {
+        return render(template, event, simulate::now(), &settings.format);
+    }
+    match event.start_time() {
+        Some(start) => format!(
+            "Meeting alert: {} at {}",
+            event.title,
+            format_time(&settings.format, start)
+        ),
+        None => format!("Meeting alert: {}", event.title),
+    }
+}
+
+fn sample_event(now: DateTime<Local>) -> CalendarEvent {
+    let start = now + Duration::minutes(5);
+    let end = start + Duration::minutes(30);
+    CalendarEvent {
+        id: "sample".to_string(),
+        calendar: "Work".to_string(),
+        calendar_type: "caldav".to_string(),
+        title: "Client sync".to_string(),
+        start: start.to_rfc3339(),
+        end: end.to_rfc3339(),
+        location: Some("Room 4.02 (8) [VC]".to_string()),
+        description: None,
+        url: None,
+        is_all_day: false,
+        has_coordinates: false,
+        organizer: Some("alex@example.com".to_string()),
+        attendees: Vec::new(),
+        filtered_reason: None,
+        video_link: Some("https://zoom.us/j/123456789".to_string()),
+        recurrence_rule: None,
+        recurrence: None,
+        location_kind: Default::default(),
+        location_display: Some("Room 4.02".to_string()),
+        map_url: None,
+        muted: false,
+        read_only: false,
+        alarm_offsets: Vec::new(),
+        is_free: false,
+        is_long_block: false,
+        time_zone: None,
+        redacted: false,
+        seconds_until_start: None,
+        relative_start: None,
+    }
+}
+
+/// Renders `template` against a sample meeting five minutes from now.
+#[tauri::command]
+pub fn preview_template(app: tauri::AppHandle, template: String) -> Result<String, String> {
+    parse(&template)?;
+    let now = simulate::now();
+    let format = settings::current(&app).format;
+    Ok(render(&template, &sample_event(now), now, &format))
+}
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone};
+use proptest::prelude::*;
+use serde_json::{json, Value};
+use tauri::ipc::{CallbackFn, InvokeBody, InvokeResponseBody};
+use tauri::test::{get_ipc_response, MockRuntime, INVOKE_KEY};
+use tauri::webview::InvokeRequest;
+use tauri::WebviewWindow;
+
+use crate::calendar::CalendarEvent;
+
+// --- Test Fixtures ---
+//
+// Events and times for the unit tests. Times are local and fixed, passed in
+// as `now` wherever the code under test takes one, so no test depends on the
+// wall clock.
+
+/// "2026-03-02 09:00" as a local time.
+pub fn at(text: &str) -> DateTime<Local> {
+    let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").unwrap();
+    Local.from_local_datetime(&naive).earliest().unwrap()
+}
+
+/// An id derived from `title`, opaque like EventKit's, so that the title
+/// doesn't show up in what a test checks through the id.
+fn id_for(title: &str) -> String {
+    let hash = title.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016X}", hash)
+}
+
+/// A meeting of `minutes` from `start`; the same title gives the same id.
+pub fn event(title: &str, start: DateTime<Local>, minutes: i64) -> CalendarEvent {
+    let end = start + Duration::minutes(minutes);
+    serde_json::from_value(json!({
+        "id": id_for(title),
+        "calendar": "Work",
+        "title": title,
+        "start": start.to_rfc3339(),
+        "end": end.to_rfc3339(),
+        "location": null,
+        "description": null,
+        "url": null,
+        "isAllDay": false,
+    }))
+    .unwrap()
+}
+
+/// Characters that an invite can carry to break things: controls and bidi
+/// overrides, line and paragraph separators, joiners and emoji, and the
+/// punctuation that templates, CSV, links and shells give meaning to.
+fn hostile_char() -> impl Strategy<Value = char> {
+    prop_oneof![
+        any::<char>(),
+        prop::sample::select(vec![
+            '\0', '\t', '\n', '\r', '\u{1b}', '\u{7f}', '\u{85}', '\u{200d}', '\u{202e}',
+            '\u{2066}', '\u{2028}', '\u{feff}', '👩', '🏽', '"', '\'', ',', '%', '{', '}', '?',
+            '#', '&', '$', '`', ';', '|', '/', '\\',
+        ]),
+    ]
+}
+
+/// Pieces that look like templates, format strings or shell syntax.
+fn hostile_piece() -> impl Strategy<Value = String> {
+    prop_oneof![
+        hostile_char().prop_map(String::from),
+        prop::sample::select(vec![
+            "{title}",
+            "{{title}}",
+            "{minutes_until",
+            "{0}",
+            "%s%n%x",
+            "%@",
+            "$(rm -rf ~)",
+            "`id`",
+            "../../",
+            "\r\n",
+            "\u{202e}gpj.exe",
+            "=HYPERLINK(\"x\")",
+        ])
+        .prop_map(String::from),
+    ]
+}
+
+/// Untrusted text: mostly short, sometimes a title of 10k characters.
+pub fn hostile_text() -> impl Strategy<Value = String> {
+    prop_oneof![
+        4 => prop::collection::vec(hostile_piece(), 0..40).prop_map(|pieces| pieces.concat()),
+        1 => prop::collection::vec(hostile_char(), 10_000..10_050).prop_map(String::from_iter),
+    ]
+}
+
+/// Calls `command` with `args` from `webview` in a mock app, through the IPC
+/// and its ACL as the frontend would.
+pub fn invoke(
+    webview: &WebviewWindow<MockRuntime>,
+    command: &str,
+    args: Value,
+) -> Result<InvokeResponseBody, Value> {
+    let request = InvokeRequest {
+        cmd: command.into(),
+        callback: CallbackFn(0),
+        error: CallbackFn(1),
+        url: "tauri://localhost".parse().unwrap(),
+        body: InvokeBody::Json(args),
+        headers: Default::default(),
+        invoke_key: INVOKE_KEY.to_string(),
+    };
+    get_ipc_response(webview, request)
+}
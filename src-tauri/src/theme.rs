@@ -0,0 +1,323 @@
+use chrono::{DateTime, Duration, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::appearance::SystemAppearance;
+use crate::calendar::CalendarEvent;
+use crate::rules::{domain_of, normalize_domain};
+
+// --- Alert Theme ---
+//
+// Colors and icon for the fullscreen alert, picked in Rust so the frontend
+// only applies them. The first matching rule (by calendar or organizer domain)
+// sets the theme, else the base one for the system appearance; an urgency
+// band or the meeting having started overrides it.
+// The scheduler re-derives it every tick and pushes changes to the alert.
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertTheme {
+    /// Any CSS color.
+    pub background: String,
+    pub accent: String,
+    /// A lucide icon name, e.g. "clock" or "briefcase".
+    pub icon: Option<String>,
+    /// Heading color; `None` keeps the alert's white.
+    #[serde(default)]
+    pub foreground: Option<String>,
+}
+
+impl AlertTheme {
+    fn new(background: &str, accent: &str, icon: &str) -> Self {
+        AlertTheme {
+            background: background.to_string(),
+            accent: accent.to_string(),
+            icon: Some(icon.to_string()),
+            foreground: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ThemeRule {
+    /// Calendar title; `None` matches every calendar.
+    #[serde(default)]
+    pub calendar: Option<String>,
+    /// e.g. "@bigclient.com"; `None` matches every organizer.
+    #[serde(default)]
+    pub organizer_domain: Option<String>,
+    pub theme: AlertTheme,
+}
+
+/// Applies from `minutes_until` before the start until the start.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UrgencyBand {
+    pub minutes_until: i64,
+    pub theme: AlertTheme,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ThemeSettings {
+    pub base: AlertTheme,
+    /// Replaces `base` while the system is in light mode; `None` keeps it.
+    pub light: Option<AlertTheme>,
+    pub rules: Vec<ThemeRule>,
+    pub urgency: Vec<UrgencyBand>,
+    /// Once the meeting is under way; `None` keeps the earlier theme.
+    pub started: Option<AlertTheme>,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        ThemeSettings {
+            base: AlertTheme::new("#0a0a0a", "#ef4444", "clock"),
+            light: Some(AlertTheme {
+                foreground: Some("#0a0a0a".to_string()),
+                ..AlertTheme::new("#f5f5f5", "#dc2626", "clock")
+            }),
+            rules: Vec::new(),
+            urgency: Vec::new(),
+            started: Some(AlertTheme::new("#450a0a", "#f87171", "alert-triangle")),
+        }
+    }
+}
+
+impl ThemeRule {
+    fn matches(&self, event: &CalendarEvent) -> bool {
+        let calendar_matches = self
+            .calendar
+            .as_ref()
+            .is_none_or(|c| c.eq_ignore_ascii_case(&event.calendar));
+        let organizer_matches = self.organizer_domain.as_ref().is_none_or(|domain| {
+            event.organizer.as_deref().and_then(domain_of) == Some(normalize_domain(domain))
+        });
+        calendar_matches && organizer_matches
+    }
+}
+
+/// The theme for `event` at `now`, in the given system appearance.
+pub fn derive(
+    settings: &ThemeSettings,
+    event: &CalendarEvent,
+    now: DateTime<Local>,
+    appearance: SystemAppearance,
+) -> AlertTheme {
+    let start = event.start_time();
+    if let (Some(started), Some(start)) = (&settings.started, start) {
+        if now >= start {
+            return started.clone();
+        }
+    }
+    let band = settings
+        .urgency
+        .iter()
+        .filter(|band| start.is_some_and(|s| s - now <= Duration::minutes(band.minutes_until)))
+        .min_by_key(|band| band.minutes_until);
+    if let Some(band) = band {
+        return band.theme.clone();
+    }
+    settings
+        .rules
+        .iter()
+        .find(|rule| rule.matches(event))
+        .map(|rule| rule.theme.clone())
+        .or_else(|| settings.light.clone().filter(|_| appearance.is_light()))
+        .unwrap_or_else(|| settings.base.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::appearance::Mode;
+    use crate::testing::{at, event};
+
+    const DARK: SystemAppearance = SystemAppearance {
+        mode: Mode::Dark,
+        reduce_transparency: false,
+    };
+    const LIGHT: SystemAppearance = SystemAppearance {
+        mode: Mode::Light,
+        reduce_transparency: false,
+    };
+
+    fn themed(background: &str) -> AlertTheme {
+        AlertTheme::new(background, "#ffffff", "clock")
+    }
+
+    fn band(minutes_until: i64, background: &str) -> UrgencyBand {
+        UrgencyBand {
+            minutes_until,
+            theme: themed(background),
+        }
+    }
+
+    fn background(
+        settings: &ThemeSettings,
+        event: &CalendarEvent,
+        now: &str,
+        appearance: SystemAppearance,
+    ) -> String {
+        derive(settings, event, at(now), appearance).background
+    }
+
+    #[test]
+    fn the_first_matching_rule_wins() {
+        let settings = ThemeSettings {
+            rules: vec![
+                ThemeRule {
+                    calendar: Some("personal".to_string()),
+                    organizer_domain: None,
+                    theme: themed("personal"),
+                },
+                ThemeRule {
+                    calendar: None,
+                    organizer_domain: Some("@BigClient.com".to_string()),
+                    theme: themed("client"),
+                },
+                ThemeRule {
+                    calendar: Some("Work".to_string()),
+                    organizer_domain: None,
+                    theme: themed("work"),
+                },
+            ],
+            ..ThemeSettings::default()
+        };
+        let mut review = event("Review", at("2026-03-02 10:00"), 30);
+        assert_eq!(
+            background(&settings, &review, "2026-03-02 09:00", DARK),
+            "work"
+        );
+        review.organizer = Some("Ana@bigclient.com".to_string());
+        assert_eq!(
+            background(&settings, &review, "2026-03-02 09:00", DARK),
+            "client"
+        );
+        review.calendar = "Personal".to_string();
+        assert_eq!(
+            background(&settings, &review, "2026-03-02 09:00", DARK),
+            "personal"
+        );
+        review.calendar = "Holidays".to_string();
+        review.organizer = Some("ana@smallclient.com".to_string());
+        assert_eq!(
+            background(&settings, &review, "2026-03-02 09:00", DARK),
+            "#0a0a0a"
+        );
+    }
+
+    #[test]
+    fn the_tightest_urgency_band_applies_over_a_rule() {
+        let settings = ThemeSettings {
+            rules: vec![ThemeRule {
+                calendar: None,
+                organizer_domain: None,
+                theme: themed("rule"),
+            }],
+            urgency: vec![band(2, "two"), band(10, "ten"), band(5, "five")],
+            ..ThemeSettings::default()
+        };
+        let standup = event("Standup", at("2026-03-02 10:00"), 15);
+        for (now, expected) in [
+            ("2026-03-02 09:30", "rule"),
+            ("2026-03-02 09:50", "ten"),
+            ("2026-03-02 09:54", "ten"),
+            ("2026-03-02 09:55", "five"),
+            ("2026-03-02 09:58", "two"),
+            ("2026-03-02 09:59", "two"),
+        ] {
+            assert_eq!(
+                background(&settings, &standup, now, DARK),
+                expected,
+                "at {}",
+                now
+            );
+        }
+    }
+
+    #[test]
+    fn a_band_starts_at_exactly_its_minutes() {
+        let settings = ThemeSettings {
+            urgency: vec![band(5, "five")],
+            ..ThemeSettings::default()
+        };
+        let standup = event("Standup", at("2026-03-02 10:00"), 15);
+        let theme = |now| derive(&settings, &standup, now, DARK).background;
+        assert_eq!(theme(at("2026-03-02 09:54")), "#0a0a0a");
+        // Five and a half minutes out is still outside a five-minute band.
+        assert_eq!(
+            theme(at("2026-03-02 09:54") + Duration::seconds(30)),
+            "#0a0a0a"
+        );
+        assert_eq!(theme(at("2026-03-02 09:55")), "five");
+        assert_eq!(
+            theme(at("2026-03-02 09:55") + Duration::seconds(30)),
+            "five"
+        );
+    }
+
+    #[test]
+    fn the_started_theme_applies_from_the_start() {
+        let settings = ThemeSettings {
+            urgency: vec![band(5, "five")],
+            ..ThemeSettings::default()
+        };
+        let standup = event("Standup", at("2026-03-02 10:00"), 15);
+        assert_eq!(
+            background(&settings, &standup, "2026-03-02 09:59", DARK),
+            "five"
+        );
+        assert_eq!(
+            background(&settings, &standup, "2026-03-02 10:00", DARK),
+            "#450a0a"
+        );
+        assert_eq!(
+            background(&settings, &standup, "2026-03-02 10:10", LIGHT),
+            "#450a0a"
+        );
+        // Without one, the last theme before the start stays.
+        let settings = ThemeSettings {
+            started: None,
+            ..settings
+        };
+        assert_eq!(
+            background(&settings, &standup, "2026-03-02 10:10", DARK),
+            "five"
+        );
+    }
+
+    #[test]
+    fn light_mode_falls_back_to_the_light_theme() {
+        let standup = event("Standup", at("2026-03-02 10:00"), 15);
+        let settings = ThemeSettings::default();
+        assert_eq!(
+            background(&settings, &standup, "2026-03-02 09:00", LIGHT),
+            "#f5f5f5"
+        );
+        assert_eq!(
+            background(&settings, &standup, "2026-03-02 09:00", DARK),
+            "#0a0a0a"
+        );
+        let settings = ThemeSettings {
+            light: None,
+            ..ThemeSettings::default()
+        };
+        assert_eq!(
+            background(&settings, &standup, "2026-03-02 09:00", LIGHT),
+            "#0a0a0a"
+        );
+        // A matching rule is used in either appearance.
+        let settings = ThemeSettings {
+            rules: vec![ThemeRule {
+                calendar: Some("work".to_string()),
+                organizer_domain: None,
+                theme: themed("work"),
+            }],
+            ..ThemeSettings::default()
+        };
+        assert_eq!(
+            background(&settings, &standup, "2026-03-02 09:00", LIGHT),
+            "work"
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use chrono::{Local, Offset};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::bus::{self, AppEvent};
+use crate::scheduler::SchedulerState;
+use crate::week::WeekCache;
+
+// --- Time Zone Changes ---
+//
+// When the Mac switches zones (e.g. after landing), cached events keep their
+// instants, but everything derived as local time (day boundaries, the week
+// view, "today at 14:00") is stale. The scheduler polls the zone every tick;
+// a change drops the caches, refetches right away and is published as
+// `timezone-changed` with the old and new zone.
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Zone {
+    /// IANA name such as "Europe/Berlin"; the UTC offset where unknown.
+    pub name: String,
+    pub offset_seconds: i32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ZoneChange {
+    pub from: Zone,
+    pub to: Zone,
+}
+
+/// The zone seen by the last `poll`.
+static CURRENT: Mutex<Option<Zone>> = Mutex::new(None);
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use std::ffi::CStr;
+
+    /// The system zone's name; `systemTimeZone` is cached until reset.
+    pub fn zone_name() -> Option<String> {
+        // SAFETY: autoreleased Foundation objects used within this call; the
+        // UTF-8 buffer is copied before they go away.
+        unsafe {
+            let _: () = msg_send![class!(NSTimeZone), resetSystemTimeZone];
+            let zone: *mut AnyObject = msg_send![class!(NSTimeZone), systemTimeZone];
+            let name: *mut AnyObject = msg_send![zone.as_ref()?, name];
+            let utf8: *const std::ffi::c_char = msg_send![name.as_ref()?, UTF8String];
+            if utf8.is_null() {
+                return None;
+            }
+            Some(CStr::from_ptr(utf8).to_string_lossy().into_owned())
+        }
+    }
+}
+
+fn read() -> Zone {
+    let offset = Local::now().offset().fix();
+    #[cfg(target_os = "macos")]
+    let name = macos::zone_name();
+    #[cfg(not(target_os = "macos"))]
+    let name: Option<String> = None;
+    Zone {
+        name: name.unwrap_or_else(|| offset.to_string()),
+        offset_seconds: offset.local_minus_utc(),
+    }
+}
+
+/// The change from `previous` to `zone`, if it is one. A DST switch keeps
+/// the name, so it doesn't count (except where the name is unknown and the
+/// offset stands in for it).
+fn change(previous: Option<Zone>, zone: Zone) -> Option<ZoneChange> {
+    let previous = previous.filter(|previous| previous.name != zone.name)?;
+    Some(ZoneChange {
+        from: previous,
+        to: zone,
+    })
+}
+
+/// Refetches and publishes `TimezoneChanged` when the system zone changed.
+pub fn poll(app: &AppHandle) {
+    let zone = read();
+    let previous = CURRENT.lock().unwrap().replace(zone.clone());
+    let Some(change) = change(previous, zone) else {
+        return;
+    };
+    app.state::<WeekCache>().clear();
+    app.state::<SchedulerState>().0.lock().unwrap().invalidate();
+    bus::publish(app, AppEvent::TimezoneChanged(change));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::{self, ClockStyle, FormatSettings};
+    use crate::rules;
+    use crate::settings::Settings;
+    use crate::testing::{at, event};
+    use chrono::{Duration, FixedOffset};
+
+    fn zone(name: &str, hours: i32) -> Zone {
+        Zone {
+            name: name.to_string(),
+            offset_seconds: hours * 3600,
+        }
+    }
+
+    #[test]
+    fn only_a_new_zone_is_a_change() {
+        let berlin = zone("Europe/Berlin", 1);
+        assert!(change(None, berlin.clone()).is_none());
+        // Summer time keeps the zone.
+        assert!(change(Some(berlin.clone()), zone("Europe/Berlin", 2)).is_none());
+        let landed = change(Some(berlin), zone("America/New_York", -5)).unwrap();
+        assert_eq!(landed.from.name, "Europe/Berlin");
+        assert_eq!(landed.to.name, "America/New_York");
+        // Without names, the offsets stand in for them.
+        assert!(change(Some(zone("+01:00", 1)), zone("-05:00", -5)).is_some());
+    }
+
+    #[test]
+    fn a_pending_alert_keeps_its_instant_across_a_zone_change() {
+        let settings = Settings {
+            lead_time_seconds: 120,
+            ..Settings::default()
+        };
+        // Fetched in Berlin, where it starts at 10:00.
+        let mut standup = event("Standup", at("2026-03-02 10:00"), 15);
+        standup.start = "2026-03-02T10:00:00+01:00".to_string();
+        standup.end = "2026-03-02T10:15:00+01:00".to_string();
+        let lead = Duration::seconds(rules::evaluate(&settings, &standup).lead_time_seconds);
+        let alert_at = standup.start_time().unwrap() - lead;
+
+        let berlin = FixedOffset::east_opt(3600).unwrap();
+        let new_york = FixedOffset::west_opt(5 * 3600).unwrap();
+        let format = FormatSettings {
+            clock: ClockStyle::TwentyFourHour,
+            ..FormatSettings::default()
+        };
+        assert_eq!(
+            locale::format_time(&format, alert_at.with_timezone(&berlin)),
+            "09:58"
+        );
+        assert_eq!(
+            locale::format_time(&format, alert_at.with_timezone(&new_york)),
+            "03:58"
+        );
+        assert_eq!(
+            alert_at.with_timezone(&berlin),
+            alert_at.with_timezone(&new_york)
+        );
+        assert_eq!(alert_at.to_utc().to_rfc3339(), "2026-03-02T08:58:00+00:00");
+    }
+}
@@ -0,0 +1,394 @@
+use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{
+    menu::{CheckMenuItem, IsMenuItem, Menu, MenuEvent, MenuItem, Submenu},
+    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+    AppHandle, Window, Wry,
+};
+
+use crate::bus::{self, AppEvent};
+use crate::ipc::{self, IpcError};
+use crate::{
+    actions, backup, days_off, locale, notify, popover, presentation, profiles, recent, retention,
+    scheduler, session, settings, shutdown, startup, untrusted, window,
+};
+use popover::TrayClick;
+
+// --- Tray ---
+//
+// The icon can vanish while the app keeps running, after SystemUIServer
+// restarts or a long sleep, which leaves no way into the app. A watchdog
+// checks every `WATCH_INTERVAL` that the icon is registered and has a place
+// in the menu bar, and rebuilds it with the current menu and badge when it
+// doesn't; the dashboard's diagnostics can ask for the same with
+// `rebuild_tray`. The old icon is removed first, taking its event handlers
+// with it, so a rebuild never leaves two icons answering clicks. While the
+// app is starting the menu only has Quit, and until the first fetch lands
+// the icon says it is loading.
+
+const TRAY_ID: &str = "tray";
+const PROFILE_PREFIX: &str = "profile:";
+const RECENT_SHOW_PREFIX: &str = "recent-show:";
+const RECENT_COPY_PREFIX: &str = "recent-copy:";
+const RECENT_IN_MENU: usize = 10;
+/// Longer titles are cut in the menu; recent meetings saved before titles
+/// were cleaned are cleaned here too.
+pub const MENU_TITLE_CHARS: usize = 80;
+const WATCH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Until the first fetch lands.
+static LOADING: AtomicBool = AtomicBool::new(true);
+/// When the icon was last rebuilt, by the watchdog or on request.
+static REBUILT_AT: Mutex<Option<DateTime<Local>>> = Mutex::new(None);
+
+/// One submenu per recent meeting, to reopen it or copy its link.
+fn recent_menu(app: &AppHandle) -> tauri::Result<Submenu<Wry>> {
+    let format = settings::current(app).format;
+    let meetings = recent::list(app, RECENT_IN_MENU);
+    let items = meetings
+        .iter()
+        .map(|meeting| {
+            let event = &meeting.event;
+            let key = event.occurrence_key();
+            let title = untrusted::line(&event.title, MENU_TITLE_CHARS);
+            let label = match event.start_time() {
+                Some(start) => format!("{} {}", locale::format_time(&format, start), title),
+                None => title,
+            };
+            let show = MenuItem::with_id(
+                app,
+                format!("{}{}", RECENT_SHOW_PREFIX, key),
+                "Show Details",
+                true,
+                None::<&str>,
+            )?;
+            let copy = MenuItem::with_id(
+                app,
+                format!("{}{}", RECENT_COPY_PREFIX, key),
+                "Copy Link",
+                event.video_link.is_some(),
+                None::<&str>,
+            )?;
+            Submenu::with_items(app, label, true, &[&show, &copy])
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let refs: Vec<&dyn IsMenuItem<Wry>> = items
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<Wry>)
+        .collect();
+    Submenu::with_items(app, "Recent Meetings", !items.is_empty(), &refs)
+}
+
+/// The menu while the stores are loading: nothing in it may touch them.
+fn starting_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let starting_i = MenuItem::with_id(app, "starting", "Starting…", false, None::<&str>)?;
+    let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    Menu::with_items(app, &[&starting_i, &quit_i])
+}
+
+fn menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    if startup::is_ready() {
+        build_menu(app)
+    } else {
+        starting_menu(app)
+    }
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let settings = settings::current(app);
+    let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let show_i = MenuItem::with_id(app, "show", "Show Dashboard", true, None::<&str>)?;
+    let export_i = MenuItem::with_id(app, "export", "Export Backup…", true, None::<&str>)?;
+    let import_i = MenuItem::with_id(app, "import", "Import Backup…", true, None::<&str>)?;
+    let pause_i = MenuItem::with_id(app, "pause", "Pause Until Free", true, None::<&str>)?;
+    let join_i = MenuItem::with_id(app, "join", "Join Next Meeting", true, None::<&str>)?;
+    let recent_i = recent_menu(app)?;
+    // Only on a day off, and until midnight.
+    let working_i = days_off::working_today_item(&settings)
+        .map(|working| {
+            CheckMenuItem::with_id(
+                app,
+                "working-today",
+                "I'm Working Today",
+                true,
+                working,
+                None::<&str>,
+            )
+        })
+        .transpose()?;
+    let mut head: Vec<&dyn IsMenuItem<Wry>> = vec![&show_i, &join_i, &pause_i];
+    if let Some(working_i) = &working_i {
+        head.push(working_i);
+    }
+    head.push(&recent_i);
+    if settings.profiles.is_empty() {
+        return Menu::with_items(
+            app,
+            &[head.as_slice(), &[&export_i, &import_i, &quit_i]].concat(),
+        );
+    }
+
+    let profile_items = settings
+        .profiles
+        .iter()
+        .map(|profile| {
+            let active = settings.active_profile.as_deref() == Some(profile.name.as_str());
+            CheckMenuItem::with_id(
+                app,
+                format!("{}{}", PROFILE_PREFIX, profile.name),
+                &profile.name,
+                true,
+                active,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let refs: Vec<&dyn IsMenuItem<Wry>> = profile_items
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<Wry>)
+        .collect();
+    let profiles_i = Submenu::with_items(app, "Profile", true, &refs)?;
+    Menu::with_items(
+        app,
+        &[
+            head.as_slice(),
+            &[&profiles_i, &export_i, &import_i, &quit_i],
+        ]
+        .concat(),
+    )
+}
+
+pub fn show_dashboard(app: &AppHandle) {
+    if let Some(window) = window::ensure_main_window(app) {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        // The exit runs the shutdown hooks.
+        "quit" => app.exit(0),
+        "show" => show_dashboard(app),
+        "join" => {
+            if let Err(e) = actions::join_next(app) {
+                notify::show(app, "Couldn't join", &e);
+            }
+        }
+        "working-today" => {
+            if let Err(e) = days_off::toggle_working_today(app) {
+                notify::show(app, "Couldn't change the day", &e);
+            }
+        }
+        "export" => backup::export_with_dialog(app),
+        "import" => backup::import_with_dialog(app),
+        "pause" => match scheduler::pause_until_free(app.clone()) {
+            Ok(until) => {
+                let format = settings::current(app).format;
+                let body = format!("Paused until {}", locale::format_time(&format, until));
+                notify::show(app, "Reminders paused", &body);
+            }
+            Err(e) => notify::show(app, "Nothing to pause", &e),
+        },
+        id => {
+            if let Some(name) = id.strip_prefix(PROFILE_PREFIX) {
+                let _ = profiles::switch(app, name);
+            } else if let Some(key) = id.strip_prefix(RECENT_SHOW_PREFIX) {
+                if let Some(meeting) = recent::find(app, key) {
+                    show_dashboard(app);
+                    bus::publish(app, AppEvent::RecentMeetingShown(Box::new(meeting)));
+                }
+            } else if let Some(key) = id.strip_prefix(RECENT_COPY_PREFIX) {
+                let link = recent::find(app, key).and_then(|m| m.event.video_link);
+                if let Some(Err(e)) = link.map(|link| actions::copy_to_clipboard(&link)) {
+                    notify::show(app, "Link not copied", &e);
+                }
+            }
+        }
+    }
+}
+
+pub fn is_alive(app: &AppHandle) -> bool {
+    missing(app).is_none()
+}
+
+pub fn rebuilt_at() -> Option<DateTime<Local>> {
+    *REBUILT_AT.lock().unwrap()
+}
+
+/// Why the icon looks gone, if it does: not registered, or with no place
+/// in the menu bar.
+fn missing(app: &AppHandle) -> Option<&'static str> {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Some("not registered");
+    };
+    // Other platforms don't report where the icon is.
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    match tray.rect() {
+        Ok(Some(rect)) => {
+            let size = rect.size.to_physical::<f64>(1.0);
+            (size.width <= 0.0 || size.height <= 0.0).then_some("has no size")
+        }
+        Ok(None) => Some("not in the menu bar"),
+        Err(_) => Some("unreachable"),
+    }
+}
+
+fn build(app: &AppHandle) -> tauri::Result<()> {
+    TrayIconBuilder::with_id(TRAY_ID)
+        .icon(app.default_window_icon().unwrap().clone())
+        .menu(&menu(app)?)
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                rect,
+                ..
+            } = event
+            {
+                let app = tray.app_handle();
+                if !startup::is_ready() {
+                    return;
+                }
+                match settings::current(app).tray_click {
+                    TrayClick::Popover => popover::toggle(app, rect),
+                    TrayClick::Dashboard => show_dashboard(app),
+                }
+            }
+        })
+        .build(app)?;
+    update_badge(app);
+    Ok(())
+}
+
+/// Replaces the icon with a fresh one; dropping the old one removes its
+/// status item and handlers.
+fn rebuild(app: &AppHandle) -> tauri::Result<()> {
+    drop(app.remove_tray_by_id(TRAY_ID));
+    build(app)?;
+    *REBUILT_AT.lock().unwrap() = Some(Local::now());
+    Ok(())
+}
+
+fn watch(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(WATCH_INTERVAL);
+        if shutdown::in_progress() {
+            return;
+        }
+        let Some(reason) = missing(&app) else {
+            continue;
+        };
+        match rebuild(&app) {
+            Ok(()) => retention::log!("tray icon {}; rebuilt it", reason),
+            Err(e) => retention::log!("tray icon {}; rebuilding failed: {}", reason, e),
+        }
+    });
+}
+
+pub fn create(app: &AppHandle) -> tauri::Result<()> {
+    build(app)?;
+    watch(app);
+
+    let events = bus::subscribe(app);
+    let handle = app.clone();
+    thread::spawn(move || {
+        for event in events {
+            // A backgrounded session catches up once it is in front again.
+            match event {
+                AppEvent::SettingsChanged if session::is_active() => refresh(&handle),
+                AppEvent::SessionChanged(true) => refresh(&handle),
+                AppEvent::PresentationChanged(_) => update_badge(&handle),
+                _ => {}
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Warnings shown in the tray, by the subsystem that raised them.
+static WARNINGS: Mutex<BTreeMap<&'static str, String>> = Mutex::new(BTreeMap::new());
+
+/// Flags a problem with a "⚠" next to the icon and the reasons as tooltip;
+/// `None` clears the warning `key` raised, leaving the others.
+pub fn set_warning(app: &AppHandle, key: &'static str, warning: Option<&str>) {
+    {
+        let mut warnings = WARNINGS.lock().unwrap();
+        match warning {
+            Some(warning) => warnings.insert(key, warning.to_string()),
+            None => warnings.remove(key),
+        };
+    }
+    update_badge(app);
+}
+
+/// A warning wins over the loading and "presenting" badges; all are next
+/// to the icon.
+fn title(warned: bool) -> Option<&'static str> {
+    if warned {
+        Some("⚠")
+    } else if LOADING.load(Ordering::SeqCst) {
+        Some("…")
+    } else if presentation::is_presenting() {
+        Some("Presenting")
+    } else {
+        None
+    }
+}
+
+/// Shows the current warnings and badge, e.g. on a rebuilt icon.
+fn update_badge(app: &AppHandle) {
+    let warning = {
+        let warnings = WARNINGS.lock().unwrap();
+        let reasons: Vec<&str> = warnings.values().map(String::as_str).collect();
+        (!reasons.is_empty()).then(|| reasons.join("\n"))
+    };
+    let warned = warning.is_some();
+    let tooltip = warning.or_else(|| {
+        LOADING
+            .load(Ordering::SeqCst)
+            .then(|| "Loading your calendar…".to_string())
+    });
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_title(title(warned));
+        let _ = tray.set_tooltip(tooltip);
+    }
+}
+
+/// The first fetch landed; the icon stops saying it is loading.
+pub fn loaded(app: &AppHandle) {
+    LOADING.store(false, Ordering::SeqCst);
+    update_badge(app);
+}
+
+/// Takes the warning or "presenting" badge off, on quit.
+pub fn clear_title(app: &AppHandle) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_title(None::<&str>);
+    }
+}
+
+/// Rebuilds the menu; runs whenever the settings change.
+pub fn refresh(app: &AppHandle) {
+    if let (Some(tray), Ok(menu)) = (app.tray_by_id(TRAY_ID), menu(app)) {
+        let _ = tray.set_menu(Some(menu));
+    }
+}
+
+/// Rebuilds the icon, for when it vanished before the watchdog noticed.
+#[tauri::command]
+pub fn rebuild_tray(app: AppHandle, window: Window) -> Result<(), IpcError> {
+    ipc::require_dashboard(&window, "rebuild_tray")?;
+    rebuild(&app).map_err(|e| e.to_string())?;
+    retention::log!("tray icon rebuilt on request");
+    Ok(())
+}
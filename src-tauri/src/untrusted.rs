@@ -0,0 +1,264 @@
+// --- Untrusted Text ---
+//
+// Titles, locations, notes and names in events come from whoever sent the
+// invite, and inbound reminders from whoever can post to the topic. They are
+// cleaned once where they come in (`helper::check_event`, `inbound`), so
+// that everything downstream can treat them as display text:
+// - control characters and bidi embeddings, overrides and isolates are
+//   dropped;
+// - line breaks become `\n`, or a space in one-line fields;
+// - each field is capped at a length.
+// Templates substitute placeholders in a single pass, so a title that looks
+// like a template stays text.
+// None of it reaches a shell: child processes get their arguments as argv,
+// never through `sh -c`. None of it goes into a file path either, since
+// helper scripts and exports have fixed or dated names. Links from events
+// are opened only through `openable_link`, and ids in the links we make
+// are escaped (`escape_in_link`).
+
+/// Schemes a link from an event may open: web pages, mail, and the apps
+/// meetings and our own links open in.
+const LINK_SCHEMES: [&str; 7] = [
+    "http", "https", "mailto", "maps", "ical", "zoommtg", "msteams",
+];
+
+/// Bidi embeddings, overrides and isolates: they reorder the text around
+/// them, so "gpj.exe" with an override reads as "exe.jpg".
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+fn is_line_break(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+fn is_dropped(c: char) -> bool {
+    c.is_control() || is_bidi_control(c)
+}
+
+fn cap(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((index, _)) => text[..index].to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// One line of text: breaks and tabs become spaces, and the ends are trimmed.
+pub fn line(text: &str, max_chars: usize) -> String {
+    let cleaned: String = text
+        .chars()
+        .map(|c| {
+            if is_line_break(c) || c == '\t' {
+                ' '
+            } else {
+                c
+            }
+        })
+        .filter(|&c| !is_dropped(c))
+        .collect();
+    cap(cleaned.trim(), max_chars)
+}
+
+/// Running text such as notes: breaks are kept, as `\n`, and so are tabs.
+pub fn text(text: &str, max_chars: usize) -> String {
+    let cleaned: String = text
+        .replace("\r\n", "\n")
+        .chars()
+        .map(|c| if is_line_break(c) { '\n' } else { c })
+        .filter(|&c| c == '\n' || c == '\t' || !is_dropped(c))
+        .collect();
+    cap(&cleaned, max_chars)
+}
+
+pub fn clean_line(value: &mut String, max_chars: usize) {
+    *value = line(value, max_chars);
+}
+
+pub fn clean_line_option(value: &mut Option<String>, max_chars: usize) {
+    if let Some(value) = value {
+        clean_line(value, max_chars);
+    }
+}
+
+pub fn clean_text_option(value: &mut Option<String>, max_chars: usize) {
+    if let Some(value) = value {
+        *value = text(value, max_chars);
+    }
+}
+
+/// `id` for the path of a link: spaces, controls and the characters that
+/// would end the path or start a parameter are percent-encoded. Anything
+/// else is left as is, since an EventKit id has nothing else in it and
+/// Calendar takes it unencoded.
+pub fn escape_in_link(id: &str) -> String {
+    let mut escaped = String::with_capacity(id.len());
+    for c in id.chars() {
+        if c.is_whitespace() || is_dropped(c) || matches!(c, '?' | '#' | '&' | '%') {
+            let mut bytes = [0; 4];
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                escaped.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// `url` if it is a link we open: one of `LINK_SCHEMES`, and nothing in it
+/// that a browser or `open` would read differently from what is shown.
+pub fn openable_link(url: &str) -> Result<&str, String> {
+    let url = url.trim();
+    if url.chars().any(|c| c.is_whitespace() || is_dropped(c)) {
+        return Err("The link has spaces or control characters in it".to_string());
+    }
+    let scheme = url
+        .split_once(':')
+        .map(|(scheme, _)| scheme.to_ascii_lowercase())
+        .ok_or("Not a link".to_string())?;
+    if !LINK_SCHEMES.contains(&scheme.as_str()) {
+        return Err(format!("{} links aren't opened", scheme));
+    }
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::FormatSettings;
+    use crate::settings::Settings;
+    use crate::testing::{at, event, hostile_text};
+    use crate::{calendar, helper, templates, tray};
+    use proptest::prelude::*;
+
+    fn is_display_line(text: &str) -> bool {
+        !text.chars().any(|c| is_dropped(c) || is_line_break(c))
+    }
+
+    /// `escaped` with its percent-escapes decoded, if they are well formed.
+    fn unescape(escaped: &str) -> Option<String> {
+        let mut bytes = Vec::new();
+        let mut rest = escaped.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            if byte == b'%' {
+                let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                rest = &tail[2..];
+            } else {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+        String::from_utf8(bytes).ok()
+    }
+
+    proptest! {
+        #[test]
+        fn a_line_is_one_capped_line(value in hostile_text(), max in 0usize..200) {
+            let cleaned = line(&value, max);
+            prop_assert!(is_display_line(&cleaned), "{:?}", cleaned);
+            prop_assert!(cleaned.chars().count() <= max);
+        }
+
+        #[test]
+        fn text_keeps_only_newlines_and_tabs(value in hostile_text(), max in 0usize..20_000) {
+            let cleaned = text(&value, max);
+            prop_assert!(
+                !cleaned.chars().any(|c| c != '\n' && c != '\t' && is_dropped(c)),
+                "{:?}",
+                cleaned
+            );
+            prop_assert!(!cleaned.contains(['\r', '\u{2028}', '\u{2029}']), "{:?}", cleaned);
+            prop_assert!(cleaned.chars().count() <= max);
+        }
+
+        #[test]
+        fn an_escaped_id_stays_in_the_path(id in hostile_text()) {
+            let escaped = escape_in_link(&id);
+            prop_assert!(
+                !escaped
+                    .chars()
+                    .any(|c| c.is_whitespace() || is_dropped(c) || matches!(c, '?' | '#' | '&')),
+                "{:?}",
+                escaped
+            );
+            prop_assert_eq!(unescape(&escaped), Some(id));
+        }
+
+        #[test]
+        fn only_clean_links_of_known_schemes_open(path in hostile_text()) {
+            for scheme in ["https", "javascript", "file"] {
+                let url = format!("{}://example.com/{}", scheme, path);
+                match openable_link(&url) {
+                    Ok(link) => {
+                        prop_assert_eq!(scheme, "https");
+                        prop_assert!(!link.chars().any(|c| c.is_whitespace() || is_dropped(c)));
+                    }
+                    Err(_) => prop_assert!(
+                        scheme != "https"
+                            || url.trim().chars().any(|c| c.is_whitespace() || is_dropped(c))
+                    ),
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// An event as the fetch helper prints it, through the templates to
+        /// the tray menu and the link that opens it in Calendar.
+        #[test]
+        fn hostile_events_stay_text_from_fetch_to_tray(
+            id in hostile_text(),
+            title in hostile_text(),
+            location in hostile_text(),
+            notes in hostile_text(),
+        ) {
+            let mut raw = event("Standup", at("2026-03-02 10:00"), 30);
+            raw.id = id;
+            raw.title = title;
+            raw.location = Some(location);
+            raw.description = Some(notes);
+            let output = format!(
+                "2026-03-02 EventKit noise\n{}\n",
+                serde_json::to_string(&[raw]).unwrap()
+            );
+            let events = helper::parse_items("fuzz.swift", &output, helper::check_event).unwrap();
+            // An id too long to be one drops the event.
+            prop_assert!(events.len() <= 1);
+            let Some(fetched) = events.first() else {
+                return Ok(());
+            };
+            let location = fetched.location.as_deref().unwrap_or_default();
+            prop_assert!(is_display_line(&fetched.title), "{:?}", fetched.title);
+            prop_assert!(is_display_line(location), "{:?}", location);
+            prop_assert!(fetched
+                .description
+                .iter()
+                .all(|notes| !notes.chars().any(|c| c != '\n' && c != '\t' && is_dropped(c))));
+
+            // Placeholders in the title are not filled in.
+            let rendered = templates::render(
+                "{title} | {location}",
+                fetched,
+                at("2026-03-02 09:58"),
+                &FormatSettings::default(),
+            );
+            prop_assert_eq!(rendered, format!("{} | {}", fetched.title, location));
+            let speech = templates::speech(&Settings::default(), fetched);
+            prop_assert!(is_display_line(&speech), "{:?}", speech);
+            prop_assert!(speech.starts_with("Meeting alert: "));
+
+            let label = line(&fetched.title, tray::MENU_TITLE_CHARS);
+            prop_assert!(is_display_line(&label));
+            prop_assert!(label.chars().count() <= tray::MENU_TITLE_CHARS);
+
+            let link = calendar::calendar_app_url(&fetched.id);
+            let (path, query) = link.split_once('?').unwrap();
+            prop_assert_eq!(query, "method=show&options=more");
+            prop_assert!(!path.contains('#'));
+            prop_assert!(!link.chars().any(|c| c.is_whitespace() || is_dropped(c)));
+        }
+    }
+}
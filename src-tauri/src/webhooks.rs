@@ -0,0 +1,279 @@
+use chrono::{DateTime, Local};
+use hmac::{Hmac, Mac};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State, Window};
+
+use crate::bus::{self, AppEvent};
+use crate::calendar::CalendarEvent;
+use crate::inbound::{self, InboundStatus};
+use crate::ipc::{self, IpcError};
+use crate::mqtt::{self, MqttStatus};
+use crate::privacy;
+use crate::retention;
+use crate::settings::{self, Settings, WebhookConfig};
+
+// --- Outbound Webhooks ---
+//
+// Lifecycle events are queued and delivered from a worker thread so a slow or
+// failing endpoint never holds up the alert itself.
+
+const MAX_ATTEMPTS: u32 = 3;
+const IDLE_WAIT: Duration = Duration::from_secs(3600);
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LifecycleEvent {
+    AlertFired,
+    AlertDismissed,
+    AlertSnoozed,
+    MeetingStarted,
+    MeetingEnded,
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    event: &'a str,
+    timestamp: DateTime<Local>,
+    data: Option<&'a CalendarEvent>,
+}
+
+struct Delivery {
+    hook: WebhookConfig,
+    event: String,
+    body: String,
+    attempt: u32,
+    next_at: Instant,
+}
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct WebhookStatus {
+    pub url: String,
+    pub last_event: Option<String>,
+    pub last_attempt_at: Option<DateTime<Local>>,
+    pub last_success_at: Option<DateTime<Local>>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct IntegrationsStatus {
+    pub webhooks: Vec<WebhookStatus>,
+    pub inbound: InboundStatus,
+    pub mqtt: MqttStatus,
+}
+
+pub struct WebhookState {
+    queue: Sender<Delivery>,
+    status: Mutex<HashMap<String, WebhookStatus>>,
+}
+
+pub fn start(app: &AppHandle) -> WebhookState {
+    let (queue, rx) = mpsc::channel();
+    let handle = app.clone();
+    thread::spawn(move || run(handle, rx));
+    let events = bus::subscribe(app);
+    let handle = app.clone();
+    thread::spawn(move || {
+        for event in events {
+            if let AppEvent::Lifecycle(kind, event) = event {
+                dispatch(&handle, kind, &event);
+            }
+        }
+    });
+    WebhookState {
+        queue,
+        status: Mutex::new(HashMap::new()),
+    }
+}
+
+/// Queues the event for every enabled hook subscribed to it.
+pub fn dispatch(app: &AppHandle, kind: LifecycleEvent, event: &CalendarEvent) {
+    let settings = settings::current(app);
+    let hooks = settings.webhooks.clone();
+    let name = event_name(kind);
+    let Ok(body) = body(&settings, &name, event, Local::now()) else {
+        return;
+    };
+
+    let state = app.state::<WebhookState>();
+    for hook in hooks {
+        if !hook.enabled || !(hook.events.is_empty() || hook.events.contains(&kind)) {
+            continue;
+        }
+        let _ = state.queue.send(Delivery {
+            hook,
+            event: name.clone(),
+            body: body.clone(),
+            attempt: 0,
+            next_at: Instant::now(),
+        });
+    }
+}
+
+/// The JSON posted for the event `name`, redacted by `privacy`.
+pub fn body(
+    settings: &Settings,
+    name: &str,
+    event: &CalendarEvent,
+    now: DateTime<Local>,
+) -> Result<String, String> {
+    let payload = Payload {
+        event: name,
+        timestamp: now,
+        data: Some(event),
+    };
+    privacy::external(settings, &payload).map(|json| json.to_string())
+}
+
+pub fn event_name(kind: LifecycleEvent) -> String {
+    serde_json::to_value(kind)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn run(app: AppHandle, rx: Receiver<Delivery>) {
+    let mut pending: Vec<Delivery> = Vec::new();
+    loop {
+        let wait = pending
+            .iter()
+            .map(|d| d.next_at.saturating_duration_since(Instant::now()))
+            .min()
+            .unwrap_or(IDLE_WAIT);
+        match rx.recv_timeout(wait) {
+            Ok(delivery) => pending.push(delivery),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        let now = Instant::now();
+        let (due, waiting): (Vec<_>, Vec<_>) = pending.into_iter().partition(|d| d.next_at <= now);
+        pending = waiting;
+        for mut delivery in due {
+            delivery.attempt += 1;
+            let result = send(&delivery.hook, &delivery.event, &delivery.body);
+            update_status(&app, &delivery, &result);
+            if let Err(error) = result {
+                if delivery.attempt < MAX_ATTEMPTS {
+                    // 2s, 4s, ... between attempts.
+                    delivery.next_at = Instant::now() + Duration::from_secs(1 << delivery.attempt);
+                    pending.push(delivery);
+                } else {
+                    retention::log!(
+                        "webhook {} failed for {} after {} attempts: {}",
+                        delivery.hook.url,
+                        delivery.event,
+                        MAX_ATTEMPTS,
+                        error
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn sign(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+fn send(hook: &WebhookConfig, event: &str, body: &str) -> Result<u16, String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client
+        .post(&hook.url)
+        .header("Content-Type", "application/json")
+        .header("X-Webhook-Event", event)
+        .body(body.to_string());
+    if let Some(secret) = hook.secret.as_deref().filter(|s| !s.is_empty()) {
+        request = request.header("X-Signature-256", sign(secret, body));
+    }
+
+    let response = request.send().map_err(|e| e.to_string())?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16())
+    } else {
+        Err(format!("HTTP {}", status.as_u16()))
+    }
+}
+
+fn update_status(app: &AppHandle, delivery: &Delivery, result: &Result<u16, String>) {
+    let state = app.state::<WebhookState>();
+    let mut statuses = state.status.lock().unwrap();
+    let status = statuses
+        .entry(delivery.hook.url.clone())
+        .or_insert_with(|| WebhookStatus {
+            url: delivery.hook.url.clone(),
+            ..Default::default()
+        });
+    let now = Local::now();
+    status.last_event = Some(delivery.event.clone());
+    status.last_attempt_at = Some(now);
+    match result {
+        Ok(_) => {
+            status.last_success_at = Some(now);
+            status.last_error = None;
+            status.consecutive_failures = 0;
+        }
+        Err(error) => {
+            status.last_error = Some(error.clone());
+            status.consecutive_failures += 1;
+        }
+    }
+}
+
+/// Delivery status by hook URL.
+pub fn statuses(app: &AppHandle) -> HashMap<String, WebhookStatus> {
+    app.state::<WebhookState>().status.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub async fn test_webhook(app: AppHandle, window: Window, url: String) -> Result<u16, IpcError> {
+    ipc::require_dashboard(&window, "test_webhook")?;
+    // Sign with the configured secret when testing a saved hook.
+    let hook = settings::current(&app)
+        .webhooks
+        .into_iter()
+        .find(|h| h.url == url)
+        .unwrap_or(WebhookConfig {
+            url,
+            ..Default::default()
+        });
+    let payload = Payload {
+        event: "test",
+        timestamp: Local::now(),
+        data: None,
+    };
+    let body = serde_json::to_string(&payload).map_err(|e| e.to_string())?;
+    let status = tauri::async_runtime::spawn_blocking(move || send(&hook, "test", &body))
+        .await
+        .map_err(|e| e.to_string())??;
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn get_integrations_status(
+    window: Window,
+    state: State<WebhookState>,
+) -> Result<IntegrationsStatus, IpcError> {
+    ipc::require_dashboard(&window, "get_integrations_status")?;
+    let mut webhooks: Vec<WebhookStatus> = state.status.lock().unwrap().values().cloned().collect();
+    webhooks.sort_by(|a, b| a.url.cmp(&b.url));
+    Ok(IntegrationsStatus {
+        webhooks,
+        inbound: inbound::status(),
+        mqtt: mqtt::status(),
+    })
+}
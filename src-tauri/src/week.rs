@@ -0,0 +1,254 @@
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, NaiveTime};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration as StdDuration, Instant};
+use tauri::{AppHandle, State};
+
+use crate::calendar::{self, CalendarEvent};
+use crate::locale::FormatSettings;
+use crate::{locale, power, settings, simulate};
+
+// --- Week Agenda ---
+//
+// One ranged fetch per week, grouped into days in Rust. Weeks are cached for
+// a poll interval so flipping back and forth doesn't rerun the Swift helper.
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WeekAgenda {
+    pub start: NaiveDate,
+    pub days: Vec<AgendaDay>,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaDay {
+    pub date: NaiveDate,
+    /// The date in the configured style, e.g. "Tue 14 Oct".
+    pub label: String,
+    /// 1440, except on DST changes (1380 or 1500).
+    pub day_minutes: i64,
+    /// Time covered by at least one timed event, overlaps counted once.
+    pub busy_minutes: i64,
+    pub all_day: Vec<CalendarEvent>,
+    pub events: Vec<CalendarEvent>,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Two timed events that overlap, by event id.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Conflict {
+    pub first: String,
+    pub second: String,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
+
+impl WeekAgenda {
+    /// Relative start times as of now; cached weeks are stamped on the way out.
+    fn stamped(mut self, format: &FormatSettings) -> Self {
+        let now = simulate::now();
+        for day in self.days.iter_mut() {
+            for event in day.all_day.iter_mut().chain(day.events.iter_mut()) {
+                event.stamp(now, format);
+            }
+        }
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct WeekCache(Mutex<HashMap<NaiveDate, (Instant, WeekAgenda)>>);
+
+impl WeekCache {
+    /// Drops every cached week, e.g. when day boundaries moved.
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+/// Local midnight; on the rare zones where midnight is skipped by DST, the
+/// first hour after it.
+pub fn start_of_day(date: NaiveDate) -> Option<DateTime<Local>> {
+    let local = |time: NaiveTime| date.and_time(time).and_local_timezone(Local).earliest();
+    local(NaiveTime::MIN).or_else(|| local(NaiveTime::from_hms_opt(1, 0, 0)?))
+}
+
+fn week_start(today: NaiveDate, starts_on: chrono::Weekday, offset: i32) -> Option<NaiveDate> {
+    let back = today.weekday().days_since(starts_on);
+    let start = today.checked_sub_days(Days::new(back.into()))?;
+    let shift = Days::new(7 * u64::from(offset.unsigned_abs()));
+    if offset < 0 {
+        start.checked_sub_days(shift)
+    } else {
+        start.checked_add_days(shift)
+    }
+}
+
+fn span(event: &CalendarEvent) -> Option<(DateTime<Local>, DateTime<Local>)> {
+    Some((event.start_time()?, event.end_time()?))
+}
+
+/// Time covered by the spans, sorted by start, overlaps counted once.
+pub fn busy_minutes(spans: &[(DateTime<Local>, DateTime<Local>)]) -> i64 {
+    let mut total = 0;
+    let mut cursor: Option<DateTime<Local>> = None;
+    for &(start, end) in spans {
+        let from = cursor.map_or(start, |c| c.max(start));
+        if end > from {
+            total += (end - from).num_minutes();
+            cursor = Some(end);
+        }
+    }
+    total
+}
+
+fn conflicts(events: &[CalendarEvent]) -> Vec<Conflict> {
+    let mut found = Vec::new();
+    for (i, a) in events.iter().enumerate() {
+        let Some((a_start, a_end)) = span(a) else {
+            continue;
+        };
+        for b in &events[i + 1..] {
+            let Some((b_start, b_end)) = span(b) else {
+                continue;
+            };
+            if b_start >= a_end {
+                break;
+            }
+            found.push(Conflict {
+                first: a.id.clone(),
+                second: b.id.clone(),
+                start: a_start.max(b_start),
+                end: a_end.min(b_end),
+            });
+        }
+    }
+    found
+}
+
+fn build_day(
+    events: &[CalendarEvent],
+    date: NaiveDate,
+    format: &FormatSettings,
+) -> Option<AgendaDay> {
+    let from = start_of_day(date)?;
+    let to = start_of_day(date.succ_opt()?)?;
+    let overlaps =
+        |e: &&CalendarEvent| span(e).is_some_and(|(start, end)| start < to && end > from);
+
+    let all_day: Vec<CalendarEvent> = events
+        .iter()
+        .filter(|e| e.is_all_day)
+        .filter(overlaps)
+        .cloned()
+        .collect();
+    let mut timed: Vec<CalendarEvent> = events
+        .iter()
+        .filter(|e| !e.is_all_day)
+        .filter(overlaps)
+        .cloned()
+        .collect();
+    timed.sort_by_key(|e| e.start_time());
+
+    // Clip to the day so overnight meetings count on both sides of midnight.
+    let spans: Vec<_> = timed
+        .iter()
+        .filter_map(span)
+        .map(|(start, end)| (start.max(from), end.min(to)))
+        .collect();
+
+    Some(AgendaDay {
+        date,
+        label: locale::format_date(format, date),
+        day_minutes: (to - from).num_minutes(),
+        busy_minutes: busy_minutes(&spans),
+        conflicts: conflicts(&timed),
+        all_day,
+        events: timed,
+    })
+}
+
+fn build_week(app: &AppHandle, start: NaiveDate) -> Result<WeekAgenda, String> {
+    let invalid = || format!("Week of {} is out of range", start);
+    let from = start_of_day(start).ok_or_else(invalid)?;
+    let end = start.checked_add_days(Days::new(7)).ok_or_else(invalid)?;
+    let to = start_of_day(end).ok_or_else(invalid)?;
+
+    let settings = settings::current(app);
+    let mut events = calendar::fetch_events_between(from, to);
+    events.retain(|e| !settings.calendar_filter.excludes(e));
+    settings.long_events.mark(&mut events);
+    for event in events.iter_mut() {
+        event.prune();
+    }
+
+    let days = start
+        .iter_days()
+        .take(7)
+        .map(|date| build_day(&events, date, &settings.format).ok_or_else(invalid))
+        .collect::<Result<_, _>>()?;
+    Ok(WeekAgenda { start, days })
+}
+
+/// The week `week_offset` weeks from the current one (negative for past
+/// weeks), starting on the configured weekday.
+#[tauri::command]
+pub async fn get_week_agenda(
+    app: AppHandle,
+    cache: State<'_, WeekCache>,
+    week_offset: i32,
+) -> Result<WeekAgenda, String> {
+    let settings = settings::current(&app);
+    let start = week_start(
+        simulate::now().date_naive(),
+        locale::week_start(&settings.format),
+        week_offset,
+    )
+    .ok_or("Week is out of range".to_string())?;
+    let ttl = StdDuration::from_secs(power::poll_interval_seconds(&settings));
+
+    if let Some((at, week)) = cache.0.lock().unwrap().get(&start) {
+        if at.elapsed() < ttl {
+            return Ok(week.clone().stamped(&settings.format));
+        }
+    }
+
+    let handle = app.clone();
+    let week = tauri::async_runtime::spawn_blocking(move || build_week(&handle, start))
+        .await
+        .map_err(|e| e.to_string())??;
+    let mut cached = cache.0.lock().unwrap();
+    cached.retain(|_, (at, _)| at.elapsed() < ttl);
+    cached.insert(start, (Instant::now(), week.clone()));
+    Ok(week.stamped(&settings.format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Weekday;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn weeks_start_on_the_configured_day() {
+        // A Wednesday.
+        let today = date("2026-03-04");
+        assert_eq!(week_start(today, Weekday::Mon, 0), Some(date("2026-03-02")));
+        assert_eq!(week_start(today, Weekday::Sun, 0), Some(date("2026-03-01")));
+        assert_eq!(week_start(today, Weekday::Sat, 0), Some(date("2026-02-28")));
+        // The start day itself opens its own week.
+        assert_eq!(week_start(today, Weekday::Wed, 0), Some(today));
+        assert_eq!(
+            week_start(today, Weekday::Sun, -1),
+            Some(date("2026-02-22"))
+        );
+        assert_eq!(week_start(today, Weekday::Sun, 2), Some(date("2026-03-15")));
+    }
+}
@@ -0,0 +1,491 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use std::time::Duration;
+use tauri::{
+    AppHandle, LogicalSize, Manager, Monitor, Runtime, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder,
+};
+
+use crate::bus::{self, AppEvent};
+use crate::calendar::CalendarEvent;
+use crate::displays::{self, Corner, DisplayInfo};
+use crate::scheduler::{self, Acknowledgement, SchedulerState};
+use crate::settings;
+use crate::{
+    accessibility, appearance, dismissal, fade, focus, presentation, renderer, retention, simulate,
+    sound, templates, theme,
+};
+
+// --- Window Logic ---
+//
+// The dashboard ("main") and the alert ("alert") are separate windows. The
+// alert window is created hidden at startup with its takeover flags already
+// set, so showing an alert never touches the dashboard. While presenting,
+// the alert shows instead as a card in its own "toast" window, which can
+// only acknowledge it.
+
+pub const MAIN_WINDOW: &str = "main";
+pub const ALERT_WINDOW: &str = "alert";
+pub const TOAST_WINDOW: &str = "toast";
+
+/// The post-join pill and an alert moved aside, in points.
+const PILL_SIZE: (f64, f64) = (320.0, 72.0);
+const CORNER_SIZE: (f64, f64) = (360.0, 132.0);
+const CORNER_MARGIN: f64 = 16.0;
+const FULLSCREEN_EXIT: Duration = Duration::from_millis(700);
+
+/// Set once the app is quitting so destroyed windows stay destroyed.
+static EXITING: AtomicBool = AtomicBool::new(false);
+/// Bumped whenever the alert window changes shape, so a docking still
+/// waiting for fullscreen to exit doesn't undo a later change.
+static GEOMETRY: AtomicU64 = AtomicU64::new(0);
+/// Alert webviews created since launch: the one at startup, plus any
+/// recreated after a crash.
+static ALERT_WINDOWS_CREATED: AtomicU64 = AtomicU64::new(0);
+static ALERTS_SHOWN: AtomicU64 = AtomicU64::new(0);
+/// Alerts that had to create a webview to show.
+static COLD_ALERTS: AtomicU64 = AtomicU64::new(0);
+
+/// How well the alert and overlay windows are reused, for debugging.
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowPoolStats {
+    pub alert_windows_created: u64,
+    pub overlay_windows_created: u64,
+    pub overlays_pooled: usize,
+    pub overlays_visible: usize,
+    pub alerts_shown: u64,
+    /// Stops growing once every connected display has its window.
+    pub cold_alerts: u64,
+}
+
+fn webviews_created() -> u64 {
+    ALERT_WINDOWS_CREATED.load(Ordering::SeqCst) + displays::overlays_created()
+}
+
+/// Hidden until an alert shows; generic so the startup test can make it in
+/// a mock app.
+pub fn create_alert_window<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<WebviewWindow<R>> {
+    ALERT_WINDOWS_CREATED.fetch_add(1, Ordering::SeqCst);
+    WebviewWindowBuilder::new(app, ALERT_WINDOW, WebviewUrl::App("index.html".into()))
+        .title("Meeting Alert")
+        .visible(false)
+        .decorations(false)
+        .transparent(true)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .resizable(false)
+        .build()
+}
+
+/// Hidden until an alert shows while presenting; it gets the alert's events
+/// from the start, so it has the alert when it shows.
+pub fn create_toast_window<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<WebviewWindow<R>> {
+    WebviewWindowBuilder::new(app, TOAST_WINDOW, WebviewUrl::App("index.html".into()))
+        .title("Meeting Alert")
+        .visible(false)
+        .focused(false)
+        .decorations(false)
+        .transparent(true)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .resizable(false)
+        .inner_size(CORNER_SIZE.0, CORNER_SIZE.1)
+        .build()
+}
+
+/// The configured fade, or none at all when the system asks for reduced
+/// motion.
+fn fade_settings(app: &AppHandle) -> fade::FadeSettings {
+    let mut fade = settings::current(app).fade;
+    if accessibility::prefs().reduce_motion {
+        fade.instant = true;
+    }
+    fade
+}
+
+/// Brings the app to the front so the alert takes key presses right away;
+/// focusing the window alone doesn't activate a background app on macOS.
+#[cfg(target_os = "macos")]
+pub fn activate_app(window: &WebviewWindow) {
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+
+    let _ = window.run_on_main_thread(|| {
+        // SAFETY: NSApplication is only touched on the main thread.
+        unsafe {
+            let app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
+            if let Some(app) = app.as_ref() {
+                let _: () = msg_send![app, activateIgnoringOtherApps: true];
+            }
+        }
+    });
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn activate_app(_window: &WebviewWindow) {}
+
+/// Shows the alert, counting it as cold if webviews were created since
+/// `created` (taken before the alert window was looked up).
+fn show_counted(window: &WebviewWindow, created: u64) {
+    show_alert(window);
+    ALERTS_SHOWN.fetch_add(1, Ordering::SeqCst);
+    if webviews_created() > created {
+        COLD_ALERTS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn show_alert(window: &WebviewWindow) {
+    let app = window.app_handle();
+    if let Some(monitor) = presentation::toast_monitor(app) {
+        show_toast(window, &monitor);
+        return;
+    }
+    hide_toast(app);
+    GEOMETRY.fetch_add(1, Ordering::SeqCst);
+    let fade = fade_settings(app);
+    if !window.is_visible().unwrap_or(false) {
+        fade::prepare(window, &fade);
+        displays::place_alert(window);
+    }
+    window.show().unwrap();
+    window.set_fullscreen(true).unwrap();
+    if focus::take_focus(app) {
+        activate_app(window);
+        window.set_focus().unwrap();
+    }
+    fade::fade_in(window, &fade);
+    displays::show_overlays(app);
+}
+
+/// While presenting: the toast in the corner of `monitor`, the built-in
+/// display, in place of the alert `window`, without taking focus or covering
+/// the other displays. It counts as cornered, so nagging and escalation
+/// pause.
+fn show_toast(window: &WebviewWindow, monitor: &Monitor) {
+    GEOMETRY.fetch_add(1, Ordering::SeqCst);
+    let app = window.app_handle();
+    displays::hide_overlays(app);
+    if window.is_visible().unwrap_or(false) {
+        hide_alert(window);
+    }
+    app.state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .set_cornered(true);
+    let Some(toast) = ensure_toast_window(app) else {
+        return;
+    };
+    let corner = settings::current(app).display.corner;
+    let position = displays::corner_position(monitor, CORNER_SIZE, corner, CORNER_MARGIN);
+    let _ = toast.set_size(LogicalSize::new(CORNER_SIZE.0, CORNER_SIZE.1));
+    let _ = toast.set_position(position);
+    let _ = toast.set_always_on_top(true);
+    let _ = toast.show();
+}
+
+fn hide_toast(app: &AppHandle) {
+    if let Some(toast) = app.get_webview_window(TOAST_WINDOW) {
+        let _ = toast.hide();
+    }
+}
+
+pub fn hide_alert(window: &WebviewWindow) {
+    GEOMETRY.fetch_add(1, Ordering::SeqCst);
+    let fade = fade_settings(window.app_handle());
+    displays::hide_overlays(window.app_handle());
+    fade::fade_out(window, &fade, |window| {
+        let _ = window.set_fullscreen(false);
+        let _ = window.hide();
+    });
+}
+
+/// Returns the main window, rebuilding it from the config if it was closed
+/// or its webview crashed.
+pub fn ensure_main_window(app: &AppHandle) -> Option<WebviewWindow> {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        return Some(window);
+    }
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|w| w.label == MAIN_WINDOW)?
+        .clone();
+    recreated(
+        app,
+        MAIN_WINDOW,
+        WebviewWindowBuilder::from_config(app, &config).and_then(|b| b.build()),
+    )
+}
+
+pub fn ensure_alert_window(app: &AppHandle) -> Option<WebviewWindow> {
+    if let Some(window) = app.get_webview_window(ALERT_WINDOW) {
+        return Some(window);
+    }
+    recreated(app, ALERT_WINDOW, create_alert_window(app))
+}
+
+fn ensure_toast_window(app: &AppHandle) -> Option<WebviewWindow> {
+    if let Some(window) = app.get_webview_window(TOAST_WINDOW) {
+        return Some(window);
+    }
+    recreated(app, TOAST_WINDOW, create_toast_window(app))
+}
+
+fn recreated(
+    app: &AppHandle,
+    label: &str,
+    result: tauri::Result<WebviewWindow>,
+) -> Option<WebviewWindow> {
+    match result {
+        Ok(window) => {
+            bus::publish(app, AppEvent::WindowRecreated(label.to_string()));
+            Some(window)
+        }
+        Err(e) => {
+            retention::log!("failed to recreate the {} window: {}", label, e);
+            None
+        }
+    }
+}
+
+/// Brings a showing alert back onto a connected display after the one it
+/// was on went away: fullscreen on the alert display again, or docked in the
+/// same corner of it.
+pub fn rehome_alert(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(ALERT_WINDOW) else {
+        return;
+    };
+    if !window.is_visible().unwrap_or(false) {
+        return;
+    }
+    let Ok(position) = window.outer_position() else {
+        return;
+    };
+    let on_screen = app
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .any(|m| DisplayInfo::of(m).contains(position.x, position.y));
+    if on_screen {
+        return;
+    }
+    let settings = settings::current(app).display;
+    let Some(monitor) = displays::alert_display(app, &settings) else {
+        return;
+    };
+    let generation = GEOMETRY.fetch_add(1, Ordering::SeqCst) + 1;
+    if !window.is_fullscreen().unwrap_or(false) {
+        let has_pill = app
+            .state::<SchedulerState>()
+            .0
+            .lock()
+            .unwrap()
+            .pill()
+            .is_some();
+        let (size, corner) = if has_pill {
+            (PILL_SIZE, Corner::TopRight)
+        } else {
+            (CORNER_SIZE, settings.corner)
+        };
+        let position = displays::corner_position(&monitor, size, corner, CORNER_MARGIN);
+        let _ = window.set_position(position);
+        return;
+    }
+    let _ = window.set_fullscreen(false);
+    thread::spawn(move || {
+        // Moving during the exit-fullscreen animation is undone by it.
+        thread::sleep(FULLSCREEN_EXIT);
+        if GEOMETRY.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let _ = window.set_position(*monitor.position());
+        let _ = window.set_fullscreen(true);
+    });
+}
+
+/// Used by the scheduler, which has no calling window to work with.
+pub fn raise_alert(app: &AppHandle) {
+    let created = webviews_created();
+    if let Some(window) = ensure_alert_window(app) {
+        show_counted(&window, created);
+    }
+}
+
+#[tauri::command]
+pub fn get_window_pool_stats(app: AppHandle) -> WindowPoolStats {
+    let (overlays_pooled, overlays_visible) = displays::pooled_overlays(&app);
+    WindowPoolStats {
+        alert_windows_created: ALERT_WINDOWS_CREATED.load(Ordering::SeqCst),
+        overlay_windows_created: displays::overlays_created(),
+        overlays_pooled,
+        overlays_visible,
+        alerts_shown: ALERTS_SHOWN.load(Ordering::SeqCst),
+        cold_alerts: COLD_ALERTS.load(Ordering::SeqCst),
+    }
+}
+
+pub fn set_exiting() {
+    EXITING.store(true, Ordering::SeqCst);
+}
+
+/// Brings an alert that was showing back in a new window; the frontend picks
+/// it up through `get_active_alert` when it loads.
+pub fn on_alert_destroyed(app: &AppHandle) {
+    if EXITING.load(Ordering::SeqCst) {
+        return;
+    }
+    let alert_showing = app
+        .state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .has_active_alert();
+    if alert_showing && renderer::alerts_as_notifications() {
+        renderer::hand_over_active_alert(app);
+    } else if alert_showing {
+        // The new window comes back fullscreen, not in the corner.
+        let state = app.state::<SchedulerState>();
+        state.0.lock().unwrap().set_cornered(false);
+        raise_alert(app);
+    }
+}
+
+/// Shows the alert window, optionally with an event to preview.
+#[tauri::command]
+pub fn enter_alert_mode(app: AppHandle, event: Option<Value>) {
+    let created = webviews_created();
+    let Some(window) = ensure_alert_window(&app) else {
+        return;
+    };
+    if let Some(mut event) = event {
+        if let Ok(parsed) = serde_json::from_value::<CalendarEvent>(event.clone()) {
+            let settings = settings::current(&app);
+            accessibility::announce_alert(&app, templates::speech(&settings, &parsed));
+            event["displayText"] = templates::display_text(&settings, &parsed).into();
+            let theme = theme::derive(
+                &settings.theme,
+                &parsed,
+                simulate::now(),
+                appearance::current(),
+            );
+            event["theme"] = serde_json::to_value(theme).unwrap_or_default();
+        }
+        bus::publish(&app, AppEvent::AlertPreview(event));
+    }
+    show_counted(&window, created);
+}
+
+/// Also clears the active alert (as dismissed) if the frontend left without
+/// acknowledging it, so later alerts don't queue up behind it. A meeting
+/// joined under a `pill_after_join` rule stays up as a pill instead. An alert
+/// that needs a hard dismiss stays up.
+#[tauri::command]
+pub fn exit_alert_mode(app: AppHandle) {
+    if dismissal::is_required(&app) {
+        return;
+    }
+    scheduler::acknowledge(&app, Acknowledgement::Dismissed);
+    let pill = app
+        .state::<SchedulerState>()
+        .0
+        .lock()
+        .unwrap()
+        .pill()
+        .cloned();
+    match pill {
+        Some(event) => scheduler::show_pill(&app, &event),
+        None => close_alert(&app),
+    }
+}
+
+/// Leaves fullscreen, then shrinks the alert window to `size` in a corner
+/// of its display, above everything.
+fn dock(window: WebviewWindow, size: (f64, f64), corner: Corner) {
+    let generation = GEOMETRY.fetch_add(1, Ordering::SeqCst) + 1;
+    displays::hide_overlays(window.app_handle());
+    let _ = window.set_fullscreen(false);
+    thread::spawn(move || {
+        // Resizing during the exit-fullscreen animation is undone by it.
+        thread::sleep(FULLSCREEN_EXIT);
+        if GEOMETRY.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let _ = window.set_size(LogicalSize::new(size.0, size.1));
+        if let Ok(Some(monitor)) = window.current_monitor() {
+            let position = displays::corner_position(&monitor, size, corner, CORNER_MARGIN);
+            let _ = window.set_position(position);
+        }
+        let _ = window.set_always_on_top(true);
+        let _ = window.show();
+    });
+}
+
+/// Shrinks the alert window to the pill; `scheduler::show_pill` has sent it
+/// the meeting.
+pub fn dock_pill(app: &AppHandle) {
+    if let Some(window) = ensure_alert_window(app) {
+        dock(window, PILL_SIZE, Corner::TopRight);
+    }
+}
+
+/// Moves the alert aside into a card in the configured corner. It stays the
+/// active alert, so it isn't marked handled, but nagging and escalation
+/// pause until it is restored or acknowledged.
+#[tauri::command]
+pub fn minimize_alert_to_corner(app: AppHandle) -> Result<(), String> {
+    focus::check_grace()?;
+    let state = app.state::<SchedulerState>();
+    if !state.0.lock().unwrap().set_cornered(true) {
+        return Err("No alert is showing".to_string());
+    }
+    let window = ensure_alert_window(&app).ok_or("The alert window is gone")?;
+    sound::stop();
+    bus::publish(&app, AppEvent::AlertCornered(true));
+    dock(window, CORNER_SIZE, settings::current(&app).display.corner);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn restore_alert_fullscreen(app: AppHandle) -> Result<(), String> {
+    let state = app.state::<SchedulerState>();
+    if !state.0.lock().unwrap().set_cornered(false) {
+        return Err("No alert is showing".to_string());
+    }
+    bus::publish(&app, AppEvent::AlertCornered(false));
+    raise_alert(&app);
+    Ok(())
+}
+
+/// Shows the joined meeting as a pill, or hides the pill (a click on it).
+#[tauri::command]
+pub fn set_pill_mode(app: AppHandle, enabled: bool) {
+    let state = app.state::<SchedulerState>();
+    let mut scheduler = state.0.lock().unwrap();
+    if !enabled {
+        scheduler.set_pill(None);
+        drop(scheduler);
+        close_alert(&app);
+        return;
+    }
+    let Some(event) = scheduler.pill().cloned() else {
+        return;
+    };
+    drop(scheduler);
+    scheduler::show_pill(&app, &event);
+}
+
+pub fn close_alert(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(ALERT_WINDOW) {
+        hide_alert(&window);
+    }
+    hide_toast(app);
+    bus::publish(app, AppEvent::PillMode(None));
+    bus::publish(app, AppEvent::AlertCornered(false));
+}
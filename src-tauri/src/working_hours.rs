@@ -0,0 +1,108 @@
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, NaiveTime, Weekday};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// --- Working Hours ---
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct WorkingHours {
+    /// When disabled every day counts as a full working day.
+    pub enabled: bool,
+    /// Weekdays missing from this list are no-work days.
+    pub days: Vec<WorkingDay>,
+    /// Replace the fullscreen alert with a notification for events entirely
+    /// outside working hours.
+    pub notify_outside_hours: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct WorkingDay {
+    pub weekday: Weekday,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let five = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+        let days = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]
+        .into_iter()
+        .map(|weekday| WorkingDay {
+            weekday,
+            start: nine,
+            end: five,
+        })
+        .collect();
+        WorkingHours {
+            enabled: false,
+            days,
+            notify_outside_hours: false,
+        }
+    }
+}
+
+fn local(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    date.and_time(time).and_local_timezone(Local).earliest()
+}
+
+fn start_of_day(date: NaiveDate) -> Option<DateTime<Local>> {
+    local(date, NaiveTime::MIN)
+}
+
+impl WorkingHours {
+    /// The working interval for `date`, or `None` on a no-work day.
+    pub fn hours_on(&self, date: NaiveDate) -> Option<(DateTime<Local>, DateTime<Local>)> {
+        if !self.enabled {
+            let next = date.checked_add_days(Days::new(1))?;
+            return Some((start_of_day(date)?, start_of_day(next)?));
+        }
+        let day = self.days.iter().find(|d| d.weekday == date.weekday())?;
+        if day.end <= day.start {
+            return None;
+        }
+        Some((local(date, day.start)?, local(date, day.end)?))
+    }
+
+    /// The parts of `[from, to)` that fall inside working hours.
+    pub fn clip(
+        &self,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+    ) -> Vec<(DateTime<Local>, DateTime<Local>)> {
+        let mut clipped = Vec::new();
+        let mut date = from.date_naive();
+        while date <= to.date_naive() {
+            if let Some((start, end)) = self.hours_on(date) {
+                let start = start.max(from);
+                let end = end.min(to);
+                if start < end {
+                    clipped.push((start, end));
+                }
+            }
+            match date.succ_opt() {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+        clipped
+    }
+
+    /// True if any part of the event touches working hours, so events
+    /// straddling the boundary still count.
+    pub fn overlaps(&self, start: DateTime<Local>, end: DateTime<Local>) -> bool {
+        if end <= start {
+            return self
+                .hours_on(start.date_naive())
+                .is_some_and(|(from, to)| from <= start && start < to);
+        }
+        !self.clip(start, end).is_empty()
+    }
+}
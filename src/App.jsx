@@ -1,9 +1,209 @@
 import React, { useState, useEffect } from 'react';
-import { Clock, Video, X, Bell, RefreshCw, ChevronRight } from 'lucide-react';
+import { Clock, Video, X, Bell, BellOff, RefreshCw, ChevronRight, PauseCircle, AlertTriangle, Briefcase, Users, CalendarX, Minimize2, Maximize2, Repeat, HelpCircle, StickyNote, Eye, Sparkles } from 'lucide-react';
 // This import connects React to your Rust Backend
-import { invoke } from '@tauri-apps/api/core';
+import { invoke as tauriInvoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { getCurrentWindow } from '@tauri-apps/api/window';
+
+// Icons the backend theme may name; anything else falls back to the clock.
+const THEME_ICONS = { clock: Clock, video: Video, bell: Bell, 'alert-triangle': AlertTriangle, briefcase: Briefcase, users: Users };
+
+// The backend API version this frontend was written against; sent with every
+// call so a mismatched backend refuses it up front (see api.rs).
+const API_VERSION = 1;
+// While the backend is still loading it refuses calls as `starting`; they
+// go again shortly, so a window opened at launch doesn't come up empty.
+const STARTING_RETRY_MS = 250;
+const invoke = (command, args) =>
+  tauriInvoke(command, args, { headers: { 'api-version': String(API_VERSION) } }).catch((error) => {
+    if (error?.kind !== 'starting') throw error;
+    return new Promise(resolve => setTimeout(resolve, STARTING_RETRY_MS)).then(() => invoke(command, args));
+  });
+
+// The same bundle runs in the dashboard and in the dedicated alert window.
+const isAlertWindow = getCurrentWindow().label === 'alert';
+const isOverlayWindow = getCurrentWindow().label.startsWith('overlay-');
+const isPopoverWindow = getCurrentWindow().label === 'popover';
+const isBoardWindow = getCurrentWindow().label === 'board';
+const isToastWindow = getCurrentWindow().label === 'toast';
+const POPOVER_EVENTS = 5;
+
+// Dims or blacks out a secondary display while an alert is up.
+function DisplayOverlay() {
+  const [opacity, setOpacity] = useState(0.6);
+  useEffect(() => {
+    const refresh = () => invoke('get_overlay_opacity').then(setOpacity).catch(console.error);
+    refresh();
+    // The window is kept between alerts, so the display mode may change
+    // while it is hidden.
+    const unlisteners = [listen('appearance-changed', refresh), listen('settings-changed', refresh)];
+    return () => { unlisteners.forEach(u => u.then(f => f())); };
+  }, []);
+  return <div className="fixed inset-0 bg-black" style={{ opacity }} />;
+}
+
+// The mini agenda under the menu bar icon: the next few events, joinable.
+function AgendaPopover() {
+  const [events, setEvents] = useState([]);
+  useEffect(() => {
+    const refresh = () => invoke('get_calendar_events', { limit: 50 })
+      .then(result => {
+        const now = new Date();
+        setEvents(JSON.parse(result).events
+          .filter(ev => !ev.isAllDay && new Date(ev.end) > now)
+          .slice(0, POPOVER_EVENTS));
+      })
+      .catch(console.error);
+    refresh();
+    // The window is kept hidden between clicks; it reads again when shown.
+    const unlisten = getCurrentWindow().onFocusChanged(({ payload: focused }) => focused && refresh());
+    return () => { unlisten.then(f => f()); };
+  }, []);
+  const time = (iso) => new Date(iso).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+  return (
+    <div className="h-screen flex flex-col bg-neutral-950 text-neutral-200 font-sans p-3 rounded-xl border border-neutral-800">
+      <div className="flex-1 space-y-2 overflow-y-auto">
+        {events.length === 0 && <p className="text-center py-12 text-neutral-500 text-sm">No upcoming meetings</p>}
+        {events.map(ev => (
+          <div key={ev.id + ev.start} className="flex items-center gap-3 p-3 bg-neutral-900 rounded-lg">
+            <div className="flex-1 min-w-0">
+              <h4 className="font-semibold text-sm truncate">{ev.title}</h4>
+              <p className="text-xs text-neutral-500">{time(ev.start)} – {time(ev.end)}</p>
+            </div>
+            {ev.videoLink && (
+              <button
+                onClick={() => invoke('open_link', { url: ev.videoLink }).catch(console.error)}
+                className="px-3 py-1.5 bg-white hover:bg-neutral-200 text-black rounded-lg text-xs font-bold cursor-pointer flex items-center gap-1"
+              >
+                <Video size={12} /> Join
+              </button>
+            )}
+          </div>
+        ))}
+      </div>
+      <button
+        onClick={() => invoke('open_dashboard').catch(console.error)}
+        className="mt-2 py-2 text-neutral-500 hover:text-white text-[10px] font-bold tracking-widest uppercase cursor-pointer"
+      >
+        Open Dashboard
+      </button>
+    </div>
+  );
+}
+
+// The agenda board on a spare display: today's events and a clock, or the
+// alert that is up. Never interactive; the alert is acknowledged as usual.
+function AgendaBoard() {
+  const [events, setEvents] = useState([]);
+  const [alert, setAlert] = useState(null);
+  const [now, setNow] = useState(new Date());
+  useEffect(() => {
+    const refresh = () => invoke('get_calendar_events', { limit: 200 })
+      .then(result => setEvents(JSON.parse(result).events.filter(ev => !ev.isAllDay)))
+      .catch(console.error);
+    refresh();
+    // Once on opening; after that the backend says when the alert or the
+    // calendar changes.
+    invoke('get_active_alert').then(setAlert).catch(console.error);
+    const unlisteners = [
+      listen('tick', (event) => setNow(new Date(event.payload))),
+      listen('alert-fired', (event) => setAlert(event.payload)),
+      listen('alert-escalated', (event) => setAlert(event.payload)),
+      listen('alert-cleared', () => setAlert(null)),
+      listen('calendar-updated', (event) => {
+        const { added, removed, changed } = event.payload.diff;
+        if (added.length || removed.length || changed.length) refresh();
+      }),
+    ];
+    return () => { unlisteners.forEach(u => u.then(f => f())); };
+  }, []);
+  const today = events.filter(ev => new Date(ev.start).toDateString() === now.toDateString());
+  const time = (date) => date.toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+  if (alert) {
+    const minutes = Math.round((new Date(alert.start) - now) / 60000);
+    return (
+      <div className="h-screen flex flex-col items-center justify-center bg-red-950 text-white font-sans p-12 text-center">
+        <Bell size={64} className="mb-8 animate-pulse" />
+        <h1 className="text-6xl font-bold mb-6 leading-tight">{alert.title}</h1>
+        <p className="text-3xl text-red-200">
+          {minutes > 0 ? `Starts in ${minutes} min` : minutes === 0 ? 'Starting now' : `Started ${-minutes} min ago`} · {time(new Date(alert.start))}
+        </p>
+      </div>
+    );
+  }
+  return (
+    <div className="h-screen flex flex-col bg-neutral-950 text-neutral-200 font-sans p-12">
+      <div className="text-8xl font-bold font-mono tracking-tight mb-2">{time(now)}</div>
+      <div className="text-2xl text-neutral-500 mb-10">{now.toLocaleDateString([], { weekday: 'long', month: 'long', day: 'numeric' })}</div>
+      <div className="flex-1 space-y-3 overflow-hidden">
+        {today.length === 0 && <p className="text-2xl text-neutral-600">Nothing on the calendar today</p>}
+        {today.map(ev => {
+          const past = new Date(ev.end) < now;
+          const current = !past && new Date(ev.start) <= now;
+          return (
+            <div key={ev.id + ev.start} className={`flex items-baseline gap-6 p-5 rounded-2xl ${current ? 'bg-neutral-800' : 'bg-neutral-900'} ${past ? 'opacity-40' : ''}`}>
+              <span className="text-2xl font-mono text-neutral-400">{time(new Date(ev.start))}</span>
+              <span className="text-3xl font-semibold truncate">{ev.title}</span>
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
+
+// The alert while presenting: a card on the built-in display that can only
+// acknowledge it. It gets the alert window's events; the backend shows and
+// hides it.
+function AlertToast() {
+  const [alert, setAlert] = useState(null);
+  useEffect(() => {
+    const unlisteners = [
+      listen('alert-fired', (event) => setAlert(event.payload)),
+      listen('alert-expired', () => setAlert(null)),
+    ];
+    return () => { unlisteners.forEach(u => u.then(f => f())); };
+  }, []);
+  if (!alert) return null;
+  const close = () => {
+    setAlert(null);
+    invoke('exit_alert_mode').catch(console.error);
+  };
+  // Right after the alert appears the backend ignores actions, and the
+  // toast stays up.
+  const acknowledge = (command, args) => invoke(command, args).then(close, (error) => {
+    if (error !== 'focus_grace') console.error(error);
+  });
+  const time = new Date(alert.start).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+  return (
+    <div className="fixed inset-0 flex flex-col justify-between p-4 bg-neutral-900/95 text-white rounded-2xl border border-neutral-700">
+      <span className="truncate font-semibold">{alert.displayText}</span>
+      <div className="font-mono text-xs text-neutral-400">{alert.title} · {time}</div>
+      <div className="flex gap-2">
+        {alert.videoLink && (
+          <button onClick={() => acknowledge('join_meeting', { url: alert.videoLink })} className="flex-1 py-1.5 bg-white text-black text-sm font-bold rounded-lg cursor-pointer">
+            Join
+          </button>
+        )}
+        <button onClick={() => acknowledge('snooze_alert', { minutes: 5 })} className="flex-1 py-1.5 bg-neutral-800 hover:bg-neutral-700 text-sm rounded-lg cursor-pointer">
+          Snooze
+        </button>
+        <button onClick={() => acknowledge('dismiss_alert')} className="flex-1 py-1.5 bg-neutral-800 hover:bg-neutral-700 text-sm rounded-lg cursor-pointer">
+          Dismiss
+        </button>
+      </div>
+    </div>
+  );
+}
 
 export default function App() {
+  if (isOverlayWindow) return <DisplayOverlay />;
+  if (isBoardWindow) return <AgendaBoard />;
+  if (isToastWindow) return <AlertToast />;
+  return isPopoverWindow ? <AgendaPopover /> : <Reminder />;
+}
+
+function Reminder() {
   const [meetings, setMeetings] = useState([]);
   const [upNextMeeting, setUpNextMeeting] = useState({});
   const [laterMeetings, setUpLaterMeetings] = useState([]);
@@ -11,8 +211,26 @@ export default function App() {
   const [currentTime, setCurrentTime] = useState(new Date());
   const [loading, setLoading] = useState(false);
   const [permissionError, setPermissionError] = useState(false);
-
-  const ALERT_THRESHOLD = 5000;
+  // The calendar couldn't be read: when the events shown were last synced.
+  const [staleSince, setStaleSince] = useState(null);
+  const [escalated, setEscalated] = useState(false);
+  const [queued, setQueued] = useState({ count: 0, events: [], merged: false, flood: false });
+  const [pausedUntil, setPausedUntil] = useState(null);
+  const [missingLink, setMissingLink] = useState(null);
+  const [explanation, setExplanation] = useState(null);
+  const [noteDraft, setNoteDraft] = useState(null);
+  const [meetingChange, setMeetingChange] = useState(null);
+  const [zoneChange, setZoneChange] = useState(null);
+  const [seriesChange, setSeriesChange] = useState(null);
+  const [recentMeeting, setRecentMeeting] = useState(null);
+  const [health, setHealth] = useState(null);
+  const [pill, setPill] = useState(null);
+  const [cornered, setCornered] = useState(false);
+  // A hard-dismiss challenge being answered: { prompt, answer, error }.
+  const [challenge, setChallenge] = useState(null);
+  const [appearance, setAppearance] = useState({ mode: 'dark', reduce_transparency: false });
+  // This version's release notes, once after an update: { version, items }.
+  const [whatsNew, setWhatsNew] = useState(null);
 
   // --- Helper: Extract Link ---
   const extractVideoLink = (event) => {
@@ -39,30 +257,46 @@ export default function App() {
     }
   };
 
+  const toMeeting = (ev) => {
+    const link = ev.videoLink || extractVideoLink(ev);
+    return {
+      id: ev.title + ev.start,
+      title: ev.title,
+      start: new Date(ev.start),
+      end: new Date(ev.end),
+      link: link,
+      platform: determinePlatform(link),
+      color: getPlatformColor(determinePlatform(link)),
+      isAllDay: ev.isAllDay,
+      timeToLeave: ev.timeToLeave || false,
+      recurrence: ev.recurrence || null,
+      muted: ev.muted || false,
+      startedMinutesAgo: ev.startedMinutesAgo ?? null,
+      displayText: ev.displayText || ev.title,
+      theme: ev.theme || null,
+      locationKind: ev.locationKind,
+      locationDisplay: ev.locationDisplay || null,
+      mapUrl: ev.mapUrl || null,
+      note: ev.note || null,
+      counterparts: ev.counterpartLocalTimes || [],
+      raw: ev
+    };
+  };
+
   // --- Data Fetching ---
   const fetchMeetings = async () => {
     setLoading(true);
     try {
-      const result = await invoke('get_calendar_events');
+      const result = await invoke('get_calendar_events', { limit: 200 });
       const eod = new Date();
       eod.setHours(23, 59, 59, 999);
       
-      const rawEvents = JSON.parse(result);
+      const page = JSON.parse(result);
+      const rawEvents = page.events;
+      setStaleSince(page.stale ? (page.lastSuccessAt || 'never') : null);
       
       const processed = rawEvents
-        .map(ev => {
-          const link = extractVideoLink(ev);
-          return {
-            id: ev.title + ev.start,
-            title: ev.title,
-            start: new Date(ev.start),
-            end: new Date(ev.end),
-            link: link,
-            platform: determinePlatform(link),
-            color: getPlatformColor(determinePlatform(link)),
-            isAllDay: ev.isAllDay
-          };
-        })
+        .map(toMeeting)
         .sort((a, b) => a.start - b.start)
         .filter(ev => new Date(ev.start) > new Date() && new Date(ev.start) < eod && !ev.isAllDay); 
 
@@ -83,61 +317,346 @@ export default function App() {
 
   // Initial Fetch & Auto-Refresh
   useEffect(() => {
+    if (isAlertWindow) return;
     fetchMeetings();
     const interval = setInterval(fetchMeetings, 5 * 60 * 1000);
     return () => clearInterval(interval);
   }, []);
 
-  // --- Timer & Alert Trigger ---
+  // --- Timer (the alert window follows the backend's tick instead) ---
   useEffect(() => {
-    const timer = setInterval(() => {
-      const now = new Date();
-      setCurrentTime(now);
-      checkMeetings(now);
-    }, 1000);
+    if (isAlertWindow) return;
+    const timer = setInterval(() => setCurrentTime(new Date()), 1000);
     return () => clearInterval(timer);
-  }, [meetings, activeAlert]);
+  }, []);
+
+  // --- Alerts (scheduled by the Rust backend) ---
+  // A recreated window starts blank, so ask the backend what is showing.
+  const restoreActiveAlert = async () => {
+    const active = await invoke('get_active_alert').catch(() => null);
+    if (active) setActiveAlert(toMeeting(active));
+  };
+
+  useEffect(() => {
+    if (!isAlertWindow) return;
+    const unlisteners = [
+      listen('alert-fired', (event) => {
+        setEscalated(false);
+        setCornered(false);
+        setChallenge(null);
+        setActiveAlert(toMeeting(event.payload));
+      }),
+      listen('alert-escalated', () => setEscalated(true)),
+      listen('alert-queued', (event) => setQueued(event.payload)),
+      listen('alert-expired', () => setActiveAlert(null)),
+      listen('alert-theme', (event) => setActiveAlert(a => a && { ...a, theme: event.payload })),
+      listen('window-recreated', restoreActiveAlert),
+      listen('pill-mode', (event) => setPill(event.payload)),
+      listen('alert-cornered', (event) => setCornered(event.payload)),
+      listen('tick', (event) => setCurrentTime(new Date(event.payload))),
+    ];
+    restoreActiveAlert();
+    return () => unlisteners.forEach(p => p.then(unlisten => unlisten()));
+  }, []);
+
+  // The way out of any alert: holding Escape for three seconds dismisses it,
+  // even one that wants a challenge answered. The backend times the hold.
+  useEffect(() => {
+    if (!isAlertWindow) return;
+    let timer = null;
+    const release = () => {
+      clearTimeout(timer);
+      timer = null;
+      invoke('end_escape').then(done => done && closeAlert()).catch(console.error);
+    };
+    const onKeyDown = (event) => {
+      if (event.key !== 'Escape' || event.repeat) return;
+      invoke('begin_escape').catch(console.error);
+      timer = setTimeout(release, 3000);
+    };
+    const onKeyUp = (event) => {
+      if (event.key === 'Escape' && timer) release();
+    };
+    window.addEventListener('keydown', onKeyDown);
+    window.addEventListener('keyup', onKeyUp);
+    return () => {
+      clearTimeout(timer);
+      window.removeEventListener('keydown', onKeyDown);
+      window.removeEventListener('keyup', onKeyUp);
+    };
+  }, []);
+
+  // System dark/light mode and Reduce Transparency, followed live.
+  useEffect(() => {
+    invoke('get_system_appearance').then(setAppearance).catch(console.error);
+    const unlisten = listen('appearance-changed', (event) => setAppearance(event.payload));
+    return () => { unlisten.then(f => f()); };
+  }, []);
 
-  // --- Window Mode Switching ---
+  // Meetings about to alert with no join link (dashboard only).
   useEffect(() => {
-    if (activeAlert) {
-      invoke('enter_alert_mode').catch(console.error);
-    } else {
-      invoke('exit_alert_mode').catch(console.error);
+    if (isAlertWindow) return;
+    const unlisten = listen('link-missing', (event) => setMissingLink(event.payload));
+    return () => { unlisten.then(f => f()); };
+  }, []);
+
+  // Meetings about to start that were cancelled or moved (dashboard only).
+  useEffect(() => {
+    if (isAlertWindow) return;
+    const unlisten = listen('meeting-cancelled', (event) => setMeetingChange(event.payload));
+    return () => { unlisten.then(f => f()); };
+  }, []);
+
+  // The Mac switched time zones; the backend refetches right away.
+  useEffect(() => {
+    if (isAlertWindow) return;
+    const unlisten = listen('timezone-changed', (event) => setZoneChange(event.payload));
+    return () => { unlisten.then(f => f()); };
+  }, []);
+
+  // A recurring meeting seems to have been recreated; its settings only move
+  // over if the user says so.
+  useEffect(() => {
+    if (isAlertWindow) return;
+    const unlisten = listen('series-changed', (event) => setSeriesChange(event.payload));
+    return () => { unlisten.then(f => f()); };
+  }, []);
+
+  // Picked from the tray's recent meetings: the event as it was when it alerted.
+  useEffect(() => {
+    if (isAlertWindow) return;
+    const unlisten = listen('show-recent-meeting', (event) => setRecentMeeting({ ...event.payload.event, note: event.payload.note }));
+    return () => { unlisten.then(f => f()); };
+  }, []);
+
+  // Release notes come once per version; the flags linked from them follow
+  // changes made anywhere else too.
+  useEffect(() => {
+    if (isAlertWindow) return;
+    invoke('get_whats_new').then(setWhatsNew).catch(console.error);
+    const unlisten = listen('feature-flag-changed', (event) => {
+      const { name, enabled } = event.payload;
+      setWhatsNew(notes => notes && {
+        ...notes,
+        items: notes.items.map(item => ({
+          ...item,
+          flags: item.flags.map(flag => flag.name === name ? { ...flag, enabled } : flag),
+        })),
+      });
+    });
+    return () => { unlisten.then(f => f()); };
+  }, []);
+
+  const toggleFeature = (flag) => {
+    invoke('set_feature_flag', { name: flag.name, enabled: !flag.enabled }).catch(console.error);
+  };
+
+  const adoptSeries = async () => {
+    try {
+      await invoke('adopt_series_settings', { oldId: seriesChange.oldId, newId: seriesChange.newId });
+    } catch (err) {
+      console.error(err);
     }
-  }, [activeAlert]);
+    setSeriesChange(null);
+  };
 
   useEffect(() => {
     setUpLaterMeetings(meetings.filter(ev => new Date(ev.start) > new Date() && ev.id !== upNextMeeting.id));
   }, [upNextMeeting]);
 
-  const checkMeetings = (now) => {
-    if (activeAlert) return;
-    
-    meetings.forEach(meeting => {
-      const timeDiff = meeting.start - now;
-      if (timeDiff > 0 && timeDiff < ALERT_THRESHOLD) {
-        triggerAlert(meeting);
-      }
-    });
+  // Previews the alert in the alert window; the dashboard stays as it is.
+  const triggerAlert = (meeting) => {
+    invoke('enter_alert_mode', { event: meeting.raw }).catch(console.error);
   };
 
-  const triggerAlert = (meeting) => {
-    setActiveAlert(meeting);
-    setTimeout(fetchMeetings, ALERT_THRESHOLD);
+  const closeAlert = () => {
+    setActiveAlert(null);
+    setChallenge(null);
+    invoke('exit_alert_mode').catch(console.error);
   };
+
+  // Acknowledge before closing: exit_alert_mode dismisses whatever is left.
+  // Right after the alert appears the backend ignores actions (they may be
+  // stray keystrokes), and the alert stays up.
+  const acknowledged = (promise, onError = console.error) => promise.then(closeAlert, (error) => {
+    if (error === 'focus_grace') return;
+    onError(error);
+    closeAlert();
+  });
+
+  // Alerts under a hard-dismiss rule ask for an answer first.
   const dismissAlert = () => {
-    setActiveAlert(null);
-    fetchMeetings();
+    invoke('request_dismiss', { eventId: activeAlert.raw.id })
+      .then((prompt) => {
+        if (!prompt) {
+          acknowledged(invoke('dismiss_alert'));
+          return;
+        }
+        // The corner card is too small for the challenge.
+        if (cornered) invoke('restore_alert_fullscreen').catch(console.error);
+        setChallenge({ prompt, answer: '', error: null });
+      })
+      .catch((error) => {
+        if (error !== 'focus_grace') console.error(error);
+      });
   };
-  
+
+  const confirmDismiss = (event) => {
+    event.preventDefault();
+    invoke('confirm_dismiss', { eventId: activeAlert.raw.id, answer: challenge.answer })
+      .then(closeAlert)
+      .catch((error) => setChallenge(c => c && { ...c, answer: '', error }));
+  };
+
+  const snoozeAlert = () => {
+    acknowledged(invoke('snooze_alert', { minutes: 5 }));
+  };
+
+  // Moves the alert aside without handling it; the corner card brings it back.
+  const minimizeAlert = () => {
+    invoke('minimize_alert_to_corner').catch((error) => {
+      if (error !== 'focus_grace') console.error(error);
+    });
+  };
+
   const joinMeeting = (link) => {
-    if (link) {
-      invoke('open_link', { url: link }).catch(() => window.open(link, '_blank'));
+    if (!link) {
+      closeAlert();
+      return;
+    }
+    acknowledged(invoke('join_meeting', { url: link }), () => window.open(link, '_blank'));
+  };
+
+  const toggleMute = (meeting) => {
+    const command = meeting.muted ? 'unmute_event' : 'mute_event';
+    invoke(command, { eventId: meeting.raw.id, occurrenceStart: meeting.raw.start })
+      .then(fetchMeetings)
+      .catch(console.error);
+  };
+
+  // "Why didn't it alert?": the backend's decisions for one occurrence.
+  const toggleExplanation = (meeting) => {
+    if (explanation?.id === meeting.id) {
+      setExplanation(null);
+      return;
+    }
+    invoke('explain_event', { eventId: meeting.raw.id, occurrenceStart: meeting.raw.start })
+      .then(result => setExplanation({ id: meeting.id, ...result }))
+      .catch(console.error);
+  };
+
+  // A confidential meeting comes redacted; the dashboard can ask for the rest.
+  const revealMeeting = (meeting) => {
+    invoke('reveal_event', { eventId: meeting.raw.id, occurrenceStart: meeting.raw.start })
+      .then(event => {
+        const revealed = toMeeting(event);
+        const swap = list => list.map(m => (m.id === meeting.id ? revealed : m));
+        setMeetings(swap);
+        setUpLaterMeetings(swap);
+        setUpNextMeeting(next => (next?.id === meeting.id ? revealed : next));
+      })
+      .catch(console.error);
+  };
+
+  // The user's own note for one occurrence, or for the whole series.
+  const toggleNote = (meeting) => {
+    if (noteDraft?.id === meeting.id) {
+      setNoteDraft(null);
+      return;
     }
-    dismissAlert();
+    invoke('get_event_note', { eventId: meeting.raw.id, occurrence: meeting.raw.start })
+      .then(note => setNoteDraft({
+        id: meeting.id,
+        eventId: meeting.raw.id,
+        occurrence: meeting.raw.start,
+        recurring: !!meeting.recurrence,
+        text: note?.text || '',
+        sticky: note ? note.occurrenceStart === null : false,
+        kept: note?.kept || false,
+      }))
+      .catch(console.error);
   };
 
+  const saveNote = (e) => {
+    e.preventDefault();
+    const { eventId, occurrence, text, sticky, kept } = noteDraft;
+    invoke('set_event_note', { eventId, occurrence, text, sticky, kept })
+      .then(() => setNoteDraft(null))
+      .catch(console.error);
+  };
+
+  // Called rather than rendered as a component, so the textarea keeps its
+  // focus while the dashboard re-renders.
+  const noteForm = () => (
+    <form onSubmit={saveNote} className="mx-4 mb-2 p-3 rounded-lg bg-neutral-900 text-xs text-neutral-400 space-y-2">
+      <textarea
+        autoFocus
+        rows={3}
+        value={noteDraft.text}
+        onChange={(e) => setNoteDraft({ ...noteDraft, text: e.target.value })}
+        placeholder="Shown on the alert; stays on this Mac"
+        className="w-full p-2 bg-neutral-950 border border-neutral-800 rounded-lg text-sm text-neutral-200"
+      />
+      <div className="flex items-center gap-4">
+        {noteDraft.recurring && (
+          <label className="flex items-center gap-1.5 cursor-pointer">
+            <input type="checkbox" checked={noteDraft.sticky} onChange={(e) => setNoteDraft({ ...noteDraft, sticky: e.target.checked })} />
+            Every occurrence
+          </label>
+        )}
+        <label className="flex items-center gap-1.5 cursor-pointer">
+          <input type="checkbox" checked={noteDraft.kept} onChange={(e) => setNoteDraft({ ...noteDraft, kept: e.target.checked })} />
+          Keep
+        </label>
+        <button type="submit" className="ml-auto px-3 py-1 bg-neutral-800 hover:bg-neutral-700 rounded-lg text-neutral-200 cursor-pointer">
+          Save
+        </button>
+      </div>
+    </form>
+  );
+
+  const togglePause = () => {
+    if (pausedUntil) {
+      invoke('resume_reminders').then(() => setPausedUntil(null)).catch(console.error);
+      return;
+    }
+    invoke('pause_until_free')
+      .then(until => setPausedUntil(new Date(until)))
+      .catch(console.error);
+  };
+
+  const toggleDiagnostics = () => {
+    if (health) {
+      setHealth(null);
+      return;
+    }
+    invoke('get_health').then(setHealth).catch(console.error);
+  };
+
+  // The result shows up in the health report once the dry run has fired.
+  const runSelfTest = () => {
+    invoke('run_self_test')
+      .then(() => invoke('get_health'))
+      .then(setHealth)
+      .catch(console.error);
+  };
+
+  // For a tray icon that vanished before the watchdog brought it back.
+  const rebuildTray = () => {
+    invoke('rebuild_tray')
+      .then(() => invoke('get_health'))
+      .then(setHealth)
+      .catch(console.error);
+  };
+
+  useEffect(() => {
+    if (isAlertWindow) return;
+    const unlisten = listen('self-test-finished', () => {
+      invoke('get_health').then(setHealth).catch(console.error);
+    });
+    return () => { unlisten.then(f => f()); };
+  }, []);
+
   // --- Formatting Helpers ---
   const formatTime = (date) => date.toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
   
@@ -154,52 +673,223 @@ export default function App() {
   };
 
   // --- Full Screen Alert Component (Elegant Black) ---
-  const FullScreenAlert = ({ meeting }) => (
-    <div className="fixed inset-0 z-50 flex flex-col items-center justify-center bg-neutral-950">
-      <div className="absolute inset-0 bg-gradient-to-b from-red-900/10 to-transparent pointer-events-none"></div>
-      
-      <button 
-        onClick={dismissAlert}
-        className="absolute top-8 right-8 p-3 hover:bg-neutral-800 rounded-full text-neutral-400 hover:text-white transition-colors cursor-pointer z-10"
+  const FullScreenAlert = ({ meeting }) => {
+    const theme = meeting.theme;
+    const ThemeIcon = THEME_ICONS[theme?.icon] || Clock;
+    const accent = theme ? { color: theme.accent } : undefined;
+    return (
+      <div
+        className="fixed inset-0 z-50 flex flex-col items-center justify-center bg-neutral-950"
+        style={theme ? { background: theme.background } : undefined}
+        onPointerDownCapture={() => invoke('end_focus_grace').catch(console.error)}
       >
-        <X size={32} />
-      </button>
-
-      <div className="text-center space-y-8 max-w-5xl px-6 relative z-10">
-        <Clock size={80} className="text-red-500 mx-auto mb-4 animate-bounce opacity-90" />
-        
-        <h2 className="text-sm md:text-base font-medium text-red-500 uppercase tracking-[0.3em]">Happening Now</h2>
-        
-        <h1 className="text-3xl md:text-6xl font-black text-white leading-tight tracking-tight">
-          {meeting.title}
-        </h1>
+        {!appearance.reduce_transparency && (
+          <div className="absolute inset-0 bg-gradient-to-b from-red-900/10 to-transparent pointer-events-none"></div>
+        )}
         
-        <div className="flex items-center justify-center gap-4 text-2xl md:text-3xl text-neutral-400 font-light">
-          <Video size={32} />
-          <span>{meeting.platform}</span>
-          <span className="text-neutral-700">•</span>
-          <span>{formatTime(meeting.start)}</span>
-        </div>
-
-        <div className="pt-12">
-          {meeting.link ? (
-            <button 
-              onClick={() => joinMeeting(meeting.link)}
-              className="px-12 py-6 bg-white text-black text-2xl md:text-3xl font-bold rounded-full hover:scale-105 transition-transform flex items-center gap-4 mx-auto cursor-pointer shadow-[0_0_40px_-10px_rgba(255,255,255,0.3)]"
+        <button
+          onClick={minimizeAlert}
+          title="Move to corner"
+          className="absolute top-8 right-24 p-3 hover:bg-neutral-800 rounded-full text-neutral-400 hover:text-white transition-colors cursor-pointer z-10"
+        >
+          <Minimize2 size={32} />
+        </button>
+        <button 
+          onClick={dismissAlert}
+          className="absolute top-8 right-8 p-3 hover:bg-neutral-800 rounded-full text-neutral-400 hover:text-white transition-colors cursor-pointer z-10"
+        >
+          <X size={32} />
+        </button>
+  
+        <div className="text-center space-y-8 max-w-5xl px-6 relative z-10">
+          <ThemeIcon size={80} style={accent} className="text-red-500 mx-auto mb-4 motion-safe:animate-bounce opacity-90" />
+          
+          <h2 style={accent} className="text-sm md:text-base font-medium text-red-500 uppercase tracking-[0.3em]">
+            {escalated
+              ? "You're Missing It"
+              : meeting.startedMinutesAgo > 0
+                ? `Started ${meeting.startedMinutesAgo} min ago`
+                : meeting.timeToLeave ? "Time To Leave" : "Happening Now"}
+          </h2>
+          
+          <h1
+            style={theme?.foreground ? { color: theme.foreground } : undefined}
+            className="text-3xl md:text-6xl font-black text-white leading-tight tracking-tight"
+          >
+            {meeting.displayText}
+          </h1>
+          
+          <div className="flex items-center justify-center gap-4 text-2xl md:text-3xl text-neutral-400 font-light">
+            <Video size={32} />
+            <span>{meeting.platform}</span>
+            <span className="text-neutral-700">•</span>
+            <span>{formatTime(meeting.start)}</span>
+          </div>
+          {meeting.locationDisplay && meeting.locationKind !== 'url' && (
+            meeting.mapUrl ? (
+              <button
+                onClick={() => invoke('open_map', { eventId: meeting.raw.id }).catch(console.error)}
+                className="text-neutral-400 hover:text-white text-xl underline underline-offset-4 cursor-pointer"
+              >
+                {meeting.locationDisplay}
+              </button>
+            ) : (
+              <div className="text-neutral-400 text-xl">{meeting.locationDisplay}</div>
+            )
+          )}
+          {meeting.counterparts.length > 0 && (
+            <div className="text-neutral-500 text-lg">
+              {meeting.counterparts.map((c, i) => (
+                <span key={c.email}>
+                  {i > 0 && <span className="text-neutral-700"> • </span>}
+                  <span className={c.outsideHours ? 'text-amber-400/80' : undefined}>
+                    {c.localTime}{c.dayOffset > 0 ? ' tomorrow' : c.dayOffset < 0 ? ' yesterday' : ''}
+                  </span>
+                  {' '}for {c.name}
+                </span>
+              ))}
+            </div>
+          )}
+          {meeting.note && (
+            <div className="max-w-2xl mx-auto px-6 py-4 bg-neutral-900/80 border border-neutral-800 rounded-2xl text-left text-xl text-neutral-200 whitespace-pre-wrap">
+              {meeting.note}
+            </div>
+          )}
+          {queued.count > 0 && (
+            queued.merged ? (
+              <div className="text-neutral-400 text-lg space-y-1">
+                {queued.flood && (
+                  <div className="text-neutral-500 text-sm uppercase tracking-wide">
+                    {queued.count + 1} alerts at once, collapsed into this one
+                  </div>
+                )}
+                {queued.events.map(ev => (
+                  <div key={ev.id + ev.start} className="flex items-center justify-center gap-3">
+                    <span>Also: {ev.title} at {formatTime(new Date(ev.start))}</span>
+                    {queued.flood && (
+                      <button
+                        onClick={() => invoke('mute_event', { eventId: ev.id, occurrenceStart: ev.start }).catch(console.error)}
+                        className="text-neutral-500 hover:text-white text-sm underline underline-offset-4 cursor-pointer"
+                      >
+                        Mute
+                      </button>
+                    )}
+                  </div>
+                ))}
+              </div>
+            ) : (
+              <span className="inline-block px-3 py-1 rounded-full bg-red-900/40 text-red-300 text-sm font-bold">
+                +{queued.count} more
+              </span>
+            )
+          )}
+          {meeting.recurrence && (
+            <div className="text-neutral-500 text-lg">{meeting.recurrence}</div>
+          )}
+  
+          <div className="pt-12">
+            {meeting.link ? (
+              <button 
+                onClick={() => joinMeeting(meeting.link)}
+                className="px-12 py-6 bg-white text-black text-2xl md:text-3xl font-bold rounded-full hover:scale-105 transition-transform flex items-center gap-4 mx-auto cursor-pointer shadow-[0_0_40px_-10px_rgba(255,255,255,0.3)]"
+              >
+                <Video className="w-8 h-8" /> JOIN CALL
+              </button>
+            ) : (
+              <div className="text-neutral-500 text-xl font-medium">No video link found</div>
+            )}
+            <button
+              onClick={snoozeAlert}
+              className="mt-8 text-neutral-500 hover:text-white text-lg font-medium transition-colors cursor-pointer"
             >
-              <Video className="w-8 h-8" /> JOIN CALL
+              Snooze 5 min
             </button>
-          ) : (
-            <div className="text-neutral-500 text-xl font-medium">No video link found</div>
-          )}
+          </div>
         </div>
       </div>
+    );
+  };
+
+  // After joining: the title and time left, until the meeting ends or a click.
+  const MeetingPill = ({ meeting }) => {
+    const left = Math.max(0, Math.ceil((new Date(meeting.end) - currentTime) / 60000));
+    return (
+      <button
+        onClick={() => invoke('set_pill_mode', { enabled: false }).catch(console.error)}
+        className="fixed inset-0 flex items-center gap-3 px-5 bg-neutral-900/95 text-white rounded-full border border-neutral-700 cursor-pointer text-left"
+      >
+        <Video size={18} className="text-red-500 shrink-0" />
+        <span className="flex-1 truncate font-semibold text-sm">{meeting.title}</span>
+        <span className="font-mono text-xs text-neutral-400">{left} min left</span>
+      </button>
+    );
+  };
+
+  // The alert moved aside: still pending, with a way back and the actions.
+  const CornerAlert = ({ meeting }) => (
+    <div className="fixed inset-0 flex flex-col justify-between p-4 bg-neutral-900/95 text-white rounded-2xl border border-neutral-700">
+      <div className="flex items-start gap-2">
+        <span className="flex-1 truncate font-semibold">{meeting.displayText}</span>
+        <button
+          onClick={() => invoke('restore_alert_fullscreen').catch(console.error)}
+          title="Back to fullscreen"
+          className="p-1 text-neutral-400 hover:text-white cursor-pointer"
+        >
+          <Maximize2 size={16} />
+        </button>
+      </div>
+      <div className="font-mono text-xs text-neutral-400">{meeting.title} · {formatTime(meeting.start)}</div>
+      <div className="flex gap-2">
+        {meeting.link && (
+          <button onClick={() => joinMeeting(meeting.link)} className="flex-1 py-1.5 bg-white text-black text-sm font-bold rounded-lg cursor-pointer">
+            Join
+          </button>
+        )}
+        <button onClick={dismissAlert} className="flex-1 py-1.5 bg-neutral-800 hover:bg-neutral-700 text-sm rounded-lg cursor-pointer">
+          Dismiss
+        </button>
+      </div>
     </div>
   );
 
+  // Called rather than rendered as a component, so the input keeps its focus
+  // across the per-second re-renders.
+  const challengeForm = () => (
+    <form
+      onSubmit={confirmDismiss}
+      className="fixed inset-0 z-[60] flex flex-col items-center justify-center gap-4 p-4 bg-neutral-950/95 text-white"
+    >
+      <div className="text-lg font-semibold text-center">{challenge.prompt.prompt}</div>
+      {challenge.prompt.code && (
+        <div className="font-mono text-4xl font-black tracking-widest">{challenge.prompt.code}</div>
+      )}
+      <input
+        autoFocus
+        value={challenge.answer}
+        onChange={(e) => setChallenge({ ...challenge, answer: e.target.value })}
+        className="w-40 px-3 py-2 bg-neutral-900 border border-neutral-700 rounded-lg text-center font-mono"
+      />
+      {challenge.error && <div className="text-red-400 text-sm">{challenge.error}</div>}
+      <div className="flex gap-3 text-sm">
+        <button type="submit" className="px-4 py-1.5 bg-neutral-800 hover:bg-neutral-700 rounded-lg cursor-pointer">
+          Dismiss
+        </button>
+        <button type="button" onClick={() => setChallenge(null)} className="px-4 py-1.5 text-neutral-400 hover:text-white cursor-pointer">
+          Back
+        </button>
+      </div>
+    </form>
+  );
+
+  if (isAlertWindow) {
+    if (activeAlert && challenge) return challengeForm();
+    if (activeAlert && cornered) return <CornerAlert meeting={activeAlert} />;
+    if (activeAlert) return <FullScreenAlert meeting={activeAlert} />;
+    return pill ? <MeetingPill meeting={pill} /> : null;
+  }
+
   return (
     <div className="min-h-screen bg-neutral-950 text-white font-sans selection:bg-red-900 selection:text-white">
-      {activeAlert && <FullScreenAlert meeting={activeAlert} />}
 
       <div className="max-w-md mx-auto p-6">
         {/* Header */}
@@ -211,13 +901,23 @@ export default function App() {
               </div>
               <h1 className="text-xl font-bold text-white tracking-tight">In Your Face Reminder</h1>
             </div>
-            <button 
-              onClick={fetchMeetings} 
-              disabled={loading}
-              className={`p-2 hover:bg-neutral-900 rounded-lg transition-colors cursor-pointer text-neutral-500 hover:text-white ${loading ? 'animate-spin' : ''}`}
-            >
-              <RefreshCw size={18} />
-            </button>
+            <div className="flex items-center gap-1">
+              <button
+                onClick={togglePause}
+                title={pausedUntil ? 'Resume reminders' : 'Pause until free'}
+                className={`flex items-center gap-1.5 p-2 hover:bg-neutral-900 rounded-lg transition-colors cursor-pointer text-xs font-medium ${pausedUntil ? 'text-amber-400' : 'text-neutral-500 hover:text-white'}`}
+              >
+                <PauseCircle size={18} />
+                {pausedUntil && <span>Paused until {formatTime(pausedUntil)}</span>}
+              </button>
+              <button 
+                onClick={fetchMeetings} 
+                disabled={loading}
+                className={`p-2 hover:bg-neutral-900 rounded-lg transition-colors cursor-pointer text-neutral-500 hover:text-white ${loading ? 'animate-spin' : ''}`}
+              >
+                <RefreshCw size={18} />
+              </button>
+            </div>
           </div>
           {/* Subtitle */}
           <p className="text-neutral-500 text-xs font-medium tracking-wide ml-1">
@@ -231,6 +931,126 @@ export default function App() {
           </div>
         )}
 
+        {staleSince && (
+          <div className="mb-6 p-4 bg-amber-950/30 border border-amber-900/50 rounded-xl text-amber-200 text-sm flex items-center gap-3">
+            <RefreshCw size={18} className="shrink-0" />
+            <span className="flex-1">
+              {staleSince === 'never'
+                ? "Couldn't read your calendar yet — retrying"
+                : <>Couldn't read your calendar — showing events from <strong>{formatTime(new Date(staleSince))}</strong>, retrying</>}
+            </span>
+          </div>
+        )}
+
+        {missingLink && (
+          <div className="mb-6 p-4 bg-amber-950/30 border border-amber-900/50 rounded-xl text-amber-200 text-sm flex items-center gap-3">
+            <AlertTriangle size={18} className="shrink-0" />
+            <span className="flex-1">
+              No join link found for <strong>'{missingLink.title}'</strong> — check the invite
+            </span>
+            <button
+              onClick={() => invoke('open_in_calendar', { eventId: missingLink.id }).catch(console.error)}
+              className="px-3 py-1.5 bg-amber-900/50 hover:bg-amber-900 rounded-lg text-xs font-semibold cursor-pointer"
+            >
+              Open in Calendar
+            </button>
+            <button onClick={() => setMissingLink(null)} className="p-1 hover:text-white cursor-pointer">
+              <X size={16} />
+            </button>
+          </div>
+        )}
+
+        {zoneChange && (
+          <div className="mb-6 p-4 bg-neutral-900 border border-neutral-800 rounded-xl text-neutral-200 text-sm flex items-center gap-3">
+            <Clock size={18} className="shrink-0" />
+            <span className="flex-1">Times updated for <strong>{zoneChange.to.name}</strong></span>
+            <button onClick={() => setZoneChange(null)} className="p-1 hover:text-white cursor-pointer">
+              <X size={16} />
+            </button>
+          </div>
+        )}
+
+        {whatsNew && (
+          <div className="mb-6 p-4 bg-neutral-900 border border-neutral-800 rounded-xl text-neutral-200 text-sm space-y-3">
+            <div className="flex items-center gap-3">
+              <Sparkles size={18} className="shrink-0" />
+              <span className="flex-1 font-semibold">What's new in {whatsNew.version}</span>
+              <button onClick={() => setWhatsNew(null)} className="p-1 hover:text-white cursor-pointer">
+                <X size={16} />
+              </button>
+            </div>
+            {whatsNew.items.map(item => (
+              <div key={item.title}>
+                <div className="font-semibold">{item.title}</div>
+                <div className="text-neutral-400">{item.body}</div>
+                {item.flags.map(flag => (
+                  <label key={flag.name} className="mt-1 flex items-center gap-2 text-xs text-neutral-400 cursor-pointer">
+                    <input type="checkbox" checked={flag.enabled} onChange={() => toggleFeature(flag)} />
+                    {flag.description}
+                  </label>
+                ))}
+              </div>
+            ))}
+          </div>
+        )}
+
+        {recentMeeting && (
+          <div className="mb-6 p-4 bg-neutral-900 border border-neutral-800 rounded-xl text-neutral-200 text-sm space-y-2">
+            <div className="flex items-center gap-3">
+              <span className="flex-1 font-semibold">{recentMeeting.title}</span>
+              <span className="font-mono text-xs text-neutral-500">{formatTime(new Date(recentMeeting.start))}</span>
+              <button onClick={() => setRecentMeeting(null)} className="p-1 hover:text-white cursor-pointer">
+                <X size={16} />
+              </button>
+            </div>
+            {recentMeeting.videoLink && (
+              <button
+                onClick={() => invoke('open_link', { url: recentMeeting.videoLink }).catch(console.error)}
+                className="text-red-400 hover:text-red-300 underline underline-offset-4 cursor-pointer break-all text-left"
+              >
+                {recentMeeting.videoLink}
+              </button>
+            )}
+            {recentMeeting.attendees.length > 0 && (
+              <div className="text-neutral-400">
+                {recentMeeting.attendees.map(a => a.name || a.email).filter(Boolean).join(', ')}
+              </div>
+            )}
+            {recentMeeting.note && (
+              <div className="text-neutral-200 whitespace-pre-wrap">{recentMeeting.note}</div>
+            )}
+            {recentMeeting.description && (
+              <div className="text-neutral-500 whitespace-pre-wrap max-h-40 overflow-y-auto">{recentMeeting.description}</div>
+            )}
+          </div>
+        )}
+
+        {seriesChange && (
+          <div className="mb-6 p-4 bg-neutral-900 border border-neutral-800 rounded-xl text-neutral-200 text-sm flex items-center gap-3">
+            <Repeat size={18} className="shrink-0" />
+            <span className="flex-1">{seriesChange.message}</span>
+            <button
+              onClick={adoptSeries}
+              className="px-3 py-1.5 bg-neutral-800 hover:bg-neutral-700 rounded-lg text-xs font-semibold cursor-pointer"
+            >
+              Keep settings
+            </button>
+            <button onClick={() => setSeriesChange(null)} className="p-1 hover:text-white cursor-pointer">
+              <X size={16} />
+            </button>
+          </div>
+        )}
+
+        {meetingChange && (
+          <div className="mb-6 p-4 bg-neutral-900 border border-neutral-800 rounded-xl text-neutral-200 text-sm flex items-center gap-3">
+            <CalendarX size={18} className="shrink-0" />
+            <span className="flex-1">{meetingChange.message}</span>
+            <button onClick={() => setMeetingChange(null)} className="p-1 hover:text-white cursor-pointer">
+              <X size={16} />
+            </button>
+          </div>
+        )}
+
         <div className="space-y-6">
           {meetings.length === 0 ? (
             <div className="text-center py-12">
@@ -262,6 +1082,15 @@ export default function App() {
                       <span className="bg-neutral-800 px-2 py-0.5 rounded text-xs text-neutral-300">{formatTime(upNextMeeting.start)}</span>
                       <span>•</span>
                       <span>{upNextMeeting.platform}</span>
+                      {upNextMeeting.raw?.redacted && (
+                        <button
+                          onClick={() => revealMeeting(upNextMeeting)}
+                          title="Show the details this calendar hides"
+                          className="p-1 text-neutral-600 hover:text-white cursor-pointer transition-colors"
+                        >
+                          <Eye size={14} />
+                        </button>
+                      )}
                     </div>
                   </div>
 
@@ -280,12 +1109,61 @@ export default function App() {
                   <h3 className="text-neutral-500 font-bold text-[10px] mb-4 px-1 tracking-widest uppercase">Later Today</h3>
                   <div className="space-y-2">
                     {laterMeetings.map((meeting) => (
-                      <div key={meeting.id} className="flex items-center gap-4 p-4 bg-neutral-900/50 hover:bg-neutral-900 rounded-xl border border-transparent hover:border-neutral-800 transition-all group">
+                      <div key={meeting.id}>
+                      <div className="flex items-center gap-4 p-4 bg-neutral-900/50 hover:bg-neutral-900 rounded-xl border border-transparent hover:border-neutral-800 transition-all group">
                         <div className={`w-1 h-8 rounded-full opacity-50 group-hover:opacity-100 transition-opacity ${meeting.color}`}></div>
                         <div className="flex-1 min-w-0">
                           <h4 className="font-semibold text-neutral-300 group-hover:text-white truncate transition-colors">{meeting.title}</h4>
                           <p className="text-xs text-neutral-500 mt-0.5">{formatTime(meeting.start)} • {meeting.platform}</p>
                         </div>
+                        {meeting.raw.redacted && (
+                          <button
+                            onClick={() => revealMeeting(meeting)}
+                            title="Show the details this calendar hides"
+                            className="p-2 text-neutral-600 hover:text-white rounded-lg cursor-pointer transition-colors"
+                          >
+                            <Eye size={16} />
+                          </button>
+                        )}
+                        <button
+                          onClick={() => toggleMute(meeting)}
+                          title={meeting.muted ? "Alert for this meeting" : "Don't alert for this meeting"}
+                          className="p-2 text-neutral-600 hover:text-white rounded-lg cursor-pointer transition-colors"
+                        >
+                          {meeting.muted ? <BellOff size={16} /> : <Bell size={16} />}
+                        </button>
+                        <button
+                          onClick={() => toggleNote(meeting)}
+                          title="Note for this meeting"
+                          className="p-2 text-neutral-600 hover:text-white rounded-lg cursor-pointer transition-colors"
+                        >
+                          <StickyNote size={16} />
+                        </button>
+                        <button
+                          onClick={() => toggleExplanation(meeting)}
+                          title="How this meeting's alert is decided"
+                          className="p-2 text-neutral-600 hover:text-white rounded-lg cursor-pointer transition-colors"
+                        >
+                          <HelpCircle size={16} />
+                        </button>
+                      </div>
+                      {noteDraft?.id === meeting.id && noteForm()}
+                      {explanation?.id === meeting.id && (
+                        <div className="mx-4 mb-2 p-3 rounded-lg bg-neutral-900 text-xs text-neutral-400 space-y-1">
+                          {explanation.steps.map((step, i) => (
+                            <div key={i} className={step.outcome === 'suppressed' ? 'text-red-300' : step.outcome === 'changed' ? 'text-neutral-200' : ''}>
+                              <span className="font-mono text-neutral-500">{step.rule}</span> {step.detail}
+                            </div>
+                          ))}
+                          <div className="pt-1 font-semibold text-neutral-300">
+                            {explanation.suppressed
+                              ? `Won't alert: ${explanation.suppressed}`
+                              : explanation.alertAt
+                                ? `Alerts at ${formatTime(new Date(explanation.alertAt))}`
+                                : "Won't alert"}
+                          </div>
+                        </div>
+                      )}
                       </div>
                     ))}
                   </div>
@@ -301,6 +1179,41 @@ export default function App() {
             </>
           )}
         </div>
+
+        {/* Diagnostics (masked; safe to paste into bug reports) */}
+        <footer className="mt-10">
+          <button
+            onClick={toggleDiagnostics}
+            className="text-neutral-600 hover:text-neutral-300 text-[10px] font-bold tracking-widest uppercase cursor-pointer"
+          >
+            {health ? 'Hide Diagnostics' : 'Diagnostics'}
+          </button>
+          {health && (
+            <div className="mt-3 p-4 bg-neutral-900/50 rounded-xl border border-neutral-800">
+              <div className="flex justify-end gap-2 mb-2">
+                <button
+                  onClick={runSelfTest}
+                  className="px-3 py-1.5 bg-neutral-800 hover:bg-neutral-700 rounded-lg text-xs font-semibold text-neutral-300 cursor-pointer"
+                >
+                  Run Self-Test
+                </button>
+                <button
+                  onClick={rebuildTray}
+                  className="px-3 py-1.5 bg-neutral-800 hover:bg-neutral-700 rounded-lg text-xs font-semibold text-neutral-300 cursor-pointer"
+                >
+                  Rebuild Tray Icon
+                </button>
+                <button
+                  onClick={() => navigator.clipboard.writeText(JSON.stringify(health, null, 2))}
+                  className="px-3 py-1.5 bg-neutral-800 hover:bg-neutral-700 rounded-lg text-xs font-semibold text-neutral-300 cursor-pointer"
+                >
+                  Copy JSON
+                </button>
+              </div>
+              <pre className="text-[11px] text-neutral-400 overflow-x-auto">{JSON.stringify(health, null, 2)}</pre>
+            </div>
+          )}
+        </footer>
       </div>
     </div>
   );
@@ -0,0 +1,2508 @@
+// Generated by `cargo api-types` from the command payloads in
+// src-tauri/src/api.rs; don't edit.
+
+export const API_VERSION = 1;
+export const MIN_CLIENT_VERSION = 1;
+
+export type AccessibilityPrefs = {
+  reduce_motion: boolean;
+  reduce_transparency: boolean;
+};
+
+export type Action = {
+  args: ArgSpec[];
+  /**
+   * Can be a step of an alert rule's `on_acknowledge` or `on_join`.
+   */
+  chainable: boolean;
+  description: string;
+  name: string;
+};
+
+export type ActionError = {
+  detail: string;
+  kind: "unknown_action";
+} | {
+  detail: string;
+  kind: "invalid_args";
+} | {
+  detail: string;
+  kind: "failed";
+} | {
+  detail: string;
+  kind: "unexpected_caller";
+};
+
+/**
+ * One action of an alert rule's chain.
+ */
+export type ActionStep = {
+  action: string;
+  args?: unknown;
+};
+
+export type AgendaDay = {
+  allDay: CalendarEvent[];
+  /**
+   * Time covered by at least one timed event, overlaps counted once.
+   */
+  busyMinutes: number;
+  conflicts: Conflict[];
+  date: string;
+  /**
+   * 1440, except on DST changes (1380 or 1500).
+   */
+  dayMinutes: number;
+  events: CalendarEvent[];
+  /**
+   * The date in the configured style, e.g. "Tue 14 Oct".
+   */
+  label: string;
+};
+
+export type AlertRule = {
+  /**
+   * Matches if any attendee has an address at this domain.
+   */
+  attendee_domain?: string | null;
+  /**
+   * Created by lead-time auto-tuning; deleting it reverts the change.
+   */
+  auto_tuned?: boolean;
+  /**
+   * Calendar title to match, or a pattern as in `calendar_matches`;
+   * `None` matches every calendar.
+   */
+  calendar?: string | null;
+  /**
+   * Count in the last seconds before the start, or don't.
+   */
+  count_in?: boolean | null;
+  /**
+   * How much of the event shows, instead of its calendar's policy.
+   */
+  display_policy?: DisplayPolicy | null;
+  /**
+   * Dismiss only after answering this challenge.
+   */
+  hard_dismiss?: DismissChallenge | null;
+  id?: string;
+  /**
+   * Alert normally even outside working hours and on days off (e.g. a
+   * Saturday one-off).
+   */
+  ignore_working_hours?: boolean;
+  lead_time_seconds?: number | null;
+  /**
+   * Actions to run, in order, after the alert is dismissed.
+   */
+  on_acknowledge?: ActionStep[] | null;
+  /**
+   * Actions to run, in order, after joining from the alert.
+   */
+  on_join?: ActionStep[] | null;
+  /**
+   * Organizer email ("boss@corp.com") or domain ("@bigclient.com").
+   */
+  organizer?: string | null;
+  /**
+   * After joining, shrink the alert to a corner pill with the time left.
+   */
+  pill_after_join?: boolean | null;
+  /**
+   * Event id of one recurring series (its occurrences share it).
+   */
+  series_id?: string | null;
+  /**
+   * System sound to play instead of the default one.
+   */
+  sound?: string | null;
+  /**
+   * Replaces the single sound with steps that get more insistent.
+   */
+  sound_profile?: SoundStage[] | null;
+  /**
+   * Where the sounds play, instead of the route in the settings.
+   */
+  sound_route?: AudioRoute | null;
+  style?: AlertStyle | null;
+  /**
+   * Case-insensitive substring of the event title.
+   */
+  title_contains?: string | null;
+};
+
+export type AlertStyle = "fullscreen" | "notification" | "silent";
+
+export type AlertTheme = {
+  accent: string;
+  /**
+   * Any CSS color.
+   */
+  background: string;
+  /**
+   * Heading color; `None` keeps the alert's white.
+   */
+  foreground?: string | null;
+  /**
+   * A lucide icon name, e.g. "clock" or "briefcase".
+   */
+  icon?: string | null;
+};
+
+export type ApiManifest = {
+  /**
+   * The types the payload schemas refer to (`#/$defs/<name>`).
+   */
+  $defs: { [key: string]: unknown };
+  appVersion: string;
+  commands: string[];
+  minClientVersion: number;
+  payloads: CommandPayload[];
+  version: number;
+};
+
+export type ApiVersion = {
+  minClientVersion: number;
+  version: number;
+};
+
+export type ArgSpec = {
+  description: string;
+  name: string;
+  required: boolean;
+} & ({
+  max: number;
+  min: number;
+  type: "integer";
+});
+
+export type ArtifactUsage = {
+  bytes: number;
+  /**
+   * File or directory name in the app data dir.
+   */
+  name: string;
+};
+
+export type AttendanceRange = {
+  from: string;
+  to: string;
+};
+
+export type AttendanceSettings = {
+  enabled?: boolean;
+  /**
+   * Sessions shorter than this, after merging, are left out of the
+   * export as false positives.
+   */
+  min_session_seconds?: number;
+};
+
+export type Attendee = {
+  email?: string | null;
+  isCurrentUser?: boolean;
+  name?: string | null;
+  /**
+   * accepted, declined, tentative, pending or unknown.
+   */
+  status: string;
+};
+
+export type AudioRoute = "follow_system" | "built_in" | "both";
+
+export type AutoTuneSettings = {
+  enabled?: boolean;
+  /**
+   * Most minutes added on top of the series' usual lead time.
+   */
+  max_extra_minutes?: number;
+  /**
+   * Lead time is never raised beyond this.
+   */
+  max_lead_minutes?: number;
+  /**
+   * Consecutive on-time occurrences that undo the adjustment.
+   */
+  reset_after_on_time?: number;
+};
+
+export type BoardSettings = {
+  /**
+   * Name of the display the board covers.
+   */
+  display?: string | null;
+  enabled?: boolean;
+};
+
+export type BudgetSettings = {
+  /**
+   * Meeting hours per day; `None` turns budgeting off.
+   */
+  daily_hours?: number | null;
+};
+
+export type BudgetStatus = {
+  dailyHours?: number | null;
+  today?: DayUsage | null;
+  tomorrow?: DayUsage | null;
+};
+
+export type BulkItemResult = {
+  /**
+   * Why the item is invalid; `None` if it is fine.
+   */
+  error?: string | null;
+  /**
+   * The item's place in the request.
+   */
+  index: number;
+  /**
+   * The calendars, series or subscription the item picked.
+   */
+  matched: string[];
+  /**
+   * The rules the item saved, or would have saved.
+   */
+  ruleIds: string[];
+};
+
+export type BulkMode = "all_or_nothing" | "skip_invalid";
+
+export type BulkReport = {
+  /**
+   * Whether anything was written.
+   */
+  applied: boolean;
+  items: BulkItemResult[];
+};
+
+/**
+ * The poll interval in effect and why; in the health report.
+ */
+export type Cadence = {
+  /**
+   * The alert or event boundary it is tightening for, if any is near.
+   */
+  nextBoundary?: string | null;
+  reason: CadenceReason;
+  seconds: number;
+};
+
+export type CadenceReason = "near_boundary" | "working_hours" | "off_hours" | "fixed" | "low_power";
+
+export type CalendarDurations = {
+  calendar?: string;
+  max_duration_minutes?: number | null;
+  min_duration_minutes?: number | null;
+};
+
+export type CalendarEvent = {
+  /**
+   * The event's own alarms, in seconds before the start.
+   */
+  alarmOffsets?: number[];
+  attendees?: Attendee[];
+  /**
+   * Title of the calendar the event belongs to.
+   */
+  calendar?: string;
+  /**
+   * EKCalendar type: local, caldav, exchange, subscription, birthday.
+   */
+  calendarType?: string;
+  description?: string | null;
+  end: string;
+  /**
+   * Why the event won't alert; only filled in for debugging.
+   */
+  filteredReason?: string | null;
+  /**
+   * The location carries map coordinates (picked from Maps in Calendar).
+   */
+  hasCoordinates?: boolean;
+  id?: string;
+  isAllDay: boolean;
+  /**
+   * Marked as free time rather than busy.
+   */
+  isFree?: boolean;
+  /**
+   * Long enough not to count as a meeting; see `long_events`.
+   */
+  isLongBlock?: boolean;
+  location?: string | null;
+  /**
+   * The location for display; room names lose their capacity/AV tags.
+   */
+  locationDisplay?: string | null;
+  locationKind?: LocationKind;
+  /**
+   * Apple Maps link when the location is an address.
+   */
+  mapUrl?: string | null;
+  /**
+   * The user muted this occurrence from the dashboard.
+   */
+  muted?: boolean;
+  /**
+   * Organizer email address.
+   */
+  organizer?: string | null;
+  /**
+   * The event's calendar doesn't allow modifications.
+   */
+  readOnly?: boolean;
+  /**
+   * e.g. "Weekly on Tue, Thu"; `None` for one-off events.
+   */
+  recurrence?: string | null;
+  /**
+   * Rule components as EventKit reports them; see `recurrence`.
+   */
+  recurrenceRule?: Recurrence | null;
+  /**
+   * Shown under a confidential display policy; see `confidential`.
+   */
+  redacted?: boolean;
+  relativeStart?: string | null;
+  /**
+   * Snapshots taken when the event was returned; the `tick` event is what
+   * keeps a countdown live.
+   */
+  secondsUntilStart?: number | null;
+  start: string;
+  /**
+   * The IANA zone the event was created in, e.g. by its organizer.
+   */
+  timeZone?: string | null;
+  title: string;
+  url?: string | null;
+  /**
+   * Video call link found in the event; kept when the notes are pruned.
+   */
+  videoLink?: string | null;
+};
+
+/**
+ * Everything known about one event, for the alert and the detail pane.
+ */
+export type CalendarEventDetails = {
+  /**
+   * The event's own alarms, in seconds before the start.
+   */
+  alarmOffsets?: number[];
+  attendees?: Attendee[];
+  /**
+   * Title of the calendar the event belongs to.
+   */
+  calendar?: string;
+  /**
+   * EKCalendar type: local, caldav, exchange, subscription, birthday.
+   */
+  calendarType?: string;
+  /**
+   * The start for attendees in other zones; see `counterparts`.
+   */
+  counterpartLocalTimes: CounterpartTime[];
+  description?: string | null;
+  end: string;
+  /**
+   * Why the event won't alert; only filled in for debugging.
+   */
+  filteredReason?: string | null;
+  /**
+   * The location carries map coordinates (picked from Maps in Calendar).
+   */
+  hasCoordinates?: boolean;
+  id?: string;
+  isAllDay: boolean;
+  /**
+   * This occurrence was edited separately from its series.
+   */
+  isDetached: boolean;
+  /**
+   * Marked as free time rather than busy.
+   */
+  isFree?: boolean;
+  /**
+   * Long enough not to count as a meeting; see `long_events`.
+   */
+  isLongBlock?: boolean;
+  location?: string | null;
+  /**
+   * The location for display; room names lose their capacity/AV tags.
+   */
+  locationDisplay?: string | null;
+  locationKind?: LocationKind;
+  /**
+   * Apple Maps link when the location is an address.
+   */
+  mapUrl?: string | null;
+  /**
+   * The user muted this occurrence from the dashboard.
+   */
+  muted?: boolean;
+  /**
+   * Organizer email address.
+   */
+  organizer?: string | null;
+  /**
+   * The event's calendar doesn't allow modifications.
+   */
+  readOnly?: boolean;
+  /**
+   * e.g. "Weekly on Tue, Thu"; `None` for one-off events.
+   */
+  recurrence?: string | null;
+  /**
+   * Rule components as EventKit reports them; see `recurrence`.
+   */
+  recurrenceRule?: Recurrence | null;
+  /**
+   * Shown under a confidential display policy; see `confidential`.
+   */
+  redacted?: boolean;
+  relativeStart?: string | null;
+  /**
+   * Snapshots taken when the event was returned; the `tick` event is what
+   * keeps a countdown live.
+   */
+  secondsUntilStart?: number | null;
+  start: string;
+  /**
+   * The IANA zone the event was created in, e.g. by its organizer.
+   */
+  timeZone?: string | null;
+  title: string;
+  url?: string | null;
+  /**
+   * Video call link found in the event; kept when the notes are pruned.
+   */
+  videoLink?: string | null;
+};
+
+/**
+ * Which calendars to hide: the explicit list plus auto-detected noise.
+ */
+export type CalendarFilter = {
+  /**
+   * Exclude birthday calendars and subscribed holiday calendars.
+   */
+  auto_exclude?: boolean;
+  /**
+   * Per-calendar limits, overriding the global ones.
+   */
+  durations?: CalendarDurations[];
+  /**
+   * Exclude timed events whose end equals their start.
+   */
+  exclude_zero_length?: boolean;
+  excluded?: string[];
+  /**
+   * Case-insensitive title fragments identifying holiday calendars.
+   */
+  holiday_patterns?: string[];
+  /**
+   * Auto-excluded calendars the user re-enabled.
+   */
+  included?: string[];
+  max_duration_minutes?: number | null;
+  min_duration_minutes?: number | null;
+};
+
+export type CalendarInfo = {
+  /**
+   * False for read-only calendars (subscriptions, some delegates): their
+   * events can't be responded to or edited.
+   */
+  allows_modifications?: boolean;
+  /**
+   * Detected as a holiday/birthday calendar and excluded by default.
+   */
+  auto_excluded?: boolean;
+  /**
+   * Shared with the user by delegation rather than owned.
+   */
+  delegate?: boolean;
+  /**
+   * Excluded from alerts and the dashboard, explicitly or automatically.
+   */
+  excluded?: boolean;
+  id: string;
+  kind: string;
+  source: string;
+  /**
+   * EKSource type: local, caldav, exchange, icloud, subscribed, birthday.
+   */
+  source_type?: string;
+  title: string;
+};
+
+export type CalendarPolicy = {
+  calendar?: string;
+  policy?: DisplayPolicy;
+};
+
+export type CancellationSettings = {
+  enabled?: boolean;
+  /**
+   * Also post a system notification, not just the dashboard banner.
+   */
+  notify?: boolean;
+  /**
+   * Only meetings starting this soon are reported.
+   */
+  window_minutes?: number;
+};
+
+/**
+ * Which of a rule's chains a step is in.
+ */
+export type ChainTrigger = "on_acknowledge" | "on_join";
+
+/**
+ * What the alert shows for a challenge; the answer stays in the backend.
+ */
+export type ChallengePrompt = {
+  /**
+   * The code to type, for `code` challenges.
+   */
+  code?: string | null;
+  kind: DismissChallenge;
+  prompt: string;
+};
+
+/**
+ * One fetch's changes as lines for the diagnostics, titles redacted per
+ * the privacy mode.
+ */
+export type ChangeLogEntry = {
+  at: string;
+  lines: string[];
+};
+
+export type ClockStyle = "system" | "12h" | "24h";
+
+/**
+ * A command's JSON schemas.
+ */
+export type CommandPayload = {
+  /**
+   * An object of the arguments; optional ones may be left out.
+   */
+  args: Schema;
+  command: string;
+  /**
+   * What a rejected call carries, if it can be rejected.
+   */
+  error?: Schema | null;
+  response: Schema;
+};
+
+export type CompactResult = {
+  bytesAfter: number;
+  bytesBefore: number;
+  pruned: PruneReport;
+};
+
+/**
+ * What happens to a fullscreen alert that comes due while one is showing.
+ */
+export type ConcurrentAlerts = "queue" | "merge";
+
+export type ConfidentialSettings = {
+  calendars?: CalendarPolicy[];
+};
+
+/**
+ * Two timed events that overlap, by event id.
+ */
+export type Conflict = {
+  end: string;
+  first: string;
+  second: string;
+  start: string;
+};
+
+export type ContactZone = {
+  email?: string;
+  /**
+   * IANA name such as "Asia/Kolkata".
+   */
+  zone?: string;
+};
+
+export type Corner = "top_left" | "top_right" | "bottom_left" | "bottom_right";
+
+export type CountInSettings = {
+  chime_sound?: string;
+  /**
+   * Off unless turned on here or by a rule.
+   */
+  enabled?: boolean;
+  /**
+   * Ticks before the start.
+   */
+  seconds?: number;
+  tick_sound?: string;
+  /**
+   * 0 to 1.
+   */
+  volume?: number;
+};
+
+export type CounterpartTime = {
+  /**
+   * Their date minus ours: -1 while it is still the day before for them.
+   */
+  dayOffset: number;
+  email: string;
+  /**
+   * The start in their zone, RFC 3339.
+   */
+  localStart: string;
+  /**
+   * "06:55" or "6:55 AM", per the clock setting.
+   */
+  localTime: string;
+  /**
+   * Their name, or their address where the invite has none.
+   */
+  name: string;
+  /**
+   * Before 08:00 or from 18:00 for them.
+   */
+  outsideHours: boolean;
+  zone: string;
+};
+
+export type CrashStats = {
+  alert: number;
+  /**
+   * Fullscreen alerts are notifications until the next launch.
+   */
+  alertsAsNotifications: boolean;
+  dashboard: number;
+  lastCrashAt?: string | null;
+  /**
+   * Popover, board and overlay webviews.
+   */
+  other: number;
+};
+
+export type DateStyle = "short" | "medium" | "long";
+
+export type DayUsage = {
+  date: string;
+  meetingMinutes: number;
+  overBudget: boolean;
+  /**
+   * e.g. "5.5h of meetings, over your 4h budget".
+   */
+  summary: string;
+};
+
+export type DaysOffSettings = {
+  enabled?: boolean;
+  /**
+   * Calendars whose all-day events are days off, e.g. a subscribed
+   * national-holiday calendar.
+   */
+  holiday_calendars?: string[];
+  /**
+   * The day "I'm Working Today" was turned on; it ends at midnight.
+   */
+  working_on?: string | null;
+};
+
+export type DetailsError = {
+  detail: string;
+  kind: "not_found";
+} | {
+  detail: {
+    id: string;
+    start: string;
+  };
+  kind: "occurrence_not_found";
+} | {
+  kind: "access_denied";
+} | {
+  detail: string;
+  kind: "failed";
+};
+
+export type Digest = {
+  /**
+   * Meeting hours against the budget, if one is set.
+   */
+  budget?: DayUsage | null;
+  date: string;
+  events: CalendarEvent[];
+  summary: string;
+};
+
+/**
+ * Morning summary of the day's meetings, sent as a native notification.
+ */
+export type DigestSettings = {
+  enabled?: boolean;
+  time?: string;
+};
+
+export type DismissChallenge = "start_time" | "code";
+
+export type DisplayMode = "single" | "dim" | "cover";
+
+export type DisplayPolicy = "show_all" | "title_only" | "busy_only";
+
+export type DisplaySettings = {
+  /**
+   * Where an alert moved aside docks.
+   */
+  corner?: Corner;
+  mode?: DisplayMode;
+  /**
+   * Name of the display to alert on; the primary display when unset or
+   * not connected.
+   */
+  monitor?: string | null;
+};
+
+export type DockSettings = {
+  badge?: boolean;
+  /**
+   * The badge is cleared when the next meeting is further away than this.
+   */
+  badge_within_minutes?: number;
+  /**
+   * Bounce the Dock icon until focused when a notification alert fires.
+   */
+  bounce_on_notification?: boolean;
+};
+
+/**
+ * What to do when an alert is still unacknowledged after the event started.
+ */
+export type EscalationSettings = {
+  /**
+   * Minutes after the event start before escalating.
+   */
+  after_minutes?: number;
+  enabled?: boolean;
+  /**
+   * ntfy.sh topic or any webhook URL that accepts a plain-text POST.
+   */
+  push_url?: string | null;
+  raise_window?: boolean;
+  replay_sound?: boolean;
+};
+
+export type EventNote = {
+  eventId: string;
+  /**
+   * Never expires.
+   */
+  kept?: boolean;
+  /**
+   * The occurrence's start; `None` for a sticky note.
+   */
+  occurrenceStart?: string | null;
+  text: string;
+  updatedAt: string;
+};
+
+export type Explanation = {
+  /**
+   * When the first alert is (or was) due.
+   */
+  alertAt?: string | null;
+  steps: TraceStep[];
+  /**
+   * `None` when the occurrence doesn't alert at all.
+   */
+  style?: AlertStyle | null;
+  /**
+   * Why it won't alert, from the first step that stopped it.
+   */
+  suppressed?: string | null;
+};
+
+export type ExportFormat = "csv" | "json";
+
+export type FadeSettings = {
+  duration_ms?: number;
+  /**
+   * Skip the animation and show or hide immediately.
+   */
+  instant?: boolean;
+};
+
+export type FeatureFlag = {
+  /**
+   * What the channel has it at.
+   */
+  default: boolean;
+  description: string;
+  enabled: boolean;
+  name: string;
+  /**
+   * The user picked `enabled` rather than the channel.
+   */
+  overridden: boolean;
+};
+
+export type FeatureSettings = {
+  /**
+   * Flags the user turned on or off, by name; the rest follow the
+   * channel. Names no longer known are kept but ignored.
+   */
+  flags?: { [key: string]: boolean };
+  /**
+   * The version whose release notes were last shown.
+   */
+  whats_new_seen?: string | null;
+};
+
+export type FloodSettings = {
+  enabled?: boolean;
+  /**
+   * More alerts than this within the window make a flood.
+   */
+  max_alerts?: number;
+  window_minutes?: number;
+};
+
+export type FocusMode = "take" | "keep";
+
+export type FocusSettings = {
+  /**
+   * How long after the alert takes focus its actions are ignored.
+   */
+  grace_ms?: number;
+  mode?: FocusMode;
+};
+
+export type FormatSettings = {
+  clock?: ClockStyle;
+  date_style?: DateStyle;
+  /**
+   * First day of the week; `None` follows the system.
+   */
+  week_start?: "Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun" | null;
+};
+
+export type FreeGap = {
+  end: string;
+  minutes: number;
+  start: string;
+};
+
+export type HealthReport = {
+  appVersion: string;
+  /**
+   * What the latest fetches changed, newest first.
+   */
+  calendarChanges: ChangeLogEntry[];
+  /**
+   * EventKit authorization, e.g. "full_access" or "denied".
+   */
+  calendarPermission: string;
+  /**
+   * The helper that refetches when the calendar changes.
+   */
+  calendarWatch: WatcherStatus;
+  inbound: InboundStatus;
+  mqtt: MqttStatus;
+  providers: string[];
+  /**
+   * Webview content processes that died, by window.
+   */
+  rendererCrashes: CrashStats;
+  scheduler: SchedulerHealth;
+  /**
+   * The latest self-test, startup or on demand.
+   */
+  selfTest?: SelfTestReport | null;
+  settingsPath?: string | null;
+  settingsWritable: boolean;
+  trayAlive: boolean;
+  /**
+   * When the tray icon was last rebuilt after vanishing.
+   */
+  trayRebuiltAt?: string | null;
+  webhooks: WebhookHealth[];
+};
+
+export type HistoryEntry = {
+  /**
+   * For dismissals and joins: how the rule's action chain went.
+   */
+  actions?: StepResult[];
+  at: string;
+  event_id: string;
+  kind: HistoryKind;
+  /**
+   * For dismissals and joins: seconds after the start (negative if
+   * early). Only recorded while punctuality tracking is on.
+   */
+  late_seconds?: number | null;
+  start: string;
+  title: string;
+  /**
+   * For missed and suppressed alerts: how the alert was decided.
+   */
+  trace?: TraceStep[];
+};
+
+export type HistoryKind = "fired" | "dismissed" | "snoozed" | "joined" | "escalated" | "expired" | "missed" | "cancelled" | "suppressed";
+
+export type ImportResult = {
+  /**
+   * Backup of the settings as they were before the import, for rollback.
+   */
+  snapshot_path: string;
+};
+
+export type InboundSettings = {
+  enabled?: boolean;
+  /**
+   * Service name of the Keychain item (a generic password) holding the
+   * bearer token; `None` connects without one.
+   */
+  keychain_service?: string | null;
+  /**
+   * A streaming endpoint, e.g. "https://ntfy.sh/<topic>/json".
+   */
+  url?: string | null;
+};
+
+/**
+ * For the integrations status and the health report; leaves out the URL,
+ * which for ntfy is as good as a password.
+ */
+export type InboundStatus = {
+  connected: boolean;
+  connectedSince?: string | null;
+  enabled: boolean;
+  lastError?: string | null;
+  lastMessageAt?: string | null;
+  nextRetryAt?: string | null;
+  received: number;
+  /**
+   * Duplicates, oversized or malformed messages.
+   */
+  rejected: number;
+};
+
+export type IntegrationsStatus = {
+  inbound: InboundStatus;
+  mqtt: MqttStatus;
+  webhooks: WebhookStatus[];
+};
+
+export type IpcError = {
+  detail: {
+    command: string;
+    window: string;
+  };
+  kind: "unexpected_caller";
+} | {
+  detail: {
+    command: string;
+  };
+  kind: "starting";
+} | {
+  detail: {
+    backend: number;
+    client: string;
+    min_client: number;
+  };
+  kind: "incompatible_client";
+} | {
+  detail: string;
+  kind: "failed";
+};
+
+export type JournaledAlert = {
+  alert_key: string;
+  end?: string | null;
+  title: string;
+};
+
+export type LatenessMode = "note" | "tag" | "history_only";
+
+export type LatenessSettings = {
+  enabled?: boolean;
+  mode?: LatenessMode;
+  tag?: string;
+  threshold_minutes?: number;
+};
+
+/**
+ * Where an event's alert times come from.
+ */
+export type LeadTimeSource = "fixed" | "event_alarms" | "earliest_of_both";
+
+export type LifecycleEvent = "alert_fired" | "alert_dismissed" | "alert_snoozed" | "meeting_started" | "meeting_ended";
+
+/**
+ * Early warning for meetings with attendees but no join link.
+ */
+export type LinkCheckSettings = {
+  enabled?: boolean;
+  /**
+   * How long before the alert itself the warning goes out.
+   */
+  minutes_before_alert?: number;
+};
+
+export type LocationInfo = {
+  display?: string | null;
+  kind: LocationKind;
+  /**
+   * Travel lead time the scheduler will use, if the location is physical.
+   */
+  lead_minutes?: number | null;
+  map_url?: string | null;
+};
+
+export type LocationKind = "none" | "url" | "room" | "address" | "other";
+
+export type LongEventCalendar = {
+  calendar: string;
+  threshold_minutes?: number | null;
+};
+
+export type LongEventSettings = {
+  /**
+   * Per-calendar thresholds, e.g. `None` for a calendar whose offsite
+   * days should alert like meetings.
+   */
+  calendars?: LongEventCalendar[];
+  /**
+   * How a long block alerts instead of taking over the screen.
+   */
+  style?: AlertStyle;
+  /**
+   * Events at least this long are long blocks; `None` turns it off.
+   */
+  threshold_minutes?: number | null;
+};
+
+export type Mode = "dark" | "light";
+
+export type MqttSettings = {
+  enabled?: boolean;
+  /**
+   * Lifecycle events to publish; empty means all of them.
+   */
+  events?: LifecycleEvent[];
+  /**
+   * Broker host, over plain TCP as brokers on a home network are.
+   */
+  host?: string;
+  password?: string | null;
+  port?: number;
+  topic_prefix?: string;
+  username?: string | null;
+};
+
+export type MqttStatus = {
+  connected: boolean;
+  last_error?: string | null;
+  last_publish_at?: string | null;
+};
+
+/**
+ * How long a fullscreen alert stays up, and how often it comes back.
+ */
+export type NagSettings = {
+  /**
+   * Hide an unacknowledged alert after this long, logged as expired.
+   */
+  auto_dismiss_after_seconds?: number | null;
+  /**
+   * Stop nagging this long after the event ended.
+   */
+  grace_minutes?: number;
+  /**
+   * Raise the alert again this often until it is acknowledged.
+   */
+  interval_seconds?: number | null;
+  replay_sound?: boolean;
+};
+
+export type OutputDevice = {
+  headset: boolean;
+  isDefault: boolean;
+  name: string;
+  transport: Transport;
+  /**
+   * CoreAudio's UID, stable across reconnects.
+   */
+  uid: string;
+};
+
+export type PendingSnooze = {
+  alert_key: string;
+  event: CalendarEvent;
+  until: string;
+};
+
+/**
+ * The scheduler's work still to do, as `get_scheduler_state` reports it.
+ */
+export type PendingWork = {
+  /**
+   * The pause runs to a fixed time rather than the next free slot.
+   */
+  pause_is_timed?: boolean;
+  paused_until?: string | null;
+  /**
+   * Fullscreen alerts waiting behind the one showing, in order.
+   */
+  queued?: JournaledAlert[];
+  /**
+   * Sorted by alert key.
+   */
+  snoozed?: PendingSnooze[];
+};
+
+export type PhaseTiming = {
+  durationMs: number;
+  name: string;
+  /**
+   * Since launch, in milliseconds.
+   */
+  startedMs: number;
+};
+
+export type PlannedAlert = {
+  at: string;
+  title: string;
+};
+
+export type PowerProfile = "high_frequency" | "low_power";
+
+export type PowerSettings = {
+  /**
+   * Stay in high-frequency mode even on battery.
+   */
+  always_high_frequency?: boolean;
+};
+
+export type PowerSource = "ac" | "battery" | "unknown";
+
+export type PowerState = {
+  profile: PowerProfile;
+  source: PowerSource;
+};
+
+export type PresentationAction = "ignore" | "toast" | "notification";
+
+export type PresentationSettings = {
+  /**
+   * What alerts do while presenting.
+   */
+  action?: PresentationAction;
+};
+
+export type PresentationStatus = {
+  presenting: boolean;
+  /**
+   * e.g. "Screen mirroring" or "Presenting to Apple TV".
+   */
+  reason?: string | null;
+  /**
+   * The built-in display a toast can go to; `None` while mirrored too.
+   */
+  toastDisplay?: string | null;
+};
+
+export type Preview = {
+  budget?: DayUsage | null;
+  date: string;
+  events: CalendarEvent[];
+  /**
+   * Events that start early or fall outside working hours.
+   */
+  flagged: CalendarEvent[];
+  summary: string;
+};
+
+/**
+ * Evening summary of tomorrow's meetings, sent as a native notification.
+ */
+export type PreviewSettings = {
+  /**
+   * Days the preview is sent on.
+   */
+  days?: ("Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun")[];
+  /**
+   * Meetings starting before this are flagged as early.
+   */
+  early_before?: string;
+  enabled?: boolean;
+  /**
+   * Days on which an empty tomorrow sends nothing.
+   */
+  skip_if_empty_on?: ("Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun")[];
+  time?: string;
+};
+
+export type PrivacyMode = "off" | "redact_content" | "titles_only";
+
+export type Profile = {
+  calendar_filter?: CalendarFilter;
+  escalation?: EscalationSettings;
+  name?: string;
+  rules?: AlertRule[];
+  webhooks?: WebhookConfig[];
+};
+
+export type ProfileList = {
+  active?: string | null;
+  profiles: Profile[];
+};
+
+/**
+ * Profiles to switch to automatically at the start of each day.
+ */
+export type ProfileSchedule = {
+  enabled?: boolean;
+  /**
+   * On days with working hours.
+   */
+  weekday?: string | null;
+  /**
+   * On days without any, per `working_hours`.
+   */
+  weekend?: string | null;
+};
+
+export type PruneReport = {
+  attendanceSessions: number;
+  historyEntries: number;
+  logs: number;
+  notes: number;
+  snapshots: number;
+};
+
+export type PunctualityInsight = {
+  eventId: string;
+  medianLateMinutes: number;
+  /**
+   * e.g. "You join 'Weekly 1:1' a median 4 minutes late".
+   */
+  message: string;
+  samples: number;
+  /**
+   * Extra lead time that would have covered the median.
+   */
+  suggestedExtraLeadMinutes: number;
+  title: string;
+};
+
+/**
+ * How late meetings get acknowledged, and when that's worth a hint.
+ */
+export type PunctualitySettings = {
+  enabled?: boolean;
+  /**
+   * Series joined or dismissed a median of more than this late get a
+   * suggestion.
+   */
+  late_threshold_minutes?: number;
+  /**
+   * Occurrences needed before a series is judged.
+   */
+  min_samples?: number;
+};
+
+export type RecentMeeting = {
+  alertedAt: string;
+  event: CalendarEvent;
+  /**
+   * The occurrence's note as it is now; looked up when listed, never
+   * stored with the entry.
+   */
+  note?: string | null;
+};
+
+export type RecentMeetingSettings = {
+  keep_days?: number;
+  limit?: number;
+  /**
+   * Privacy: off keeps event content out of the app data dir.
+   */
+  persist?: boolean;
+};
+
+export type Recurrence = {
+  days: RecurrenceDay[];
+  /**
+   * 1 to 31, negative counting from the end of the month.
+   */
+  daysOfMonth: number[];
+  endDate?: string | null;
+  /**
+   * daily, weekly, monthly or yearly.
+   */
+  frequency: string;
+  interval: number;
+  occurrenceCount?: number | null;
+};
+
+export type RecurrenceDay = {
+  /**
+   * Week of the month ("second Tuesday"); 0 means every week, -1 the last.
+   */
+  weekNumber: number;
+  /**
+   * EventKit weekday number, 1 = Sunday through 7 = Saturday.
+   */
+  weekday: number;
+};
+
+export type RetentionSettings = {
+  attendance_days?: number;
+  history_days?: number;
+  /**
+   * The rotated logs, all together.
+   */
+  logs_max_bytes?: number;
+  /**
+   * After the occurrence, or after the last edit for a sticky note.
+   */
+  notes_days?: number;
+  /**
+   * Snapshots taken before an import; the newest is always kept.
+   */
+  snapshots_kept?: number;
+  snapshots_max_bytes?: number;
+};
+
+export type RuleSelector = {
+  /**
+   * Account the calendars are in, by name ("Exchange", "iCloud").
+   */
+  account?: string | null;
+  /**
+   * Calendar title, or a pattern with `*`.
+   */
+  calendar?: string | null;
+};
+
+/**
+ * The scheduler's side of the health report.
+ */
+export type SchedulerHealth = {
+  activeAlert?: string | null;
+  eventsCached: number;
+  lastFetchAt?: string | null;
+  /**
+   * The oldest last good sync among the enabled sources.
+   */
+  lastSuccessAt?: string | null;
+  lastTick?: string | null;
+  nextAlert?: PlannedAlert | null;
+  /**
+   * When the next failing source retries.
+   */
+  nextRetryAt?: string | null;
+  pausedUntil?: string | null;
+  pollCadence: Cadence;
+  queuedAlerts: number;
+  /**
+   * Ticked within the last few seconds.
+   */
+  running: boolean;
+  /**
+   * Some events are left over from an earlier sync, because a source's
+   * last fetch failed.
+   */
+  stale: boolean;
+};
+
+export type Schema = { [key: string]: unknown } | boolean;
+
+export type SelfTestReport = {
+  dueAt: string;
+  /**
+   * `None` while the dry run is pending.
+   */
+  passed?: boolean | null;
+  stages: StageResult[];
+  startedAt: string;
+};
+
+export type SelfTestSettings = {
+  /**
+   * Run the self-test every launch.
+   */
+  on_startup?: boolean;
+};
+
+/**
+ * A series by id, or every series with this title among the events known now.
+ */
+export type SeriesSelector = {
+  seriesId?: string | null;
+  title?: string | null;
+};
+
+export type Settings = {
+  active_profile?: string | null;
+  /**
+   * Poll often just before alerts and meetings, rarely at night; see
+   * `cadence`.
+   */
+  adaptive_polling?: boolean;
+  /**
+   * A meeting that was moved alerts again only after this long, unless it
+   * moved by more than its lead time.
+   */
+  alert_cooldown_minutes?: number;
+  /**
+   * Whether a new alert takes keyboard focus, and how soon it reacts.
+   */
+  alert_focus?: FocusSettings;
+  /**
+   * Name of a macOS system sound (e.g. "Glass"), `None` for silence.
+   */
+  alert_sound?: string | null;
+  /**
+   * Records when meetings were actually attended, for timesheets.
+   */
+  attendance?: AttendanceSettings;
+  /**
+   * Raise the lead time of series that are consistently joined late.
+   */
+  auto_tune?: AutoTuneSettings;
+  /**
+   * A spare display showing the agenda all the time.
+   */
+  board?: BoardSettings;
+  /**
+   * Daily cap on meeting hours, and the warning when tomorrow exceeds it.
+   */
+  budget?: BudgetSettings;
+  /**
+   * Calendars whose events never alert or show on the dashboard.
+   */
+  calendar_filter?: CalendarFilter;
+  /**
+   * Reporting meetings that are cancelled or moved shortly before they start.
+   */
+  cancellations?: CancellationSettings;
+  /**
+   * Meetings found up to this long after their start (at launch or on
+   * wake) still alert; older ones are logged as missed.
+   */
+  catch_up_minutes?: number;
+  concurrent_alerts?: ConcurrentAlerts;
+  /**
+   * How much of each calendar's events shows on screen.
+   */
+  confidential?: ConfidentialSettings;
+  /**
+   * People's time zones, for the start as it is for them.
+   */
+  contact_time_zones?: ContactZone[];
+  /**
+   * Ticks in the last seconds before the start, then a chime on it.
+   */
+  count_in?: CountInSettings;
+  /**
+   * Weekends and holidays: no digest, and alerts only for meetings.
+   */
+  days_off?: DaysOffSettings;
+  digest?: DigestSettings;
+  /**
+   * Which display shows the alert and what happens to the others.
+   */
+  display?: DisplaySettings;
+  dock?: DockSettings;
+  escalation?: EscalationSettings;
+  /**
+   * Fade-in and fade-out of the fullscreen alert.
+   */
+  fade?: FadeSettings;
+  /**
+   * Flags the user flipped, and which release notes were shown.
+   */
+  features?: FeatureSettings;
+  /**
+   * When many alerts at once collapse into one, e.g. after an import.
+   */
+  flood?: FloodSettings;
+  /**
+   * Clock, week start and date style for backend-made text.
+   */
+  format?: FormatSettings;
+  /**
+   * Reminders received from a relay such as an ntfy.sh topic.
+   */
+  inbound?: InboundSettings;
+  /**
+   * Opt-in notes on occurrences joined late.
+   */
+  lateness?: LatenessSettings;
+  /**
+   * How long before the event start the fullscreen alert fires.
+   */
+  lead_time_seconds?: number;
+  /**
+   * Whether the events' own alarms replace or add to the lead time.
+   */
+  lead_time_source?: LeadTimeSource;
+  link_check?: LinkCheckSettings;
+  /**
+   * Offsites and all-morning blocks: when they stop counting as meetings.
+   */
+  long_events?: LongEventSettings;
+  /**
+   * Lifecycle events published to an MQTT broker.
+   */
+  mqtt?: MqttSettings;
+  nag?: NagSettings;
+  /**
+   * How often the scheduler refetches the calendar, unless
+   * `adaptive_polling` picks the interval.
+   */
+  poll_interval_seconds?: number;
+  power?: PowerSettings;
+  /**
+   * What alerts do while a display is mirrored or is a TV or projector.
+   */
+  presentation?: PresentationSettings;
+  /**
+   * How much about events goes to webhooks, push, the status file and logs.
+   */
+  privacy_mode?: PrivacyMode;
+  profile_schedule?: ProfileSchedule;
+  profiles?: Profile[];
+  /**
+   * Tracks how late alerts are acknowledged, for lead-time hints.
+   */
+  punctuality?: PunctualitySettings;
+  /**
+   * How many alerted meetings to keep for later, and whether on disk.
+   */
+  recent_meetings?: RecentMeetingSettings;
+  /**
+   * How long the history and attendance are kept.
+   */
+  retention?: RetentionSettings;
+  rules?: AlertRule[];
+  self_test?: SelfTestSettings;
+  /**
+   * Which outputs alert sounds play on, unless a rule says otherwise.
+   */
+  sound_route?: AudioRoute;
+  /**
+   * Calendar sources the user turned off, and when a failing one warns.
+   */
+  sources?: SourceSettings;
+  /**
+   * Next-meeting status for status bars and shell prompts.
+   */
+  status_file?: StatusFileSettings;
+  /**
+   * Custom wording for the alert, VoiceOver and notifications.
+   */
+  templates?: TemplateSettings;
+  /**
+   * Colors and icon of the fullscreen alert.
+   */
+  theme?: ThemeSettings;
+  tomorrow_preview?: PreviewSettings;
+  travel?: TravelSettings;
+  /**
+   * What a left click on the menu bar icon opens.
+   */
+  tray_click?: TrayClick;
+  webhooks?: WebhookConfig[];
+  working_hours?: WorkingHours;
+};
+
+/**
+ * A step of a rule's chain as it would run, without running it.
+ */
+export type SimulatedStep = {
+  action: string;
+  description?: string | null;
+  /**
+   * Why the step would fail before running, if it would.
+   */
+  error?: ActionError | null;
+  trigger: ChainTrigger;
+};
+
+/**
+ * One step of a sound escalation: from `offset_seconds` after the start
+ * (negative before it), `sound` plays at `volume` (0 to 1), over and over
+ * if `looping`, until the next step or an acknowledgement.
+ */
+export type SoundStage = {
+  looping?: boolean;
+  offset_seconds: number;
+  sound: string;
+  volume?: number;
+};
+
+export type SourceSettings = {
+  /**
+   * Ids of sources the user turned off.
+   */
+  disabled?: string[];
+  /**
+   * The tray warns once an enabled source has failed this many times in
+   * a row, or for `warn_after_minutes`, whichever comes first.
+   */
+  warn_after_failures?: number;
+  warn_after_minutes?: number;
+};
+
+export type SourceStatus = {
+  displayName: string;
+  enabled: boolean;
+  /**
+   * Events in the current 24-hour window.
+   */
+  eventCount: number;
+  failingSince?: string | null;
+  id: string;
+  lastError?: string | null;
+  lastSyncAt?: string | null;
+  nextRetryAt?: string | null;
+};
+
+export type Stage = "scheduled" | "fired" | "window-resolvable" | "sound-device-available";
+
+export type StageResult = {
+  at: string;
+  detail?: string | null;
+  ok: boolean;
+  stage: Stage;
+};
+
+/**
+ * Milliseconds since launch for each milestone, once reached.
+ */
+export type StartupTimings = {
+  firstFetchMs?: number | null;
+  phases: PhaseTiming[];
+  readyMs?: number | null;
+  trayMs?: number | null;
+};
+
+export type StatusFileSettings = {
+  enabled?: boolean;
+  /**
+   * Where `status.json` goes; `status.txt` is written next to it.
+   */
+  path?: string | null;
+};
+
+/**
+ * How a step of the decision bore on an event's alert.
+ */
+export type StepOutcome = "passed" | "changed" | "suppressed";
+
+/**
+ * How one step of a chain went, kept with the history entry.
+ */
+export type StepResult = {
+  action: string;
+  /**
+   * What the action returned, or why it failed.
+   */
+  detail?: unknown;
+  ok: boolean;
+};
+
+export type StorageUsage = {
+  /**
+   * Largest first.
+   */
+  artifacts: ArtifactUsage[];
+  totalBytes: number;
+};
+
+export type SystemAppearance = {
+  mode: Mode;
+  reduce_transparency: boolean;
+};
+
+/**
+ * `None` keeps the built-in wording.
+ */
+export type TemplateSettings = {
+  /**
+   * Heading of the fullscreen alert, sent as `display_text`.
+   */
+  alert?: string | null;
+  /**
+   * Body of notification-style alerts.
+   */
+  notification?: string | null;
+  /**
+   * What VoiceOver announces when the alert shows.
+   */
+  speech?: string | null;
+  /**
+   * The line in the status file's text variant.
+   */
+  status?: string | null;
+};
+
+export type ThemeRule = {
+  /**
+   * Calendar title; `None` matches every calendar.
+   */
+  calendar?: string | null;
+  /**
+   * e.g. "@bigclient.com"; `None` matches every organizer.
+   */
+  organizer_domain?: string | null;
+  theme: AlertTheme;
+};
+
+export type ThemeSettings = {
+  base?: AlertTheme;
+  /**
+   * Replaces `base` while the system is in light mode; `None` keeps it.
+   */
+  light?: AlertTheme | null;
+  rules?: ThemeRule[];
+  /**
+   * Once the meeting is under way; `None` keeps the earlier theme.
+   */
+  started?: AlertTheme | null;
+  urgency?: UrgencyBand[];
+};
+
+/**
+ * One decision, e.g. a matching rule or the working hours.
+ */
+export type TraceStep = {
+  detail: string;
+  outcome: StepOutcome;
+  /**
+   * The step ("working_hours"), or "rule:<id>" for a matching rule.
+   */
+  rule: string;
+};
+
+export type Transport = "built_in" | "bluetooth" | "usb" | "air_play" | "other" | "display" | "virtual";
+
+/**
+ * A fixed buffer for locations containing `pattern` (case-insensitive).
+ */
+export type TravelBuffer = {
+  lead_minutes: number;
+  pattern: string;
+};
+
+/**
+ * Extra lead time for in-person meetings, so the alert means "time to leave".
+ */
+export type TravelSettings = {
+  buffers?: TravelBuffer[];
+  /**
+   * Lead time for physical locations that match no pattern.
+   */
+  default_lead_minutes?: number;
+  enabled?: boolean;
+};
+
+/**
+ * What a left click on the menu bar icon opens.
+ */
+export type TrayClick = "popover" | "dashboard";
+
+/**
+ * Applies from `minutes_until` before the start until the start.
+ */
+export type UrgencyBand = {
+  minutes_until: number;
+  theme: AlertTheme;
+};
+
+export type WatcherStatus = {
+  lastChangeAt?: string | null;
+  /**
+   * Why the helper last stopped.
+   */
+  lastError?: string | null;
+  /**
+   * The helper is running and listening for changes.
+   */
+  listening: boolean;
+  restarts: number;
+};
+
+export type WebhookConfig = {
+  enabled?: boolean;
+  /**
+   * Lifecycle events to deliver; empty means all of them.
+   */
+  events?: LifecycleEvent[];
+  /**
+   * Used to sign each payload (`X-Signature-256: sha256=<hex hmac>`).
+   */
+  secret?: string | null;
+  url?: string;
+};
+
+export type WebhookHealth = {
+  consecutiveFailures: number;
+  enabled: boolean;
+  lastError?: string | null;
+  lastSuccessAt?: string | null;
+  url: string;
+};
+
+export type WebhookStatus = {
+  consecutive_failures: number;
+  last_attempt_at?: string | null;
+  last_error?: string | null;
+  last_event?: string | null;
+  last_success_at?: string | null;
+  url: string;
+};
+
+export type WeekAgenda = {
+  days: AgendaDay[];
+  start: string;
+};
+
+export type WhatsNew = {
+  items: WhatsNewItem[];
+  version: string;
+};
+
+export type WhatsNewItem = {
+  body: string;
+  /**
+   * The flags of the feature, to try it or turn it off from the note.
+   */
+  flags: FeatureFlag[];
+  title: string;
+};
+
+/**
+ * How well the alert and overlay windows are reused, for debugging.
+ */
+export type WindowPoolStats = {
+  alertWindowsCreated: number;
+  alertsShown: number;
+  /**
+   * Stops growing once every connected display has its window.
+   */
+  coldAlerts: number;
+  overlayWindowsCreated: number;
+  overlaysPooled: number;
+  overlaysVisible: number;
+};
+
+export type WorkingDay = {
+  end: string;
+  start: string;
+  weekday: "Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun";
+};
+
+export type WorkingHours = {
+  /**
+   * Weekdays missing from this list are no-work days.
+   */
+  days?: WorkingDay[];
+  /**
+   * When disabled every day counts as a full working day.
+   */
+  enabled?: boolean;
+  /**
+   * Replace the fullscreen alert with a notification for events entirely
+   * outside working hours.
+   */
+  notify_outside_hours?: boolean;
+};
+
+/** Every command: what it takes, returns, and is rejected with. */
+export interface Commands {
+  get_calendar_events: {
+    args: {
+      includeDetails?: boolean | null;
+      includeFiltered?: boolean | null;
+      limit?: number | null;
+      offset?: number | null;
+    };
+    response: string;
+    error: string;
+  };
+  get_calendars: {
+    args: Record<string, never>;
+    response: CalendarInfo[];
+    error: never;
+  };
+  get_event_details: {
+    args: {
+      eventId: string;
+      occurrenceStart?: string | null;
+    };
+    response: CalendarEventDetails;
+    error: DetailsError;
+  };
+  enter_alert_mode: {
+    args: {
+      event: unknown;
+    };
+    response: null;
+    error: never;
+  };
+  exit_alert_mode: {
+    args: Record<string, never>;
+    response: null;
+    error: never;
+  };
+  set_pill_mode: {
+    args: {
+      enabled: boolean;
+    };
+    response: null;
+    error: never;
+  };
+  open_link: {
+    args: {
+      url: string;
+    };
+    response: null;
+    error: string;
+  };
+  open_in_calendar: {
+    args: {
+      eventId: string;
+    };
+    response: null;
+    error: never;
+  };
+  get_settings: {
+    args: Record<string, never>;
+    response: Settings;
+    error: IpcError;
+  };
+  save_settings: {
+    args: {
+      settings: Settings;
+    };
+    response: null;
+    error: IpcError;
+  };
+  preview_template: {
+    args: {
+      template: string;
+    };
+    response: string;
+    error: string;
+  };
+  get_alert_history: {
+    args: Record<string, never>;
+    response: HistoryEntry[];
+    error: never;
+  };
+  get_punctuality_insights: {
+    args: Record<string, never>;
+    response: PunctualityInsight[];
+    error: never;
+  };
+  get_recent_meetings: {
+    args: {
+      limit?: number | null;
+    };
+    response: RecentMeeting[];
+    error: never;
+  };
+  dismiss_alert: {
+    args: Record<string, never>;
+    response: null;
+    error: string;
+  };
+  request_dismiss: {
+    args: {
+      eventId: string;
+    };
+    response: ChallengePrompt | null;
+    error: string;
+  };
+  confirm_dismiss: {
+    args: {
+      answer: string;
+      eventId: string;
+    };
+    response: null;
+    error: string;
+  };
+  begin_escape: {
+    args: Record<string, never>;
+    response: null;
+    error: never;
+  };
+  end_escape: {
+    args: Record<string, never>;
+    response: boolean;
+    error: never;
+  };
+  snooze_alert: {
+    args: {
+      minutes?: number | null;
+    };
+    response: null;
+    error: string;
+  };
+  join_meeting: {
+    args: {
+      url: string;
+    };
+    response: null;
+    error: string;
+  };
+  get_active_alert: {
+    args: Record<string, never>;
+    response: CalendarEvent | null;
+    error: never;
+  };
+  get_scheduler_state: {
+    args: Record<string, never>;
+    response: PendingWork;
+    error: never;
+  };
+  test_webhook: {
+    args: {
+      url: string;
+    };
+    response: number;
+    error: IpcError;
+  };
+  get_integrations_status: {
+    args: Record<string, never>;
+    response: IntegrationsStatus;
+    error: IpcError;
+  };
+  get_health: {
+    args: Record<string, never>;
+    response: HealthReport;
+    error: string;
+  };
+  run_self_test: {
+    args: Record<string, never>;
+    response: SelfTestReport;
+    error: never;
+  };
+  get_system_appearance: {
+    args: Record<string, never>;
+    response: SystemAppearance;
+    error: never;
+  };
+  get_overlay_opacity: {
+    args: Record<string, never>;
+    response: number;
+    error: never;
+  };
+  end_focus_grace: {
+    args: Record<string, never>;
+    response: null;
+    error: never;
+  };
+  load_simulation: {
+    args: {
+      path: string;
+    };
+    response: number;
+    error: IpcError;
+  };
+  minimize_alert_to_corner: {
+    args: Record<string, never>;
+    response: null;
+    error: string;
+  };
+  restore_alert_fullscreen: {
+    args: Record<string, never>;
+    response: null;
+    error: string;
+  };
+  get_window_pool_stats: {
+    args: Record<string, never>;
+    response: WindowPoolStats;
+    error: never;
+  };
+  get_sources: {
+    args: Record<string, never>;
+    response: SourceStatus[];
+    error: never;
+  };
+  refresh_source: {
+    args: {
+      id: string;
+    };
+    response: null;
+    error: string;
+  };
+  set_source_enabled: {
+    args: {
+      enabled: boolean;
+      id: string;
+    };
+    response: null;
+    error: IpcError;
+  };
+  adopt_series_settings: {
+    args: {
+      newId: string;
+      oldId: string;
+    };
+    response: number;
+    error: IpcError;
+  };
+  get_alert_rules: {
+    args: Record<string, never>;
+    response: AlertRule[];
+    error: never;
+  };
+  save_alert_rule: {
+    args: {
+      rule: AlertRule;
+    };
+    response: AlertRule;
+    error: IpcError;
+  };
+  delete_alert_rule: {
+    args: {
+      id: string;
+    };
+    response: null;
+    error: IpcError;
+  };
+  simulate_rule_actions: {
+    args: {
+      ruleId: string;
+    };
+    response: SimulatedStep[];
+    error: string;
+  };
+  reset_auto_tuned_rules: {
+    args: Record<string, never>;
+    response: null;
+    error: IpcError;
+  };
+  dispatch_action: {
+    args: {
+      action: string;
+      args: unknown;
+    };
+    response: unknown;
+    error: ActionError;
+  };
+  list_actions: {
+    args: Record<string, never>;
+    response: Action[];
+    error: never;
+  };
+  get_free_gaps: {
+    args: Record<string, never>;
+    response: FreeGap[];
+    error: never;
+  };
+  get_digest: {
+    args: {
+      includeOutsideHours?: boolean | null;
+    };
+    response: Digest;
+    error: never;
+  };
+  get_meeting_budget_status: {
+    args: Record<string, never>;
+    response: BudgetStatus;
+    error: never;
+  };
+  trigger_tomorrow_preview: {
+    args: Record<string, never>;
+    response: Preview;
+    error: string;
+  };
+  list_profiles: {
+    args: Record<string, never>;
+    response: ProfileList;
+    error: never;
+  };
+  switch_profile: {
+    args: {
+      name: string;
+    };
+    response: null;
+    error: IpcError;
+  };
+  save_current_as_profile: {
+    args: {
+      name: string;
+    };
+    response: null;
+    error: IpcError;
+  };
+  export_backup: {
+    args: {
+      path: string;
+    };
+    response: null;
+    error: IpcError;
+  };
+  import_backup: {
+    args: {
+      path: string;
+    };
+    response: ImportResult;
+    error: IpcError;
+  };
+  rollback_import: {
+    args: {
+      snapshotPath?: string | null;
+    };
+    response: string;
+    error: IpcError;
+  };
+  classify_location: {
+    args: {
+      eventId: string;
+    };
+    response: LocationInfo;
+    error: string;
+  };
+  open_map: {
+    args: {
+      eventId: string;
+    };
+    response: null;
+    error: string;
+  };
+  get_power_state: {
+    args: Record<string, never>;
+    response: PowerState;
+    error: never;
+  };
+  get_presentation_status: {
+    args: Record<string, never>;
+    response: PresentationStatus;
+    error: never;
+  };
+  set_lateness_annotation: {
+    args: {
+      enabled: boolean;
+      mode: LatenessMode;
+      thresholdMinutes: number;
+    };
+    response: null;
+    error: IpcError;
+  };
+  get_accessibility_prefs: {
+    args: Record<string, never>;
+    response: AccessibilityPrefs;
+    error: never;
+  };
+  mute_event: {
+    args: {
+      eventId: string;
+      occurrenceStart: string;
+    };
+    response: null;
+    error: string;
+  };
+  unmute_event: {
+    args: {
+      eventId: string;
+      occurrenceStart: string;
+    };
+    response: null;
+    error: string;
+  };
+  force_alert: {
+    args: {
+      eventId: string;
+      leadMinutes: number;
+      occurrenceStart: string;
+    };
+    response: null;
+    error: string;
+  };
+  pause_until_free: {
+    args: Record<string, never>;
+    response: string;
+    error: string;
+  };
+  resume_reminders: {
+    args: Record<string, never>;
+    response: null;
+    error: never;
+  };
+  get_week_agenda: {
+    args: {
+      weekOffset: number;
+    };
+    response: WeekAgenda;
+    error: string;
+  };
+  explain_event: {
+    args: {
+      eventId: string;
+      occurrenceStart: string;
+    };
+    response: Explanation;
+    error: string;
+  };
+  open_dashboard: {
+    args: Record<string, never>;
+    response: null;
+    error: never;
+  };
+  export_attendance: {
+    args: {
+      format: ExportFormat;
+      hashTitles?: boolean | null;
+      range: AttendanceRange;
+    };
+    response: string;
+    error: IpcError;
+  };
+  get_storage_usage: {
+    args: Record<string, never>;
+    response: StorageUsage;
+    error: IpcError;
+  };
+  compact_storage: {
+    args: Record<string, never>;
+    response: CompactResult;
+    error: IpcError;
+  };
+  set_event_note: {
+    args: {
+      eventId: string;
+      kept?: boolean | null;
+      occurrence: string;
+      sticky?: boolean | null;
+      text: string;
+    };
+    response: EventNote | null;
+    error: string;
+  };
+  get_event_note: {
+    args: {
+      eventId: string;
+      occurrence: string;
+    };
+    response: EventNote | null;
+    error: string;
+  };
+  enable_board_mode: {
+    args: {
+      displayName: string;
+    };
+    response: null;
+    error: IpcError;
+  };
+  disable_board_mode: {
+    args: Record<string, never>;
+    response: null;
+    error: IpcError;
+  };
+  get_api_version: {
+    args: Record<string, never>;
+    response: ApiVersion;
+    error: never;
+  };
+  get_api_manifest: {
+    args: Record<string, never>;
+    response: ApiManifest;
+    error: never;
+  };
+  reveal_event: {
+    args: {
+      eventId: string;
+      occurrenceStart?: string | null;
+    };
+    response: CalendarEvent;
+    error: IpcError;
+  };
+  rebuild_tray: {
+    args: Record<string, never>;
+    response: null;
+    error: IpcError;
+  };
+  get_startup_timings: {
+    args: Record<string, never>;
+    response: StartupTimings;
+    error: never;
+  };
+  get_feature_flags: {
+    args: Record<string, never>;
+    response: FeatureFlag[];
+    error: never;
+  };
+  set_feature_flag: {
+    args: {
+      enabled: boolean;
+      name: string;
+    };
+    response: FeatureFlag;
+    error: IpcError;
+  };
+  get_whats_new: {
+    args: Record<string, never>;
+    response: WhatsNew | null;
+    error: IpcError;
+  };
+  get_audio_outputs: {
+    args: Record<string, never>;
+    response: OutputDevice[];
+    error: never;
+  };
+  set_contact_timezone: {
+    args: {
+      email: string;
+      zone: string;
+    };
+    response: null;
+    error: IpcError;
+  };
+  apply_rules_bulk: {
+    args: {
+      items: ([RuleSelector, AlertRule])[];
+      mode?: BulkMode | null;
+    };
+    response: BulkReport;
+    error: IpcError;
+  };
+  mute_series_bulk: {
+    args: {
+      mode?: BulkMode | null;
+      selectors: SeriesSelector[];
+    };
+    response: BulkReport;
+    error: IpcError;
+  };
+  add_ics_subscriptions_bulk: {
+    args: {
+      mode?: BulkMode | null;
+      urls: string[];
+    };
+    response: BulkReport;
+    error: IpcError;
+  };
+  copy_from_profile: {
+    args: {
+      mode?: BulkMode | null;
+      name: string;
+    };
+    response: BulkReport;
+    error: IpcError;
+  };
+}